@@ -0,0 +1,78 @@
+use benches::query;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use search::{executor::SearchExecutor, query::Query};
+use types::jotoba::{language::Language, search::SearchTarget};
+
+#[global_allocator]
+static ALLOC: snmalloc_rs::SnMalloc = snmalloc_rs::SnMalloc;
+
+/// Japanese word queries (kanji, kana, romaji and inflected)
+const NATIVE_WORDS: &[&str] = &[
+    "音楽",
+    "おはよう",
+    "kanji",
+    "食べられなかった",
+    "日本語能力試験",
+];
+
+/// Foreign word queries in various languages
+const FOREIGN_WORDS: &[(&str, Language)] = &[
+    ("house", Language::English),
+    ("to eat", Language::English),
+    ("Haustür", Language::German),
+    ("l'eau", Language::French),
+];
+
+/// Kanji queries (literals, meanings and readings)
+const KANJI: &[&str] = &["事", "音楽", "music", "ジ"];
+
+fn words(c: &mut Criterion) {
+    let mut group = c.benchmark_group("word search");
+
+    for inp in NATIVE_WORDS {
+        let query = query(inp, SearchTarget::Words, Language::English);
+        group.bench_with_input(BenchmarkId::new("native", inp), &query, |b, q| {
+            b.iter(|| word_search(q))
+        });
+    }
+
+    for (inp, lang) in FOREIGN_WORDS {
+        let query = query(inp, SearchTarget::Words, *lang);
+        let id = format!("{inp} ({})", lang.to_query_format());
+        group.bench_with_input(BenchmarkId::new("foreign", id), &query, |b, q| {
+            b.iter(|| word_search(q))
+        });
+    }
+
+    let query = query("事 ジ", SearchTarget::Words, Language::English);
+    group.bench_function("kanji reading", |b| b.iter(|| word_search(&query)));
+
+    group.finish();
+}
+
+fn kanji(c: &mut Criterion) {
+    let mut group = c.benchmark_group("kanji search");
+
+    for inp in KANJI {
+        let query = query(inp, SearchTarget::Kanji, Language::English);
+        group.bench_with_input(BenchmarkId::from_parameter(inp), &query, |b, q| {
+            b.iter(|| search::kanji::search(q).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+#[inline]
+fn word_search(query: &Query) {
+    let _res = SearchExecutor::new(search::word::Search::new(query)).run();
+}
+
+fn setup(c: &mut Criterion) {
+    benches::load();
+    words(c);
+    kanji(c);
+}
+
+criterion_group!(search_benches, setup);
+criterion_main!(search_benches);
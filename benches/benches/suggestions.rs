@@ -0,0 +1,50 @@
+use benches::suggestion_request;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use types::{
+    api::app::completions::Request,
+    jotoba::{language::Language, search::SearchTarget},
+};
+
+#[global_allocator]
+static ALLOC: snmalloc_rs::SnMalloc = snmalloc_rs::SnMalloc;
+
+/// Inputs as they get sent while typing
+const INPUTS: &[(&str, SearchTarget, Language)] = &[
+    ("た", SearchTarget::Words, Language::English),
+    ("たべ", SearchTarget::Words, Language::English),
+    ("taber", SearchTarget::Words, Language::English),
+    ("hou", SearchTarget::Words, Language::English),
+    ("Haus", SearchTarget::Words, Language::German),
+    ("事 ", SearchTarget::Words, Language::English),
+    ("mus", SearchTarget::Kanji, Language::English),
+    ("たなか", SearchTarget::Names, Language::English),
+];
+
+fn suggestions(c: &mut Criterion) {
+    benches::load();
+
+    let mut group = c.benchmark_group("suggestions");
+
+    for (inp, target, lang) in INPUTS {
+        let id = format!("{inp} ({target:?})");
+        group.bench_function(BenchmarkId::from_parameter(id), |b| {
+            b.iter(|| {
+                let request = suggestion_request(inp, *target, *lang);
+                api::app::completions::suggestion_ep_inner(request).unwrap()
+            })
+        });
+    }
+
+    let request = || Request {
+        smart: true,
+        ..suggestion_request("おん", SearchTarget::Words, Language::English)
+    };
+    group.bench_function("smart", |b| {
+        b.iter(|| api::app::completions::suggestion_ep_inner(request()).unwrap())
+    });
+
+    group.finish();
+}
+
+criterion_group!(suggestion_benches, suggestions);
+criterion_main!(suggestion_benches);
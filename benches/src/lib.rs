@@ -0,0 +1,82 @@
+use search::query::{parser::QueryParser, Query, UserSettings};
+use std::path::{Path, PathBuf};
+use types::{
+    api::app::completions::Request,
+    jotoba::{language::Language, search::SearchTarget},
+};
+
+/// Environment variable pointing to the resource set to benchmark against
+pub const FIXTURE_ENV: &str = "JOTOBA_BENCH_RESOURCES";
+
+/// Resource set used if `FIXTURE_ENV` is not set
+const DEFAULT_FIXTURES: &str = "../resources";
+
+/// Returns the folder of the resource set to benchmark against
+pub fn fixture_dir() -> PathBuf {
+    std::env::var(FIXTURE_ENV)
+        .unwrap_or_else(|_| DEFAULT_FIXTURES.to_string())
+        .into()
+}
+
+/// Loads resources, indexes, suggestions and the sentence parser of the fixture resource set.
+/// Benchmark results are only comparable if they were run against the same resource set
+pub fn load() {
+    let dir = fixture_dir();
+    assert!(
+        dir.exists(),
+        "Fixture resources not found at {dir:?}. Set {FIXTURE_ENV} to their location"
+    );
+    load_from(&dir);
+}
+
+fn load_from(dir: &Path) {
+    rayon::scope(|s| {
+        s.spawn(|_| {
+            resources::load(dir.join("storage_data")).expect("Failed to load resources");
+        });
+        s.spawn(|_| {
+            indexes::storage::load(dir.join("indexes")).expect("Failed to load indexes");
+        });
+        s.spawn(|_| {
+            resources::wait();
+            let gloss_langs = api::internal::reload::suggestion_gloss_langs();
+            indexes::storage::suggestions::load(
+                dir.join("suggestions"),
+                &Default::default(),
+                gloss_langs,
+            )
+            .expect("Failed to load suggestions");
+        });
+        s.spawn(|_| {
+            sentence_reader::load_parser(dir.join("unidic-mecab"));
+        });
+    });
+
+    // Cached suggestions would only measure the cache
+    api::app::completions::cache::init(0, Default::default());
+}
+
+/// Parses `inp` to a query for the given search target
+pub fn query(inp: &str, target: SearchTarget, lang: Language) -> Query {
+    let settings = UserSettings {
+        user_lang: lang,
+        show_english: true,
+        ..UserSettings::default()
+    };
+    QueryParser::new(inp.to_string(), target, settings)
+        .parse()
+        .expect("Invalid benchmark query")
+}
+
+/// Creates a suggestion request for `inp`
+pub fn suggestion_request(inp: &str, target: SearchTarget, lang: Language) -> Request {
+    Request {
+        input: inp.to_string(),
+        lang: lang.to_query_format().to_string(),
+        search_target: target,
+        radicals: vec![],
+        hashtag: false,
+        smart: false,
+        session: None,
+    }
+}
@@ -0,0 +1,46 @@
+/**
+ * Loads heavy sections of result pages after the page got displayed. Elements with a
+ * "data-fragment" attribute get filled with the HTML fragment at the given URL. If the element
+ * also has a "data-fragment-slots" attribute, the parts of the fragment get moved into the
+ * elements with a matching "data-slot" attribute instead.
+ */
+function loadFragments() {
+    document.querySelectorAll("[data-fragment]").forEach(loadFragment);
+}
+
+// Loads a single fragment into the element `target`
+function loadFragment(target) {
+    fetch(target.dataset.fragment)
+        .then(response => {
+            if (!response.ok) {
+                throw new Error("Loading fragment failed: " + response.status);
+            }
+            return response.text();
+        })
+        .then(html => {
+            if (target.hasAttribute("data-fragment-slots")) {
+                fillSlots(html);
+            } else {
+                target.innerHTML = html;
+                if (html.trim().length > 0) {
+                    target.classList.remove("hidden");
+                }
+            }
+            document.dispatchEvent(new CustomEvent("fragmentloaded", { detail: target }));
+        })
+        .catch(err => console.error(err));
+}
+
+// Moves the parts of `html` into the slots with the same name
+function fillSlots(html) {
+    let container = document.createElement("div");
+    container.innerHTML = html;
+
+    container.querySelectorAll("[data-slot]").forEach(part => {
+        document.querySelectorAll("[data-slot='" + part.dataset.slot + "']").forEach(slot => {
+            slot.innerHTML = part.innerHTML;
+        });
+    });
+}
+
+Util.awaitDocumentReady(loadFragments);
@@ -297,6 +297,56 @@ function toggleCompounds(event) {
     event.target.parentElement.children[0].classList.toggle("closed");
 }
 
+// Loads the next page of reading compounds and appends them to the list above the button
+function loadMoreCompounds(event, kanjiLiteral, kind) {
+    let button = event.target;
+    let list = button.previousElementSibling;
+    let page = parseInt(button.dataset.page) + 1;
+
+    let payload = {
+        "query_str": kanjiLiteral,
+        "page": page,
+        "settings": {
+            "user_lang": Settings.language.searchLang.val,
+            "show_english": Settings.search.alwaysShowEnglish.val,
+            "page_size": 10,
+            "show_example_sentences": false,
+            "sentence_furigana": false,
+        }
+    };
+
+    $.ajax({
+        type : "POST",
+        url : "/api/app/k_comps",
+        data: JSON.stringify(payload),
+        headers: {
+            'Content-Type': 'application/json'
+        },
+        success : function(result) {
+            let compounds = result.compounds[0] || {};
+
+            for (let word of compounds[kind] || []) {
+                let entry = document.createElement("span");
+                let link = document.createElement("a");
+                link.classList.add("clickable", "green");
+                link.href = "/search/" + encodeURIComponent(word.jp);
+                link.innerText = word.jp;
+                entry.appendChild(link);
+                entry.appendChild(document.createTextNode(" 【" + word.kana + "】" + word.translations.join(", ")));
+                list.appendChild(entry);
+            }
+
+            button.dataset.page = page;
+            if (!compounds["has_more_" + kind]) {
+                button.remove();
+            }
+        },
+        error : function(result) {
+            console.log("caught error loading compounds:", result);
+        }
+    });
+}
+
 // Toggle all compounds on keypress
 $(document).on("keypress", (event) => {
     if ($('input:text').is(":focus")) return;
@@ -20,9 +20,16 @@ Settings.search = {
     showEnglishOnTop: { isCookie: true, id: "show_english_on_top", dataType: "boolean", val: false },
     showExampleSentences: { isCookie: true, id: "show_sentences", dataType: "boolean", val: true },
     showFurigana: { isCookie: true, id: "sentence_furigana", dataType: "boolean", val: true },
+    sentenceScript: { isCookie: true, id: "sentence_script", dataType: "string", val: "native" },
+    maxKatakanaPercent: { isCookie: true, id: "sentence_max_katakana", dataType: "int", val: 100 },
+    maxKanjiGrade: { isCookie: true, id: "sentence_max_kanji_grade", dataType: "int", val: 0 },
     focusSearchbar: { isCookie: false, id: "focus_searchbar", dataType: "boolean", val: false },
     selectSearchbarContent: { isCookie: false, id: "select_searchbar_content", dataType: "boolean", val: false },
     itemsPerPage: { isCookie: true, id: "items_per_page", dataType: "int", val: 10 },
+    showWaniKani: { isCookie: true, id: "show_wk_level", dataType: "boolean", val: false },
+    sectionOrder: { isCookie: true, id: "section_order", dataType: "string", val: "words,kanji,sentences,names" },
+    showKanjiPanel: { isCookie: true, id: "show_kanji_panel", dataType: "boolean", val: true },
+    filterPresets: { isCookie: true, id: "filter_presets", dataType: "string", val: "" },
     kanjiPerPage: { isCookie: true, id: "kanji_page_size", dataType: "int", val: 4 },
     showFullGraph: { isCookie: false, id: "show_full_graph", dataType: "boolean", val: true },
 }
@@ -108,6 +115,11 @@ alterLanguage_page = function (html, value) {
     Settings.alterLanguage("pageLang", value, true);
 }
 
+// Used for the Choices-Hook on function calls
+alterSentenceScript = function (html, value) {
+    Settings.alterSearch("sentenceScript", value);
+}
+
 // Alters a "search" setting and reloads if needed
 Settings.alterSearch = function (key, value, updateSub) {
     Settings.search[key].val = value;
@@ -47,6 +47,16 @@ OverlaySettings.updateDropdowns = function() {
             choicesInner[1].children[0].innerHTML = e.innerHTML;
         }
     });
+
+    // "Search" page
+    document.querySelectorAll("#sentence-script-select > .choices__item--choice").forEach((e) => {
+        if (e.dataset.value == Settings.search.sentenceScript.val) {
+            let choicesInner = e.parentElement.parentElement.parentElement.children[0].children;
+
+            choicesInner[0].children[0].innerHTML = e.innerHTML;
+            choicesInner[1].children[0].innerHTML = e.innerHTML;
+        }
+    });
 }
 
 // Updates all checkboxes
@@ -56,6 +66,8 @@ OverlaySettings.updateCheckboxes = function() {
     Util.setMdlCheckboxState("show_eng_on_top_settings", Settings.search.showEnglishOnTop.val);
     Util.setMdlCheckboxState("show_example_sentences_settings", Settings.search.showExampleSentences.val);
     Util.setMdlCheckboxState("show_sentence_furigana_settings", Settings.search.showFurigana.val);
+    Util.setMdlCheckboxState("show_wk_level_settings", Settings.search.showWaniKani.val);
+    Util.setMdlCheckboxState("show_kanji_panel_settings", Settings.search.showKanjiPanel.val);
     Util.setMdlCheckboxState("focus_search_bar_settings", Settings.search.focusSearchbar.val);
     Util.setMdlCheckboxState("select_searchbar_content_settings", Settings.search.selectSearchbarContent.val);
    
@@ -85,5 +97,9 @@ OverlaySettings.updateSliders = function() {
 // Updates all inputs
 OverlaySettings.updateInputs = function() {
     setInput("#items_per_page_input", Settings.search.itemsPerPage.val);
+    setInput("#filter_presets_input", Settings.search.filterPresets.val);
+    setInput("#section_order_input", Settings.search.sectionOrder.val);
     setInput("#kanji_per_page_input", Settings.search.kanjiPerPage.val);
+    setInput("#max_katakana_input", Settings.search.maxKatakanaPercent.val);
+    setInput("#max_kanji_grade_input", Settings.search.maxKanjiGrade.val);
 }
\ No newline at end of file
@@ -1,8 +1,8 @@
 // Object reference for sentence reader
 const sr = document.getElementById("sr");
 
-// Enable sentence-example expander
-$(".expander").on("click", (event) => {
+// Enable sentence-example expander. Example sentences are loaded as fragments, so the handler is delegated
+$(document).on("click", ".expander", (event) => {
     event.target.classList.toggle("on");
     event.target.parentElement.children[0].classList.toggle("collapsed");
 });
@@ -12,6 +12,9 @@ hideUnusedExpanders();
 centerSentenceReaderIfNeeded();
 var screenWidth = $(window).width();
 
+// Example sentences are loaded after the page
+document.addEventListener("fragmentloaded", hideUnusedExpanders);
+
 $(window).resize(() => {
     // Mobile scrolling sends resize events because of the (dis-)appearing url input. Simple fix: ignore height changes.
     if ($(window).width() == screenWidth) {
@@ -106,8 +106,8 @@ function loadSuggestionApiData(result) {
             secondaryResult = "(" + result.suggestions[i].primary + ")";
         }
 
-        // Get target page
-        var currentPage = JotoTools.getCurrentSearchType();
+        // Get target page. Mixed suggestions link to the search type they belong to
+        var currentPage = result.suggestions[i].search_type || JotoTools.getCurrentSearchType();
 
         // Generate the /search/
         let searchValue = "";
@@ -1,7 +1,6 @@
 use crate::webserver::prepare_data;
 use config::Config;
 use ngindex::index_framework::traits::{backend::Backend, storage::IndexStorage};
-use types::jotoba::language::Language;
 
 /// Checks resources and returns `true` if required features are available
 pub fn resources() -> bool {
@@ -41,7 +40,17 @@ fn check_all() -> bool {
     println!("Testing indexes");
     let ind = indexes();
 
-    res && ind
+    println!("Testing integrity");
+    let integrity = integrity();
+
+    res && ind && integrity
+}
+
+/// Checks references between the loaded resources and prints a report of all issues found
+fn integrity() -> bool {
+    let report = resources::integrity::check(resources::get());
+    print!("{report}");
+    report.is_ok()
 }
 
 fn indexes() -> bool {
@@ -51,9 +60,8 @@ fn indexes() -> bool {
 fn sentences() -> bool {
     let sentence_retrieve = resources::get().sentences();
 
-    let fg_index = indexes::get().sentence().foreign();
-
-    for language in Language::iter_word() {
+    for language in indexes::get().sentence().languages() {
+        let fg_index = indexes::get().sentence().foreign(language).unwrap();
         for id in fg_index.storage().iter().map(|i| *i.document()) {
             if sentence_retrieve.by_id(id).is_none() {
                 println!("Sentence index ({language:?}) don't not match");
@@ -98,11 +106,8 @@ fn names() -> bool {
 fn words() -> bool {
     let word_retrieve = resources::get().words();
 
-    for language in Language::iter_word() {
-        let w_index = indexes::get()
-            .word()
-            .foreign(language)
-            .expect(&format!("Missing index {:?}", language));
+    for language in indexes::get().word().languages() {
+        let w_index = indexes::get().word().foreign(language).unwrap();
 
         for doc_vec in w_index.storage().iter() {
             let seq_id = *doc_vec.document();
@@ -127,8 +132,8 @@ fn words() -> bool {
 fn regex() -> bool {
     let w_retrieve = resources::get().words();
 
-    let regex_index = indexes::get().word().regex();
-    for (_, words) in regex_index.iter() {
+    let substring_index = indexes::get().word().substring();
+    for (_, words) in substring_index.iter() {
         if words.iter().any(|i| w_retrieve.by_sequence(*i).is_none()) {
             println!("Regex index invalid");
             return false;
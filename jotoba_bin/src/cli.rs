@@ -1,6 +1,6 @@
 use std::process::exit;
 
-use argparse::{ArgumentParser, Print, StoreTrue};
+use argparse::{ArgumentParser, Print, Store, StoreOption, StoreTrue};
 
 /// Command line arguments
 #[derive(Default)]
@@ -9,11 +9,66 @@ pub struct Options {
     pub start: bool,
     pub debug: bool,
     pub check_resources: bool,
+    /// Write a snapshot of all runtime state into the given file
+    pub snapshot: Option<String>,
+    /// Restore a snapshot from the given file
+    pub restore: Option<String>,
+    /// Build the indexes into the given folder
+    pub build_indexes: Option<String>,
+    /// Split words, names and sentences off the storage data into segments in the given folder
+    pub split_resources: Option<String>,
+    /// Write an offline bundle into the given file
+    pub export_bundle: Option<String>,
+    /// Comma separated languages of the glosses and translations in the bundle
+    pub bundle_languages: String,
+    /// Import names from the given JMnedict file into the storage data
+    pub import_names: Option<String>,
+    /// Replace the aliases of names with the ones of the given file
+    pub import_aliases: Option<String>,
+    /// Import pitch accents from the given comma separated files, highest priority first
+    pub import_pitch: Option<String>,
+    /// How to resolve pitch accents of sources which disagree
+    pub pitch_resolution: String,
+    /// Import the configured word and kanji frequency lists
+    pub import_frequencies: bool,
+    /// Import the configured word and kanji JLPT lists
+    pub import_jlpt: bool,
+    /// Append new sentences from the given extracted Tatoeba export
+    pub update_sentences: Option<String>,
+    /// Download the latest Tatoeba export before appending new sentences
+    pub download_sentences: bool,
+    /// Replace the search radicals of kanji with the given radkfile or kradfile
+    pub import_radicals: Option<String>,
+    /// Replace the WaniKani levels of words and kanji with the given subject mapping
+    pub import_wanikani: Option<String>,
+    /// Replace the mnemonics of kanji with the ones of the given file
+    pub import_mnemonics: Option<String>,
+    /// Replace the example sentences of senses with the ones of the given Tanaka corpus indices
+    pub import_sense_sentences: Option<String>,
+    /// Replace the loanword sources of senses with the ones of the given JMdict file
+    pub import_gairaigo: Option<String>,
+    /// Update the revision dates of words with the given JMdict release
+    pub import_revisions: Option<String>,
+    /// Link transitive and intransitive verbs forming a pair
+    pub link_transitivity: bool,
+    /// Resolve cross references and antonyms of senses to the referenced words
+    pub link_references: bool,
+    /// Compute the script statistics of all sentences
+    pub compute_sentence_stats: bool,
+    /// Extract collocations from the given extracted Tatoeba export
+    pub extract_collocations: Option<String>,
+    /// Whether extracted collocations get added to or replace the existing ones
+    pub collocation_mode: String,
 }
 
 // Parse CLI args
 pub fn parse() -> Options {
-    let mut options = Options::default();
+    let mut options = Options {
+        pitch_resolution: "first".to_string(),
+        collocation_mode: "augment".to_string(),
+        bundle_languages: "eng".to_string(),
+        ..Options::default()
+    };
     {
         let mut ap = ArgumentParser::new();
         ap.set_description("A multilang japanese dictionary");
@@ -33,7 +88,157 @@ pub fn parse() -> Options {
         ap.refer(&mut options.check_resources).add_option(
             &["--check", "-c"],
             StoreTrue,
-            "Check resources",
+            "Check resources, indexes and references between entries and print a report of all issues",
+        );
+
+        ap.refer(&mut options.snapshot).add_option(
+            &["--snapshot"],
+            StoreOption,
+            "Snapshot resources, indexes, suggestions and news into a file",
+        );
+
+        ap.refer(&mut options.restore).add_option(
+            &["--restore"],
+            StoreOption,
+            "Restore a snapshot created with --snapshot",
+        );
+
+        ap.refer(&mut options.build_indexes).add_option(
+            &["--build-indexes"],
+            StoreOption,
+            "Build the indexes from the configured resources into the given folder",
+        );
+
+        ap.refer(&mut options.split_resources).add_option(
+            &["--split-resources"],
+            StoreOption,
+            "Split words, names and sentences off the storage data into lazily loaded segments",
+        );
+
+        ap.refer(&mut options.export_bundle).add_option(
+            &["--export-bundle"],
+            StoreOption,
+            "Write a compact bundle of words, kanji and sentences for offline clients into the given file",
+        );
+
+        ap.refer(&mut options.bundle_languages).add_option(
+            &["--bundle-languages"],
+            Store,
+            "Comma separated languages of the glosses and translations in the bundle, eg. 'eng,ger' (default: 'eng')",
+        );
+
+        ap.refer(&mut options.import_names).add_option(
+            &["--import-names"],
+            StoreOption,
+            "Update the names of the storage data from a JMnedict file. Interrupted imports continue where they stopped",
+        );
+
+        ap.refer(&mut options.import_aliases).add_option(
+            &["--import-aliases"],
+            StoreOption,
+            "Replace the historical and alternative names of places and eras with the ones of a tab separated file (name, kind, alias)",
+        );
+
+        ap.refer(&mut options.import_pitch).add_option(
+            &["--import-pitch"],
+            StoreOption,
+            "Import pitch accents from comma separated files (word, kana, drops), highest priority first",
+        );
+
+        ap.refer(&mut options.pitch_resolution).add_option(
+            &["--pitch-resolution"],
+            Store,
+            "How to choose accents if sources disagree: 'first' (default) or 'union'",
+        );
+
+        ap.refer(&mut options.import_frequencies).add_option(
+            &["--import-frequencies"],
+            StoreTrue,
+            "Import the word and kanji frequency lists set in the [import] section of the config. Word frequencies replace the ones extracted by --extract-collocations and vice versa",
+        );
+
+        ap.refer(&mut options.import_jlpt).add_option(
+            &["--import-jlpt"],
+            StoreTrue,
+            "Replace the JLPT levels of words and kanji with the CSVs set in the [import] section of the config",
+        );
+
+        ap.refer(&mut options.update_sentences).add_option(
+            &["--update-sentences"],
+            StoreOption,
+            "Append new sentences from a folder with an extracted Tatoeba export (sentences.csv, links.csv, jpn_indices.csv, tags.csv)",
+        );
+
+        ap.refer(&mut options.download_sentences).add_option(
+            &["--download-sentences"],
+            StoreTrue,
+            "Download the latest Tatoeba export into the folder of --update-sentences first",
+        );
+
+        ap.refer(&mut options.import_radicals).add_option(
+            &["--import-radicals"],
+            StoreOption,
+            "Replace the radicals used to search kanji with the given UTF-8 radkfile or kradfile",
+        );
+
+        ap.refer(&mut options.import_wanikani).add_option(
+            &["--import-wanikani"],
+            StoreOption,
+            "Replace the WaniKani levels of words and kanji with a CSV mapping subjects (id, type, level, entry) to JMdict entries and kanji",
+        );
+
+        ap.refer(&mut options.import_mnemonics).add_option(
+            &["--import-mnemonics"],
+            StoreOption,
+            "Replace the mnemonics of kanji with the ones of a tab separated file (kanji, mnemonic)",
+        );
+
+        ap.refer(&mut options.import_sense_sentences).add_option(
+            &["--import-sense-sentences"],
+            StoreOption,
+            "Replace the example sentences of senses with the ones stated in the Tanaka corpus word indices (Tatoeba's jpn_indices.csv)",
+        );
+
+        ap.refer(&mut options.import_gairaigo).add_option(
+            &["--import-gairaigo"],
+            StoreOption,
+            "Replace the source languages and original terms of loanwords with the ones of a JMdict XML file",
+        );
+
+        ap.refer(&mut options.import_revisions).add_option(
+            &["--import-revisions"],
+            StoreOption,
+            "Mark words which were added or changed in a JMdict XML release compared to the previously imported one as new (#new, /api/v2/words/recent)",
+        );
+
+        ap.refer(&mut options.link_transitivity).add_option(
+            &["--link-transitivity"],
+            StoreTrue,
+            "Link transitive and intransitive verbs which form a pair by their readings and cross references. Existing links are kept",
+        );
+
+        ap.refer(&mut options.link_references).add_option(
+            &["--link-references"],
+            StoreTrue,
+            "Resolve the cross references and antonyms of senses to the referenced words, replacing all resolved references",
+        );
+
+        ap.refer(&mut options.compute_sentence_stats).add_option(
+            &["--compute-sentence-stats"],
+            StoreTrue,
+            "Compute the share of katakana and the highest kanji grade of all sentences, used to hide sentences with too many loanwords or rare kanji",
+        );
+
+        ap.refer(&mut options.extract_collocations).add_option(
+            &["--extract-collocations"],
+            StoreOption,
+            "Extract collocations and corpus frequencies of words from the tokenized sentences (jpn_indices.csv) of a folder with an extracted Tatoeba export",
+        );
+
+        ap.refer(&mut options.collocation_mode).add_option(
+            &["--collocation-mode"],
+            Store,
+            "Whether extracted collocations get added to the existing ones: 'augment' (default) or 'replace'",
         );
 
         ap.parse_args_or_exit();
@@ -44,5 +249,10 @@ pub fn parse() -> Options {
         exit(1);
     }
 
+    if options.snapshot.is_some() && options.restore.is_some() {
+        println!("Can't use snapshot and restore at once");
+        exit(1);
+    }
+
     options
 }
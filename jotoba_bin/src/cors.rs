@@ -0,0 +1,237 @@
+//! Cross origin and frame embedding policies of all routes. Policies are configured per path
+//! prefix in the `cors` section of the config file. See [`CorsRule`] for an example.
+
+use std::{
+    future::{ready, Future, Ready},
+    pin::Pin,
+    rc::Rc,
+};
+
+use actix_web::{
+    body::EitherBody,
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    http::{
+        header::{
+            HeaderMap, HeaderValue, ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS,
+            ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS, ACCESS_CONTROL_MAX_AGE,
+            ACCESS_CONTROL_REQUEST_HEADERS, ACCESS_CONTROL_REQUEST_METHOD, CONTENT_SECURITY_POLICY,
+            ORIGIN, RETRY_AFTER, VARY, X_FRAME_OPTIONS,
+        },
+        Method,
+    },
+    Error, HttpResponse,
+};
+use config::CorsRule;
+use log::debug;
+
+use crate::rate_limit::{API_KEY_HEADER, LIMIT_HEADER, REMAINING_HEADER};
+
+/// How long browsers are allowed to cache preflight responses in seconds
+const PREFLIGHT_MAX_AGE: u32 = 86400;
+
+/// Header marking deprecated routes
+pub const DEPRECATION_HEADER: &str = "Deprecation";
+
+/// Middleware applying the configured [`CorsRule`]s
+pub struct Cors(Rc<Vec<CorsRule>>);
+
+impl Cors {
+    #[inline]
+    pub fn new(rules: Vec<CorsRule>) -> Self {
+        Self(Rc::new(rules))
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for Cors
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = CorsMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(CorsMiddleware {
+            service: Rc::new(service),
+            rules: self.0.clone(),
+        }))
+    }
+}
+
+pub struct CorsMiddleware<S> {
+    service: Rc<S>,
+    rules: Rc<Vec<CorsRule>>,
+}
+
+impl<S> CorsMiddleware<S> {
+    /// Returns the rule with the longest path matching `path`
+    fn find_rule(&self, path: &str) -> Option<usize> {
+        self.rules
+            .iter()
+            .enumerate()
+            .filter(|(_, rule)| rule.matches(path))
+            .max_by_key(|(_, rule)| rule.path.len())
+            .map(|(pos, _)| pos)
+    }
+}
+
+impl<S, B> Service<ServiceRequest> for CorsMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let rule = match self.find_rule(req.path()) {
+            Some(rule) => rule,
+            None => {
+                let fut = self.service.call(req);
+                return Box::pin(async move { Ok(fut.await?.map_into_left_body()) });
+            }
+        };
+
+        let origin = req
+            .headers()
+            .get(ORIGIN)
+            .and_then(|i| i.to_str().ok())
+            .map(|i| i.to_string());
+
+        if req.method() == Method::OPTIONS
+            && req.headers().contains_key(ACCESS_CONTROL_REQUEST_METHOD)
+        {
+            let rule = &self.rules[rule];
+            let method = req
+                .headers()
+                .get(ACCESS_CONTROL_REQUEST_METHOD)
+                .and_then(|i| i.to_str().ok())
+                .unwrap_or_default();
+
+            let res = match origin {
+                Some(ref origin) if rule.allows_origin(origin) && rule.allows_method(method) => {
+                    let mut res = HttpResponse::NoContent().finish();
+                    add_cors_headers(res.headers_mut(), rule, origin);
+                    let requested = req.headers().get(ACCESS_CONTROL_REQUEST_HEADERS);
+                    add_allowed_headers(res.headers_mut(), rule, requested);
+                    res.headers_mut()
+                        .insert(ACCESS_CONTROL_MAX_AGE, PREFLIGHT_MAX_AGE.into());
+                    res
+                }
+                _ => {
+                    debug!(
+                        "Rejected preflight request from {origin:?} to {}",
+                        req.path()
+                    );
+                    HttpResponse::Forbidden().finish()
+                }
+            };
+
+            return Box::pin(ready(Ok(req.into_response(res).map_into_right_body())));
+        }
+
+        let method = req.method().clone();
+        let rules = self.rules.clone();
+        let service = self.service.clone();
+        Box::pin(async move {
+            let mut res = service.call(req).await?;
+            let rule = &rules[rule];
+            let headers = res.headers_mut();
+
+            if let Some(origin) = origin {
+                if rule.allows_origin(&origin) && rule.allows_method(method.as_str()) {
+                    add_cors_headers(headers, rule, &origin);
+                }
+            }
+
+            if let Some(ref ancestors) = rule.frame_ancestors {
+                add_frame_headers(headers, ancestors);
+            }
+
+            Ok(res.map_into_left_body())
+        })
+    }
+}
+
+fn add_cors_headers(headers: &mut HeaderMap, rule: &CorsRule, origin: &str) {
+    let allowed_origin = if rule.origins.iter().any(|i| i == "*") {
+        HeaderValue::from_static("*")
+    } else {
+        // The response depends on the origin if not all origins are allowed
+        headers.append(VARY, HeaderValue::from_static("Origin"));
+        match HeaderValue::from_str(origin) {
+            Ok(origin) => origin,
+            Err(_) => return,
+        }
+    };
+
+    headers.insert(ACCESS_CONTROL_ALLOW_ORIGIN, allowed_origin);
+
+    if let Ok(methods) = HeaderValue::from_str(&rule.methods.join(", ")) {
+        headers.insert(ACCESS_CONTROL_ALLOW_METHODS, methods);
+    }
+
+    let exposed = [
+        LIMIT_HEADER,
+        REMAINING_HEADER,
+        RETRY_AFTER.as_str(),
+        api::app::search::SKIPPED_STAGES_HEADER,
+        DEPRECATION_HEADER,
+    ];
+    if let Ok(exposed) = HeaderValue::from_str(&exposed.join(", ")) {
+        headers.insert(ACCESS_CONTROL_EXPOSE_HEADERS, exposed);
+    }
+}
+
+/// Adds the request headers `rule` allows to a preflight response. `requested` are the headers
+/// the preflight request asked for
+fn add_allowed_headers(headers: &mut HeaderMap, rule: &CorsRule, requested: Option<&HeaderValue>) {
+    if rule.allows_all_headers() {
+        // The response depends on the requested headers
+        headers.append(
+            VARY,
+            HeaderValue::from_static("Access-Control-Request-Headers"),
+        );
+        if let Some(requested) = requested {
+            headers.insert(ACCESS_CONTROL_ALLOW_HEADERS, requested.clone());
+        }
+        return;
+    }
+
+    let allowed = match rule.headers {
+        Some(ref allowed) => allowed.join(", "),
+        None => format!("Content-Type, {API_KEY_HEADER}"),
+    };
+    if let Ok(allowed) = HeaderValue::from_str(&allowed) {
+        headers.insert(ACCESS_CONTROL_ALLOW_HEADERS, allowed);
+    }
+}
+
+fn add_frame_headers(headers: &mut HeaderMap, ancestors: &[String]) {
+    let policy = if ancestors.is_empty() {
+        "'none'".to_string()
+    } else {
+        ancestors.join(" ")
+    };
+
+    if let Ok(csp) = HeaderValue::from_str(&format!("frame-ancestors {policy}")) {
+        headers.insert(CONTENT_SECURITY_POLICY, csp);
+    }
+
+    // Fallback for older browsers which don't support `frame-ancestors`. X-Frame-Options can't
+    // express lists of sources, so it's only set for the policies it's able to express
+    let frame_options = match ancestors {
+        [] => Some("DENY"),
+        [only] if only == "'self'" => Some("SAMEORIGIN"),
+        _ => None,
+    };
+    if let Some(frame_options) = frame_options {
+        headers.insert(X_FRAME_OPTIONS, HeaderValue::from_static(frame_options));
+    }
+}
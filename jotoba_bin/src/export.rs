@@ -0,0 +1,144 @@
+use crate::webserver::read_resources;
+use config::Config;
+use resources::ResourceStorage;
+use std::{
+    collections::HashSet,
+    error::Error,
+    fs::{self, File},
+    io::{BufWriter, Write},
+    str::FromStr,
+};
+use types::{
+    bundle::{BundleKanji, BundleSense, BundleSentence, BundleWord, BundleWriter},
+    jotoba::language::Language,
+};
+
+/// Writes an offline bundle of all words, kanji and sentences with glosses and translations in
+/// the comma separated `languages` into `out`
+pub fn bundle(config: &Config, out: &str, languages: &str) -> Result<(), Box<dyn Error>> {
+    let languages = languages
+        .split(',')
+        .map(|i| Language::from_str(i.trim()).map_err(|_| format!("Unknown language: {i}")))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let storage = read_resources(
+        &config.get_storage_data_path(),
+        config.server.get_overlay_folder(),
+        config.server.get_resource_segments(),
+        None,
+    )?;
+
+    let mut writer = BundleWriter::new(&languages);
+    let sentences = add_sentences(&mut writer, &storage, &languages)?;
+    let words = add_words(&mut writer, &storage, &languages, &sentences)?;
+    let kanji = add_kanji(&mut writer, &storage)?;
+
+    // Write into a temporary file first so an existing bundle stays intact if writing fails
+    let tmp = format!("{out}.tmp");
+    let mut file = BufWriter::new(File::create(&tmp)?);
+    writer.write(&mut file)?;
+    file.flush()?;
+    drop(file);
+    fs::rename(&tmp, out)?;
+
+    println!(
+        "Bundled {words} words, {kanji} kanji and {} sentences",
+        sentences.len()
+    );
+    Ok(())
+}
+
+/// Adds all sentences translated into one of `languages` and returns their IDs
+fn add_sentences(
+    writer: &mut BundleWriter,
+    storage: &ResourceStorage,
+    languages: &[Language],
+) -> Result<HashSet<u32>, Box<dyn Error>> {
+    let mut added = HashSet::new();
+
+    for sentence in storage.sentences().iter() {
+        let translations: Vec<_> = sentence
+            .translations
+            .iter()
+            .filter(|i| languages.contains(&i.language))
+            .map(|i| (i.language, i.text.clone()))
+            .collect();
+        if translations.is_empty() {
+            continue;
+        }
+
+        writer.add_sentence(&BundleSentence {
+            id: sentence.id,
+            japanese: sentence.japanese.clone(),
+            furigana: sentence.furigana.clone(),
+            translations,
+        })?;
+        added.insert(sentence.id);
+    }
+
+    Ok(added)
+}
+
+/// Adds all words having senses in one of `languages`. Example sentences which aren't in the
+/// bundle are dropped
+fn add_words(
+    writer: &mut BundleWriter,
+    storage: &ResourceStorage,
+    languages: &[Language],
+    sentences: &HashSet<u32>,
+) -> Result<usize, Box<dyn Error>> {
+    let mut count = 0;
+
+    for word in storage.words().iter() {
+        let senses: Vec<_> = word
+            .senses
+            .iter()
+            .filter(|i| languages.contains(&i.language))
+            .map(|sense| BundleSense {
+                language: sense.language,
+                part_of_speech: sense.part_of_speech.iter().map(|i| (*i).into()).collect(),
+                glosses: sense.glosses.iter().map(|i| i.gloss.clone()).collect(),
+                example_sentence: sense.example_sentence.filter(|i| sentences.contains(i)),
+            })
+            .collect();
+        if senses.is_empty() {
+            continue;
+        }
+
+        writer.add_word(&BundleWord {
+            sequence: word.sequence,
+            common: word.is_common(),
+            jlpt: word.get_jlpt_lvl(),
+            kana: word.reading.kana.reading.clone(),
+            kanji: word.reading.kanji.as_ref().map(|i| i.reading.clone()),
+            furigana: word.furigana.clone(),
+            senses,
+        })?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+fn add_kanji(
+    writer: &mut BundleWriter,
+    storage: &ResourceStorage,
+) -> Result<usize, Box<dyn Error>> {
+    let mut count = 0;
+
+    for kanji in storage.kanji().iter() {
+        writer.add_kanji(&BundleKanji {
+            literal: kanji.literal,
+            stroke_count: kanji.stroke_count,
+            grade: kanji.grade,
+            jlpt: kanji.jlpt,
+            frequency: kanji.frequency,
+            onyomi: kanji.onyomi.clone(),
+            kunyomi: kanji.kunyomi.clone(),
+            meanings: kanji.meanings.clone(),
+        })?;
+        count += 1;
+    }
+
+    Ok(count)
+}
@@ -0,0 +1,371 @@
+use config::Config;
+use indexes::storage::{build, sentence, suggestions};
+use resources::import::{self, collocation::Mode, pitch::Resolution, ImportStats, Progress};
+use std::{
+    error::Error,
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+/// Imports the JMnedict file `source` into the configured storage data and rebuilds the name
+/// search and suggestion indexes if any name changed
+pub fn names(config: &Config, source: &str) -> Result<(), Box<dyn Error>> {
+    let path = config.get_storage_data_path();
+    let mut storage = load_storage(config, &path)?;
+
+    let (stats, journal) = import::jmnedict::import(&mut storage, &path, source, print_progress)?;
+    store(&storage, &path)?;
+    journal.remove()?;
+
+    print_stats("names", &stats);
+
+    if stats.added + stats.updated + stats.removed > 0 {
+        let names: Vec<_> = storage.names.names.values().collect();
+
+        let index_dir = Path::new(config.get_indexes_source());
+        build::write_names(index_dir, &names).map_err(|i| i.to_string())?;
+
+        let suggestion_file = Path::new(config.get_suggestion_sources());
+        let mut suggestions = suggestions::load_raw(suggestion_file).map_err(|i| i.to_string())?;
+        let (native, foreign) = build::suggestions::names(names.iter().copied());
+        suggestions.set_names(native, foreign);
+        build::write_suggestions(suggestion_file, &suggestions).map_err(|i| i.to_string())?;
+
+        println!("Rebuilt name search and suggestion indexes");
+    }
+
+    Ok(())
+}
+
+/// Replaces the aliases of all names in the configured storage data with the ones of `source`
+pub fn aliases(config: &Config, source: &str) -> Result<(), Box<dyn Error>> {
+    let path = config.get_storage_data_path();
+    let mut storage = load_storage(config, &path)?;
+
+    let stats = import::aliases::import(&mut storage, source, print_progress)?;
+    store(&storage, &path)?;
+
+    print_stats("name aliases", &stats);
+    Ok(())
+}
+
+/// Imports pitch accents from the comma separated files in `sources` into the configured storage
+/// data
+pub fn pitch(config: &Config, sources: &str, resolution: Resolution) -> Result<(), Box<dyn Error>> {
+    let path = config.get_storage_data_path();
+    let mut storage = load_storage(config, &path)?;
+
+    let sources: Vec<_> = sources.split(',').map(|i| i.trim()).collect();
+    let stats = import::pitch::import(&mut storage, &sources, resolution, print_progress)?;
+    store(&storage, &path)?;
+
+    print_stats("pitch entries", &stats);
+    println!("{} entries with disagreeing sources", stats.conflicts);
+    Ok(())
+}
+
+/// Imports the configured word and kanji frequency lists into the configured storage data
+pub fn frequencies(config: &Config) -> Result<(), Box<dyn Error>> {
+    let sources = config.get_import();
+    if sources.word_frequencies.is_none() && sources.kanji_frequencies.is_none() {
+        return Err("No frequency lists configured".into());
+    }
+
+    let path = config.get_storage_data_path();
+    let mut storage = load_storage(config, &path)?;
+
+    if let Some(ref source) = sources.word_frequencies {
+        let stats = import::frequency::import_words(&mut storage, source, print_progress)?;
+        print_stats("word frequencies", &stats);
+    }
+
+    if let Some(ref source) = sources.kanji_frequencies {
+        let stats = import::frequency::import_kanji(&mut storage, source, print_progress)?;
+        print_stats("kanji frequency ranks", &stats);
+    }
+
+    store(&storage, &path)
+}
+
+/// Imports the configured word and kanji JLPT lists into the configured storage data. Both get
+/// written at once so words and kanji never have levels from different lists
+pub fn jlpt(config: &Config) -> Result<(), Box<dyn Error>> {
+    let sources = config.get_import();
+    if sources.word_jlpt.is_none() && sources.kanji_jlpt.is_none() {
+        return Err("No JLPT lists configured".into());
+    }
+
+    let path = config.get_storage_data_path();
+    let mut storage = load_storage(config, &path)?;
+
+    if let Some(ref source) = sources.word_jlpt {
+        let stats = import::jlpt::import_words(&mut storage, source, print_progress)?;
+        print_stats("word levels", &stats);
+    }
+
+    if let Some(ref source) = sources.kanji_jlpt {
+        let stats = import::jlpt::import_kanji(&mut storage, source, print_progress)?;
+        print_stats("kanji levels", &stats);
+    }
+
+    store(&storage, &path)
+}
+
+/// Appends new sentences of the extracted Tatoeba export in `dir` to the configured storage data
+/// and adds them to the configured sentence indexes. If `download` is set, the latest export gets
+/// downloaded into `dir` first
+pub fn sentences(config: &Config, dir: &str, download: bool) -> Result<(), Box<dyn Error>> {
+    if download {
+        println!("Downloading Tatoeba export into {dir}");
+        import::tatoeba::download(dir)?;
+    }
+
+    let path = config.get_storage_data_path();
+    let mut storage = load_storage(config, &path)?;
+
+    let stats = import::tatoeba::sync(&mut storage, dir, print_progress)?;
+    if stats.added > 0 {
+        store(&storage, &path)?;
+
+        let index_dir = Path::new(config.get_indexes_source());
+        let mut index = sentence::load_sync(index_dir).map_err(|i| i.to_string())?;
+        for id in stats.ids.iter() {
+            if let Some(sentence) = storage.sentences.sentences.get(*id) {
+                search::sentence::sync::index_sentence(&mut index, sentence);
+            }
+        }
+        build::write_sync(index_dir, &index).map_err(|i| i.to_string())?;
+    }
+
+    println!();
+    println!(
+        "Added {} of {} sentences ({} linked words)",
+        stats.added, stats.sentences, stats.word_links
+    );
+    println!(
+        "Skipped {} flagged and {} untranslated sentences",
+        stats.flagged, stats.untranslated
+    );
+    Ok(())
+}
+
+/// Replaces the search radicals of all kanji in the configured storage data with the ones of the
+/// radkfile or kradfile `source`
+pub fn radicals(config: &Config, source: &str) -> Result<(), Box<dyn Error>> {
+    let path = config.get_storage_data_path();
+    let mut storage = load_storage(config, &path)?;
+
+    let stats = import::radkfile::import(&mut storage, source, print_progress)?;
+    store(&storage, &path)?;
+
+    print_stats("kanji decompositions", &stats.import);
+    if !stats.unknown.is_empty() {
+        let unknown: String = stats.unknown.iter().collect();
+        println!("Ignored unknown radicals: {unknown}");
+    }
+    Ok(())
+}
+
+/// Replaces the WaniKani levels of all words and kanji in the configured storage data with the ones
+/// of the subject mapping `source`
+pub fn wanikani(config: &Config, source: &str) -> Result<(), Box<dyn Error>> {
+    let path = config.get_storage_data_path();
+    let mut storage = load_storage(config, &path)?;
+
+    let stats = import::wanikani::import(&mut storage, source, print_progress)?;
+    store(&storage, &path)?;
+
+    print_stats("word levels", &stats.words);
+    print_stats("kanji levels", &stats.kanji);
+    Ok(())
+}
+
+/// Replaces the mnemonics of all kanji in the configured storage data with the ones of `source`
+pub fn mnemonics(config: &Config, source: &str) -> Result<(), Box<dyn Error>> {
+    let path = config.get_storage_data_path();
+    let mut storage = load_storage(config, &path)?;
+
+    let stats = import::mnemonics::import(&mut storage, source, print_progress)?;
+    store(&storage, &path)?;
+
+    print_stats("kanji mnemonics", &stats);
+    Ok(())
+}
+
+/// Extracts collocations and corpus frequencies from the sentences of the extracted Tatoeba export
+/// in `dir` and writes them into the configured storage data
+pub fn collocations(config: &Config, dir: &str, mode: Mode) -> Result<(), Box<dyn Error>> {
+    let path = config.get_storage_data_path();
+    let mut storage = load_storage(config, &path)?;
+
+    let stats = import::collocation::extract(&mut storage, dir, mode, print_progress)?;
+    store(&storage, &path)?;
+
+    println!();
+    println!(
+        "Found {} strongly associated token pairs, {} not written like any word",
+        stats.pairs, stats.import.skipped
+    );
+    println!(
+        "Collocations of {} words: {} added, {} updated, {} removed",
+        stats.import.added + stats.import.updated,
+        stats.import.added,
+        stats.import.updated,
+        stats.import.removed
+    );
+    println!("Corpus frequencies of {} words", stats.frequencies);
+    Ok(())
+}
+
+/// Replaces the example sentences of english senses in the configured storage data with the
+/// sentences of the Tanaka corpus word indices `source`
+pub fn sense_sentences(config: &Config, source: &str) -> Result<(), Box<dyn Error>> {
+    let path = config.get_storage_data_path();
+    let mut storage = load_storage(config, &path)?;
+
+    let report = import::sense_sentences::import(&mut storage, source, print_progress)?;
+    store(&storage, &path)?;
+
+    print_stats("sense examples", &report.import);
+    print!("{report}");
+    Ok(())
+}
+
+/// Replaces the loanword sources of senses in the configured storage data with the ones of the
+/// JMdict file `source`
+pub fn gairaigo(config: &Config, source: &str) -> Result<(), Box<dyn Error>> {
+    let path = config.get_storage_data_path();
+    let mut storage = load_storage(config, &path)?;
+
+    let stats = import::gairaigo::import(&mut storage, source, print_progress)?;
+    store(&storage, &path)?;
+
+    print_stats("loanword sources", &stats);
+    Ok(())
+}
+
+/// Updates the revision dates of words in the configured storage data by comparing the JMdict
+/// release `source` with the previously imported one
+pub fn revisions(config: &Config, source: &str) -> Result<(), Box<dyn Error>> {
+    let path = config.get_storage_data_path();
+    let mut storage = load_storage(config, &path)?;
+
+    let stats = import::revisions::import(&mut storage, source, print_progress)?;
+    store(&storage, &path)?;
+
+    print_stats("JMdict entries", &stats.import);
+    if stats.baseline {
+        println!(
+            "No previous release imported. Entries of the next release get compared with the one of {}",
+            stats.release
+        );
+    } else {
+        println!(
+            "{} words added or changed in the release of {}",
+            stats.import.added + stats.import.updated,
+            stats.release
+        );
+    }
+    Ok(())
+}
+
+/// Links transitive and intransitive verbs of the configured storage data which form a pair
+pub fn transitivity(config: &Config) -> Result<(), Box<dyn Error>> {
+    let path = config.get_storage_data_path();
+    let mut storage = load_storage(config, &path)?;
+
+    let stats = import::transitivity::link(&mut storage);
+    if stats.added > 0 {
+        store(&storage, &path)?;
+    }
+
+    println!(
+        "Detected {} transitivity pairs, linked {}",
+        stats.entries, stats.added
+    );
+    if stats.conflicts > 0 {
+        println!(
+            "{} pairs disagree with existing links and were left unchanged",
+            stats.conflicts
+        );
+    }
+    Ok(())
+}
+
+/// Resolves the cross references and antonyms of senses in the configured storage data to the
+/// referenced words
+pub fn references(config: &Config) -> Result<(), Box<dyn Error>> {
+    let path = config.get_storage_data_path();
+    let mut storage = load_storage(config, &path)?;
+
+    let stats = import::references::link(&mut storage);
+    store(&storage, &path)?;
+
+    print_stats("references", &stats);
+    Ok(())
+}
+
+/// Computes the script statistics of all sentences in the configured storage data
+pub fn sentence_stats(config: &Config) -> Result<(), Box<dyn Error>> {
+    let path = config.get_storage_data_path();
+    let mut storage = load_storage(config, &path)?;
+
+    storage.compute_sentence_stats();
+    store(&storage, &path)?;
+
+    println!(
+        "Computed script statistics of {} sentences",
+        storage.sentences.script_stats.len()
+    );
+    Ok(())
+}
+
+/// Loads the storage data at `path` to import into it. Words, names and sentences of storage data
+/// split with `--split-resources` are kept in segments which can't be updated, so importing into
+/// it is refused
+fn load_storage(config: &Config, path: &str) -> Result<resources::ResourceStorage, Box<dyn Error>> {
+    if let Some(segments) = config.server.get_resource_segments() {
+        return Err(format!(
+            "Can't import into storage data split into segments ({segments}). Import into the \
+            unsplit storage data and split it again instead"
+        )
+        .into());
+    }
+    resources::load_raw(path)
+}
+
+/// Replaces the storage data at `path` with `storage`. The new data is written into a temporary
+/// file first so the old data stays intact if writing fails
+fn store(storage: &resources::ResourceStorage, path: &str) -> Result<(), Box<dyn Error>> {
+    let tmp = format!("{path}.tmp");
+    let mut out = BufWriter::new(File::create(&tmp)?);
+    resources::store(&mut out, storage)?;
+    out.flush()?;
+    drop(out);
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+fn print_progress(progress: Progress) {
+    print!(
+        "\r{} entries ({:.1}%)",
+        progress.entries,
+        progress.percent()
+    );
+    std::io::stdout().flush().ok();
+}
+
+fn print_stats(kind: &str, stats: &ImportStats) {
+    println!();
+    if stats.resumed > 0 {
+        println!("Resumed after {} entries", stats.resumed);
+    }
+    println!(
+        "Imported {} {kind}: {} added, {} updated, {} removed",
+        stats.entries, stats.added, stats.updated, stats.removed
+    );
+    if stats.skipped > 0 {
+        println!("{} entries didn't match anything", stats.skipped);
+    }
+}
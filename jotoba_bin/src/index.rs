@@ -0,0 +1,37 @@
+use config::Config;
+use indexes::storage::build::{self, Sources};
+use search::sentence::terms;
+use std::{error::Error, path::Path};
+
+/// Builds the indexes from the configured resources into `out`
+pub fn build(config: &Config, out: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut storage =
+        resources::load_raw(config.get_storage_data_path()).map_err(|i| i.to_string())?;
+    if let Some(segments) = config.server.get_resource_segments() {
+        storage
+            .open_segments(segments, None)
+            .map_err(|i| i.to_string())?;
+    }
+    resources::set(storage);
+    let resources = resources::get();
+
+    let words: Vec<_> = resources.words().iter().collect();
+    let kanji: Vec<_> = resources.kanji().iter().cloned().collect();
+    let names: Vec<_> = resources.names().iter().collect();
+    let sentences: Vec<_> = resources.sentences().iter().collect();
+
+    let sources = Sources {
+        words: &words,
+        kanji: &kanji,
+        names: &names,
+        sentences: &sentences,
+        native_terms: &terms::native_terms,
+        foreign_terms: &terms::foreign_terms,
+    };
+
+    for file in build::build_all(Path::new(out), &sources)? {
+        println!("Written {}", file.display());
+    }
+
+    Ok(())
+}
@@ -6,6 +6,14 @@ static ALLOC: snmalloc_rs::SnMalloc = snmalloc_rs::SnMalloc;
 
 mod check;
 mod cli;
+mod cors;
+mod export;
+mod import;
+mod index;
+mod rate_limit;
+mod snapshot;
+#[cfg(feature = "otlp")]
+mod telemetry;
 mod webserver;
 
 #[actix_web::main]
@@ -18,6 +26,144 @@ pub async fn main() {
         return;
     }
 
+    if let Some(ref out) = options.snapshot {
+        let config = config::Config::new(None).expect("Config invalid");
+        snapshot::snapshot(&config, out).expect("Failed to create snapshot");
+        println!("Snapshot written to {out}");
+        return;
+    }
+
+    if let Some(ref src) = options.restore {
+        let config = config::Config::new(None).expect("Config invalid");
+        snapshot::restore(&config, src).expect("Failed to restore snapshot");
+        println!("Snapshot restored");
+        return;
+    }
+
+    if let Some(ref out) = options.build_indexes {
+        let config = config::Config::new(None).expect("Config invalid");
+        index::build(&config, out).expect("Failed to build indexes");
+        return;
+    }
+
+    if let Some(ref src) = options.import_names {
+        let config = config::Config::new(None).expect("Config invalid");
+        import::names(&config, src).expect("Failed to import names");
+        return;
+    }
+
+    if let Some(ref src) = options.import_aliases {
+        let config = config::Config::new(None).expect("Config invalid");
+        import::aliases(&config, src).expect("Failed to import name aliases");
+        return;
+    }
+
+    if let Some(ref src) = options.import_pitch {
+        let config = config::Config::new(None).expect("Config invalid");
+        let resolution = options
+            .pitch_resolution
+            .parse()
+            .expect("Invalid pitch resolution");
+        import::pitch(&config, src, resolution).expect("Failed to import pitch accents");
+        return;
+    }
+
+    if options.import_frequencies {
+        let config = config::Config::new(None).expect("Config invalid");
+        import::frequencies(&config).expect("Failed to import frequency lists");
+        return;
+    }
+
+    if options.import_jlpt {
+        let config = config::Config::new(None).expect("Config invalid");
+        import::jlpt(&config).expect("Failed to import JLPT levels");
+        return;
+    }
+
+    if let Some(ref dir) = options.update_sentences {
+        let config = config::Config::new(None).expect("Config invalid");
+        import::sentences(&config, dir, options.download_sentences)
+            .expect("Failed to update sentences");
+        return;
+    }
+
+    if let Some(ref src) = options.import_radicals {
+        let config = config::Config::new(None).expect("Config invalid");
+        import::radicals(&config, src).expect("Failed to import radicals");
+        return;
+    }
+
+    if let Some(ref src) = options.import_wanikani {
+        let config = config::Config::new(None).expect("Config invalid");
+        import::wanikani(&config, src).expect("Failed to import WaniKani levels");
+        return;
+    }
+
+    if let Some(ref src) = options.import_mnemonics {
+        let config = config::Config::new(None).expect("Config invalid");
+        import::mnemonics(&config, src).expect("Failed to import kanji mnemonics");
+        return;
+    }
+
+    if let Some(ref src) = options.import_sense_sentences {
+        let config = config::Config::new(None).expect("Config invalid");
+        import::sense_sentences(&config, src).expect("Failed to import sense example sentences");
+        return;
+    }
+
+    if let Some(ref src) = options.import_gairaigo {
+        let config = config::Config::new(None).expect("Config invalid");
+        import::gairaigo(&config, src).expect("Failed to import loanword sources");
+        return;
+    }
+
+    if let Some(ref src) = options.import_revisions {
+        let config = config::Config::new(None).expect("Config invalid");
+        import::revisions(&config, src).expect("Failed to import revision dates");
+        return;
+    }
+
+    if options.link_transitivity {
+        let config = config::Config::new(None).expect("Config invalid");
+        import::transitivity(&config).expect("Failed to link transitivity pairs");
+        return;
+    }
+
+    if options.link_references {
+        let config = config::Config::new(None).expect("Config invalid");
+        import::references(&config).expect("Failed to link references");
+        return;
+    }
+
+    if options.compute_sentence_stats {
+        let config = config::Config::new(None).expect("Config invalid");
+        import::sentence_stats(&config).expect("Failed to compute sentence statistics");
+        return;
+    }
+
+    if let Some(ref dir) = options.extract_collocations {
+        let config = config::Config::new(None).expect("Config invalid");
+        let mode = options
+            .collocation_mode
+            .parse()
+            .expect("Invalid collocation mode");
+        import::collocations(&config, dir, mode).expect("Failed to extract collocations");
+        return;
+    }
+
+    if let Some(ref out) = options.export_bundle {
+        let config = config::Config::new(None).expect("Config invalid");
+        export::bundle(&config, out, &options.bundle_languages).expect("Failed to export bundle");
+        return;
+    }
+
+    if let Some(ref dir) = options.split_resources {
+        let config = config::Config::new(None).expect("Config invalid");
+        split_resources(&config, dir).expect("Failed to split resources");
+        println!("Segments written to {dir}. Set `storage_data` to {dir}/storage_data and `resource_segments` to {dir} to use them");
+        return;
+    }
+
     // Start the webserver on --stat/-s
     if options.start {
         webserver::start(options).await.expect("webserver failed");
@@ -27,3 +173,19 @@ pub async fn main() {
     // User didn't read the docs
     println!("Nothing to do. Use `-s` to start the dictionary");
 }
+
+/// Moves words, names and sentences of the configured storage data into segments within `dir`.
+/// The remaining data gets written to `dir/storage_data`
+fn split_resources(config: &config::Config, dir: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if config.server.get_resource_segments().is_some() {
+        return Err("The configured storage data has already been split".into());
+    }
+    let mut storage = resources::load_raw(config.get_storage_data_path())?;
+    storage.split_segments(dir)?;
+    let out = std::path::Path::new(dir).join("storage_data");
+    resources::store(
+        std::io::BufWriter::new(std::fs::File::create(out)?),
+        &storage,
+    )?;
+    Ok(())
+}
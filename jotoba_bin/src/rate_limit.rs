@@ -0,0 +1,355 @@
+//! Optional API keys with per-key rate limits and daily quotas for the API. Clients without a
+//! key are limited by their IP address. Limits are configured in the `rate_limit` section of the
+//! config file and nothing gets limited if that section is missing.
+
+use std::{
+    collections::HashMap,
+    future::{ready, Future, Ready},
+    pin::Pin,
+    rc::Rc,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use actix_web::{
+    body::EitherBody,
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    http::header::{HeaderName, HeaderValue, RETRY_AFTER},
+    Error, HttpResponse, ResponseError,
+};
+use config::{ApiKeyConfig, RateLimitConfig};
+use error::api_error::RestError;
+use log::debug;
+
+/// Header containing the API key of a request
+pub const API_KEY_HEADER: &str = "X-API-Key";
+
+/// Header containing the per minute limit of the client
+pub const LIMIT_HEADER: &str = "x-ratelimit-limit";
+
+/// Header containing the amount of requests left in the current minute
+pub const REMAINING_HEADER: &str = "x-ratelimit-remaining";
+
+/// Max amount of clients to keep track of. Usage of inactive clients gets dropped first, clients
+/// with a daily quota are kept for the whole day so dropping them doesn't reset their quota
+const MAX_CLIENTS: usize = 100_000;
+
+/// Keeps track of the API usage of all clients
+pub struct RateLimiter {
+    config: Option<RateLimitConfig>,
+    usage: Mutex<Clients>,
+}
+
+#[derive(Default)]
+struct Clients {
+    usage: HashMap<String, Usage>,
+    /// Minute inactive clients were dropped in the last time
+    evicted: u64,
+}
+
+/// Usage of a single client within the current minute and day
+#[derive(Default)]
+struct Usage {
+    minute: u64,
+    minute_count: u32,
+    day: u64,
+    day_count: u32,
+    /// Whether the client has a daily quota
+    quota: bool,
+}
+
+/// Limits applying to a single client
+#[derive(Clone, Copy)]
+struct Limits {
+    per_minute: Option<u32>,
+    daily: Option<u32>,
+}
+
+/// Reason a request got rejected
+enum Rejection {
+    UnknownKey,
+    /// Limit exceeded. Contains the amount of seconds until the client may retry
+    Limited(u64),
+}
+
+impl RateLimiter {
+    pub fn new(config: Option<RateLimitConfig>) -> Self {
+        Self {
+            config,
+            usage: Mutex::new(Clients::default()),
+        }
+    }
+
+    /// Counts `req` towards the usage of its client. Returns the per minute limit and the
+    /// remaining requests of the client if it's limited at all
+    fn check(&self, req: &ServiceRequest) -> Result<Option<(u32, u32)>, Rejection> {
+        let config = match self.config {
+            Some(ref config) => config,
+            None => return Ok(None),
+        };
+
+        let key = req
+            .headers()
+            .get(API_KEY_HEADER)
+            .and_then(|i| i.to_str().ok());
+
+        let (client, limits) = match key {
+            Some(key) => {
+                let key = config.find_key(key).ok_or(Rejection::UnknownKey)?;
+                (format!("key:{}", key.key), Limits::from(key))
+            }
+            None => {
+                let peer = req
+                    .peer_addr()
+                    .map(|i| i.ip().to_string())
+                    .unwrap_or_default();
+
+                (client_ip(req, config, peer), Limits::from(config))
+            }
+        };
+
+        if limits.per_minute.is_none() && limits.daily.is_none() {
+            return Ok(None);
+        }
+
+        self.count(client, limits)
+    }
+
+    fn count(&self, client: String, limits: Limits) -> Result<Option<(u32, u32)>, Rejection> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|i| i.as_secs())
+            .unwrap_or_default();
+        let minute = now / 60;
+        let day = now / 86400;
+
+        let mut clients = self.usage.lock().unwrap();
+        if clients.usage.len() >= MAX_CLIENTS && !clients.usage.contains_key(&client) {
+            if clients.evicted != minute {
+                clients.evict(minute, day);
+            }
+            if clients.usage.len() >= MAX_CLIENTS {
+                clients.shrink();
+            }
+        }
+
+        let client_usage = clients.usage.entry(client).or_default();
+        client_usage.quota = limits.daily.is_some();
+        if client_usage.minute != minute {
+            client_usage.minute = minute;
+            client_usage.minute_count = 0;
+        }
+        if client_usage.day != day {
+            client_usage.day = day;
+            client_usage.day_count = 0;
+        }
+
+        if limits.daily.map_or(false, |i| client_usage.day_count >= i) {
+            return Err(Rejection::Limited(86400 - now % 86400));
+        }
+
+        if limits
+            .per_minute
+            .map_or(false, |i| client_usage.minute_count >= i)
+        {
+            return Err(Rejection::Limited(60 - now % 60));
+        }
+
+        client_usage.minute_count += 1;
+        client_usage.day_count += 1;
+
+        Ok(limits
+            .per_minute
+            .map(|limit| (limit, limit - client_usage.minute_count)))
+    }
+}
+
+impl Clients {
+    /// Drops the usage of all clients which aren't limited by it anymore. Usage within the
+    /// current minute is kept for all clients and the usage within the current day for clients
+    /// with a daily quota
+    fn evict(&mut self, minute: u64, day: u64) {
+        self.usage
+            .retain(|_, i| i.minute == minute || (i.quota && i.day == day));
+        self.evicted = minute;
+    }
+
+    /// Drops clients until there is room for new ones again. Only happens if more clients than
+    /// [`MAX_CLIENTS`] were active within the current minute. Clients with a daily quota are
+    /// dropped last
+    fn shrink(&mut self) {
+        let target = MAX_CLIENTS - MAX_CLIENTS / 10;
+
+        for quota in [false, true] {
+            let mut excess = self.usage.len().saturating_sub(target);
+            if excess == 0 {
+                break;
+            }
+            self.usage.retain(|_, i| {
+                if excess > 0 && i.quota == quota {
+                    excess -= 1;
+                    return false;
+                }
+                true
+            });
+        }
+    }
+}
+
+/// Returns the IP address of the client sending `req`. Forwarding headers can be set by anyone,
+/// so they're only used for requests coming from a trusted reverse proxy. Each proxy appends the
+/// address it got the request from, so the chain gets walked from the right and the first
+/// address that isn't a trusted proxy is the client
+fn client_ip(req: &ServiceRequest, config: &RateLimitConfig, peer: String) -> String {
+    if !config.is_trusted_proxy(&peer) {
+        return peer;
+    }
+
+    let headers = req.headers();
+
+    let forwarded_for = headers
+        .get_all("x-forwarded-for")
+        .filter_map(|i| i.to_str().ok())
+        .flat_map(|i| i.split(','))
+        .map(|i| i.trim().to_string())
+        .collect::<Vec<_>>();
+
+    let chain = if !forwarded_for.is_empty() {
+        forwarded_for
+    } else {
+        headers
+            .get_all("forwarded")
+            .filter_map(|i| i.to_str().ok())
+            .flat_map(|i| i.split(','))
+            .filter_map(forwarded_for_param)
+            .collect()
+    };
+
+    chain
+        .into_iter()
+        .rev()
+        .find(|i| !i.is_empty() && !config.is_trusted_proxy(i))
+        .unwrap_or(peer)
+}
+
+/// Returns the address in the `for` parameter of a single `Forwarded` header element, eg.
+/// `for="[2001:db8::1]:4711";proto=https`
+fn forwarded_for_param(element: &str) -> Option<String> {
+    let value = element.split(';').find_map(|param| {
+        let (name, value) = param.trim().split_once('=')?;
+        name.eq_ignore_ascii_case("for")
+            .then(|| value.trim().trim_matches('"'))
+    })?;
+
+    // Strip the port of IPv6 (`[::1]:80`) and IPv4 (`1.2.3.4:80`) addresses
+    let addr = match value.strip_prefix('[') {
+        Some(v6) => v6.split(']').next().unwrap_or(v6),
+        None if value.matches(':').count() == 1 => value.split(':').next().unwrap_or(value),
+        None => value,
+    };
+    Some(addr.to_string())
+}
+
+impl From<&RateLimitConfig> for Limits {
+    #[inline]
+    fn from(config: &RateLimitConfig) -> Self {
+        Self {
+            per_minute: config.requests_per_minute,
+            daily: config.daily_quota,
+        }
+    }
+}
+
+impl From<&ApiKeyConfig> for Limits {
+    #[inline]
+    fn from(key: &ApiKeyConfig) -> Self {
+        Self {
+            per_minute: key.requests_per_minute,
+            daily: key.daily_quota,
+        }
+    }
+}
+
+impl Rejection {
+    fn response(&self) -> HttpResponse {
+        match self {
+            Rejection::UnknownKey => RestError::Unauthorized.error_response(),
+            Rejection::Limited(retry_after) => {
+                let mut res = RestError::TooManyRequests.error_response();
+                res.headers_mut()
+                    .insert(RETRY_AFTER, HeaderValue::from(*retry_after));
+                res
+            }
+        }
+    }
+}
+
+/// Middleware applying the limits of a [`RateLimiter`]
+pub struct RateLimit(Arc<RateLimiter>);
+
+impl RateLimit {
+    #[inline]
+    pub fn new(limiter: Arc<RateLimiter>) -> Self {
+        Self(limiter)
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RateLimit
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = RateLimitMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RateLimitMiddleware {
+            service: Rc::new(service),
+            limiter: self.0.clone(),
+        }))
+    }
+}
+
+pub struct RateLimitMiddleware<S> {
+    service: Rc<S>,
+    limiter: Arc<RateLimiter>,
+}
+
+impl<S, B> Service<ServiceRequest> for RateLimitMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let limit = match self.limiter.check(&req) {
+            Ok(limit) => limit,
+            Err(rejection) => {
+                debug!("Rejected API request to {}", req.path());
+                let res = req.into_response(rejection.response());
+                return Box::pin(ready(Ok(res.map_into_right_body())));
+            }
+        };
+
+        let service = self.service.clone();
+        Box::pin(async move {
+            let mut res = service.call(req).await?;
+
+            if let Some((limit, remaining)) = limit {
+                let headers = res.headers_mut();
+                headers.insert(HeaderName::from_static(LIMIT_HEADER), limit.into());
+                headers.insert(HeaderName::from_static(REMAINING_HEADER), remaining.into());
+            }
+
+            Ok(res.map_into_left_body())
+        })
+    }
+}
@@ -0,0 +1,222 @@
+//! Snapshots of all runtime state of an instance (resources, resource segments, overlays,
+//! indexes, suggestions, news and the TTS cache) bundled into a single file, so instances can be moved to other machines.
+
+use config::Config;
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Error, ErrorKind, Read, Write},
+    path::{Component, Path, PathBuf},
+};
+
+/// Identifies snapshot files
+const MAGIC: &[u8] = b"JOTOBA_SNAPSHOT";
+
+/// Version of the snapshot format
+const VERSION: u32 = 1;
+
+/// Parts of an instances state which are stored in snapshots. Each part gets restored to the
+/// path configured on the target machine, so paths don't have to match between instances
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Part {
+    Resources,
+    Segments,
+    Overlays,
+    Indexes,
+    Suggestions,
+    News,
+    TtsCache,
+}
+
+impl Part {
+    const ALL: [Part; 7] = [
+        Part::Resources,
+        Part::Segments,
+        Part::Overlays,
+        Part::Indexes,
+        Part::Suggestions,
+        Part::News,
+        Part::TtsCache,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Part::Resources => "resources",
+            Part::Segments => "segments",
+            Part::Overlays => "overlays",
+            Part::Indexes => "indexes",
+            Part::Suggestions => "suggestions",
+            Part::News => "news",
+            Part::TtsCache => "tts_cache",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Part> {
+        Part::ALL.into_iter().find(|i| i.name() == name)
+    }
+
+    /// Returns the path of the part as configured in `config` or `None` if the part isn't
+    /// configured at all
+    fn path(&self, config: &Config) -> Option<PathBuf> {
+        let path = match self {
+            Part::Resources => return Some(PathBuf::from(config.get_storage_data_path())),
+            Part::Segments => config.server.get_resource_segments()?,
+            Part::Overlays => config.server.get_overlay_folder(),
+            Part::Indexes => config.get_indexes_source(),
+            Part::Suggestions => config.get_suggestion_sources(),
+            Part::News => config.server.get_news_folder(),
+            Part::TtsCache => config.server.get_tts_cache(),
+        };
+        Some(PathBuf::from(path))
+    }
+}
+
+/// Writes all runtime state of the instance into a single snapshot file at `out`
+pub fn snapshot(config: &Config, out: &str) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(out)?);
+    writer.write_all(MAGIC)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+
+    for part in Part::ALL {
+        let Some(path) = part.path(config) else {
+            continue;
+        };
+        if !path.exists() {
+            println!("Skipping {}: {path:?} doesn't exist", part.name());
+            continue;
+        }
+
+        println!("Adding {} ({path:?})", part.name());
+        for file in collect_files(&path)? {
+            let rel = file.strip_prefix(&path).unwrap_or(Path::new(""));
+            write_entry(&mut writer, part, rel, &file)?;
+        }
+    }
+
+    // End marker
+    write_str(&mut writer, "")?;
+    writer.flush()
+}
+
+/// Restores a snapshot created with [`snapshot`]. Existing files get overwritten
+pub fn restore(config: &Config, src: &str) -> io::Result<()> {
+    let mut reader = BufReader::new(File::open(src)?);
+
+    let mut magic = vec![0u8; MAGIC.len()];
+    reader.read_exact(&mut magic)?;
+    if magic != MAGIC {
+        return Err(Error::new(ErrorKind::InvalidData, "Not a snapshot file"));
+    }
+
+    let version = read_u32(&mut reader)?;
+    if version != VERSION {
+        let msg = format!("Unsupported snapshot version {version}");
+        return Err(Error::new(ErrorKind::InvalidData, msg));
+    }
+
+    loop {
+        let part_name = read_str(&mut reader)?;
+        if part_name.is_empty() {
+            break;
+        }
+
+        let part = Part::from_name(&part_name)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "Unknown snapshot part"))?;
+        let rel = PathBuf::from(read_str(&mut reader)?);
+        if rel.components().any(|i| !matches!(i, Component::Normal(_))) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Invalid path in snapshot",
+            ));
+        }
+
+        // Restoring without the part being configured would silently drop it and result in a
+        // different dataset than the one the snapshot was taken from
+        let mut target = part.path(config).ok_or_else(|| {
+            let msg = format!("Snapshot contains {} which aren't configured", part.name());
+            Error::new(ErrorKind::InvalidInput, msg)
+        })?;
+        if !rel.as_os_str().is_empty() {
+            target.push(rel);
+        }
+        println!("Restoring {target:?}");
+        restore_file(&mut reader, &target)?;
+    }
+
+    Ok(())
+}
+
+/// Returns all files within `path` or `path` itself if it's a file
+fn collect_files(path: &Path) -> io::Result<Vec<PathBuf>> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let mut out = vec![];
+    for entry in fs::read_dir(path)? {
+        out.extend(collect_files(&entry?.path())?);
+    }
+    out.sort();
+    Ok(out)
+}
+
+fn write_entry<W: Write>(writer: &mut W, part: Part, rel: &Path, file: &Path) -> io::Result<()> {
+    let rel = rel
+        .to_str()
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "Non UTF-8 path"))?;
+
+    write_str(writer, part.name())?;
+    write_str(writer, rel)?;
+
+    let mut file = File::open(file)?;
+    writer.write_all(&file.metadata()?.len().to_le_bytes())?;
+    io::copy(&mut file, writer)?;
+    Ok(())
+}
+
+/// Writes the next file from `reader` to `target`. The data is written to a temporary file
+/// first, so `target` doesn't end up half written if the snapshot is truncated
+fn restore_file<R: Read>(reader: &mut R, target: &Path) -> io::Result<()> {
+    let len = read_u64(reader)?;
+
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut tmp = target.as_os_str().to_owned();
+    tmp.push(".restore");
+    let tmp = PathBuf::from(tmp);
+    let mut out = BufWriter::new(File::create(&tmp)?);
+    let copied = io::copy(&mut reader.take(len), &mut out)?;
+    out.flush()?;
+
+    if copied != len {
+        fs::remove_file(&tmp)?;
+        return Err(Error::new(ErrorKind::UnexpectedEof, "Snapshot truncated"));
+    }
+
+    fs::rename(tmp, target)
+}
+
+fn write_str<W: Write>(writer: &mut W, s: &str) -> io::Result<()> {
+    writer.write_all(&(s.len() as u32).to_le_bytes())?;
+    writer.write_all(s.as_bytes())
+}
+
+fn read_str<R: Read>(reader: &mut R) -> io::Result<String> {
+    let len = read_u32(reader)? as usize;
+    let mut buf = vec![0u8; len];
+    reader.read_exact(&mut buf)?;
+    String::from_utf8(buf).map_err(|_| Error::new(ErrorKind::InvalidData, "Invalid string"))
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> io::Result<u32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
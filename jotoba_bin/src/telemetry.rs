@@ -0,0 +1,56 @@
+use config::{Config, TracingConfig};
+use log::{info, warn};
+use opentelemetry::{
+    sdk::{
+        trace::{self, Sampler},
+        Resource,
+    },
+    KeyValue,
+};
+use opentelemetry_otlp::WithExportConfig;
+use tracing_subscriber::prelude::*;
+
+/// Exports the spans of the search stack to the configured OTLP collector. Does nothing if no
+/// collector is configured
+pub fn setup(config: &Config) {
+    let tracing_config = match config.tracing {
+        Some(ref tracing_config) => tracing_config,
+        None => return,
+    };
+
+    if let Err(err) = install(tracing_config) {
+        warn!("Failed to set up OTLP exporter: {err}");
+        return;
+    }
+
+    info!("Exporting traces to {}", tracing_config.otlp_endpoint);
+}
+
+fn install(config: &TracingConfig) -> Result<(), Box<dyn std::error::Error>> {
+    let exporter = opentelemetry_otlp::new_exporter()
+        .tonic()
+        .with_endpoint(&config.otlp_endpoint);
+
+    let trace_config = trace::config()
+        .with_sampler(Sampler::TraceIdRatioBased(config.get_sample_ratio()))
+        .with_resource(Resource::new(vec![KeyValue::new(
+            "service.name",
+            config.get_service_name().to_string(),
+        )]));
+
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(exporter)
+        .with_trace_config(trace_config)
+        .install_batch(opentelemetry::runtime::Tokio)?;
+
+    let subscriber =
+        tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(tracer));
+    tracing::subscriber::set_global_default(subscriber)?;
+    Ok(())
+}
+
+/// Exports all remaining spans. Has to be called before exiting
+pub fn shutdown() {
+    opentelemetry::global::shutdown_tracer_provider();
+}
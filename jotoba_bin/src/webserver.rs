@@ -1,13 +1,15 @@
 use actix_files::NamedFile;
 use actix_web_httpauth::{extractors::bearer::BearerAuth, middleware::HttpAuthentication};
+use api::version::ApiVersion;
 use error::api_error::RestError;
-use indexes::storage::suggestions;
+use indexes::storage::{suggestions, LoadOptions};
 use localization::TranslationDict;
+use resources::ResourceStorage;
 
 use actix_web::{
-    dev::ServiceRequest,
+    dev::{Service, ServiceRequest},
     http::{
-        header::{ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_ORIGIN, CACHE_CONTROL},
+        header::{CACHE_CONTROL, LINK},
         StatusCode,
     },
     middleware::{self, Compat, Compress},
@@ -16,13 +18,70 @@ use actix_web::{
 };
 use config::Config;
 use log::{debug, warn};
-use std::{path::Path, sync::Arc, thread, time::Instant};
+use std::{
+    path::Path,
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+use types::jotoba::language::Language;
 
-use crate::{check, cli::Options};
+use crate::{
+    check,
+    cli::Options,
+    cors::{Cors, DEPRECATION_HEADER},
+    rate_limit::{RateLimit, RateLimiter},
+};
 
 /// How long frontend assets are going to be cached by the clients. Currently 1 week
 const ASSET_CACHE_MAX_AGE: u64 = 604800;
 
+/// Search endpoints which existed before the API got versioned
+fn search_scope() -> actix_web::Scope {
+    actixweb::scope("search")
+        .route("words", actixweb::post().to(api::search::word::word_search))
+        .route(
+            "kanji",
+            actixweb::post().to(api::search::kanji::kanji_search),
+        )
+        .route("names", actixweb::post().to(api::search::name::name_search))
+        .route(
+            "sentences",
+            actixweb::post().to(api::search::sentence::sentence_search),
+        )
+}
+
+/// Routes of the public API which existed before versioning. They're still served unversioned
+/// (deprecated) under `/api`
+fn legacy_api(cfg: &mut actixweb::ServiceConfig) {
+    cfg.service(search_scope());
+}
+
+/// Routes of the public API served under `/api/v2`
+fn public_api(cfg: &mut actixweb::ServiceConfig) {
+    cfg.service(search_scope().route(
+        "stream",
+        actixweb::get().to(api::search::stream::stream_search),
+    ))
+    .service(
+        actixweb::scope("words")
+            .route("recent", actixweb::get().to(api::recent::recent_words))
+            .route(
+                "{seq}/inflections",
+                actixweb::get().to(api::inflections::word_inflections),
+            ),
+    )
+    .service(
+        actixweb::scope("radicals")
+            .route("", actixweb::get().to(api::radicals::list))
+            .route("/{literal}", actixweb::get().to(api::radicals::lookup))
+            .route("/{literal}/kanji", actixweb::get().to(api::radicals::kanji)),
+    )
+    .route("/pitch", actixweb::get().to(api::pitch::pitch))
+    .route("/export", actixweb::get().to(api::export::export))
+    .route("/analyze", actixweb::post().to(api::analyze::analyze));
+}
+
 /// Start the webserver
 pub(super) async fn start(options: Options) -> std::io::Result<()> {
     if options.debug {
@@ -49,6 +108,14 @@ pub(super) async fn start(options: Options) -> std::io::Result<()> {
     #[cfg(feature = "sentry_error")]
     setup_sentry(&config);
 
+    #[cfg(feature = "otlp")]
+    crate::telemetry::setup(&config);
+
+    #[cfg(not(feature = "otlp"))]
+    if config.tracing.is_some() {
+        warn!("Tracing configured but not available. Build with \"otlp\" feature");
+    }
+
     let address = config.server.listen_address.clone();
 
     if !check() {
@@ -58,25 +125,53 @@ pub(super) async fn start(options: Options) -> std::io::Result<()> {
     debug!("Resource loading took {:?}", start.elapsed());
     debug_info();
 
+    #[cfg(unix)]
+    reload_on_sighup(&config);
+
+    api::app::completions::cache::init(
+        config.get_suggestion_cache_size(),
+        config.get_suggestion_cache_ttl(),
+    );
+
+    init_engines(&config);
+    warm_up(&config);
+
+    let rate_limiter = Arc::new(RateLimiter::new(config.rate_limit.clone()));
+
     HttpServer::new(move || {
         let app = App::new()
             // Data
             .app_data(Data::new(config.clone()))
             .app_data(Data::new(locale_dict_arc.clone()))
             // Middlewares
+            .wrap_fn(|req, srv| {
+                // Entries of evicted segments and replaced data stay alive until all requests
+                // which might use them are done
+                let pin = utils::epoch::pin();
+                let res = srv.call(req);
+                async move {
+                    let res = res.await;
+                    drop(pin);
+                    res
+                }
+            })
             .wrap(middleware::Logger::default())
+            .wrap(Cors::new(config.get_cors_rules()))
             .service(
                 actixweb::resource("/")
                     .wrap(Compat::new(middleware::Compress::default()))
                     .route(actixweb::get().to(frontend::index::index)),
             )
             .service(actixweb::resource("/robots.txt").route(actixweb::get().to(robotstxt)))
+            // Probes. `/healthy`, `/ready`, `/health/live` and `/health/ready` are kept as aliases
+            // of `/healthz` and `/readyz` for existing deployments
             .service(
-                actixweb::resource("/ready").route(actixweb::get().to(frontend::liveness::ready)),
+                actixweb::resource(["/healthz", "/healthy", "/health/live"])
+                    .route(actixweb::get().to(frontend::liveness::healthz)),
             )
             .service(
-                actixweb::resource("/healthy")
-                    .route(actixweb::get().to(frontend::liveness::healthy)),
+                actixweb::resource(["/readyz", "/ready", "/health/ready"])
+                    .route(actixweb::get().to(frontend::liveness::readyz)),
             )
             .service(
                 actixweb::resource("/docs.html")
@@ -113,6 +208,24 @@ pub(super) async fn start(options: Options) -> std::io::Result<()> {
                     .wrap(Compat::new(middleware::Compress::default()))
                     .route(actixweb::get().to(frontend::direct::direct_ep)),
             )
+            .service(
+                actixweb::scope("/fragment")
+                    .wrap(Compat::new(middleware::Compress::default()))
+                    .route("/kanji", actixweb::get().to(frontend::fragments::kanji))
+                    .route(
+                        "/sentences",
+                        actixweb::get().to(frontend::fragments::sentences),
+                    )
+                    .route(
+                        "/collocations/{sequence}",
+                        actixweb::get().to(frontend::fragments::collocations),
+                    ),
+            )
+            .service(
+                actixweb::resource("/embed")
+                    .wrap(Compat::new(middleware::Compress::default()))
+                    .route(actixweb::get().to(frontend::embed::embed)),
+            )
             .service(
                 actixweb::resource("/about")
                     .wrap(Compat::new(middleware::Compress::default()))
@@ -132,14 +245,11 @@ pub(super) async fn start(options: Options) -> std::io::Result<()> {
             // API
             .service(
                 actixweb::scope("/api")
-                    .wrap(
-                        middleware::DefaultHeaders::new()
-                            .add((ACCESS_CONTROL_ALLOW_ORIGIN, "*"))
-                            .add((ACCESS_CONTROL_ALLOW_HEADERS, "Content-Type")),
-                    )
+                    .wrap(RateLimit::new(rate_limiter.clone()))
                     .wrap(Compat::new(Compress::default()))
                     .route("/", actixweb::get().to(docs))
-                    .default_service(actix_web::Route::new().to(docs))
+                    .route("/docs", actixweb::get().to(docs))
+                    .default_service(actix_web::Route::new().to(api_not_found))
                     .service(
                         actixweb::scope("app")
                             .route(
@@ -175,18 +285,19 @@ pub(super) async fn start(options: Options) -> std::io::Result<()> {
                                     ),
                             ),
                     )
+                    .route(
+                        "data-version",
+                        actixweb::get().to(api::data_version::data_version),
+                    )
                     .service(
-                        actixweb::scope("search")
-                            .route("words", actixweb::post().to(api::search::word::word_search))
-                            .route(
-                                "kanji",
-                                actixweb::post().to(api::search::kanji::kanji_search),
-                            )
-                            .route("names", actixweb::post().to(api::search::name::name_search))
-                            .route(
-                                "sentences",
-                                actixweb::post().to(api::search::sentence::sentence_search),
-                            ),
+                        actixweb::resource("graphql")
+                            .route(actixweb::post().to(api::graphql::graphql))
+                            .route(actixweb::get().to(api::graphql::sdl)),
+                    )
+                    .service(
+                        actixweb::scope("v2")
+                            .app_data(ApiVersion::V2)
+                            .configure(public_api),
                     )
                     .service(
                         actixweb::scope("internal")
@@ -194,7 +305,21 @@ pub(super) async fn start(options: Options) -> std::io::Result<()> {
                             .service(actixweb::scope("info").route(
                                 "words",
                                 actixweb::post().to(api::internal::info::words::word_info),
-                            )),
+                            ))
+                            .route(
+                                "reload/suggestions",
+                                actixweb::post().to(api::internal::reload::suggestions),
+                            )
+                            .route("reload/data", actixweb::post().to(reload_data))
+                            .route(
+                                "stats/suggestion_cache",
+                                actixweb::get().to(api::internal::stats::suggestion_cache),
+                            ),
+                    )
+                    .service(
+                        actixweb::resource("words/dump")
+                            .wrap(HttpAuthentication::bearer(internal_validator))
+                            .route(actixweb::get().to(api::internal::dump::words)),
                     )
                     .service(
                         actixweb::scope("kanji")
@@ -215,11 +340,20 @@ pub(super) async fn start(options: Options) -> std::io::Result<()> {
                         "/suggestion",
                         actixweb::post().to(api::app::completions::suggestion_ep),
                     )
+                    .route(
+                        "/suggestion/selected",
+                        actixweb::post().to(api::app::completions::personalize::selection_ep),
+                    )
+                    .route(
+                        "/suggestion/tags",
+                        actixweb::post().to(api::app::completions::tags::tag_completion_ep),
+                    )
                     .route(
                         "/os-suggestions",
                         actixweb::get().to(api::app::completions::opensearch::suggestion_ep),
                     )
                     .route("/img_scan", actixweb::post().to(api::app::img::scan_ep))
+                    .route("/img_search", actixweb::post().to(api::app::img::search_ep))
                     .route(
                         "/news/short",
                         actixweb::post().to(api::app::news::short::news),
@@ -227,6 +361,20 @@ pub(super) async fn start(options: Options) -> std::io::Result<()> {
                     .route(
                         "/news/detailed",
                         actixweb::post().to(api::app::news::detailed::news),
+                    )
+                    .route("/settings", actixweb::get().to(api::app::settings::get))
+                    .route("/settings", actixweb::post().to(api::app::settings::set))
+                    // Unversioned legacy API. Has to be registered last since it matches all
+                    // remaining paths
+                    .service(
+                        actixweb::scope("")
+                            .wrap(
+                                middleware::DefaultHeaders::new()
+                                    .add((DEPRECATION_HEADER, "true"))
+                                    .add((LINK, "</api/v2>; rel=\"successor-version\"")),
+                            )
+                            .configure(legacy_api)
+                            .default_service(actix_web::Route::new().to(api_not_found)),
                     ),
             )
             // Static files
@@ -236,6 +384,13 @@ pub(super) async fn start(options: Options) -> std::io::Result<()> {
                         middleware::DefaultHeaders::new()
                             .add((CACHE_CONTROL, format!("max-age={}", ASSET_CACHE_MAX_AGE))),
                     )
+                    // Audio of words without a recording gets synthesized on request, so the
+                    // endpoint shares the limits of the API
+                    .service(
+                        actixweb::resource("/word/{seq}")
+                            .wrap(RateLimit::new(rate_limiter.clone()))
+                            .route(actixweb::get().to(api::audio::word_audio)),
+                    )
                     .service(
                         actix_files::Files::new("", config.server.get_audio_files())
                             .show_files_listing(),
@@ -245,9 +400,7 @@ pub(super) async fn start(options: Options) -> std::io::Result<()> {
                 actixweb::scope("/assets")
                     .wrap(
                         middleware::DefaultHeaders::new()
-                            .add((CACHE_CONTROL, format!("max-age={}", ASSET_CACHE_MAX_AGE)))
-                            .add((ACCESS_CONTROL_ALLOW_ORIGIN, "*"))
-                            .add((ACCESS_CONTROL_ALLOW_HEADERS, "Content-Type")),
+                            .add((CACHE_CONTROL, format!("max-age={}", ASSET_CACHE_MAX_AGE))),
                     )
                     .wrap(Compat::new(Compress::default()))
                     .service(
@@ -275,7 +428,12 @@ pub(super) async fn start(options: Options) -> std::io::Result<()> {
     })
     .bind(&address)?
     .run()
-    .await
+    .await?;
+
+    #[cfg(feature = "otlp")]
+    crate::telemetry::shutdown();
+
+    Ok(())
 }
 
 async fn service_worker(_req: HttpRequest) -> actix_web::Result<NamedFile> {
@@ -302,10 +460,23 @@ async fn docs(_req: HttpRequest) -> actix_web::Result<NamedFile> {
     Ok(NamedFile::open("html/docs.html")?)
 }
 
+/// Answers requests to unknown API paths
+async fn api_not_found(_req: HttpRequest) -> Result<HttpResponse, RestError> {
+    Err(RestError::NotFound)
+}
+
 pub(crate) fn prepare_data(ccf: &Config) {
     let cf = ccf.clone();
     thread::spawn(move || {
-        suggestions::load(cf.get_suggestion_sources()).expect("Failed to load suggestions");
+        // Suggestions are ranked by the glosses of the words they suggest
+        resources::wait();
+        let gloss_langs = api::internal::reload::suggestion_gloss_langs();
+        suggestions::load(
+            cf.get_suggestion_sources(),
+            &index_options(&cf),
+            gloss_langs,
+        )
+        .expect("Failed to load suggestions");
         log::debug!("Suggestions loaded");
     });
 
@@ -313,7 +484,12 @@ pub(crate) fn prepare_data(ccf: &Config) {
         let cf = ccf.clone();
         s.spawn(move |_| {
             log::debug!("Loading Resources");
-            load_resources(&cf.get_storage_data_path());
+            load_resources(
+                &cf.get_storage_data_path(),
+                cf.server.get_overlay_folder(),
+                cf.server.get_resource_segments(),
+                index_options(&cf).get_languages(),
+            );
         });
 
         let cf = ccf.clone();
@@ -338,6 +514,124 @@ pub(crate) fn prepare_data(ccf: &Config) {
                 warn!("Failed to load news: {}", err);
             }
         });
+
+        let cf = ccf.clone();
+        s.spawn(move |_| {
+            log::debug!("Loading Textbooks");
+            if let Err(err) = resources::textbooks::load(cf.server.get_textbook_folder()) {
+                warn!("Failed to load textbooks: {}", err);
+            }
+        });
+    });
+}
+
+/// Applies the configured engine settings
+fn init_engines(config: &Config) {
+    use search::{
+        engine::settings::{self, EngineKind, EngineSettings},
+        executor::out_builder::MergeStrategy,
+    };
+    use types::jotoba::search::SearchTarget;
+
+    let engines = config.get_engines();
+    let settings = [
+        (EngineKind::NativeWords, engines.native_words),
+        (EngineKind::ForeignWords, engines.foreign_words),
+        (EngineKind::Sentences, engines.sentences),
+        (EngineKind::Names, engines.names),
+    ]
+    .into_iter()
+    .filter_map(|(kind, engine)| {
+        let engine = engine?;
+        let settings = EngineSettings {
+            threshold: engine.threshold,
+            limit: engine.limit,
+            vector_limit: engine.vector_limit,
+        };
+        Some((kind, settings))
+    })
+    .collect();
+
+    settings::init(settings);
+
+    let merge = config.get_merge();
+    let merge = [
+        (SearchTarget::Words, merge.words),
+        (SearchTarget::Sentences, merge.sentences),
+        (SearchTarget::Names, merge.names),
+    ]
+    .into_iter()
+    .filter_map(|(target, strategy)| {
+        let strategy = match strategy? {
+            config::MergeStrategy::First => MergeStrategy::First,
+            config::MergeStrategy::Max => MergeStrategy::Max,
+            config::MergeStrategy::Sum => MergeStrategy::Sum,
+        };
+        Some((target, strategy))
+    })
+    .collect();
+
+    settings::init_merge(merge);
+}
+
+/// Max time the warm-up waits for the suggestions to be loaded
+const SUGGESTION_WAIT: Duration = Duration::from_secs(600);
+
+/// Searches the most common queries in the background to warm up indexes and caches. Progress is
+/// reported by the readiness endpoint
+fn warm_up(config: &Config) {
+    let queries = match config.get_warmup_queries() {
+        Some(path) => match std::fs::read_to_string(path) {
+            Ok(content) => content
+                .lines()
+                .map(|i| i.trim())
+                .filter(|i| !i.is_empty())
+                .take(config.get_warmup_count())
+                .map(|i| i.to_string())
+                .collect(),
+            Err(err) => {
+                warn!("Failed to read warm-up queries: {err}");
+                vec![]
+            }
+        },
+        None => vec![],
+    };
+
+    thread::spawn(move || {
+        let start = Instant::now();
+
+        // Suggestions get loaded in the background and never finish if loading them failed
+        while !suggestions::is_loaded() {
+            if start.elapsed() > SUGGESTION_WAIT {
+                warn!("Suggestions not loaded after {SUGGESTION_WAIT:?}. Skipping warm-up");
+                return;
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+
+        {
+            let _pin = utils::epoch::pin();
+            api::app::completions::warm_up();
+        }
+
+        search::warmup::run(&queries, |query| {
+            let request = types::api::app::completions::Request {
+                input: query.to_string(),
+                lang: String::new(),
+                search_target: Default::default(),
+                radicals: vec![],
+                hashtag: false,
+                smart: false,
+                session: None,
+            };
+            api::app::completions::suggestion_ep_inner(request).ok();
+        });
+
+        debug!(
+            "Warm-up of {} queries took {:?}",
+            queries.len(),
+            start.elapsed()
+        );
     });
 }
 
@@ -365,12 +659,56 @@ fn debug_info() {
     log::debug!("Not supported: {:?}", resources::get().missing_features());
 }
 
-pub fn load_resources(src: &str) {
+pub fn load_resources(
+    src: &str,
+    overlay_folder: &str,
+    segments: Option<&str>,
+    languages: Option<&[Language]>,
+) {
     let start = Instant::now();
-    resources::load(src).expect("Failed to load resource storage");
+    let storage = read_resources(src, overlay_folder, segments, languages)
+        .expect("Failed to load resource storage");
+    resources::set(storage);
     debug!("Resources took: {:?}", start.elapsed());
 }
 
+/// Loads the resource storage from `src` and applies the configured languages, segments and
+/// overlays
+pub(crate) fn read_resources(
+    src: &str,
+    overlay_folder: &str,
+    segments: Option<&str>,
+    languages: Option<&[Language]>,
+) -> Result<ResourceStorage, Box<dyn std::error::Error>> {
+    let mut storage = resources::load_raw(src)?;
+
+    if let Some(languages) = languages {
+        storage.retain_languages(languages);
+    }
+
+    if let Some(segments) = segments {
+        storage.open_segments(segments, languages)?;
+    }
+
+    if Path::new(overlay_folder).is_dir() {
+        match resources::overlay::apply_dir(&mut storage, overlay_folder) {
+            Ok(applied) => {
+                for overlay in applied.iter() {
+                    log::info!(
+                        "Applied overlay {}: {} patches ({} skipped)",
+                        overlay.file,
+                        overlay.applied,
+                        overlay.skipped
+                    );
+                }
+            }
+            Err(err) => warn!("Failed to apply overlays: {}", err),
+        }
+    }
+
+    Ok(storage)
+}
+
 fn load_translations(config: &Config) -> Arc<TranslationDict> {
     let locale_dict = TranslationDict::new(
         config.server.get_locale_path(),
@@ -382,7 +720,89 @@ fn load_translations(config: &Config) -> Arc<TranslationDict> {
 }
 
 pub fn load_indexes(config: &Config) {
-    indexes::storage::load(config.get_indexes_source()).expect("Failed to load index files");
+    indexes::storage::load_with_options(config.get_indexes_source(), &index_options(config))
+        .expect("Failed to load index files");
+}
+
+/// Returns the options for loading the configured indexes
+#[inline]
+fn index_options(config: &Config) -> LoadOptions {
+    LoadOptions::with_language_names(config.server.get_languages())
+}
+
+/// Reloads resources and indexes from the configured files without restarting the server. Both
+/// get loaded completely before they replace the data in use, so running requests aren't affected
+async fn reload_data(config: Data<Config>) -> Result<HttpResponse, RestError> {
+    if !actixweb::block(move || replace_data(&config)).await? {
+        return Err(RestError::Internal);
+    }
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Loads the configured resources and indexes and replaces the ones in use with them, along with
+/// the suggestions and all caches built from them. Returns `false` if loading failed, in which
+/// case the old data stays in place
+fn replace_data(config: &Config) -> bool {
+    let start = Instant::now();
+    let options = index_options(config);
+
+    let (storage, store) = rayon::join(
+        || {
+            read_resources(
+                &config.get_storage_data_path(),
+                config.server.get_overlay_folder(),
+                config.server.get_resource_segments(),
+                options.get_languages(),
+            )
+            .map_err(|err| err.to_string())
+        },
+        || indexes::storage::load_raw(config.get_indexes_source(), &options),
+    );
+
+    let storage = match storage {
+        Ok(storage) if storage.check() => storage,
+        Ok(storage) => {
+            log::error!(
+                "Reloaded resources miss required features: {:?}",
+                storage.missing_but_required()
+            );
+            return false;
+        }
+        Err(err) => {
+            log::error!("Failed to reload resources: {err}");
+            return false;
+        }
+    };
+
+    let store = match store {
+        Ok(store) if store.check() => store,
+        Ok(_) => {
+            log::error!("Not all reloaded indexes are available");
+            return false;
+        }
+        Err(err) => {
+            log::error!("Failed to reload indexes: {err}");
+            return false;
+        }
+    };
+
+    let _pin = utils::epoch::pin();
+
+    resources::replace(storage);
+    indexes::storage::replace(store);
+    utils::epoch::invalidate();
+
+    // Suggestion indexes are built from the resources as well
+    api::internal::reload::reload_suggestions(config.get_suggestion_sources(), &options);
+    api::app::completions::cache::clear();
+
+    // Rebuild the caches derived from the replaced data before the next requests need them
+    api::app::completions::warm_up();
+    search::warmup::indexes();
+
+    log::info!("Reloaded resources and indexes in {:?}", start.elapsed());
+    true
 }
 
 fn check() -> bool {
@@ -424,6 +844,30 @@ fn setup_sentry(config: &Config) {
     }
 }
 
+/// Reloads the suggestion indexes whenever the process receives SIGHUP
+#[cfg(unix)]
+fn reload_on_sighup(config: &Config) {
+    use actix_web::rt::signal::unix::{signal, SignalKind};
+
+    let path = config.get_suggestion_sources().to_string();
+    let options = index_options(config);
+    actix_web::rt::spawn(async move {
+        let mut hangup = match signal(SignalKind::hangup()) {
+            Ok(hangup) => hangup,
+            Err(err) => {
+                warn!("Failed to listen for SIGHUP: {err}");
+                return;
+            }
+        };
+
+        while hangup.recv().await.is_some() {
+            let (path, options) = (path.clone(), options.clone());
+            let reload = move || api::internal::reload::reload_suggestions(&path, &options);
+            actix_web::rt::task::spawn_blocking(reload).await.ok();
+        }
+    });
+}
+
 async fn internal_validator(
     req: ServiceRequest,
     credentials: BearerAuth,
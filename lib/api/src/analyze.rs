@@ -0,0 +1,111 @@
+use actix_web::web::{self, Json};
+use engine::task::SearchTask;
+use error::api_error::RestError;
+use japanese::normalize::normalize;
+use jp_utils::JapaneseExt;
+use search::{engine::words::native::Engine, word::order::native::NativeOrder};
+use sentence_reader::{output::ParseResult, Parser};
+use std::collections::HashMap;
+use types::{
+    api::analyze::{AnalyzedWord, Request, Response, UnknownLemma},
+    jotoba::words::Word,
+};
+
+/// Max amount of characters of a text to analyze
+const MAX_TEXT_LEN: usize = 20_000;
+
+/// Characters at which texts get split into sentences before being segmented
+const SENTENCE_ENDS: &[char] = &['。', '！', '？', '!', '?', '\n', '「', '」', '『', '』'];
+
+/// Segments a text and returns dictionary entries for all distinct words used in it, along with
+/// the amount of times they occur
+pub async fn analyze(payload: Json<Request>) -> Result<Json<Response>, RestError> {
+    let len = payload.text.chars().count();
+    if payload.text.trim().is_empty() || len > MAX_TEXT_LEN {
+        return Err(RestError::BadRequest);
+    }
+
+    let res = web::block(move || analyze_text(&payload)).await?;
+    Ok(Json(res))
+}
+
+fn analyze_text(req: &Request) -> Response {
+    let mut words: Vec<AnalyzedWord> = vec![];
+    let mut word_pos: HashMap<u32, usize> = HashMap::new();
+    let mut unknown = vec![];
+
+    for (lemma, count) in count_lemmas(&normalize(&req.text)) {
+        let mut word = match find_word(&lemma) {
+            Some(word) => word,
+            None => {
+                unknown.push(UnknownLemma { lemma, count });
+                continue;
+            }
+        };
+
+        // Different lemmas can map to the same word (eg. 食べる and たべる)
+        if let Some(pos) = word_pos.get(&word.sequence) {
+            let entry = &mut words[*pos];
+            entry.count += count;
+            entry.lemmas.push(lemma);
+            continue;
+        }
+
+        word.adjust_language(req.lang_param());
+        word_pos.insert(word.sequence, words.len());
+        words.push(AnalyzedWord {
+            count,
+            lemmas: vec![lemma],
+            word: (&word).into(),
+        });
+    }
+
+    words.sort_by(|a, b| b.count.cmp(&a.count));
+    unknown.sort_by(|a, b| b.count.cmp(&a.count));
+
+    Response { words, unknown }
+}
+
+/// Returns all distinct lemmas of `text` with the amount of their occurrences, in the order they
+/// first appear
+fn count_lemmas(text: &str) -> Vec<(String, usize)> {
+    let mut lemmas: Vec<(String, usize)> = vec![];
+    let mut lemma_pos: HashMap<String, usize> = HashMap::new();
+
+    let segments = text.split(SENTENCE_ENDS).filter(|i| !i.trim().is_empty());
+    for segment in segments {
+        let parsed = match Parser::new(segment).parse() {
+            ParseResult::Sentence(s) => s.iter().map(|i| i.get_normalized()).collect(),
+            ParseResult::InflectedWord(w) => vec![w.get_normalized()],
+            ParseResult::None => vec![],
+        };
+
+        for lemma in parsed.into_iter().filter(|i| i.has_japanese()) {
+            match lemma_pos.get(&lemma) {
+                Some(pos) => lemmas[*pos].1 += 1,
+                None => {
+                    lemma_pos.insert(lemma.clone(), lemmas.len());
+                    lemmas.push((lemma, 1));
+                }
+            }
+        }
+    }
+
+    lemmas
+}
+
+/// Returns the word written exactly like `lemma`. Words having `lemma` as main reading are
+/// preferred over words only having it as alternative reading. Fuzzy matches are never returned
+fn find_word(lemma: &str) -> Option<Word> {
+    let mut task = SearchTask::<Engine>::new(lemma)
+        .with_limit(10)
+        .with_threshold(0.8)
+        .with_custom_order(NativeOrder::new(lemma.to_string()));
+
+    let res = task.find().into_inner();
+    let item = res
+        .iter()
+        .find(|i| i.item.has_main_reading(lemma))
+        .or_else(|| res.iter().find(|i| i.item.has_reading(lemma)))?;
+    Some(item.item.clone())
+}
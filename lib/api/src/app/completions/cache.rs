@@ -0,0 +1,214 @@
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use lru::LruCache;
+use once_cell::sync::{Lazy, OnceCell};
+use search::query::Query;
+use types::{
+    api::{app::completions::Response, internal::stats::CacheStats},
+    jotoba::language::Language,
+};
+
+/// Max amount of cached responses if not configured
+const DEFAULT_CAPACITY: usize = 10_000;
+
+/// Time after which cached responses expire if not configured
+const DEFAULT_TTL: Duration = Duration::from_secs(600);
+
+/// Capacity and TTL of the cache
+static SETTINGS: OnceCell<(usize, Duration)> = OnceCell::new();
+
+/// LRU cache of suggestion responses. A small set of prefixes makes up most of the suggestion
+/// requests, so those don't have to be computed over and over again
+static CACHE: Lazy<Mutex<Cache>> = Lazy::new(|| Mutex::new(Cache::new(settings().0)));
+
+static HITS: AtomicU64 = AtomicU64::new(0);
+static MISSES: AtomicU64 = AtomicU64::new(0);
+
+/// Identifies a suggestion response. It's built from the parsed query, so queries which only
+/// differ in their width, surrounding whitespace or the case of their tags share a response
+#[derive(Hash, PartialEq, Eq, Clone)]
+pub(crate) struct Key {
+    query: String,
+    /// Literal and reading of a kanji reading query being typed
+    kanji_reading: Option<(char, String)>,
+    target: u8,
+    language: Language,
+    radicals: Vec<char>,
+    smart: bool,
+}
+
+impl Key {
+    pub(crate) fn new(query: &Query, radicals: &[char], smart: bool) -> Self {
+        let mut radicals = radicals.to_vec();
+        radicals.sort_unstable();
+        radicals.dedup();
+
+        Self {
+            query: query.query_str.clone(),
+            kanji_reading: super::as_kanji_reading(query).map(|i| (i.literal, i.reading)),
+            target: query.target as u8,
+            language: query.settings.user_lang,
+            radicals,
+            smart,
+        }
+    }
+}
+
+struct Cache {
+    entries: LruCache<Key, Entry>,
+}
+
+struct Entry {
+    response: Response,
+    inserted: Instant,
+}
+
+impl Cache {
+    fn new(capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity.max(1)).unwrap();
+        Self {
+            entries: LruCache::new(capacity),
+        }
+    }
+
+    fn get(&mut self, key: &Key, ttl: Duration) -> Option<Response> {
+        let entry = self.entries.get(key)?;
+
+        if entry.inserted.elapsed() > ttl {
+            self.entries.pop(key);
+            return None;
+        }
+
+        Some(entry.response.clone())
+    }
+
+    /// Inserts `response` and evicts the least recently used entry if the cache is full
+    fn insert(&mut self, key: Key, response: Response) {
+        let entry = Entry {
+            response,
+            inserted: Instant::now(),
+        };
+        self.entries.put(key, entry);
+    }
+}
+
+/// Sets the max amount of cached responses and the time after which they expire. Has to be
+/// called before the first suggestion request to take effect
+pub fn init(capacity: usize, ttl: Duration) {
+    SETTINGS.set((capacity, ttl)).ok();
+}
+
+/// Returns the cached response for `key` or computes and caches it using `compute`
+pub(crate) fn get_or_insert_with<F>(key: Key, compute: F) -> Response
+where
+    F: FnOnce() -> Response,
+{
+    let (capacity, ttl) = settings();
+    if capacity == 0 {
+        return compute();
+    }
+
+    if let Some(response) = CACHE.lock().unwrap().get(&key, ttl) {
+        HITS.fetch_add(1, Ordering::Relaxed);
+        return response;
+    }
+    MISSES.fetch_add(1, Ordering::Relaxed);
+
+    // Don't hold the lock while computing suggestions
+    let response = compute();
+    CACHE.lock().unwrap().insert(key, response.clone());
+    response
+}
+
+/// Removes all cached responses. Has to be called when the suggestion indexes change
+pub fn clear() {
+    CACHE.lock().unwrap().entries.clear();
+}
+
+/// Returns usage statistics of the cache
+pub fn stats() -> CacheStats {
+    let entries = CACHE.lock().unwrap().entries.len();
+    let hits = HITS.load(Ordering::Relaxed);
+    let misses = MISSES.load(Ordering::Relaxed);
+    CacheStats::new(entries, settings().0, hits, misses)
+}
+
+#[inline]
+fn settings() -> (usize, Duration) {
+    SETTINGS
+        .get()
+        .copied()
+        .unwrap_or((DEFAULT_CAPACITY, DEFAULT_TTL))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use search::query::parser::QueryParser;
+    use types::{api::app::completions::WordPair, jotoba::search::SearchTarget};
+
+    fn key(query: &str) -> Key {
+        Key {
+            query: query.to_string(),
+            kanji_reading: None,
+            target: 0,
+            language: Language::English,
+            radicals: vec![],
+            smart: false,
+        }
+    }
+
+    fn response(primary: &str) -> Response {
+        Response::new(vec![WordPair::new(primary.to_string())])
+    }
+
+    #[test]
+    fn test_lru_eviction() {
+        let ttl = Duration::from_secs(60);
+        let mut cache = Cache::new(2);
+        cache.insert(key("a"), response("a"));
+        cache.insert(key("b"), response("b"));
+
+        // Makes "b" the least recently used entry
+        assert!(cache.get(&key("a"), ttl).is_some());
+
+        cache.insert(key("c"), response("c"));
+        assert!(cache.get(&key("a"), ttl).is_some());
+        assert!(cache.get(&key("b"), ttl).is_none());
+        assert!(cache.get(&key("c"), ttl).is_some());
+    }
+
+    fn parsed_key(query: &str, radicals: &[char]) -> Key {
+        let query = QueryParser::new(query.to_string(), SearchTarget::Words, Default::default())
+            .parse()
+            .unwrap();
+        Key::new(&query, radicals, false)
+    }
+
+    #[test]
+    fn test_normalized_key() {
+        assert!(parsed_key("cat", &[]) == parsed_key("ｃａｔ ", &[]));
+        assert!(parsed_key("cat #kanji", &[]) == parsed_key("cat  #Kanji", &[]));
+        assert!(parsed_key("木", &['一', '木']) == parsed_key("木", &['木', '一', '木']));
+
+        assert!(parsed_key("cat", &[]) != parsed_key("cat #kanji", &[]));
+        // A kanji followed by a space starts a kanji reading query
+        assert!(parsed_key("猫", &[]) != parsed_key("猫 ", &[]));
+    }
+
+    #[test]
+    fn test_expiry() {
+        let mut cache = Cache::new(2);
+        cache.insert(key("a"), response("a"));
+        std::thread::sleep(Duration::from_millis(1));
+        assert!(cache.get(&key("a"), Duration::ZERO).is_none());
+        assert!(cache.entries.is_empty());
+    }
+}
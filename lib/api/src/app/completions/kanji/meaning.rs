@@ -8,7 +8,8 @@ use search::query::Query;
 
 /// Returns kanji meaning suggestions
 pub fn suggestions(query: &Query) -> Option<Response> {
-    let index = indexes::get_suggestions().kanji_meanings();
+    let store = indexes::get_suggestions();
+    let index = store.kanji_meanings();
 
     let mut suggestion_task = SuggestionTask::new(30);
 
@@ -21,7 +22,7 @@ pub fn suggestions(query: &Query) -> Option<Response> {
     suggestion_task.add_query(def_query);
 
     if let Some(hira_query) = try_romaji(&query.query_str) {
-        let jp_index = indexes::get_suggestions().jp_words();
+        let jp_index = store.jp_words();
         let mut rom_sug_query = SuggestionQuery::new(jp_index, hira_query);
         rom_sug_query.weights.total_weight = 0.5;
 
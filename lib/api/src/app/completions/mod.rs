@@ -1,7 +1,12 @@
+pub mod cache;
 mod kanji;
 mod names;
 pub mod opensearch;
+pub mod personalize;
 mod request;
+mod sentences;
+mod smart;
+pub mod tags;
 mod words;
 
 use actix_web::web::Json;
@@ -18,7 +23,12 @@ pub async fn suggestion_ep(payload: Json<Request>) -> Result<Json<Response>, act
 }
 
 /// Get search suggestions endpoint
-pub(crate) fn suggestion_ep_inner(payload: Request) -> Result<Response, actix_web::Error> {
+#[tracing::instrument(
+    name = "suggestions",
+    skip_all,
+    fields(input = %payload.input, target = ?payload.search_target)
+)]
+pub fn suggestion_ep_inner(payload: Request) -> Result<Response, actix_web::Error> {
     request::validate(&payload)?;
 
     if payload.hashtag {
@@ -29,6 +39,14 @@ pub(crate) fn suggestion_ep_inner(payload: Request) -> Result<Response, actix_we
         return Ok(Response::default());
     }
 
+    // Complete the tag which is currently being typed instead of the query
+    if let Some(tag) = tags::trailing_tag(&payload.input) {
+        return Ok(tags::suggestions(tag));
+    }
+
+    let session = payload.session.clone();
+    let smart = payload.smart;
+
     // Adjust payload and parse to query
     let (query, radicals) = request::get_query(request::adjust(payload))?;
 
@@ -37,7 +55,28 @@ pub(crate) fn suggestion_ep_inner(payload: Request) -> Result<Response, actix_we
         return Ok(Response::default());
     }
 
-    Ok(get_suggestions(query, radicals))
+    // Search types explicitly given within the input (eg. "#kanji") take precedence
+    let smart = smart && query.target == SearchTarget::Words;
+
+    let key = cache::Key::new(&query, &radicals, smart);
+    let mut response = cache::get_or_insert_with(key, || {
+        if smart {
+            smart::suggestions(query, radicals)
+        } else {
+            get_suggestions(query, radicals)
+        }
+    });
+    if let Some(session) = session {
+        personalize::apply(&session, &mut response);
+    }
+
+    Ok(response)
+}
+
+/// Builds all lazily built suggestion indexes. Requires resources and suggestions to be loaded
+pub fn warm_up() {
+    names::warm_up();
+    sentences::warm_up();
 }
 
 /// Returns best matching suggestions for the given query
@@ -49,7 +88,11 @@ fn get_suggestions(query: Query, radicals: Vec<char>) -> Response {
             if let Some(kanji_reading) = as_kanji_reading(&query) {
                 kanji::reading::suggestions(kanji_reading)
             } else {
-                words::suggestions(query, &radicals)
+                let target = query.target;
+                let sentences = sentences::suggestions(&query);
+                let mut res = words::suggestions(query, &radicals).unwrap_or_default();
+                sentences::insert(&mut res.suggestions, sentences, target);
+                Some(res)
             }
         }
     };
@@ -88,6 +131,7 @@ pub(crate) fn convert_results(engine_output: Vec<autocompletion::index::Output>)
         .map(|i| WordPair {
             primary: i.primary,
             secondary: i.secondary,
+            search_target: None,
         })
         .collect()
 }
@@ -0,0 +1,156 @@
+use itertools::Itertools;
+use types::{api::app::completions::WordPair, jotoba::names::Name};
+use utils::epoch::Cached;
+
+/// Max amount of shortest matches of a single prefix which get sorted. Short prefixes can match
+/// hundreds of thousands of names
+const MAX_CANDIDATES: usize = 5000;
+
+/// Prefix index over the kana, kanji and transcriptions of all JMnedict names
+static INDEX: Cached<NameIndex> = Cached::new(NameIndex::build);
+
+/// Builds the name index so the first suggestion request doesn't have to
+#[inline]
+pub(super) fn warm_up() {
+    Cached::force(&INDEX);
+}
+
+struct NameIndex {
+    /// Lowercased keys and sequence ids of the names they belong to, sorted by key
+    entries: Vec<(Box<str>, u32)>,
+}
+
+impl NameIndex {
+    fn build() -> Self {
+        let mut entries: Vec<(Box<str>, u32)> = resources::get()
+            .names()
+            .iter()
+            .flat_map(|name| {
+                let seq = name.sequence;
+                let kanji = name.kanji.as_deref().map(|i| (i.into(), seq));
+                let kana = (name.kana.as_str().into(), seq);
+                let transcription = (name.transcription.to_lowercase().into(), seq);
+                kanji.into_iter().chain([kana, transcription])
+            })
+            .collect();
+        entries.sort_unstable();
+        entries.dedup();
+        Self { entries }
+    }
+
+    /// Returns the sequence ids of all names starting with `prefix`, shortest names first
+    fn find(&self, prefix: &str) -> Vec<u32> {
+        let start = self.entries.partition_point(|i| &*i.0 < prefix);
+        let matches = &self.entries[start..];
+        let end = matches.partition_point(|i| i.0.starts_with(prefix));
+
+        let matches = &matches[..end];
+
+        // Length and position of each match. Matches of the same length stay sorted by key
+        let mut candidates: Vec<(usize, usize)> = matches
+            .iter()
+            .enumerate()
+            .map(|(pos, i)| (i.0.chars().count(), pos))
+            .collect();
+
+        // Only the shortest matches get sorted, so they have to be selected before truncating
+        if candidates.len() > MAX_CANDIDATES {
+            candidates.select_nth_unstable(MAX_CANDIDATES);
+            candidates.truncate(MAX_CANDIDATES);
+        }
+        candidates.sort_unstable();
+
+        candidates
+            .into_iter()
+            .map(|i| matches[i.1].1)
+            .unique()
+            .collect()
+    }
+}
+
+/// Returns up to `limit` names starting with one of `prefixes`, along with their readings
+pub(super) fn suggestions<S: AsRef<str>>(prefixes: &[S], limit: usize) -> Vec<WordPair> {
+    let names = resources::get().names();
+
+    prefixes
+        .iter()
+        .flat_map(|prefix| INDEX.find(&prefix.as_ref().to_lowercase()))
+        .unique()
+        .filter_map(|seq| names.by_sequence(seq))
+        .map(to_word_pair)
+        .unique()
+        .take(limit)
+        .collect()
+}
+
+/// Adds names from `names` to `suggestions` until `limit` is reached and adds the reading of
+/// suggestions from the suggestion index which don't have one yet
+pub(super) fn complete(suggestions: &mut Vec<WordPair>, names: Vec<WordPair>, limit: usize) {
+    for suggestion in suggestions.iter_mut() {
+        if suggestion.secondary.is_some() {
+            continue;
+        }
+
+        let name = names
+            .iter()
+            .find(|i| i.secondary.as_ref() == Some(&suggestion.primary));
+        if let Some(name) = name {
+            *suggestion = name.clone();
+        }
+    }
+
+    for name in names {
+        if suggestions.len() >= limit {
+            break;
+        }
+
+        if !suggestions.contains(&name) {
+            suggestions.push(name);
+        }
+    }
+}
+
+/// Converts a name into a suggestion showing its kana reading and kanji (if any)
+fn to_word_pair(name: &Name) -> WordPair {
+    match name.kanji {
+        Some(ref kanji) => WordPair::with_secondary(name.kana.clone(), kanji.clone()),
+        None => WordPair::new(name.kana.clone()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn index(keys: &[(&str, u32)]) -> NameIndex {
+        let mut entries: Vec<(Box<str>, u32)> = keys.iter().map(|i| (i.0.into(), i.1)).collect();
+        entries.sort_unstable();
+        NameIndex { entries }
+    }
+
+    #[test]
+    fn test_find_shortest_first() {
+        let index = index(&[
+            ("tanaka", 1),
+            ("tan", 2),
+            ("tanabe", 3),
+            ("tomo", 4),
+            ("ta", 5),
+        ]);
+        assert_eq!(index.find("tan"), vec![2, 3, 1]);
+        assert_eq!(index.find("x"), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn test_find_beyond_candidates() {
+        // The shortest match sorts after all longer ones, so it would be dropped by truncating
+        // before sorting
+        let mut keys: Vec<(String, u32)> = (0..MAX_CANDIDATES as u32 + 10)
+            .map(|i| (format!("a{i:06}"), i + 1))
+            .collect();
+        keys.push(("az".to_string(), 0));
+        let keys: Vec<_> = keys.iter().map(|i| (i.0.as_str(), i.1)).collect();
+
+        assert_eq!(index(&keys).find("a").first(), Some(&0));
+    }
+}
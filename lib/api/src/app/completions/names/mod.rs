@@ -1,3 +1,5 @@
+mod jmnedict;
+
 use super::{convert_results, Response};
 use autocompletion::suggest::{
     extension::ngram::NGramExtension, query::SuggestionQuery, task::SuggestionTask,
@@ -6,6 +8,15 @@ use japanese::ToKanaExt;
 use search::query::{Query, QueryLang};
 use wana_kana::to_katakana::to_katakana;
 
+/// Max amount of name suggestions
+const MAX_SUGGESTIONS: usize = 30;
+
+/// Builds the lazily built name indexes
+#[inline]
+pub(super) fn warm_up() {
+    jmnedict::warm_up();
+}
+
 /// Returns name suggestions
 pub(crate) fn suggestions(query: Query) -> Option<Response> {
     match query.q_lang {
@@ -18,9 +29,10 @@ pub(crate) fn suggestions(query: Query) -> Option<Response> {
 /// Returns trascripted name suggestions
 pub fn transcription_suggestions(query: &Query) -> Option<Response> {
     let query_str = &query.query_str;
-    let index = indexes::get_suggestions().names_foreign();
+    let store = indexes::get_suggestions();
+    let index = store.names_foreign();
 
-    let mut task = SuggestionTask::new(30);
+    let mut task = SuggestionTask::new(MAX_SUGGESTIONS);
 
     let mut def_query = SuggestionQuery::new(index, query_str);
     let ng_ext = NGramExtension::new(index);
@@ -28,17 +40,23 @@ pub fn transcription_suggestions(query: &Query) -> Option<Response> {
 
     task.add_query(def_query);
 
+    let mut prefixes = vec![query_str.clone()];
+
     if let Some(romaji_query) = super::words::foreign::try_romaji(query_str) {
-        let jp_index = indexes::get_suggestions().names_native();
+        let jp_index = store.names_native();
         task.add_query(SuggestionQuery::new(jp_index, romaji_query.clone()));
 
         let katakana = to_katakana(romaji_query.as_str());
         if katakana != romaji_query {
-            task.add_query(SuggestionQuery::new(index, katakana));
+            task.add_query(SuggestionQuery::new(index, katakana.clone()));
+            prefixes.push(katakana);
         }
+        prefixes.push(romaji_query);
     }
 
-    let suggestions = convert_results(task.search());
+    let mut suggestions = convert_results(task.search());
+    let names = jmnedict::suggestions(&prefixes, MAX_SUGGESTIONS);
+    jmnedict::complete(&mut suggestions, names, MAX_SUGGESTIONS);
     Some(Response::new(suggestions))
 }
 
@@ -46,8 +64,9 @@ pub fn transcription_suggestions(query: &Query) -> Option<Response> {
 pub fn native_suggestions(query: &Query) -> Option<Response> {
     let query_str = &query.query_str;
 
-    let index = indexes::get_suggestions().names_native();
-    let mut task = SuggestionTask::new(30);
+    let store = indexes::get_suggestions();
+    let index = store.names_native();
+    let mut task = SuggestionTask::new(MAX_SUGGESTIONS);
 
     let mut def_query = SuggestionQuery::new(index, query_str);
     let ng_ext = NGramExtension::new(index);
@@ -55,17 +74,23 @@ pub fn native_suggestions(query: &Query) -> Option<Response> {
 
     task.add_query(def_query);
 
+    let mut prefixes = vec![query_str.clone()];
+
     let katakana = to_katakana(query_str.as_str());
     if &katakana != query_str {
-        task.add_query(SuggestionQuery::new(index, katakana));
+        task.add_query(SuggestionQuery::new(index, katakana.clone()));
+        prefixes.push(katakana);
     }
 
     let hiragana = query_str.to_hiragana();
     if &hiragana != query_str {
-        task.add_query(SuggestionQuery::new(index, hiragana));
+        task.add_query(SuggestionQuery::new(index, hiragana.clone()));
+        prefixes.push(hiragana);
     }
 
-    let suggestions = convert_results(task.search());
+    let mut suggestions = convert_results(task.search());
+    let names = jmnedict::suggestions(&prefixes, MAX_SUGGESTIONS);
+    jmnedict::complete(&mut suggestions, names, MAX_SUGGESTIONS);
     Some(Response {
         suggestions,
         ..Default::default()
@@ -53,5 +53,7 @@ fn make_request(inp: String, search_target: SearchTarget) -> Request {
         search_target,
         radicals: vec![],
         hashtag: false,
+        smart: false,
+        session: None,
     }
 }
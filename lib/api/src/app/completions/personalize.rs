@@ -0,0 +1,126 @@
+use std::{
+    collections::VecDeque,
+    num::NonZeroUsize,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use actix_web::{web::Json, HttpResponse};
+use error::api_error::RestError;
+use lru::LruCache;
+use once_cell::sync::Lazy;
+use types::api::app::completions::{Response, SelectionRequest, WordPair};
+
+/// Amount of selected suggestions remembered per session
+const MAX_RECENT: usize = 20;
+
+/// Max amount of sessions kept at once. The least recently used session gets dropped when a new
+/// one is started
+const MAX_SESSIONS: usize = 10_000;
+
+/// Max length of session tokens and selected suggestions
+const MAX_LEN: usize = 100;
+
+/// Sessions which haven't been used for this long get dropped
+const SESSION_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Recently selected suggestions of all sessions
+static SESSIONS: Lazy<Mutex<LruCache<String, Session>>> = Lazy::new(|| {
+    let capacity = NonZeroUsize::new(MAX_SESSIONS).unwrap();
+    Mutex::new(LruCache::new(capacity))
+});
+
+struct Session {
+    /// Selected suggestions, most recent first
+    recent: VecDeque<String>,
+    last_used: Instant,
+}
+
+/// Endpoint for reporting a selected suggestion. The suggestion gets ranked higher in subsequent
+/// suggestion requests carrying the same session token
+pub async fn selection_ep(payload: Json<SelectionRequest>) -> Result<HttpResponse, RestError> {
+    let payload = payload.into_inner();
+
+    let suggestion = payload.suggestion.trim();
+    if !valid_token(&payload.session) || suggestion.is_empty() || suggestion.len() > MAX_LEN {
+        return Err(RestError::BadRequest);
+    }
+
+    select(payload.session, suggestion.to_string());
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Moves suggestions which have recently been selected within `session` to the top, keeping the
+/// order of all other suggestions
+pub(crate) fn apply(session: &str, response: &mut Response) {
+    let mut sessions = SESSIONS.lock().unwrap();
+    let entry = match sessions.get_mut(session) {
+        Some(entry) => entry,
+        None => return,
+    };
+
+    if entry.last_used.elapsed() >= SESSION_TTL {
+        sessions.pop(session);
+        return;
+    }
+
+    entry.last_used = Instant::now();
+
+    response
+        .suggestions
+        .sort_by_key(|i| recent_pos(&entry.recent, i).unwrap_or(usize::MAX));
+}
+
+fn select(token: String, suggestion: String) {
+    let mut sessions = SESSIONS.lock().unwrap();
+
+    let session = sessions.get_or_insert_mut(token, || Session {
+        recent: VecDeque::new(),
+        last_used: Instant::now(),
+    });
+    if session.last_used.elapsed() >= SESSION_TTL {
+        session.recent.clear();
+    }
+    session.last_used = Instant::now();
+
+    session.recent.retain(|i| *i != suggestion);
+    session.recent.push_front(suggestion);
+    session.recent.truncate(MAX_RECENT);
+}
+
+/// Returns the position of `pair` within the recently selected suggestions
+#[inline]
+fn recent_pos(recent: &VecDeque<String>, pair: &WordPair) -> Option<usize> {
+    recent.iter().position(|i| pair.has_reading(i))
+}
+
+#[inline]
+fn valid_token(token: &str) -> bool {
+    !token.is_empty() && token.len() <= MAX_LEN
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_apply() {
+        let token = "test_apply".to_string();
+        select(token.clone(), "たべもの".to_string());
+        select(token.clone(), "食べる".to_string());
+
+        let mut response = Response::new(vec![
+            WordPair::new("たべる".to_string()),
+            WordPair::with_secondary("たべもの".to_string(), "食べ物".to_string()),
+            WordPair::with_secondary("たべる".to_string(), "食べる".to_string()),
+        ]);
+        apply(&token, &mut response);
+
+        let readings: Vec<_> = response
+            .suggestions
+            .iter()
+            .map(|i| i.secondary_preferred())
+            .collect();
+        assert_eq!(readings, vec!["食べる", "食べ物", "たべる"]);
+    }
+}
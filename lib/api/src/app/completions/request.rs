@@ -1,6 +1,7 @@
 use std::str::FromStr;
 
 use error::api_error::RestError;
+use japanese::{guessing::partial_romaji, normalize::normalize};
 use jp_utils::JapaneseExt;
 use search::query::{self, parser::QueryParser, Query, QueryLang, UserSettings};
 use types::{api::app::completions::Request, jotoba::language::Language};
@@ -20,18 +21,8 @@ pub(crate) fn adjust(request: Request) -> Request {
         query_str = query_str.replace("ｎ", "ん");
     }
 
-    let last_chars = query_str.chars().rev().take(2).collect::<Vec<_>>();
-    if lang == QueryLang::Japanese
-        && !last_chars.iter().any(|i| !i.is_japanese())
-        && query_len > 1
-        && !last_chars.is_empty()
-    {
-        let len: usize = last_chars
-            .into_iter()
-            .filter(|i| i.is_roman_letter())
-            .map(|i| i.len_utf8())
-            .sum();
-        query_str = query_str[..query_str.len() - len].to_string();
+    if lang == QueryLang::Japanese && query_len > 1 {
+        query_str = convert_trailing_romaji(&query_str);
     }
 
     Request {
@@ -40,6 +31,28 @@ pub(crate) fn adjust(request: Request) -> Request {
     }
 }
 
+/// Converts the romaji of kana which are currently being typed (eg. "たべr" or "たべｒｕ") into
+/// kana. Romaji which can't be converted yet gets stripped off
+fn convert_trailing_romaji(query_str: &str) -> String {
+    let romaji_start = query_str
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| c.is_roman_letter())
+        .last()
+        .map(|(pos, _)| pos);
+
+    let romaji_start = match romaji_start {
+        Some(pos) if pos > 0 => pos,
+        _ => return query_str.to_string(),
+    };
+
+    let (head, romaji) = query_str.split_at(romaji_start);
+    let kana = partial_romaji(&normalize(romaji))
+        .map(|(kana, _)| kana)
+        .unwrap_or_default();
+    format!("{head}{kana}")
+}
+
 /// Returns a `Query` based on the `Request`
 pub(crate) fn get_query(request: Request) -> Result<(Query, Vec<char>), RestError> {
     let query_str = request.input.trim_start().to_string();
@@ -0,0 +1,111 @@
+use itertools::Itertools;
+use japanese::ToKanaExt;
+use search::query::{Query, QueryLang};
+use types::{api::app::completions::WordPair, jotoba::search::SearchTarget};
+use utils::{epoch::Cached, real_string_len};
+
+/// Min length of the query for sentences to be suggested. Shorter input matches too many
+/// sentences to be useful
+const MIN_QUERY_LEN: usize = 4;
+
+/// Max amount of index entries looked at for a single prefix
+const MAX_SCAN: usize = 1000;
+
+/// Max amount of suggested sentences if the sentence search is targeted
+const MAX_SENTENCES: usize = 8;
+
+/// Max amount of suggested sentences within word suggestions
+const MAX_SENTENCES_WORDS: usize = 3;
+
+/// Amount of word suggestions to be shown before suggested sentences
+const WORDS_FIRST: usize = 5;
+
+/// Prefix index over the text and kana reading of all example sentences
+static INDEX: Cached<SentenceIndex> = Cached::new(SentenceIndex::build);
+
+/// Builds the sentence index so the first suggestion request doesn't have to
+#[inline]
+pub(super) fn warm_up() {
+    Cached::force(&INDEX);
+}
+
+struct SentenceIndex {
+    /// Keys and ids of the sentences they belong to, sorted by key
+    entries: Vec<(Box<str>, u32)>,
+}
+
+impl SentenceIndex {
+    fn build() -> Self {
+        let mut entries: Vec<(Box<str>, u32)> = resources::get()
+            .sentences()
+            .iter()
+            .flat_map(|sentence| {
+                let text = (sentence.japanese.as_str().into(), sentence.id);
+                let kana = (sentence.get_kana().to_hiragana().into(), sentence.id);
+                [text, kana]
+            })
+            .collect();
+        entries.sort_unstable();
+        entries.dedup();
+        Self { entries }
+    }
+
+    /// Returns the ids of all sentences starting with `prefix`, shortest sentences first
+    fn find(&self, prefix: &str) -> Vec<u32> {
+        let start = self.entries.partition_point(|i| &*i.0 < prefix);
+
+        self.entries[start..]
+            .iter()
+            .take(MAX_SCAN)
+            .take_while(|i| i.0.starts_with(prefix))
+            .sorted_by_key(|i| i.0.chars().count())
+            .map(|i| i.1)
+            .unique()
+            .collect()
+    }
+}
+
+/// Returns example sentences starting with the query. Only longer Japanese queries get sentences
+/// suggested
+pub(super) fn suggestions(query: &Query) -> Vec<WordPair> {
+    let query_str = query.query_str.trim();
+    if query.q_lang != QueryLang::Japanese || real_string_len(query_str) < MIN_QUERY_LEN {
+        return vec![];
+    }
+
+    let limit = match query.target {
+        SearchTarget::Sentences => MAX_SENTENCES,
+        _ => MAX_SENTENCES_WORDS,
+    };
+
+    let sentences = resources::get().sentences();
+
+    [query_str.to_string(), query_str.to_hiragana()]
+        .iter()
+        .unique()
+        .flat_map(|prefix| INDEX.find(prefix))
+        .unique()
+        .filter_map(|id| sentences.by_id(id))
+        .take(limit)
+        .map(|sentence| WordPair {
+            primary: sentence.japanese.clone(),
+            secondary: None,
+            search_target: Some(SearchTarget::Sentences),
+        })
+        .collect()
+}
+
+/// Inserts suggested `sentences` into `suggestions`. Sentences come first if the sentence search
+/// is targeted and after the best word suggestions otherwise
+pub(super) fn insert(
+    suggestions: &mut Vec<WordPair>,
+    sentences: Vec<WordPair>,
+    target: SearchTarget,
+) {
+    let pos = match target {
+        SearchTarget::Sentences => 0,
+        _ => suggestions.len().min(WORDS_FIRST),
+    };
+
+    suggestions.splice(pos..pos, sentences);
+}
@@ -0,0 +1,131 @@
+use search::query::Query;
+use types::{
+    api::app::completions::{Response, SuggestionType, WordPair},
+    jotoba::search::SearchTarget,
+};
+
+/// Max amount of suggestions in a mixed response
+const MAX_SUGGESTIONS: usize = 12;
+
+/// Amount of suggestions reserved for each search type, so words don't crowd out everything
+/// else. Slots not used by one type get filled up with the remaining suggestions of the others
+const QUOTAS: [(SearchTarget, usize); 3] = [
+    (SearchTarget::Words, 6),
+    (SearchTarget::Kanji, 3),
+    (SearchTarget::Names, 3),
+];
+
+/// Returns word, kanji and name suggestions for `query`, labeled with their search type
+pub(super) fn suggestions(query: Query, radicals: Vec<char>) -> Response {
+    // Kanji readings are unambiguous
+    if super::as_kanji_reading(&query).is_some() {
+        return super::get_suggestions(query, radicals);
+    }
+
+    let results = QUOTAS
+        .iter()
+        .map(|(target, quota)| {
+            let query = Query {
+                target: *target,
+                ..query.clone()
+            };
+            let suggestions = super::get_suggestions(query, radicals.clone()).suggestions;
+            (*target, *quota, suggestions)
+        })
+        .collect::<Vec<_>>();
+
+    Response::with_type(mix(results, MAX_SUGGESTIONS), SuggestionType::Mixed)
+}
+
+/// Mixes the suggestions of multiple search types, taking up to `quota` suggestions of each type
+/// first and filling up the remaining slots in the given order. Duplicates are only kept for the
+/// first type they appear in
+fn mix(results: Vec<(SearchTarget, usize, Vec<WordPair>)>, limit: usize) -> Vec<WordPair> {
+    let mut queues = results
+        .into_iter()
+        .map(|(target, quota, suggestions)| {
+            let suggestions = suggestions.into_iter().map(move |mut i| {
+                i.search_target.get_or_insert(target);
+                i
+            });
+            (quota, suggestions)
+        })
+        .collect::<Vec<_>>();
+
+    let mut out: Vec<WordPair> = Vec::with_capacity(limit);
+
+    // Guaranteed slots
+    for (quota, suggestions) in queues.iter_mut() {
+        let mut taken = 0;
+        while taken < *quota && out.len() < limit {
+            match suggestions.next() {
+                Some(item) => {
+                    if push_unique(&mut out, item) {
+                        taken += 1;
+                    }
+                }
+                None => break,
+            }
+        }
+    }
+
+    // Leftover slots
+    for (_, suggestions) in queues.iter_mut() {
+        for item in suggestions {
+            if out.len() >= limit {
+                return out;
+            }
+            push_unique(&mut out, item);
+        }
+    }
+
+    out
+}
+
+/// Pushes `item` to `out` if it's not already in there. Returns `true` if it was pushed
+fn push_unique(out: &mut Vec<WordPair>, item: WordPair) -> bool {
+    let duplicate = out
+        .iter()
+        .any(|i| i.primary == item.primary && i.secondary == item.secondary);
+    if !duplicate {
+        out.push(item);
+    }
+    !duplicate
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn pairs(primaries: &[&str]) -> Vec<WordPair> {
+        primaries
+            .iter()
+            .map(|i| WordPair::new(i.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_mix_quota() {
+        let results = vec![
+            (SearchTarget::Words, 2, pairs(&["w1", "w2", "w3", "w4"])),
+            (SearchTarget::Kanji, 1, pairs(&["k1", "k2"])),
+            (SearchTarget::Names, 1, pairs(&[])),
+        ];
+        let mixed = mix(results, 5);
+        let primaries: Vec<_> = mixed.iter().map(|i| i.primary.as_str()).collect();
+        assert_eq!(primaries, ["w1", "w2", "k1", "w3", "w4"]);
+        assert_eq!(mixed[2].search_target, Some(SearchTarget::Kanji));
+    }
+
+    #[test]
+    fn test_mix_duplicates() {
+        let results = vec![
+            (SearchTarget::Words, 1, pairs(&["a"])),
+            (SearchTarget::Kanji, 1, pairs(&["a", "b"])),
+        ];
+        let mixed = mix(results, 5);
+        assert_eq!(mixed.len(), 2);
+        assert_eq!(mixed[0].search_target, Some(SearchTarget::Words));
+        assert_eq!(mixed[1].primary, "b");
+    }
+}
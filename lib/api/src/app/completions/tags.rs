@@ -0,0 +1,48 @@
+use actix_web::web::Json;
+use types::api::app::completions::{Response, SuggestionType, TagRequest, WordPair};
+
+/// Max amount of tags returned
+const MAX_COMPLETIONS: usize = 10;
+
+/// Tag completion endpoint. Completes partial tags like "#ad" to "#adjective" and "#adverb",
+/// using the same tag definitions as the query parser
+pub async fn tag_completion_ep(payload: Json<TagRequest>) -> Json<Response> {
+    Json(suggestions(&payload.input))
+}
+
+/// Returns all tags starting with `partial` along with their descriptions
+pub(crate) fn suggestions(partial: &str) -> Response {
+    let suggestions = search::query::parser::tags::complete(partial)
+        .into_iter()
+        .take(MAX_COMPLETIONS)
+        .map(|tag| match search::query::parser::tags::describe(&tag) {
+            Some(description) => WordPair::with_secondary(tag, description),
+            None => WordPair::new(tag),
+        })
+        .collect();
+
+    Response::with_type(suggestions, SuggestionType::Hashtag)
+}
+
+/// Returns the tag which is currently being typed at the end of `input` (eg. "#ad" for "猫 #ad")
+pub(crate) fn trailing_tag(input: &str) -> Option<&str> {
+    let fragment = input.rsplit(char::is_whitespace).next()?;
+    let name = fragment.strip_prefix('#')?;
+    name.chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-')
+        .then(|| fragment)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_trailing_tag() {
+        assert_eq!(trailing_tag("猫 #ad"), Some("#ad"));
+        assert_eq!(trailing_tag("#"), Some("#"));
+        assert_eq!(trailing_tag("#n5 猫"), None);
+        assert_eq!(trailing_tag("#ad "), None);
+        assert_eq!(trailing_tag("猫#"), None);
+    }
+}
@@ -6,28 +6,45 @@ use autocompletion::suggest::{
     query::SuggestionQuery,
     task::SuggestionTask,
 };
+use indexes::storage::suggestions::SuggestionStorage;
 use japanese::{
-    guessing::{could_be_romaji, is_romaji_repl},
+    guessing::{could_be_romaji, is_romaji_repl, partial_romaji},
     to_hira_fmt,
 };
 use types::jotoba::language::Language;
 use utils::real_string_len;
 
-use super::super::*;
+use super::{
+    super::*,
+    frequency,
+    fuzzy_ext::FuzzyExtension,
+    gloss_langs::{has_glosses, rank_by_glosses},
+};
+
+/// Misspelled input gets corrected if less suggestions than this were found
+const FUZZY_THRESHOLD: usize = 10;
+
+/// Min amount of morae with a vowel partially typed romaji needs before it gets converted. Shorter
+/// input (eg. "tab" or "bank") is more likely to be the beginning of a foreign word
+const MIN_PARTIAL_MORAE: usize = 2;
 
 /// Returns suggestions based on non japanese input
 pub fn suggestions(query: &Query, query_str: &str) -> Option<Vec<WordPair>> {
     let query_lower = autocompletion::index::basic::basic_format(query_str.trim());
+    let store = indexes::get_suggestions();
     let mut task = SuggestionTask::new(30);
 
-    let lang = query.settings.language();
+    // Suggestion indexes are only loaded for the configured languages
+    let lang = indexes::get()
+        .word()
+        .loaded_language(query.settings.language());
 
     // Default search query
-    task.add_query(new_suggestion_query(&query_lower, lang)?);
+    task.add_query(new_suggestion_query(&store, &query_lower, lang)?);
 
     // Add results for english
     if query.settings.show_english() {
-        let mut en_sugg_query = new_suggestion_query(&query_lower, Language::English)?;
+        let mut en_sugg_query = new_suggestion_query(&store, &query_lower, Language::English)?;
         en_sugg_query.weights.total_weight = 0.75;
         en_sugg_query.weights.freq_weight = 0.15;
         task.add_query(en_sugg_query);
@@ -35,11 +52,14 @@ pub fn suggestions(query: &Query, query_str: &str) -> Option<Vec<WordPair>> {
 
     // Romaji result
     //if let Some(hira_query) = try_romaji(query_str.trim()) {
-    let hira_query =
-        try_romaji(query_str.trim()).unwrap_or_else(|| japanese::to_hira_fmt(query_str));
+    // Romaji which is still being typed gets converted as far as possible (eg. "tabem" -> "たべ").
+    // Completions matching the pending romaji are boosted below
+    let hira_query = typed_romaji(query_str)
+        .or_else(|| try_romaji(query_str.trim()))
+        .unwrap_or_else(|| japanese::to_hira_fmt(query_str));
     //let hira_query = query_str.to_hiragana();
-    println!("hira query: {hira_query}");
-    let jp_engine = indexes::get_suggestions().jp_words();
+    tracing::debug!(%hira_query);
+    let jp_engine = store.jp_words();
     let mut rom_query = SuggestionQuery::new(jp_engine, hira_query.clone());
     if could_be_romaji(query_str) {
         rom_query.weights.total_weight = 0.99;
@@ -75,7 +95,16 @@ pub fn suggestions(query: &Query, query_str: &str) -> Option<Vec<WordPair>> {
     ng_ext.cust_query = Some(hira_query.clone());
     rom_query.add_extension(ng_ext);
 
-    task.set_rel_mod(|i, rel| {
+    task.set_rel_mod(move |i, rel| {
+        let rel = frequency::weighted(i.word_id(), rel);
+
+        // Demote words without glosses in the users language
+        let rel = if rank_by_glosses(lang) && !has_glosses(store, i.word_id(), lang) {
+            rel / 2
+        } else {
+            rel
+        };
+
         let out = i.to_output();
         let kana = &out.primary;
         if japanese::romaji_prefix(query_str.trim(), &kana) {
@@ -90,8 +119,12 @@ pub fn suggestions(query: &Query, query_str: &str) -> Option<Vec<WordPair>> {
     Some(convert_results(task.search()))
 }
 
-fn new_suggestion_query(query: &str, lang: Language) -> Option<SuggestionQuery> {
-    let engine = indexes::get_suggestions().foreign_words(lang)?;
+fn new_suggestion_query<'a>(
+    store: &'a SuggestionStorage,
+    query: &str,
+    lang: Language,
+) -> Option<SuggestionQuery<'a>> {
+    let engine = store.foreign_words(lang)?;
 
     let mut suggestion_query = SuggestionQuery::new(engine, &query);
     suggestion_query.weights.str_weight = 1.5;
@@ -106,9 +139,27 @@ fn new_suggestion_query(query: &str, lang: Language) -> Option<SuggestionQuery>
     ng_ex.options.threshold = 5;
     suggestion_query.add_extension(ng_ex);
 
+    // Fix typos (eg. "recieve")
+    let mut fuzzy_ext = FuzzyExtension::new(engine);
+    fuzzy_ext.options.threshold = FUZZY_THRESHOLD;
+    fuzzy_ext.options.weights.total_weight = 0.6;
+    fuzzy_ext.options.weights.freq_weight = 0.3;
+    suggestion_query.add_extension(fuzzy_ext);
+
     Some(suggestion_query)
 }
 
+/// Converts `query_str` into kana if it's plausibly romaji which is still being typed
+fn typed_romaji(query_str: &str) -> Option<String> {
+    let (kana, pending) = partial_romaji(query_str)?;
+    if pending.is_empty() {
+        return Some(kana);
+    }
+
+    let morae = kana.chars().filter(|i| !matches!(i, 'ん' | 'っ')).count();
+    (morae >= MIN_PARTIAL_MORAE).then(|| kana)
+}
+
 /// Returns Some(String) if `query_str` could be (part of) romaji search input and None if not
 pub(crate) fn try_romaji(query_str: &str) -> Option<String> {
     let mut query_str = query_str.replace("-", "ー");
@@ -185,4 +236,14 @@ mod test {
         assert_eq!(strip_str_end(inp, 2), "これはかっこいいテキ");
         assert_eq!(strip_str_end(inp, 3), "これはかっこいいテ");
     }
+
+    #[test]
+    fn test_typed_romaji() {
+        assert_eq!(typed_romaji("tabe"), Some("たべ".to_string()));
+        assert_eq!(typed_romaji("tabem"), Some("たべ".to_string()));
+        assert_eq!(typed_romaji("kaish"), Some("かい".to_string()));
+        assert_eq!(typed_romaji("tab"), None);
+        assert_eq!(typed_romaji("bank"), None);
+        assert_eq!(typed_romaji("table"), None);
+    }
 }
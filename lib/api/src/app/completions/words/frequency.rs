@@ -0,0 +1,35 @@
+/// Relevance added to suggestions of the most frequent words. Less frequent words get a share of
+/// it relative to the logarithm of their frequency
+const MAX_BOOST: f64 = 300.0;
+
+/// Frequency at which words get the full boost
+const FULL_BOOST_FREQ: f64 = 1_000_000.0;
+
+/// Adds a boost based on the frequency of the word with the given sequence id to `rel`,
+/// so common words (食べる) get suggested before rare ones sharing the same prefix
+#[inline]
+pub(crate) fn weighted(word_id: u32, rel: u16) -> u16 {
+    match resources::get().words().frequency(word_id) {
+        Some(freq) => rel.saturating_add(boost(freq)),
+        None => rel,
+    }
+}
+
+/// Returns the relevance boost for words with a frequency of `freq`
+fn boost(freq: u32) -> u16 {
+    let share = (freq as f64).ln_1p() / FULL_BOOST_FREQ.ln_1p();
+    (share.min(1.0) * MAX_BOOST) as u16
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_boost() {
+        assert_eq!(boost(0), 0);
+        assert!(boost(10) < boost(1000));
+        assert!(boost(1000) < boost(100_000));
+        assert_eq!(boost(u32::MAX), MAX_BOOST as u16);
+    }
+}
@@ -0,0 +1,133 @@
+use autocompletion::{
+    index::{basic::BasicIndex, IndexItem},
+    relevance::{item::EngineItem, RelevanceCalc},
+    suggest::{
+        extension::{Extension, ExtensionOptions},
+        query::SuggestionQuery,
+    },
+};
+use indexes::storage::suggestions::FG_WORDS_NGRAM;
+use priority_container::PrioContainerMax;
+
+/// Extension suggesting terms within a small edit distance of the query, so misspelled input
+/// (eg. "recieve") still gets completed. Only terms starting with the same character as the
+/// query are looked at
+#[derive(Clone, Copy)]
+pub struct FuzzyExtension<'a> {
+    pub options: ExtensionOptions,
+    index: &'a BasicIndex<FG_WORDS_NGRAM>,
+}
+
+impl<'a> FuzzyExtension<'a> {
+    /// Create a new Fuzzy-Extension
+    pub fn new(index: &'a BasicIndex<FG_WORDS_NGRAM>) -> Self {
+        let mut options = ExtensionOptions::default();
+        options.weights.freq_weight = 0.01;
+        options.min_query_len = 5;
+        Self { options, index }
+    }
+}
+
+impl<'a> Extension<'a> for FuzzyExtension<'a> {
+    fn run(&self, query: &SuggestionQuery, rel_weight: f64) -> Vec<EngineItem<'a>> {
+        let query_str = &query.query_str;
+
+        let max_dist = max_distance(query_str);
+        if max_dist == 0 {
+            return vec![];
+        }
+
+        let first_char = match query_str.chars().next() {
+            Some(c) => c,
+            None => return vec![],
+        };
+
+        let rel_weight = rel_weight * self.options.weights.total_weight;
+        let mut out = PrioContainerMax::new(self.options.limit);
+
+        let rel_calc = RelevanceCalc::new(self.options.weights).with_total_weight(rel_weight);
+
+        let mut buf = [0u8; 4];
+        let items = self
+            .index
+            .trie
+            .iter_prefix_str(first_char.encode_utf8(&mut buf));
+        for (term, ids) in items {
+            // Exact prefix matches are found by the query itself
+            let dist = prefix_distance(query_str, term.as_str());
+            if dist == 0 || dist > max_dist {
+                continue;
+            }
+
+            for id in ids {
+                let mut item = self.index.get_item(*id).into_engine_item();
+                let str_rel = item.inner().str_relevance(query_str);
+                let rel = rel_calc.calc(&item, str_rel) / (dist as u16 + 1);
+                item.set_relevance(rel);
+                out.insert(item);
+            }
+        }
+
+        let out = out.into_iter().map(|i| i.0).collect::<Vec<_>>();
+        let rel_calc = RelevanceCalc::new(self.options.weights).with_total_weight(rel_weight);
+        query.order_items(out, rel_calc)
+    }
+
+    #[inline]
+    fn should_run(&self, already_found: usize, query: &SuggestionQuery) -> bool {
+        self.options.enabled
+            && already_found < self.options.threshold
+            && query.query_str.chars().count() >= self.options.min_query_len
+    }
+
+    #[inline]
+    fn get_options(&self) -> &ExtensionOptions {
+        &self.options
+    }
+}
+
+/// Max edit distance allowed for `query`. Short queries don't get corrected at all since nearly
+/// every term is in reach of them
+#[inline]
+fn max_distance(query: &str) -> usize {
+    match query.chars().count() {
+        0..=3 => 0,
+        4..=5 => 1,
+        _ => 2,
+    }
+}
+
+/// Returns the smallest edit distance between `query` and a prefix of `term` which is about as
+/// long as `query`, so incomplete input gets matched as well
+fn prefix_distance(query: &str, term: &str) -> usize {
+    let query_len = query.chars().count();
+
+    (query_len.saturating_sub(1)..=query_len + 1)
+        .map(|len| {
+            let end = term.char_indices().nth(len).map_or(term.len(), |i| i.0);
+            utils::levenshtein(query, &term[..end])
+        })
+        .min()
+        .unwrap_or(usize::MAX)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_max_distance() {
+        // Swapped letters count as two edits
+        assert_eq!(max_distance("recieve"), 2);
+        assert_eq!(max_distance("dog"), 0);
+    }
+
+    #[test]
+    fn test_prefix_distance() {
+        assert_eq!(prefix_distance("recieve", "receive"), 2);
+        assert_eq!(prefix_distance("recieve", "receiver"), 2);
+        assert_eq!(prefix_distance("recie", "receive"), 1);
+        assert_eq!(prefix_distance("recei", "receive"), 0);
+        assert_eq!(prefix_distance("tomatoe", "tomato"), 1);
+    }
+}
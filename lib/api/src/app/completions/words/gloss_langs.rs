@@ -0,0 +1,22 @@
+use indexes::storage::suggestions::SuggestionStorage;
+use types::jotoba::language::Language;
+
+/// Returns `true` if the word with the given sequence id has glosses in `lang`. Unknown words
+/// are treated as if they had glosses in all languages
+#[inline]
+pub(crate) fn has_glosses(store: &SuggestionStorage, word_id: u32, lang: Language) -> bool {
+    let mask = match store.gloss_langs(word_id) {
+        Some(mask) => mask,
+        None => return true,
+    };
+
+    let lang: i32 = lang.into();
+    mask & (1 << lang) != 0
+}
+
+/// Returns `true` if suggestions for `lang` should be ranked by the availability of glosses.
+/// English is available for all words so there is nothing to rank by
+#[inline]
+pub(crate) fn rank_by_glosses(lang: Language) -> bool {
+    lang != Language::English
+}
@@ -7,7 +7,8 @@ pub fn suggestions(query: &str, search_target: SearchTarget) -> Option<Vec<WordP
         return Some(empty(search_target));
     }
 
-    let index = indexes::get_suggestions().hashtags();
+    let store = indexes::get_suggestions();
+    let index = store.hashtags();
     let res = index.ngram_search(query, &[search_target]);
     let max = res.first()?.1;
 
@@ -20,9 +21,10 @@ pub fn suggestions(query: &str, search_target: SearchTarget) -> Option<Vec<WordP
     Some(out)
 }
 
+#[tracing::instrument(level = "debug")]
 fn empty(search_target: SearchTarget) -> Vec<WordPair> {
-    let start = std::time::Instant::now();
-    let index = &indexes::get_suggestions().hashtags();
+    let store = indexes::get_suggestions();
+    let index = store.hashtags();
     let ngindex = index.index.deref();
 
     let mut out: Vec<_> = ngindex
@@ -34,11 +36,8 @@ fn empty(search_target: SearchTarget) -> Vec<WordPair> {
 
     out.sort_by(|a, b| a.freq.total_cmp(&b.freq).reverse());
 
-    let res = out
-        .into_iter()
+    out.into_iter()
         .take(10)
         .map(|i| WordPair::new(i.tag.clone()))
-        .collect();
-    println!("took: {:?}", start.elapsed());
-    res
+        .collect()
 }
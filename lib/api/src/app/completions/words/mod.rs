@@ -1,9 +1,12 @@
 pub mod foreign;
+mod frequency;
+mod fuzzy_ext;
+mod gloss_langs;
 pub mod hashtag;
 pub mod kana_end_ext;
 pub mod native;
 
-use std::{cmp::Ordering, time::Instant};
+use std::cmp::Ordering;
 
 use jp_utils::JapaneseExt;
 use search::query::{Query, QueryLang};
@@ -26,8 +29,8 @@ pub(crate) fn suggestions(query: Query, radicals: &[char]) -> Option<Response> {
 }
 
 /// Returns Ok(suggestions) for the given query ordered and ready to display
+#[tracing::instrument(level = "debug", skip_all, fields(lang = ?query.q_lang))]
 fn try_word_suggestions(query: &Query, radicals: &[char]) -> Option<Vec<WordPair>> {
-    let start = Instant::now();
     // Get sugesstions for matching language
 
     let romaji_query = to_romaji(query.query_str.as_str());
@@ -42,8 +45,6 @@ fn try_word_suggestions(query: &Query, radicals: &[char]) -> Option<Vec<WordPair
             res
         }
     };
-    log::debug!("Suggestions took: {:?}", start.elapsed());
-
     Some(word_pairs)
 }
 
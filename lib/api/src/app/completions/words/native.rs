@@ -1,4 +1,9 @@
-use super::{super::*, kana_end_ext::KanaEndExtension};
+use super::{
+    super::*,
+    frequency,
+    gloss_langs::{has_glosses, rank_by_glosses},
+    kana_end_ext::KanaEndExtension,
+};
 use autocompletion::{
     index::{str_item::StringItem, IndexItem},
     suggest::{
@@ -16,7 +21,8 @@ const MAX_SENTENCE_LEN: usize = 15;
 
 /// Get suggestions for foreign search input
 pub fn suggestions(query: &Query, _romaji_query: &str, radicals: &[char]) -> Option<Vec<WordPair>> {
-    let jp_engine = indexes::get_suggestions().jp_words();
+    let store = indexes::get_suggestions();
+    let jp_engine = store.jp_words();
     let query_str = query.query_str.as_str();
 
     let mut suggestion_task = SuggestionTask::new(30);
@@ -91,6 +97,18 @@ pub fn suggestions(query: &Query, _romaji_query: &str, radicals: &[char]) -> Opt
         suggestion_task.add_custom_entries(items);
     }
 
+    // Prefer frequent words and demote words without glosses in the users language
+    let lang = query.settings.language();
+    let demote_glossless = rank_by_glosses(lang);
+    suggestion_task.set_rel_mod(move |item, rel| {
+        let rel = frequency::weighted(item.word_id(), rel);
+        if demote_glossless && !has_glosses(store, item.word_id(), lang) {
+            rel / 2
+        } else {
+            rel
+        }
+    });
+
     // radical filter
     let word_res = resources::get().words();
     suggestion_task.set_filter(move |item| {
@@ -27,7 +27,7 @@ fn sentence_details(payload: &DetailsPayload) -> Option<sentence::Details> {
     let sentence =
         search::sentence::result::Sentence::from_m_sentence(sentence, payload.lang_param())?;
 
-    let sentence = convert_sentence(sentence);
+    let sentence = convert_sentence(sentence, payload.sentence_script);
     Some(sentence::Details::new(sentence, words, kanji))
 }
 
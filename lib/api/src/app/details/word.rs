@@ -6,7 +6,7 @@ use types::{
     api::app::{
         details::{
             query::DetailsPayload,
-            word::{self, TransitivityPair},
+            word::{self, Collocation, TransitivityPair},
         },
         search::responses::{kanji::Kanji, words::Word},
     },
@@ -21,6 +21,9 @@ pub async fn details(payload: Json<DetailsPayload>) -> Result<Json<word::Details
     ))
 }
 
+/// Max amount of collocations returned for a word
+const MAX_COLLOCATIONS: usize = 10;
+
 pub(crate) struct Details<'a> {
     payload: &'a DetailsPayload,
     word: &'static types::jotoba::words::Word,
@@ -82,19 +85,12 @@ impl<'a> Details<'a> {
         None
     }
 
-    fn get_collocations(&self) -> Vec<Word> {
-        let collocations = match &self.word.collocations {
-            Some(colloc) => colloc,
-            None => return vec![],
-        };
-        let retrieve = resources::get().words();
-
-        collocations
-            .iter()
-            .filter_map(|i| {
-                let word = retrieve.by_sequence(*i)?;
-                Some(self.format_word(word))
-            })
+    fn get_collocations(&self) -> Vec<Collocation> {
+        resources::get()
+            .words()
+            .collocations(self.word, MAX_COLLOCATIONS)
+            .into_iter()
+            .map(|(word, freq)| Collocation::new(self.format_word(word), freq))
             .collect()
     }
 
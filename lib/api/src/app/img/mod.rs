@@ -1,5 +1,5 @@
 #![allow(unused)]
-#[cfg(feature = "img_scan")]
+mod ocr;
 pub mod request;
 
 use actix_multipart::Multipart;
@@ -10,7 +10,10 @@ use itertools::Itertools;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use std::path::Path;
-use types::api::app::image::{Request, Response};
+use types::api::app::{
+    image::{Request, Response, SearchRequest, SearchResponse},
+    search::query::{SearchPayload, UserSettings},
+};
 
 // MAX 2MB
 const MAX_UPLOAD_SIZE: usize = 2 * 1024 * 1024;
@@ -25,27 +28,77 @@ pub async fn scan_ep(
     args: web::Query<Request>,
     config: web::Data<Config>,
 ) -> Result<Json<Response>, actix_web::Error> {
+    let text = scan(payload, args.threshold, config).await?;
+    Ok(Json(Response { text }))
+}
+
+/// Scans an image and runs a word search with the recognized text, so the analyzer splits it
+/// into words just like a regular sentence search
+pub async fn search_ep(
+    payload: Multipart,
+    args: web::Query<SearchRequest>,
+    config: web::Data<Config>,
+) -> Result<Json<SearchResponse>, actix_web::Error> {
+    let text = scan(payload, args.threshold, config).await?;
+
+    let settings = UserSettings {
+        user_lang: args.lang.unwrap_or_default(),
+        show_english: args.show_english,
+        page_size: args.page_size,
+        show_example_sentences: false,
+        sentence_furigana: true,
+        sentence_script: Default::default(),
+        sentence_max_katakana: None,
+        sentence_max_kanji_grade: None,
+    };
+    let mut search_payload = SearchPayload::new(text.clone(), settings);
+    search_payload.page = args.page;
+    let (words, _) = super::search::words::search_words(&search_payload, None).await?;
+
+    Ok(Json(SearchResponse::new(text, words)))
+}
+
+/// Stores the uploaded image, scans it and returns the recognized text
+async fn scan(
+    payload: Multipart,
+    threshold: i32,
+    config: web::Data<Config>,
+) -> Result<String, actix_web::Error> {
     // Load payload
     let local_file = request::read_payload(&config, payload).await?;
 
     // Scan image
     let local_file_cloned = local_file.clone();
-    let res = web::block(move || scan_image(local_file_cloned, &args, &config)).await;
+    let res = web::block(move || scan_image(local_file_cloned, threshold, &config)).await;
 
     // Cleanup file
     web::block(move || std::fs::remove_file(local_file)).await??;
 
     // Handle result after cleaning up files
-    Ok(Json(res??))
+    Ok(res??)
 }
 
-/// Scans an image and returns a `Response` with the recognized text or an error
-#[cfg(feature = "img_scan")]
+/// Scans an image using the configured OCR backend and returns the recognized japanese text
 fn scan_image<P: AsRef<Path>>(
     file: P,
-    req: &Request,
+    threshold: i32,
+    config: &Config,
+) -> Result<String, RestError> {
+    let text = match config.server.ocr_command {
+        Some(ref command) => ocr::run(command, file.as_ref())?,
+        None => scan_builtin(file, threshold, config)?,
+    };
+
+    format_text(text).ok_or(RestError::NoTextFound)
+}
+
+/// Scans an image using the tesseract bindings
+#[cfg(feature = "img_scan")]
+fn scan_builtin<P: AsRef<Path>>(
+    file: P,
+    threshold: i32,
     config: &Config,
-) -> Result<Response, RestError> {
+) -> Result<String, RestError> {
     let tess_data = config.server.tess_data.as_ref().map(|i| i.as_str());
     let mut lt = leptess::LepTess::new(tess_data, "jpn").map_err(|_| RestError::Internal)?;
     lt.set_image(file).map_err(|_| RestError::NoTextFound)?;
@@ -54,17 +107,22 @@ fn scan_image<P: AsRef<Path>>(
         lt.set_source_resolution(70)
     }
 
-    if lt.mean_text_conf() < req.threshold {
+    if lt.mean_text_conf() < threshold {
         return Err(RestError::NoTextFound);
     }
 
-    let text = lt
-        .get_utf8_text()
-        .ok()
-        .and_then(|text| format_text(text))
-        .ok_or(RestError::NoTextFound)?;
+    lt.get_utf8_text().map_err(|_| RestError::NoTextFound)
+}
 
-    Ok(Response { text })
+/// Built without tesseract bindings, so scanning images requires an OCR command to be configured
+#[cfg(not(feature = "img_scan"))]
+fn scan_builtin<P: AsRef<Path>>(
+    _file: P,
+    _threshold: i32,
+    _config: &Config,
+) -> Result<String, RestError> {
+    log::warn!("Image scan requested but neither img_scan feature nor ocr_command is enabled");
+    Err(RestError::FormatNotSupported)
 }
 
 /// Format non-japanese characters from scanned result
@@ -82,27 +140,3 @@ fn format_text(text: String) -> Option<String> {
 
     (!modded_text.is_empty()).then(|| modded_text)
 }
-
-#[cfg(not(feature = "img_scan"))]
-mod request {
-    use super::*;
-    use std::path::PathBuf;
-    pub(crate) async fn read_payload(
-        config: &Config,
-        mut payload: Multipart,
-    ) -> Result<PathBuf, RestError> {
-        todo!()
-    }
-}
-
-/// Scans an image and returns a `Response` with the recognized text or an error
-#[cfg(not(feature = "img_scan"))]
-fn scan_image<P: AsRef<Path>>(
-    _file: P,
-    _req: &Request,
-    _config: &Config,
-) -> Result<Response, RestError> {
-    Ok(Response {
-        text: String::from("unsupported"),
-    })
-}
@@ -0,0 +1,37 @@
+use error::api_error::RestError;
+use std::{path::Path, process::Command, time::Duration};
+
+/// Placeholder within the configured command which gets replaced with the image path
+const FILE_PLACEHOLDER: &str = "{file}";
+
+/// Max time the OCR backend may take for a single image
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Runs the configured OCR backend `command` on `file` and returns the text it wrote to stdout
+pub(crate) fn run(command: &str, file: &Path) -> Result<String, RestError> {
+    let file = file.to_str().ok_or(RestError::Internal)?;
+
+    let mut args = command
+        .split_whitespace()
+        .map(|arg| arg.replace(FILE_PLACEHOLDER, file));
+    let program = args.next().ok_or(RestError::Internal)?;
+
+    let mut cmd = Command::new(&program);
+    cmd.args(args);
+    let output = utils::process::output_with_timeout(cmd, TIMEOUT)
+        .map_err(|err| {
+            log::error!("Failed to run OCR backend {program:?}: {err}");
+            RestError::Internal
+        })?
+        .ok_or_else(|| {
+            log::warn!("OCR backend didn't finish within {TIMEOUT:?}");
+            RestError::Timeout
+        })?;
+
+    if !output.status.success() {
+        log::warn!("OCR backend exited with {}", output.status);
+        return Err(RestError::NoTextFound);
+    }
+
+    String::from_utf8(output.stdout).map_err(|_| RestError::NoTextFound)
+}
@@ -5,11 +5,12 @@ pub mod kanji;
 pub mod news;
 pub mod radical;
 pub mod search;
+pub mod settings;
 
 use error::api_error::RestError;
 use types::{
     api::app::search::responses::words,
-    jotoba::{self, language::Language},
+    jotoba::{self, language::Language, words::reference::ReferenceKind},
 };
 
 pub type Result<T> = std::result::Result<T, RestError>;
@@ -20,6 +21,12 @@ pub(crate) fn conv_word(word: jotoba::words::Word, lang: Language) -> words::Wor
 
     let audio = word.audio_file().map(|audio| format!("/audio/{audio}"));
 
+    let word_storage = resources::get().words();
+    let linked_names = word_storage.linked_names(word.sequence).to_vec();
+
+    let see_also = word_storage.referenced(word.sequence, ReferenceKind::SeeAlso);
+    let antonyms = word_storage.referenced(word.sequence, ReferenceKind::Antonym);
+
     let reading = word
         .furigana
         .as_ref()
@@ -52,6 +59,9 @@ pub(crate) fn conv_word(word: jotoba::words::Word, lang: Language) -> words::Wor
         intransive_version: word.intransive_version.map(|i| i.get()),
         sentences_available: word.sentences_available,
         audio,
+        linked_names,
+        see_also,
+        antonyms,
     }
 }
 
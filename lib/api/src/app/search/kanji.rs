@@ -4,6 +4,7 @@ use super::convert_payload;
 use crate::app::Result;
 use actix_web::web::{self, Json};
 use error::api_error::RestError;
+use search::kanji::result::{compound_page, has_more_compounds};
 
 use types::jotoba::language::param::AsLangParam;
 use types::{
@@ -25,7 +26,7 @@ pub type SearchResp = Response<kanji::KanjiResponse>;
 
 /// Do an app kanji search via API
 pub async fn search(payload: Json<SearchPayload>) -> Result<Json<SearchResp>> {
-    let query = convert_payload(&payload)
+    let query = convert_payload(&payload)?
         .parse()
         .ok_or(RestError::BadRequest)?;
 
@@ -47,25 +48,39 @@ pub async fn search(payload: Json<SearchPayload>) -> Result<Json<SearchResp>> {
     Ok(Json(super::new_response(page, SearchTarget::Kanji, &query)))
 }
 
-/// Kanji compound request
+/// Kanji compound request. Returns all compounds if no page is requested, so clients which don't
+/// know about pages keep getting the full lists
 pub async fn reading_compounds(payload: Json<SearchPayload>) -> Result<Json<CompoundResponse>> {
     let lang = payload.lang_param();
+    let page = payload.page.map(|i| i.max(1) as usize);
 
     let compounds: Vec<_> = payload
         .query_str
         .chars()
         .filter_map(|i| resources::get().kanji().by_literal(i))
         .map(|i| {
-            let on_words = convert_dicts(&i.on_dicts, lang);
-            let kun_words = convert_dicts(&i.on_dicts, lang);
-            CompoundSet::new(on_words, kun_words)
+            let (on_dicts, has_more_on) = paginate(&i.on_dicts, page);
+            let (kun_dicts, has_more_kun) = paginate(&i.kun_dicts, page);
+            let on_words = convert_dicts(on_dicts, lang);
+            let kun_words = convert_dicts(kun_dicts, lang);
+            CompoundSet::new(on_words, kun_words, has_more_on, has_more_kun)
         })
         .collect();
     Ok(Json(CompoundResponse::new(compounds)))
 }
 
+/// Returns the compounds on `page` and whether there are more after it. Returns all compounds if
+/// `page` is `None`
 #[inline]
-fn convert_dicts(dicts: &Vec<u32>, lang: impl AsLangParam) -> Vec<CompoundWord> {
+fn paginate(dicts: &[u32], page: Option<usize>) -> (&[u32], bool) {
+    match page {
+        Some(page) => (compound_page(dicts, page), has_more_compounds(dicts, page)),
+        None => (dicts, false),
+    }
+}
+
+#[inline]
+fn convert_dicts(dicts: &[u32], lang: impl AsLangParam) -> Vec<CompoundWord> {
     load_dicts(dicts, lang)
         .into_iter()
         .filter_map(|j| Some(CompoundWord::from_word(&j)))
@@ -73,7 +88,7 @@ fn convert_dicts(dicts: &Vec<u32>, lang: impl AsLangParam) -> Vec<CompoundWord>
 }
 
 #[inline]
-fn load_dicts(dicts: &Vec<u32>, lang: impl AsLangParam) -> Vec<Word> {
+fn load_dicts(dicts: &[u32], lang: impl AsLangParam) -> Vec<Word> {
     let word_storage = resources::get().words();
     let mut words: Vec<_> = dicts
         .iter()
@@ -3,11 +3,15 @@ pub mod names;
 pub mod sentences;
 pub mod words;
 
+use actix_web::{CustomizeResponder, HttpRequest, Responder};
+use error::api_error::RestError;
 use search::{
+    executor::budget::Deadline,
     query::UserSettings,
-    query::{parser::QueryParser, Query},
+    query::{parser::QueryParser, user_settings::MAX_PAGE_SIZE, Query},
 };
 use serde::Serialize;
+use std::time::Duration;
 use types::{
     api::app::search::{query::SearchPayload, responses::Response},
     jotoba::{
@@ -17,7 +21,48 @@ use types::{
 };
 
 const FIRST_PAGE: u32 = 1;
-const LAST_PAGE: u32 = 100;
+/// Last page which can be requested
+pub(crate) const LAST_PAGE: u32 = 100;
+
+/// Request header containing the time in milliseconds a search is allowed to take
+pub const LATENCY_BUDGET_HEADER: &str = "X-Latency-Budget";
+
+/// Response header listing all search stages which have been skipped due to the latency budget
+pub const SKIPPED_STAGES_HEADER: &str = "X-Skipped-Stages";
+
+/// Stage of the response which isn't part of the search itself but gets skipped if the latency
+/// budget is used up
+pub(crate) const KANJI_STAGE: &str = "Kanji";
+
+/// Returns the latency budget requested by the client. Has to be called when the request comes
+/// in, as the budget starts running at that moment
+pub(crate) fn latency_budget(request: &HttpRequest) -> Option<Deadline> {
+    let budget = request.headers().get(LATENCY_BUDGET_HEADER)?;
+    let ms: u64 = budget.to_str().ok()?.trim().parse().ok()?;
+    Some(Deadline::new(Duration::from_millis(ms)))
+}
+
+/// Returns `true` if `budget` is used up. Optional stages get skipped then and added to `skipped`
+pub(crate) fn over_budget(
+    budget: Option<Deadline>,
+    stage: &str,
+    skipped: &mut Vec<String>,
+) -> bool {
+    if !budget.map_or(false, |i| i.is_over()) {
+        return false;
+    }
+    skipped.push(stage.to_string());
+    true
+}
+
+/// Adds the header listing `skipped` stages to `res` if stages have been skipped
+pub(crate) fn with_skipped<R: Responder>(res: R, skipped: &[String]) -> CustomizeResponder<R> {
+    let res = res.customize();
+    if skipped.is_empty() {
+        return res;
+    }
+    res.insert_header((SKIPPED_STAGES_HEADER, skipped.join(", ")))
+}
 
 pub(crate) fn new_response<T: Serialize>(
     page: Page<T>,
@@ -30,6 +75,7 @@ pub(crate) fn new_response<T: Serialize>(
         }
         search::build_help(q_type, &query)
     })
+    .with_unknown_tags(query.unknown_tags.clone())
 }
 
 pub(crate) fn new_page<V: Serialize + Clone>(
@@ -53,8 +99,12 @@ pub(crate) fn new_page<V: Serialize + Clone>(
     pagination
 }
 
-pub(crate) fn convert_payload(pl: &SearchPayload) -> QueryParser {
+/// Converts a search payload to a query parser. Fails if the payload has invalid settings
+pub(crate) fn convert_payload(pl: &SearchPayload) -> Result<QueryParser, RestError> {
     let user_settings = convert_user_settings(&pl.settings);
+    user_settings
+        .validate()
+        .map_err(|_| RestError::BadRequest)?;
 
     let mut q_parser = QueryParser::new(
         pl.query_str.clone(),
@@ -62,13 +112,18 @@ pub(crate) fn convert_payload(pl: &SearchPayload) -> QueryParser {
         user_settings,
     )
     .with_page(pl.page.unwrap_or_default() as usize)
-    .with_word_index(pl.word_index.unwrap_or_default());
+    .with_word_index(pl.word_index.unwrap_or_default())
+    .with_filter_presets(pl.filter_presets.clone());
 
     if let Some(lang) = pl.lang_overwrite {
         q_parser = q_parser.with_lang_overwrite(lang);
     }
 
-    q_parser
+    for (group, limit) in pl.name_group_limits.iter() {
+        q_parser = q_parser.with_name_group_limit(*group, *limit);
+    }
+
+    Ok(q_parser)
 }
 
 pub(crate) fn convert_user_settings(
@@ -78,9 +133,14 @@ pub(crate) fn convert_user_settings(
         user_lang: settings.user_lang,
         show_english: settings.show_english,
         english_on_top: true,
-        page_size: settings.page_size,
+        // Clients used to be allowed to request larger pages, so these get clamped instead of
+        // rejected
+        page_size: settings.page_size.clamp(1, MAX_PAGE_SIZE),
         show_example_sentences: settings.show_example_sentences,
         sentence_furigana: settings.sentence_furigana,
+        sentence_script: settings.sentence_script,
+        sentence_max_katakana: settings.sentence_max_katakana,
+        sentence_max_kanji_grade: settings.sentence_max_kanji_grade,
         ..Default::default()
     }
 }
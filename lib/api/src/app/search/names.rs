@@ -2,7 +2,10 @@ use super::new_page;
 
 use super::convert_payload;
 use crate::app::Result;
-use actix_web::web::{self, Json};
+use actix_web::{
+    web::{self, Json},
+    CustomizeResponder, HttpRequest,
+};
 use error::api_error::RestError;
 use search::SearchExecutor;
 use types::{
@@ -17,20 +20,65 @@ use types::{
 pub type Resp = Response<names::Response>;
 
 /// Do an app name search via API
-pub async fn search(payload: Json<SearchPayload>) -> Result<Json<Resp>> {
-    let query = convert_payload(&payload)
+pub async fn search(
+    payload: Json<SearchPayload>,
+    request: HttpRequest,
+) -> Result<CustomizeResponder<Json<Resp>>> {
+    let budget = super::latency_budget(&request);
+    let query = convert_payload(&payload)?
         .parse()
         .ok_or(RestError::BadRequest)?;
 
     let query_c = query.clone();
     let result = web::block(move || {
         let search = search::name::Search::new(&query_c);
-        SearchExecutor::new(search).run()
+        SearchExecutor::new(search).with_budget(budget).run()
     })
     .await?;
-    let res = names::Response::new(result.items.into_iter().cloned().collect());
     let len = result.total as u32;
+    let name_storage = resources::get().names();
+    let linked_words = result
+        .items
+        .iter()
+        .map(|name| (name.sequence, name_storage.linked_words(name.sequence)))
+        .filter(|(_, words)| !words.is_empty())
+        .map(|(seq, words)| (seq, words.to_vec()))
+        .collect();
+    let aliases = result
+        .items
+        .iter()
+        .map(|name| (name.sequence, name_storage.aliases(name.sequence)))
+        .filter(|(_, aliases)| !aliases.is_empty())
+        .map(|(seq, aliases)| (seq, aliases.to_vec()))
+        .collect();
+    let mut skipped = result.skipped;
+    let kanji = if super::over_budget(budget, super::KANJI_STAGE, &mut skipped) {
+        Default::default()
+    } else {
+        result
+            .items
+            .iter()
+            .map(|name| (name.sequence, search::name::kanji::breakdown(name)))
+            .filter(|(_, kanji)| !kanji.is_empty())
+            .map(|(seq, kanji)| (seq, kanji.into_iter().map(|i| i.into()).collect()))
+            .collect()
+    };
+    let groups = search::name::group::group(&result.items)
+        .into_iter()
+        .map(|(group, names)| {
+            let seqs = names.iter().map(|i| i.sequence).collect();
+            names::Group::new(group, seqs)
+        })
+        .collect();
+    let res = names::Response::new(
+        result.items.into_iter().cloned().collect(),
+        result.other_data.transliterations,
+        linked_words,
+        aliases,
+        kanji,
+        groups,
+    );
     let page = new_page(&payload, res, len, payload.settings.page_size);
     let res = super::new_response(page, SearchTarget::Names, &query);
-    Ok(Json(res))
+    Ok(super::with_skipped(Json(res), &skipped))
 }
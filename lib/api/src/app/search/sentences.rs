@@ -2,36 +2,45 @@ use super::new_page;
 
 use super::convert_payload;
 use crate::app::Result;
-use actix_web::web::{self, Json};
+use actix_web::{
+    web::{self, Json},
+    CustomizeResponder, HttpRequest,
+};
 use error::api_error::RestError;
 use types::{
     api::app::search::{
         query::SearchPayload,
         responses::{sentences, Response},
     },
-    jotoba::search::SearchTarget,
+    jotoba::{search::SearchTarget, sentences::script::SentenceScript},
 };
 
 /// API response type
 pub type Resp = Response<sentences::Response>;
 
 /// Do an app sentence search via API
-pub async fn search(payload: Json<SearchPayload>) -> Result<Json<Resp>> {
-    let query = convert_payload(&payload)
+pub async fn search(
+    payload: Json<SearchPayload>,
+    request: HttpRequest,
+) -> Result<CustomizeResponder<Json<Resp>>> {
+    let budget = super::latency_budget(&request);
+    let query = convert_payload(&payload)?
         .parse()
         .ok_or(RestError::BadRequest)?;
 
     let query_c = query.clone();
     let result = web::block(move || {
         let search = search::sentence::Search::new(&query_c);
-        search::SearchExecutor::new(search).run()
+        search::SearchExecutor::new(search)
+            .with_budget(budget)
+            .run()
     })
     .await?;
 
     let items = result
         .items
         .into_iter()
-        .map(|i| convert_sentence(i))
+        .map(|i| convert_sentence(i, payload.settings.sentence_script))
         .collect::<Vec<_>>();
 
     let res = sentences::Response::new(items);
@@ -39,16 +48,19 @@ pub async fn search(payload: Json<SearchPayload>) -> Result<Json<Resp>> {
 
     let page = new_page(&payload, res, len, payload.settings.page_size);
     let res = super::new_response(page, SearchTarget::Sentences, &query);
-    Ok(Json(res))
+    Ok(super::with_skipped(Json(res), &result.skipped))
 }
 
 #[inline]
 pub(crate) fn convert_sentence(
     sentence: search::sentence::result::Sentence,
+    script: SentenceScript,
 ) -> sentences::Sentence {
+    let converted = sentence.converted(script);
     sentences::Sentence::new(
         sentence.id,
         sentence.furigana.to_string(),
         sentence.translation.to_string(),
     )
+    .with_converted(converted)
 }
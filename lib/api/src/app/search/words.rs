@@ -2,10 +2,13 @@ use super::new_page;
 
 use super::convert_payload;
 use crate::app::Result;
-use actix_web::web::{self, Json};
+use actix_web::{
+    web::{self, Json},
+    CustomizeResponder, HttpRequest,
+};
 use error::api_error::RestError;
 use search::word::Search;
-use search::SearchExecutor;
+use search::{executor::budget::Deadline, SearchExecutor};
 use types::{
     api::app::search::{
         query::SearchPayload,
@@ -21,8 +24,22 @@ use types::{
 pub type Resp = Response<words::Response>;
 
 /// Do an app word search via API
-pub async fn search(payload: Json<SearchPayload>) -> Result<Json<Resp>> {
-    let query = convert_payload(&payload)
+pub async fn search(
+    payload: Json<SearchPayload>,
+    request: HttpRequest,
+) -> Result<CustomizeResponder<Json<Resp>>> {
+    let budget = super::latency_budget(&request);
+    let (res, skipped) = search_words(&payload, budget).await?;
+    Ok(super::with_skipped(Json(res), &skipped))
+}
+
+/// Runs a word search for `payload` and returns the response along with all search stages which
+/// have been skipped due to `budget`
+pub(crate) async fn search_words(
+    payload: &SearchPayload,
+    budget: Option<Deadline>,
+) -> Result<(Resp, Vec<String>)> {
+    let query = convert_payload(payload)?
         .parse()
         .ok_or(RestError::BadRequest)?;
     let user_lang = query.settings.user_lang;
@@ -30,14 +47,20 @@ pub async fn search(payload: Json<SearchPayload>) -> Result<Json<Resp>> {
     let query_c = query.clone();
     let result = web::block(move || {
         let search = Search::new(&query_c);
-        SearchExecutor::new(search).run()
+        SearchExecutor::new(search).with_budget(budget).run()
     })
     .await?;
 
-    let kanji = search::word::kanji::load_word_kanji_info(&result.items)
-        .into_iter()
-        .map(|i| i.into())
-        .collect::<Vec<_>>();
+    let mut skipped = result.skipped.clone();
+
+    let kanji = if super::over_budget(budget, super::KANJI_STAGE, &mut skipped) {
+        vec![]
+    } else {
+        search::word::kanji::load_word_kanji_info(&result.items)
+            .into_iter()
+            .map(|i| i.into())
+            .collect::<Vec<_>>()
+    };
 
     let words = result
         .items
@@ -61,9 +84,9 @@ pub async fn search(payload: Json<SearchPayload>) -> Result<Json<Resp>> {
     let res = words::Response::new(words, kanji, infl_info, sentence, original_query, number);
     let len = result.total as u32;
 
-    let page = new_page(&payload, res, len, payload.settings.page_size);
+    let page = new_page(payload, res, len, payload.settings.page_size);
     let res = super::new_response(page, SearchTarget::Words, &query);
-    Ok(Json(res))
+    Ok((res, skipped))
 }
 
 fn conv_sentence(sentence: sentence_reader::Sentence, index: usize) -> Sentence {
@@ -0,0 +1,51 @@
+use actix_web::{
+    cookie::{time::Duration, Cookie},
+    web::Json,
+    HttpRequest, HttpResponse,
+};
+use error::api_error::RestError;
+use search::query::UserSettings;
+use types::api::app::settings::{Request, Response};
+
+/// How long settings cookies are kept by browsers. Matches the settings overlay
+const COOKIE_MAX_AGE: Duration = Duration::days(365);
+
+/// Returns the settings stored in the cookies of the request
+pub async fn get(request: HttpRequest) -> Json<Response> {
+    Json(to_response(&from_cookies(&request)))
+}
+
+/// Changes the settings stored in the cookies of the request. Fails without changing anything if
+/// any key is unknown or any value is invalid
+pub async fn set(request: HttpRequest, payload: Json<Request>) -> Result<HttpResponse, RestError> {
+    let mut settings = from_cookies(&request);
+    for (key, value) in payload.settings.iter() {
+        settings
+            .set(key, value)
+            .map_err(|_| RestError::BadRequest)?;
+    }
+
+    let mut response = HttpResponse::Ok();
+    for (key, value) in settings.values() {
+        let cookie = Cookie::build(key, value)
+            .path("/")
+            .max_age(COOKIE_MAX_AGE)
+            .finish();
+        response.cookie(cookie);
+    }
+
+    Ok(response.json(to_response(&settings)))
+}
+
+fn from_cookies(request: &HttpRequest) -> UserSettings {
+    UserSettings::from_values(|key| request.cookie(key).map(|i| i.value().to_string()))
+}
+
+fn to_response(settings: &UserSettings) -> Response {
+    let settings = settings
+        .values()
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect();
+    Response { settings }
+}
@@ -0,0 +1,40 @@
+mod tts;
+
+use actix_files::NamedFile;
+use actix_web::web;
+use config::Config;
+use error::api_error::RestError;
+use std::path::Path;
+
+/// Serves the audio of the word with the given sequence id. Pre-recorded audio is preferred and
+/// audio for all other words gets synthesized using the configured TTS backend, if any
+pub async fn word_audio(
+    seq: web::Path<u32>,
+    config: web::Data<Config>,
+) -> Result<NamedFile, RestError> {
+    let word = resources::get()
+        .words()
+        .by_sequence(*seq)
+        .ok_or(RestError::NotFound)?;
+
+    let audio_dir = config.server.get_audio_files();
+    if let Some(file) = word.audio_file_in(audio_dir) {
+        let path = Path::new(audio_dir).join(file);
+        return Ok(NamedFile::open_async(path).await?);
+    }
+
+    let command = config
+        .server
+        .tts_command
+        .clone()
+        .ok_or(RestError::NotFound)?;
+
+    let cached = Path::new(config.server.get_tts_cache()).join(format!("{}.mp3", word.sequence));
+    if !cached.exists() {
+        let kana = word.get_kana().to_string();
+        let target = cached.clone();
+        web::block(move || tts::synthesize_cached(&command, &kana, &target)).await??;
+    }
+
+    Ok(NamedFile::open_async(cached).await?)
+}
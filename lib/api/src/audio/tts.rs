@@ -0,0 +1,100 @@
+use error::api_error::RestError;
+use once_cell::sync::Lazy;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+/// Placeholder within the configured command which gets replaced with the text to synthesize
+const TEXT_PLACEHOLDER: &str = "{text}";
+
+/// Placeholder within the configured command which gets replaced with the output file
+const OUT_PLACEHOLDER: &str = "{out}";
+
+/// Max time the TTS backend may take for a single word
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Counter to give concurrently generated files unique names
+static TMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Locks of all files which are currently being generated
+static RUNNING: Lazy<Mutex<HashMap<PathBuf, Arc<Mutex<()>>>>> = Lazy::new(Default::default);
+
+/// Generates the audio at `target` like [`synthesize`] unless it exists already. Concurrent
+/// requests for the same file wait for the first one instead of running the backend again
+pub(crate) fn synthesize_cached(command: &str, text: &str, target: &Path) -> Result<(), RestError> {
+    let lock = RUNNING
+        .lock()
+        .unwrap()
+        .entry(target.to_path_buf())
+        .or_default()
+        .clone();
+
+    let res = {
+        let _guard = lock.lock().unwrap_or_else(|i| i.into_inner());
+        if target.exists() {
+            Ok(())
+        } else {
+            synthesize(command, text, target)
+        }
+    };
+
+    // Only the map and this request hold the lock, so nobody else is waiting for it
+    let mut running = RUNNING.lock().unwrap();
+    if Arc::strong_count(&lock) == 2 {
+        running.remove(target);
+    }
+
+    res
+}
+
+/// Runs the configured TTS backend `command` for `text` and stores the generated audio at
+/// `target`. The audio is generated into a temporary file first, so concurrent requests never
+/// serve a half written file
+pub(crate) fn synthesize(command: &str, text: &str, target: &Path) -> Result<(), RestError> {
+    if let Some(dir) = target.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let tmp_id = TMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let tmp = target.with_extension(format!("{tmp_id}.tmp.mp3"));
+    let tmp_str = tmp.to_str().ok_or(RestError::Internal)?;
+
+    let mut args = command.split_whitespace().map(|arg| {
+        arg.replace(TEXT_PLACEHOLDER, text)
+            .replace(OUT_PLACEHOLDER, tmp_str)
+    });
+    let program = args.next().ok_or(RestError::Internal)?;
+
+    let mut cmd = Command::new(&program);
+    cmd.args(args);
+    let output = utils::process::output_with_timeout(cmd, TIMEOUT).map_err(|err| {
+        log::error!("Failed to run TTS backend {program:?}: {err}");
+        RestError::Internal
+    })?;
+
+    let status = match output {
+        Some(output) => output.status,
+        None => {
+            log::warn!("TTS backend didn't finish within {TIMEOUT:?}");
+            fs::remove_file(&tmp).ok();
+            return Err(RestError::Timeout);
+        }
+    };
+
+    if !status.success() || !tmp.exists() {
+        log::warn!("TTS backend exited with {status}");
+        fs::remove_file(&tmp).ok();
+        return Err(RestError::NotFound);
+    }
+
+    fs::rename(&tmp, target)?;
+    Ok(())
+}
@@ -0,0 +1,12 @@
+use actix_web::web::Json;
+use types::api::data_version::{Overlay, Response};
+
+/// Returns the version of the loaded data along with all overlays applied to it
+pub async fn data_version() -> Json<Response> {
+    let overlays = resources::overlay::applied()
+        .iter()
+        .map(|i| Overlay::new(i.file.clone(), i.applied, i.skipped))
+        .collect();
+
+    Json(Response::new(resources::GIT_HASH.to_string(), overlays))
+}
@@ -0,0 +1,161 @@
+use actix_web::{
+    http::header::CONTENT_DISPOSITION,
+    web::{self, Bytes},
+    HttpResponse,
+};
+use error::api_error::RestError;
+use futures::stream;
+use itertools::Itertools;
+use search::{
+    query::{parser::QueryParser, UserSettings},
+    SearchExecutor,
+};
+use serde_json::{json, Map, Value};
+use types::{
+    api::export::{Column, ExportFormat, Request},
+    jotoba::{language::Language, search::SearchTarget, words::Word},
+};
+
+/// Max amount of words which can be exported at once
+const MAX_LIMIT: u32 = 1000;
+
+/// Runs a word search and returns its results as CSV, TSV or JSON Lines file
+pub async fn export(req: web::Query<Request>) -> Result<HttpResponse, RestError> {
+    let columns = req.columns().ok_or(RestError::BadRequest)?;
+    if req.limit == 0 || req.limit > MAX_LIMIT {
+        return Err(RestError::BadRequest);
+    }
+
+    let lang = req.language.unwrap_or_default();
+    let settings = UserSettings {
+        user_lang: lang,
+        page_size: req.limit,
+        ..UserSettings::default()
+    };
+    let query = QueryParser::new(req.query.clone(), SearchTarget::Words, settings)
+        .parse()
+        .ok_or(RestError::BadRequest)?;
+
+    let words = web::block(move || {
+        let search = search::word::Search::new(&query);
+        SearchExecutor::new(search).run()
+    })
+    .await?
+    .items;
+
+    let format = req.format;
+    let mut rows = Vec::with_capacity(words.len() + 1);
+    if format != ExportFormat::Jsonl {
+        rows.push(format_row(
+            columns.iter().map(|i| i.name().to_string()),
+            format,
+        ));
+    }
+    rows.extend(words.iter().map(|word| match format {
+        ExportFormat::Jsonl => json_row(word, &columns, lang),
+        _ => format_row(columns.iter().map(|c| text_value(word, *c, lang)), format),
+    }));
+
+    let body = stream::iter(rows.into_iter().map(|i| Ok::<_, RestError>(Bytes::from(i))));
+    let disposition = format!("attachment; filename=\"jotoba.{}\"", format.extension());
+
+    Ok(HttpResponse::Ok()
+        .content_type(format.content_type())
+        .insert_header((CONTENT_DISPOSITION, disposition))
+        .streaming(body))
+}
+
+/// Formats a single CSV or TSV row
+fn format_row<I: Iterator<Item = String>>(values: I, format: ExportFormat) -> String {
+    let (sep, escape): (_, fn(String) -> String) = match format {
+        ExportFormat::Tsv => ("\t", escape_tsv),
+        _ => (",", escape_csv),
+    };
+
+    let mut row = values.map(escape).join(sep);
+    row.push('\n');
+    row
+}
+
+fn json_row(word: &Word, columns: &[Column], lang: Language) -> String {
+    let object: Map<String, Value> = columns
+        .iter()
+        .map(|c| (c.name().to_string(), json_value(word, *c, lang)))
+        .collect();
+
+    let mut row = Value::Object(object).to_string();
+    row.push('\n');
+    row
+}
+
+fn text_value(word: &Word, column: Column, lang: Language) -> String {
+    match column {
+        Column::Sequence => word.sequence.to_string(),
+        Column::Kanji => word
+            .reading
+            .kanji
+            .as_ref()
+            .map(|i| i.reading.clone())
+            .unwrap_or_default(),
+        Column::Kana => word.get_kana().to_string(),
+        Column::Glosses => senses(word, lang).join("; "),
+        Column::Jlpt => word
+            .get_jlpt_lvl()
+            .map(|i| format!("N{i}"))
+            .unwrap_or_default(),
+        Column::Pitch => word.accents.iter().join(";"),
+    }
+}
+
+fn json_value(word: &Word, column: Column, lang: Language) -> Value {
+    match column {
+        Column::Sequence => json!(word.sequence),
+        Column::Jlpt => json!(word.get_jlpt_lvl()),
+        Column::Glosses => json!(senses(word, lang)),
+        Column::Pitch => json!(word.accents.iter().collect::<Vec<_>>()),
+        Column::Kanji | Column::Kana => json!(text_value(word, column, lang)),
+    }
+}
+
+/// Returns the glosses of all senses in `lang`, falling back to english
+fn senses(word: &Word, lang: Language) -> Vec<String> {
+    let mut senses = word.senses_by_lang(lang);
+    if senses.is_empty() {
+        senses = word.senses_by_lang(Language::English);
+    }
+
+    senses
+        .into_iter()
+        .map(|i| i.glosses.iter().map(|g| &g.gloss).join(", "))
+        .collect()
+}
+
+fn escape_csv(value: String) -> String {
+    if !value.contains(|c: char| matches!(c, ',' | '"' | '\n' | '\r')) {
+        return value;
+    }
+
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
+
+fn escape_tsv(value: String) -> String {
+    value.replace(|c: char| matches!(c, '\t' | '\n' | '\r'), " ")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_format_row() {
+        let values = ["犬", "to eat, to consume", "say \"hi\""].map(|i| i.to_string());
+        assert_eq!(
+            format_row(values.clone().into_iter(), ExportFormat::Csv),
+            "犬,\"to eat, to consume\",\"say \"\"hi\"\"\"\n"
+        );
+        assert_eq!(
+            format_row(values.into_iter(), ExportFormat::Tsv),
+            "犬\tto eat, to consume\tsay \"hi\"\n"
+        );
+    }
+}
@@ -0,0 +1,81 @@
+//! Responses which can be returned as JSON, MessagePack or CBOR, depending on the `Accept` header
+//! of the request. JSON is used if the client doesn't explicitly accept one of the binary formats.
+
+use actix_web::{
+    body::BoxBody,
+    http::header::{ACCEPT, VARY},
+    HttpRequest, HttpResponse, Responder, ResponseError,
+};
+use error::api_error::RestError;
+use serde::Serialize;
+
+/// Supported response formats
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Format {
+    Json,
+    MessagePack,
+    Cbor,
+}
+
+impl Format {
+    /// Returns the format accepted by the client sending `req`
+    pub fn from_request(req: &HttpRequest) -> Self {
+        req.headers()
+            .get_all(ACCEPT)
+            .filter_map(|i| i.to_str().ok())
+            .flat_map(|i| i.split(','))
+            .find_map(|i| Self::from_mime(i.split(';').next().unwrap_or_default().trim()))
+            .unwrap_or(Format::Json)
+    }
+
+    fn from_mime(mime: &str) -> Option<Self> {
+        match mime {
+            "application/json" => Some(Format::Json),
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack" => {
+                Some(Format::MessagePack)
+            }
+            "application/cbor" => Some(Format::Cbor),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Format::Json => "application/json",
+            Format::MessagePack => "application/msgpack",
+            Format::Cbor => "application/cbor",
+        }
+    }
+
+    /// Encodes `value` in the format
+    pub fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, RestError> {
+        match self {
+            Format::Json => serde_json::to_vec(value).map_err(|_| RestError::Internal),
+            Format::MessagePack => rmp_serde::to_vec_named(value).map_err(|_| RestError::Internal),
+            Format::Cbor => {
+                let mut buf = vec![];
+                ciborium::ser::into_writer(value, &mut buf).map_err(|_| RestError::Internal)?;
+                Ok(buf)
+            }
+        }
+    }
+}
+
+/// Response encoded in the format accepted by the client
+pub struct Formatted<T>(pub T);
+
+impl<T: Serialize> Responder for Formatted<T> {
+    type Body = BoxBody;
+
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse<Self::Body> {
+        let format = Format::from_request(req);
+        match format.encode(&self.0) {
+            Ok(body) => HttpResponse::Ok()
+                .content_type(format.content_type())
+                .insert_header((VARY, "Accept"))
+                .body(body),
+            Err(err) => err.error_response(),
+        }
+    }
+}
@@ -0,0 +1,170 @@
+mod objects;
+
+use crate::app::search::LAST_PAGE;
+use actix_web::web::{self, Json};
+use async_graphql::{EmptyMutation, EmptySubscription, Object, Result, Schema};
+use objects::{Kanji, Name, Sentence, Word};
+use once_cell::sync::Lazy;
+use search::{
+    query::{parser::QueryParser, Query, UserSettings},
+    SearchExecutor,
+};
+use std::str::FromStr;
+use types::jotoba::{language::Language, search::SearchTarget};
+
+/// Max depth of nested fields within a single request
+const MAX_DEPTH: usize = 8;
+
+/// Max complexity of a single request
+const MAX_COMPLEXITY: usize = 5000;
+
+/// Max amount of results per search
+const MAX_LIMIT: u32 = 100;
+
+/// Amount of results per search if no limit is requested
+const DEFAULT_LIMIT: u32 = 10;
+
+/// Complexity of running a single search on top of the complexity of its results. Counted for
+/// every search field, so requesting a search multiple times using aliases can't bypass
+/// `MAX_COMPLEXITY`
+const SEARCH_COMPLEXITY: usize = 500;
+
+type JotobaSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+static SCHEMA: Lazy<JotobaSchema> = Lazy::new(|| {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .limit_depth(MAX_DEPTH)
+        .limit_complexity(MAX_COMPLEXITY)
+        .finish()
+});
+
+/// Executes a GraphQL request
+pub async fn graphql(request: Json<async_graphql::Request>) -> Json<async_graphql::Response> {
+    Json(SCHEMA.execute(request.into_inner()).await)
+}
+
+/// Returns the GraphQL schema in SDL format
+pub async fn sdl() -> String {
+    SCHEMA.sdl()
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Returns the word with the given JMdict sequence ID
+    async fn word(&self, sequence: u32, language: Option<String>) -> Option<Word> {
+        let word = resources::get().words().by_sequence(sequence)?;
+        Some(Word::new(word.clone(), parse_lang(language)))
+    }
+
+    /// Searches for words
+    #[graphql(complexity = "search_complexity(limit, child_complexity)")]
+    async fn words(
+        &self,
+        query: String,
+        language: Option<String>,
+        limit: Option<u32>,
+        page: Option<usize>,
+    ) -> Result<Vec<Word>> {
+        let lang = parse_lang(language);
+        let query = parse_query(query, SearchTarget::Words, lang, limit, page)?;
+        let result = web::block(move || {
+            let search = search::word::Search::new(&query);
+            SearchExecutor::new(search).run()
+        })
+        .await?;
+
+        Ok(result
+            .items
+            .into_iter()
+            .map(|i| Word::new(i, lang))
+            .collect())
+    }
+
+    /// Returns the kanji with the given literal
+    async fn kanji(&self, literal: String, language: Option<String>) -> Option<Kanji> {
+        let literal = literal.chars().next()?;
+        let kanji = resources::get().kanji().by_literal(literal)?;
+        Some(Kanji::new(kanji, parse_lang(language)))
+    }
+
+    /// Searches for sentences
+    #[graphql(complexity = "search_complexity(limit, child_complexity)")]
+    async fn sentences(
+        &self,
+        query: String,
+        language: Option<String>,
+        limit: Option<u32>,
+        page: Option<usize>,
+    ) -> Result<Vec<Sentence>> {
+        let lang = parse_lang(language);
+        let query = parse_query(query, SearchTarget::Sentences, lang, limit, page)?;
+        let result = web::block(move || {
+            let search = search::sentence::Search::new(&query);
+            SearchExecutor::new(search).run()
+        })
+        .await?;
+
+        let sentences = resources::get().sentences();
+        Ok(result
+            .items
+            .into_iter()
+            .filter_map(|i| sentences.by_id(i.id))
+            .map(|i| Sentence::new(i, lang))
+            .collect())
+    }
+
+    /// Searches for names
+    #[graphql(complexity = "search_complexity(limit, child_complexity)")]
+    async fn names(
+        &self,
+        query: String,
+        limit: Option<u32>,
+        page: Option<usize>,
+    ) -> Result<Vec<Name>> {
+        let query = parse_query(query, SearchTarget::Names, Language::English, limit, page)?;
+        let result = web::block(move || {
+            let search = search::name::Search::new(&query);
+            SearchExecutor::new(search).run()
+        })
+        .await?;
+
+        Ok(result.items.into_iter().map(Name).collect())
+    }
+}
+
+fn parse_query(
+    query: String,
+    target: SearchTarget,
+    user_lang: Language,
+    limit: Option<u32>,
+    page: Option<usize>,
+) -> Result<Query> {
+    let settings = UserSettings {
+        user_lang,
+        page_size: limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT),
+        ..UserSettings::default()
+    };
+
+    QueryParser::new(query, target, settings)
+        .with_page(page.unwrap_or(1).clamp(1, LAST_PAGE as usize))
+        .parse()
+        .ok_or_else(|| "Invalid query".into())
+}
+
+/// Returns the complexity of a search field returning up to `limit` results, each of the
+/// complexity `child_complexity`
+#[inline]
+fn search_complexity(limit: Option<u32>, child_complexity: usize) -> usize {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT) as usize;
+    SEARCH_COMPLEXITY + limit * child_complexity
+}
+
+/// Parses the language argument of a field. Falls back to english
+#[inline]
+fn parse_lang(language: Option<String>) -> Language {
+    language
+        .and_then(|i| Language::from_str(&i).ok())
+        .unwrap_or_default()
+}
@@ -0,0 +1,298 @@
+use async_graphql::{Object, SimpleObject};
+use itertools::Itertools;
+use types::jotoba::{
+    language::Language,
+    names::Name as JName,
+    sentences::Sentence as JSentence,
+    words::{sense::Sense as JSense, Word as JWord},
+};
+
+/// Max amount of example sentences returned for a single word or kanji
+const MAX_SENTENCES: usize = 10;
+
+pub struct Word {
+    word: JWord,
+    lang: Language,
+}
+
+impl Word {
+    /// Creates a new word showing senses in `lang` and english
+    pub fn new(mut word: JWord, lang: Language) -> Self {
+        word.adjust_language((lang, true));
+        Self { word, lang }
+    }
+}
+
+#[Object]
+impl Word {
+    async fn sequence(&self) -> u32 {
+        self.word.sequence
+    }
+
+    async fn kana(&self) -> &str {
+        self.word.get_kana()
+    }
+
+    async fn kanji(&self) -> Option<&str> {
+        self.word.reading.kanji.as_ref().map(|i| i.reading.as_str())
+    }
+
+    async fn furigana(&self) -> Option<&str> {
+        self.word.furigana.as_deref()
+    }
+
+    /// Alternative readings of the word
+    async fn alt_readings(&self) -> Vec<&str> {
+        self.word
+            .reading
+            .alternative
+            .iter()
+            .map(|i| i.reading.as_str())
+            .collect()
+    }
+
+    async fn common(&self) -> bool {
+        self.word.is_common()
+    }
+
+    async fn jlpt(&self) -> Option<u8> {
+        self.word.get_jlpt_lvl()
+    }
+
+    async fn senses(&self) -> Vec<Sense> {
+        self.word.senses.iter().cloned().map(Sense).collect()
+    }
+
+    /// Pitch accent of the word
+    async fn pitch(&self) -> Vec<PitchPart> {
+        self.word
+            .get_first_pitch()
+            .map(|i| i.parts)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|i| PitchPart {
+                part: i.part,
+                high: i.high,
+            })
+            .collect()
+    }
+
+    /// Path of the audio file of the word, if there is one
+    async fn audio(&self) -> Option<String> {
+        self.word.audio_file().map(|i| format!("/audio/{i}"))
+    }
+
+    /// All kanji used to write the word
+    async fn kanji_info(&self) -> Vec<Kanji> {
+        let kanji = resources::get().kanji();
+        self.word
+            .get_reading_str()
+            .chars()
+            .unique()
+            .filter_map(|i| kanji.by_literal(i))
+            .map(|i| Kanji::new(i, self.lang))
+            .collect()
+    }
+
+    /// Example sentences of the words senses
+    async fn sentences(&self) -> Vec<Sentence> {
+        let sentences = resources::get().sentences();
+        self.word
+            .senses
+            .iter()
+            .filter_map(|i| i.example_sentence)
+            .unique()
+            .filter_map(|i| sentences.by_id(i))
+            .take(MAX_SENTENCES)
+            .map(|i| Sentence::new(i, self.lang))
+            .collect()
+    }
+}
+
+pub struct Sense(JSense);
+
+#[Object]
+impl Sense {
+    async fn glosses(&self) -> Vec<&str> {
+        self.0.glosses.iter().map(|i| i.gloss.as_str()).collect()
+    }
+
+    async fn language(&self) -> &str {
+        self.0.language.to_query_format()
+    }
+
+    /// Simplified parts of speech
+    async fn pos(&self) -> Vec<String> {
+        self.0
+            .part_of_speech
+            .iter()
+            .flat_map(|i| i.to_pos_simple())
+            .unique()
+            .map(|i| i.as_str().to_string())
+            .collect()
+    }
+
+    async fn misc(&self) -> Option<&str> {
+        self.0.misc.as_ref().map(|i| i.as_ref())
+    }
+
+    async fn information(&self) -> Option<&str> {
+        self.0.information.as_deref()
+    }
+
+    async fn xref(&self) -> Option<&str> {
+        self.0.xref.as_deref()
+    }
+
+    async fn antonym(&self) -> Option<&str> {
+        self.0.antonym.as_deref()
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct PitchPart {
+    part: String,
+    high: bool,
+}
+
+pub struct Kanji {
+    kanji: &'static types::jotoba::kanji::Kanji,
+    lang: Language,
+}
+
+impl Kanji {
+    #[inline]
+    pub fn new(kanji: &'static types::jotoba::kanji::Kanji, lang: Language) -> Self {
+        Self { kanji, lang }
+    }
+}
+
+#[Object]
+impl Kanji {
+    async fn literal(&self) -> String {
+        self.kanji.literal.to_string()
+    }
+
+    async fn meanings(&self) -> &[String] {
+        &self.kanji.meanings
+    }
+
+    async fn onyomi(&self) -> &[String] {
+        &self.kanji.onyomi
+    }
+
+    async fn kunyomi(&self) -> &[String] {
+        &self.kanji.kunyomi
+    }
+
+    async fn stroke_count(&self) -> u8 {
+        self.kanji.stroke_count
+    }
+
+    async fn grade(&self) -> Option<u8> {
+        self.kanji.grade
+    }
+
+    async fn jlpt(&self) -> Option<u8> {
+        self.kanji.jlpt
+    }
+
+    async fn frequency(&self) -> Option<u16> {
+        self.kanji.frequency
+    }
+
+    async fn radical(&self) -> String {
+        self.kanji.radical.literal.to_string()
+    }
+
+    /// Parts the kanji consists of
+    async fn parts(&self) -> Vec<String> {
+        self.kanji.parts.iter().map(|i| i.to_string()).collect()
+    }
+
+    /// Parts of the kanji which are kanji themselves
+    async fn part_kanji(&self) -> Vec<Kanji> {
+        let kanji = resources::get().kanji();
+        self.kanji
+            .parts
+            .iter()
+            .filter(|i| **i != self.kanji.literal)
+            .filter_map(|i| kanji.by_literal(*i))
+            .map(|i| Kanji::new(i, self.lang))
+            .collect()
+    }
+}
+
+pub struct Sentence {
+    sentence: &'static JSentence,
+    lang: Language,
+}
+
+impl Sentence {
+    #[inline]
+    pub fn new(sentence: &'static JSentence, lang: Language) -> Self {
+        Self { sentence, lang }
+    }
+}
+
+#[Object]
+impl Sentence {
+    async fn id(&self) -> u32 {
+        self.sentence.id
+    }
+
+    async fn japanese(&self) -> &str {
+        &self.sentence.japanese
+    }
+
+    async fn furigana(&self) -> &str {
+        &self.sentence.furigana
+    }
+
+    /// Translation in the requested language, falling back to english
+    async fn translation(&self) -> Option<&str> {
+        self.sentence.get_translation((self.lang, true))
+    }
+}
+
+pub struct Name(pub &'static JName);
+
+#[Object]
+impl Name {
+    async fn sequence(&self) -> u32 {
+        self.0.sequence
+    }
+
+    async fn kana(&self) -> &str {
+        &self.0.kana
+    }
+
+    async fn kanji(&self) -> Option<&str> {
+        self.0.kanji.as_deref()
+    }
+
+    async fn transcription(&self) -> &str {
+        &self.0.transcription
+    }
+
+    async fn name_types(&self) -> Vec<String> {
+        self.0
+            .name_type
+            .iter()
+            .flatten()
+            .map(|i| format!("{i:?}"))
+            .collect()
+    }
+
+    /// Words the name is linked to
+    async fn words(&self) -> Vec<Word> {
+        let words = resources::get().words();
+        let names = resources::get().names();
+        names
+            .linked_words(self.0.sequence)
+            .iter()
+            .filter_map(|i| words.by_sequence(*i))
+            .map(|i| Word::new(i.clone(), Language::English))
+            .collect()
+    }
+}
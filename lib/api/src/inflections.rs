@@ -0,0 +1,20 @@
+use actix_web::web;
+use error::api_error::RestError;
+use types::api::inflections::Response;
+
+use crate::format::Formatted;
+
+/// Returns the conjugation table of the word with the given sequence id
+pub async fn word_inflections(seq: web::Path<u32>) -> Result<Formatted<Response>, RestError> {
+    let word = resources::get()
+        .words()
+        .by_sequence(*seq)
+        .ok_or(RestError::NotFound)?;
+
+    let reading = word.get_reading().reading.clone();
+    Ok(Formatted(Response::new(
+        word.sequence,
+        reading,
+        word.get_inflections(),
+    )))
+}
@@ -0,0 +1,46 @@
+use actix_web::web::{self, Json};
+use error::api_error::RestError;
+use types::api::internal::dump::{Request, Response};
+use utils::epoch::Cached;
+
+/// Amount of words returned per page
+const PAGE_SIZE: usize = 1000;
+
+/// Sequence ids of all words in ascending order
+static SEQUENCES: Cached<Vec<u32>> = Cached::new(|| {
+    let mut seqs: Vec<_> = resources::get()
+        .words()
+        .iter()
+        .map(|i| i.sequence)
+        .collect();
+    seqs.sort_unstable();
+    seqs
+});
+
+/// Returns a page of all words in the word storage, ordered by their sequence ids
+pub async fn words(query: web::Query<Request>) -> Result<Json<Response>, RestError> {
+    let start = match (query.after, query.page) {
+        (Some(after), _) => SEQUENCES.partition_point(|i| *i <= after),
+        (None, Some(page)) if page > 0 => (page as usize - 1) * PAGE_SIZE,
+        (None, Some(_)) => return Err(RestError::BadRequest),
+        (None, None) => 0,
+    };
+
+    let seqs = SEQUENCES.get(start..).unwrap_or_default();
+    let seqs = &seqs[..seqs.len().min(PAGE_SIZE)];
+
+    let word_retr = resources::get().words();
+    let words = seqs
+        .iter()
+        .filter_map(|i| word_retr.by_sequence(*i))
+        .cloned()
+        .collect();
+
+    let next = if start + seqs.len() < SEQUENCES.len() {
+        seqs.last().copied()
+    } else {
+        None
+    };
+
+    Ok(Json(Response::new(words, SEQUENCES.len(), PAGE_SIZE, next)))
+}
@@ -1 +1,4 @@
+pub mod dump;
 pub mod info;
+pub mod reload;
+pub mod stats;
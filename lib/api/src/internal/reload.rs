@@ -0,0 +1,42 @@
+use actix_web::{web, HttpResponse};
+use config::Config;
+use error::api_error::RestError;
+use indexes::storage::{build, LoadOptions};
+use log::{error, info};
+use std::collections::HashMap;
+
+/// Reloads the suggestion indexes from the configured suggestion sources without restarting the
+/// server. Suggestions keep being served from the old indexes until the new ones are loaded
+pub async fn suggestions(config: web::Data<Config>) -> Result<HttpResponse, RestError> {
+    let path = config.get_suggestion_sources().to_string();
+    let options = LoadOptions::with_language_names(config.server.get_languages());
+
+    if !web::block(move || reload_suggestions(&path, &options)).await? {
+        return Err(RestError::Internal);
+    }
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Reloads the suggestion indexes from `path` using `options`. Returns `false` if loading them
+/// failed, in which case the old indexes stay in place. Requires the resources to be loaded
+pub fn reload_suggestions(path: &str, options: &LoadOptions) -> bool {
+    let gloss_langs = suggestion_gloss_langs();
+    match indexes::storage::suggestions::load(path, options, gloss_langs) {
+        Ok(_) => {
+            crate::app::completions::cache::clear();
+            info!("Reloaded suggestions from {path}");
+            true
+        }
+        Err(err) => {
+            error!("Failed to reload suggestions from {path}: {err}");
+            false
+        }
+    }
+}
+
+/// Builds the gloss languages of all words for the suggestion indexes
+#[inline]
+pub fn suggestion_gloss_langs() -> HashMap<u32, u16> {
+    build::suggestions::gloss_langs(resources::get().words().iter())
+}
@@ -0,0 +1,7 @@
+use actix_web::web::Json;
+use types::api::internal::stats::CacheStats;
+
+/// Returns usage statistics of the suggestion response cache
+pub async fn suggestion_cache() -> Json<CacheStats> {
+    Json(crate::app::completions::cache::stats())
+}
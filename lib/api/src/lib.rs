@@ -1,8 +1,41 @@
+/// Vocabulary lists of whole texts
+pub mod analyze;
+
 /// API endpoints for the webapp
 pub mod app;
 
+/// Version information about the loaded data
+pub mod data_version;
+
+/// Pre-recorded and synthesized audio of words
+pub mod audio;
+
+/// Export of search results as CSV, TSV or JSON Lines
+pub mod export;
+
+/// Content negotiation for API responses
+pub mod format;
+
+/// GraphQL endpoint for words, kanji, sentences and names
+pub mod graphql;
+
+/// Conjugation tables of words
+pub mod inflections;
+
 /// API endpoints for internal communication
 pub mod internal;
 
+/// Pitch accents of words
+pub mod pitch;
+
+/// Radicals and the kanji using them
+pub mod radicals;
+
+/// Recently added or changed words
+pub mod recent;
+
 /// Search API endpoint
 pub mod search;
+
+/// Versioning of the public API
+pub mod version;
@@ -0,0 +1,87 @@
+use actix_web::web;
+use error::api_error::RestError;
+use japanese::normalize::normalize;
+use jp_utils::JapaneseExt;
+use search::{
+    query::{parser::QueryParser, UserSettings},
+    SearchExecutor,
+};
+use types::{
+    api::pitch::{Accent, PitchWord, Request, Response},
+    jotoba::{
+        search::SearchTarget,
+        words::{pitch::split_kana, Word},
+    },
+};
+
+use crate::format::Formatted;
+
+/// Max length of the text to look up
+const MAX_TEXT_LEN: usize = 40;
+
+/// Returns the pitch accents of all words written like the requested text. If a drop is given,
+/// the pattern for the raw kana text is returned instead
+pub async fn pitch(query: web::Query<Request>) -> Result<Formatted<Response>, RestError> {
+    let text = normalize(query.text.trim());
+    if text.is_empty() || text.chars().count() > MAX_TEXT_LEN || !text.is_japanese() {
+        return Err(RestError::BadRequest);
+    }
+
+    if let Some(drop) = query.drop {
+        if !text.is_kana() || drop as usize > split_kana(&text).count() {
+            return Err(RestError::BadRequest);
+        }
+
+        let word = PitchWord {
+            sequence: None,
+            reading: text.clone(),
+            accents: vec![Accent::new(&text, drop)],
+            kana: text,
+        };
+        return Ok(Formatted(Response { words: vec![word] }));
+    }
+
+    let settings = UserSettings {
+        page_size: 50,
+        ..UserSettings::default()
+    };
+    let search_query = QueryParser::new(text.clone(), SearchTarget::Words, settings)
+        .parse()
+        .ok_or(RestError::BadRequest)?;
+
+    let result = web::block(move || {
+        let search = search::word::Search::new(&search_query);
+        SearchExecutor::new(search).run()
+    })
+    .await?;
+
+    let words: Vec<_> = result
+        .items
+        .iter()
+        .filter(|w| w.reading_iter(true).any(|r| r.reading == text))
+        .filter_map(|w| pitch_word(w, &text))
+        .collect();
+
+    if words.is_empty() {
+        return Err(RestError::NotFound);
+    }
+
+    Ok(Formatted(Response { words }))
+}
+
+/// Returns the accents of `word` found by `text`. Alternative kana readings have their own
+/// accents
+fn pitch_word(word: &Word, text: &str) -> Option<PitchWord> {
+    let kana = if text.is_kana() {
+        text
+    } else {
+        word.get_kana()
+    };
+    let accents = resources::get().words().accents(word.sequence, kana)?;
+    Some(PitchWord {
+        sequence: Some(word.sequence),
+        reading: word.get_reading().reading.clone(),
+        kana: kana.to_string(),
+        accents: accents.iter().map(|d| Accent::new(kana, d)).collect(),
+    })
+}
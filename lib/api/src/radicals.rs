@@ -0,0 +1,71 @@
+use actix_web::web;
+use error::api_error::RestError;
+use japanese::radicals::{get_stroke_count, RADICALS};
+use types::api::radicals::{KanjiResponse, ListResponse, Radical, RadicalKanji, RadicalResponse};
+
+use crate::format::Formatted;
+
+/// Lists all radicals which can be used to search for kanji, ordered by their stroke count
+pub async fn list() -> Formatted<ListResponse> {
+    let kanji_retr = resources::get().kanji();
+
+    let radicals = RADICALS
+        .iter()
+        .flat_map(|(strokes, rads)| rads.iter().map(move |r| (*strokes, r)))
+        .filter_map(|(strokes, rad)| {
+            let literal = rad.chars().next()?;
+            Some(Radical::new(literal, strokes, kanji_retr.radical(literal)))
+        })
+        .collect();
+
+    Formatted(ListResponse { radicals })
+}
+
+/// Returns a single radical by its literal
+pub async fn lookup(literal: web::Path<String>) -> Result<Formatted<RadicalResponse>, RestError> {
+    let literal = parse_literal(&literal)?;
+    let kanji_retr = resources::get().kanji();
+
+    let detailed = kanji_retr.radical(literal);
+    let stroke_count = get_stroke_count(literal)
+        .or_else(|| detailed.map(|i| i.stroke_count as u32))
+        .ok_or(RestError::NotFound)?;
+
+    let radical = Radical::new(literal, stroke_count, detailed);
+    let kanji_count = kanji_retr.by_radicals(&[literal]).len();
+    Ok(Formatted(RadicalResponse {
+        radical,
+        kanji_count,
+    }))
+}
+
+/// Returns all kanji using the given radical
+pub async fn kanji(literal: web::Path<String>) -> Result<Formatted<KanjiResponse>, RestError> {
+    let radical = parse_literal(&literal)?;
+
+    let mut kanji: Vec<_> = resources::get()
+        .kanji()
+        .by_radicals(&[radical])
+        .into_iter()
+        .map(|k| RadicalKanji {
+            literal: k.literal,
+            stroke_count: k.stroke_count,
+        })
+        .collect();
+
+    if kanji.is_empty() && get_stroke_count(radical).is_none() {
+        return Err(RestError::NotFound);
+    }
+
+    kanji.sort_by_key(|i| (i.stroke_count, i.literal));
+    Ok(Formatted(KanjiResponse { radical, kanji }))
+}
+
+/// Parses a path segment consisting of a single literal
+fn parse_literal(inp: &str) -> Result<char, RestError> {
+    let mut chars = inp.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(RestError::BadRequest),
+    }
+}
@@ -0,0 +1,31 @@
+use actix_web::web::{self, Json};
+use error::api_error::RestError;
+use types::api::recent::{RecentWord, Request, Response};
+
+/// Max amount of words returned per request
+const MAX_LIMIT: usize = 1000;
+
+/// Lists words which were added or changed in the newest JMdict releases, newest first
+pub async fn recent_words(query: web::Query<Request>) -> Result<Json<Response>, RestError> {
+    if query.releases == 0 || query.limit == 0 || query.limit > MAX_LIMIT {
+        return Err(RestError::BadRequest);
+    }
+
+    let word_retr = resources::get().words();
+    let lang = query.lang_param();
+
+    let words = word_retr
+        .recent(query.releases)
+        .rev()
+        .take(query.limit)
+        .filter_map(|word| {
+            let revision = word_retr.revision_date(word.sequence)?;
+            let mut word = word.clone();
+            word.adjust_language(lang);
+            Some(RecentWord::new(revision, word))
+        })
+        .collect();
+
+    let releases = word_retr.releases(query.releases).to_vec();
+    Ok(Json(Response::new(releases, words)))
+}
@@ -0,0 +1,95 @@
+//! Sparse fieldsets for API responses. Paths are relative to the root of the response and are
+//! separated by dots. Arrays are transparent, so `words.reading` selects the reading of every
+//! word in the `words` array.
+
+use super::Result;
+use crate::format::Formatted;
+use error::api_error::RestError;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use types::api::search::FieldSelection;
+
+/// Response which is either returned as it is or pruned to the selected fields
+pub type Selected<T> = Formatted<Fields<T>>;
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum Fields<T> {
+    All(T),
+    Selected(Value),
+}
+
+/// Tree of selected fields. A node without children selects the whole value
+#[derive(Default, Debug)]
+struct FieldTree(BTreeMap<String, FieldTree>);
+
+impl FieldTree {
+    fn new<'a>(paths: impl Iterator<Item = &'a str>) -> Self {
+        let mut root = FieldTree::default();
+        for path in paths {
+            let mut node = &mut root;
+            for part in path.split('.') {
+                node = node.0.entry(part.to_string()).or_default();
+            }
+        }
+        root
+    }
+
+    /// Removes all fields from `value` which aren't selected
+    fn prune(&self, value: &mut Value) {
+        match value {
+            Value::Array(items) => items.iter_mut().for_each(|i| self.prune(i)),
+            Value::Object(map) => {
+                map.retain(|k, _| self.0.contains_key(k));
+                for (key, value) in map.iter_mut() {
+                    let child = &self.0[key];
+                    if !child.0.is_empty() {
+                        child.prune(value);
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Returns `res` pruned to the fields selected in `selection`. `res` is returned unmodified if
+/// no fields were selected
+pub fn select<T: Serialize>(res: T, selection: &FieldSelection) -> Result<Selected<T>> {
+    let tree = FieldTree::new(selection.paths());
+    if tree.0.is_empty() {
+        return Ok(Formatted(Fields::All(res)));
+    }
+
+    let mut value = serde_json::to_value(res).map_err(|_| RestError::Internal)?;
+    tree.prune(&mut value);
+    Ok(Formatted(Fields::Selected(value)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_prune() {
+        let mut value = json!({
+            "words": [
+                { "reading": { "kana": "いぬ", "kanji": "犬" }, "common": true },
+                { "reading": { "kana": "ねこ" }, "common": false }
+            ],
+            "kanji": [{ "literal": "犬" }]
+        });
+
+        FieldTree::new(["words.reading.kana"].into_iter()).prune(&mut value);
+
+        let expected = json!({
+            "words": [
+                { "reading": { "kana": "いぬ" } },
+                { "reading": { "kana": "ねこ" } }
+            ]
+        });
+        assert_eq!(value, expected);
+    }
+}
@@ -1,18 +1,33 @@
 use actix_web::web::{self, Json};
+use search::query::Query;
 use types::{
-    api::search::kanji::{Kanji, Response},
+    api::search::{
+        kanji::{Kanji, Response},
+        FieldSelection,
+    },
     jotoba::search::SearchTarget,
 };
 
-use super::{Result, SearchRequest};
+use super::{
+    fields::{self, Selected},
+    Result, SearchRequest,
+};
 
 /// Do a kanji search via API
-pub async fn kanji_search(payload: Json<SearchRequest>) -> Result<Json<Response>> {
-    let query = super::parse_query(payload, SearchTarget::Kanji)?;
+pub async fn kanji_search(
+    payload: Json<SearchRequest>,
+    selection: web::Query<FieldSelection>,
+) -> Result<Selected<Response>> {
+    let query = super::parse_query(&payload, SearchTarget::Kanji)?;
+    fields::select(run(query).await?, &selection)
+}
+
+/// Runs the kanji search for `query`
+pub(crate) async fn run(query: Query) -> Result<Response> {
     let result = web::block(move || search::kanji::search(&query))
         .await??
         .items;
-    Ok(Json(to_response(result)))
+    Ok(to_response(result))
 }
 
 #[inline]
@@ -1,25 +1,39 @@
+pub mod fields;
 pub mod kanji;
 pub mod name;
 pub mod sentence;
+pub mod stream;
 pub mod word;
 
-use actix_web::web::Json;
+use crate::app::search::LAST_PAGE;
 use error::api_error::RestError;
 use search::query::{parser::QueryParser, Query, UserSettings};
 use types::{api::search::SearchRequest, jotoba::search::SearchTarget};
 
 pub type Result<T> = std::result::Result<T, RestError>;
 
-pub(crate) fn parse_query(payload: Json<SearchRequest>, q_type: SearchTarget) -> Result<Query> {
+/// Max amount of results per page
+const MAX_PAGE_SIZE: u32 = 100;
+
+pub(crate) fn parse_query(payload: &SearchRequest, q_type: SearchTarget) -> Result<Query> {
+    let default_settings = UserSettings::default();
+    let page_size = payload
+        .page_size
+        .unwrap_or(default_settings.page_size)
+        .clamp(1, MAX_PAGE_SIZE);
+
     let settings = UserSettings {
         user_lang: payload.language,
         show_english: !payload.no_english,
-        ..UserSettings::default()
+        sentence_script: payload.sentence_script,
+        page_size,
+        ..default_settings
     };
 
     let q_str = payload.query_str.clone();
 
     let query = QueryParser::new(q_str, q_type, settings)
+        .with_page(payload.page.unwrap_or(1).clamp(1, LAST_PAGE as usize))
         .parse()
         .ok_or(RestError::BadRequest)?;
 
@@ -1,16 +1,40 @@
 use actix_web::web::{self, Json};
-use search::SearchExecutor;
-use types::{api::search::name::Response, jotoba::search::SearchTarget};
+use search::{query::Query, SearchExecutor};
+use types::{
+    api::search::{name::Response, FieldSelection},
+    jotoba::search::SearchTarget,
+};
 
-use super::{Result, SearchRequest};
+use super::{
+    fields::{self, Selected},
+    Result, SearchRequest,
+};
+use crate::version::ApiVersion;
 
 /// Do a name search via API
-pub async fn name_search(payload: Json<SearchRequest>) -> Result<Json<Response>> {
-    let query = super::parse_query(payload, SearchTarget::Kanji)?;
+pub async fn name_search(
+    payload: Json<SearchRequest>,
+    selection: web::Query<FieldSelection>,
+    version: ApiVersion,
+) -> Result<Selected<Response>> {
+    // v1 parses the query as kanji query, which is kept for compatibility
+    let target = match version {
+        ApiVersion::V1 => SearchTarget::Kanji,
+        _ => SearchTarget::Names,
+    };
+    let query = super::parse_query(&payload, target)?;
+    fields::select(run(query).await?, &selection)
+}
+
+/// Runs the name search for `query`
+pub(crate) async fn run(query: Query) -> Result<Response> {
     let result = web::block(move || {
         let search = search::name::Search::new(&query);
         SearchExecutor::new(search).run()
     })
     .await?;
-    Ok(Json(result.items.into()))
+    Ok(Response::new(
+        result.items,
+        result.other_data.transliterations,
+    ))
 }
@@ -1,14 +1,37 @@
 use actix_web::web::{self, Json};
+use search::query::Query;
 use types::{
-    api::search::sentence::{Response, Sentence},
-    jotoba::search::SearchTarget,
+    api::search::{
+        sentence::{Response, Sentence},
+        FieldSelection,
+    },
+    jotoba::{search::SearchTarget, sentences::script::SentenceScript},
 };
 
-use super::{Result, SearchRequest};
+use super::{
+    fields::{self, Selected},
+    Result, SearchRequest,
+};
+use crate::version::ApiVersion;
 
 /// Do a Sentence search via API
-pub async fn sentence_search(payload: Json<SearchRequest>) -> Result<Json<Response>> {
-    let query = super::parse_query(payload, SearchTarget::Kanji)?;
+pub async fn sentence_search(
+    payload: Json<SearchRequest>,
+    selection: web::Query<FieldSelection>,
+    version: ApiVersion,
+) -> Result<Selected<Response>> {
+    // v1 parses the query as kanji query, which is kept for compatibility
+    let target = match version {
+        ApiVersion::V1 => SearchTarget::Kanji,
+        _ => SearchTarget::Sentences,
+    };
+    let query = super::parse_query(&payload, target)?;
+    fields::select(run(query).await?, &selection)
+}
+
+/// Runs the sentence search for `query`
+pub(crate) async fn run(query: Query) -> Result<Response> {
+    let script = query.settings.sentence_script;
 
     let result = web::block(move || {
         let search = search::sentence::Search::new(&query);
@@ -17,15 +40,19 @@ pub async fn sentence_search(payload: Json<SearchRequest>) -> Result<Json<Respon
     .await?
     .items
     .into_iter()
-    .map(|i| search_to_sentence(i))
+    .map(|i| search_to_sentence(i, script))
     .collect::<Vec<_>>();
 
-    Ok(Json(result.into()))
+    Ok(Response::from(result))
 }
 
 #[inline]
-fn search_to_sentence(sentence: search::sentence::result::Sentence) -> Sentence {
+fn search_to_sentence(
+    sentence: search::sentence::result::Sentence,
+    script: SentenceScript,
+) -> Sentence {
     Sentence {
+        converted: sentence.converted(script),
         eng: sentence.get_english().map(|i| i.to_owned()),
         content: sentence.content.to_string(),
         furigana: sentence.furigana.to_string(),
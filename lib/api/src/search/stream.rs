@@ -0,0 +1,87 @@
+//! Server-Sent Events endpoint running the searches of all targets one after another. The result
+//! of each target is sent as soon as it's available (words first, then kanji, sentences and
+//! names), so clients can render the results progressively.
+
+use super::{kanji, name, sentence, word, Result, SearchRequest};
+use actix_web::{
+    http::header::{ContentEncoding, CACHE_CONTROL},
+    web::{self, Bytes},
+    HttpResponse,
+};
+use error::api_error::RestError;
+use futures::stream::{self, StreamExt};
+use search::query::Query;
+use serde::Serialize;
+use types::jotoba::search::SearchTarget;
+
+/// Targets in the order their results are streamed
+const TARGETS: [SearchTarget; 4] = [
+    SearchTarget::Words,
+    SearchTarget::Kanji,
+    SearchTarget::Sentences,
+    SearchTarget::Names,
+];
+
+/// Streams the results of all search targets for the given query
+pub async fn stream_search(payload: web::Query<SearchRequest>) -> Result<HttpResponse> {
+    // Parse all queries upfront so invalid requests fail with a proper status code
+    let queries = TARGETS
+        .into_iter()
+        .map(|target| Ok((target, super::parse_query(&payload, target)?)))
+        .collect::<Result<Vec<_>>>()?;
+
+    // The searches run while the response is streamed, after the request itself is done
+    let pin = utils::epoch::pin();
+
+    let done = stream::once(async { Ok(Bytes::from_static(b"event: done\ndata: {}\n\n")) });
+    let events = stream::iter(queries)
+        .then(|(target, query)| event(target, query))
+        .chain(done)
+        .map(move |event| {
+            let _pin = &pin;
+            event
+        });
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header((CACHE_CONTROL, "no-cache"))
+        // Compressing would buffer the events
+        .insert_header(ContentEncoding::Identity)
+        .streaming(events))
+}
+
+/// Runs the search for a single target and returns its result as event
+async fn event(target: SearchTarget, query: Query) -> std::result::Result<Bytes, RestError> {
+    let name = event_name(target);
+
+    let data = match target {
+        SearchTarget::Words => encode(word::run(query).await),
+        SearchTarget::Kanji => encode(kanji::run(query).await),
+        SearchTarget::Sentences => encode(sentence::run(query).await),
+        SearchTarget::Names => encode(name::run(query).await),
+    };
+
+    let event = match data {
+        Ok(data) => format!("event: {name}\ndata: {data}\n\n"),
+        Err(err) => {
+            let err = err.name();
+            format!("event: error\ndata: {{\"target\":\"{name}\",\"error\":\"{err}\"}}\n\n")
+        }
+    };
+
+    Ok(Bytes::from(event))
+}
+
+fn encode<T: Serialize>(res: Result<T>) -> Result<String> {
+    serde_json::to_string(&res?).map_err(|_| RestError::Internal)
+}
+
+#[inline]
+fn event_name(target: SearchTarget) -> &'static str {
+    match target {
+        SearchTarget::Words => "words",
+        SearchTarget::Kanji => "kanji",
+        SearchTarget::Sentences => "sentences",
+        SearchTarget::Names => "names",
+    }
+}
@@ -1,17 +1,47 @@
-use super::{Result, SearchRequest};
+use super::{
+    fields::{self, Selected},
+    Result, SearchRequest,
+};
 use actix_web::web::{self, Json};
-use search::{word::Search, SearchExecutor};
+use search::{query::Query, word::Search, SearchExecutor};
 use types::{
     api::search::{
         kanji::Kanji,
-        word::{Response, Word},
+        word::{KanjiParts, Response, Word},
+        FieldSelection,
     },
     jotoba::search::SearchTarget,
 };
 
 /// Do a word search via API
-pub async fn word_search(payload: Json<SearchRequest>) -> Result<Json<Response>> {
-    let query = super::parse_query(payload, SearchTarget::Words)?;
+pub async fn word_search(
+    payload: Json<SearchRequest>,
+    selection: web::Query<FieldSelection>,
+) -> Result<Selected<Response>> {
+    let query = super::parse_query(&payload, SearchTarget::Words)?;
+    let mut res = run(query).await?;
+    if payload.kanji_parts {
+        add_kanji_parts(&mut res);
+    }
+    fields::select(res, &selection)
+}
+
+/// Adds the radical and component breakdown of their kanji to all words in `res`
+fn add_kanji_parts(res: &mut Response) {
+    let kanji_retr = resources::get().kanji();
+    for word in res.words_mut() {
+        let parts = word
+            .kanji_literals()
+            .into_iter()
+            .filter_map(|i| kanji_retr.by_literal(i))
+            .map(KanjiParts::from)
+            .collect();
+        word.set_kanji_parts(parts);
+    }
+}
+
+/// Runs the word search for `query`
+pub(crate) async fn run(query: Query) -> Result<Response> {
     let result = web::block(move || {
         let search = Search::new(&query);
         SearchExecutor::new(search).run()
@@ -23,5 +53,5 @@ pub async fn word_search(payload: Json<SearchRequest>) -> Result<Json<Response>>
         .map(|i| (&i).into())
         .collect();
     let words: Vec<Word> = result.items.into_iter().map(|i| (&i).into()).collect();
-    Ok(Json(Response::new(words, kanji)))
+    Ok(Response::new(words, kanji))
 }
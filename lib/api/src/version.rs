@@ -0,0 +1,27 @@
+use actix_web::{dev::Payload, FromRequest, HttpRequest};
+use std::{
+    convert::Infallible,
+    future::{ready, Ready},
+};
+
+/// Version of the public API a request was made against. Handlers which changed their response
+/// shape in a newer version can extract this to keep serving the old shape for older versions
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
+pub enum ApiVersion {
+    /// Unversioned API (`/api/...`), deprecated
+    #[default]
+    V1,
+    /// `/api/v2/...`
+    V2,
+}
+
+impl FromRequest for ApiVersion {
+    type Error = Infallible;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let version = req.app_data::<ApiVersion>().copied();
+        ready(Ok(version.unwrap_or_default()))
+    }
+}
@@ -14,7 +14,12 @@ use std::{
 pub struct Config {
     pub server: ServerConfig,
     pub sentry: Option<SentryConfig>,
+    pub tracing: Option<TracingConfig>,
     pub search: Option<SearchConfig>,
+    pub rate_limit: Option<RateLimitConfig>,
+    pub import: Option<ImportConfig>,
+    #[serde(default)]
+    pub cors: Vec<CorsRule>,
 
     #[serde(skip)]
     pub asset_hash: String,
@@ -28,7 +33,29 @@ pub struct ServerConfig {
     pub storage_data: Option<String>,
     pub img_upload_dir: Option<String>,
     pub tess_data: Option<String>,
+    /// Command running a local OCR backend for image scans, eg. `tesseract {file} stdout -l jpn`.
+    /// `{file}` gets replaced with the path of the uploaded image and the recognized text is read
+    /// from stdout. The built-in tesseract bindings are used if not set
+    pub ocr_command: Option<String>,
+    /// Command synthesizing speech for words without pre-recorded audio, eg.
+    /// `tts-to-mp3 {text} {out}`. `{text}` gets replaced with the kana reading of the word and
+    /// `{out}` with the mp3 file to write. Generated files are cached in `tts_cache`
+    pub tts_command: Option<String>,
+    pub tts_cache: Option<String>,
     pub news_folder: Option<String>,
+    pub textbook_folder: Option<String>,
+    /// Folder with overlay files patching words at load time. Patches are only displayed and
+    /// don't change which words are found by a search
+    pub overlay_folder: Option<String>,
+    /// Folder with words, names and sentences split off the storage data with
+    /// `--split-resources`. They get loaded lazily in segments instead of being kept in memory.
+    /// Data can't be imported into split storage data
+    pub resource_segments: Option<String>,
+    /// Gloss languages to load, eg. `["eng", "ger"]`. Senses and sentence translations in other
+    /// languages get dropped while loading and their word, sentence and suggestion indexes aren't
+    /// loaded. English is always loaded as it's the fallback of all languages. All languages are
+    /// loaded if not set
+    pub languages: Option<Vec<String>>,
     pub unidic_dict: Option<String>,
     pub debug_mode: Option<bool>,
     pub internal_api_key: String,
@@ -39,14 +66,271 @@ pub struct SentryConfig {
     pub dsn: String,
 }
 
+/// Exports spans of the search stack (query parsing, producers, engines) to an OpenTelemetry
+/// collector. Requires the "otlp" feature, eg.
+///
+/// ```toml
+/// [tracing]
+/// otlp_endpoint = "http://localhost:4317"
+/// sample_ratio = 0.1
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TracingConfig {
+    /// gRPC endpoint of the collector
+    pub otlp_endpoint: String,
+    /// Defaults to "jotoba"
+    pub service_name: Option<String>,
+    /// Ratio of traced requests between 0 and 1. Defaults to 1
+    pub sample_ratio: Option<f64>,
+}
+
+impl TracingConfig {
+    #[inline]
+    pub fn get_service_name(&self) -> &str {
+        self.service_name.as_deref().unwrap_or("jotoba")
+    }
+
+    #[inline]
+    pub fn get_sample_ratio(&self) -> f64 {
+        self.sample_ratio.unwrap_or(1.0).clamp(0.0, 1.0)
+    }
+}
+
+/// Source files of the importers, eg.
+///
+/// ```toml
+/// [import]
+/// word_frequencies = "./data/word_freq.tsv"
+/// kanji_frequencies = "./data/kanji_freq.txt"
+/// word_jlpt = "./data/jlpt_words.csv"
+/// kanji_jlpt = "./data/jlpt_kanji.csv"
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ImportConfig {
+    /// Word frequency list, most frequent word first. Replaces corpus frequencies extracted from
+    /// sentences
+    pub word_frequencies: Option<String>,
+    /// Kanji frequency list, most frequent kanji first. Only used for kanji without a frequency
+    /// rank in KANJIDIC
+    pub kanji_frequencies: Option<String>,
+    /// CSV with the JLPT levels of words
+    pub word_jlpt: Option<String>,
+    /// CSV with the JLPT levels of kanji
+    pub kanji_jlpt: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct SearchConfig {
     pub suggestion_sources: Option<String>,
     pub indexes_source: Option<String>,
     pub report_queries_after: Option<u64>,
+    /// Max amount of cached suggestion responses. Caching is disabled if set to 0
+    pub suggestion_cache_size: Option<usize>,
+    /// Seconds after which cached suggestion responses expire
+    pub suggestion_cache_ttl: Option<u64>,
+    /// File with the most common queries, one per line and most common first. They get searched
+    /// once after startup to warm up indexes and caches
+    pub warmup_queries: Option<String>,
+    /// Amount of queries from `warmup_queries` to run. Defaults to 500
+    pub warmup_count: Option<usize>,
+    /// Overwrites the defaults of the search engines
+    pub engines: Option<EnginesConfig>,
+    /// How results found by multiple producers get merged, per search type
+    pub merge: Option<MergeConfig>,
+}
+
+/// Merge strategies of the search types, eg.
+///
+/// ```toml
+/// [search.merge]
+/// words = "sum"
+/// names = "first"
+/// ```
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default)]
+pub struct MergeConfig {
+    pub words: Option<MergeStrategy>,
+    pub sentences: Option<MergeStrategy>,
+    pub names: Option<MergeStrategy>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeStrategy {
+    /// Keep the relevance of the producer which found the item first
+    First,
+    /// Keep the highest relevance
+    Max,
+    /// Add up the relevances
+    Sum,
+}
+
+/// Settings of the search engines. Unset values keep the defaults of the search, eg.
+///
+/// ```toml
+/// [search.engines.foreign_words]
+/// threshold = 0.4
+/// vector_limit = 20000
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct EnginesConfig {
+    pub native_words: Option<EngineConfig>,
+    pub foreign_words: Option<EngineConfig>,
+    pub sentences: Option<EngineConfig>,
+    pub names: Option<EngineConfig>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default)]
+pub struct EngineConfig {
+    /// Min relevance of results
+    pub threshold: Option<f32>,
+    /// Max amount of results
+    pub limit: Option<usize>,
+    /// Max amount of vectors retrieved from the index
+    pub vector_limit: Option<usize>,
+}
+
+/// Rate limits for the public API. Requests without an API key are limited by their IP address,
+/// eg.
+///
+/// ```toml
+/// [rate_limit]
+/// requests_per_minute = 60
+/// # Reverse proxies whose forwarded client address gets used
+/// trusted_proxies = ["127.0.0.1"]
+///
+/// [[rate_limit.api_keys]]
+/// key = "secret"
+/// name = "some app"
+/// requests_per_minute = 600
+/// daily_quota = 100000
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct RateLimitConfig {
+    /// Requests per minute for clients without an API key. Unlimited if not set
+    pub requests_per_minute: Option<u32>,
+    /// Requests per day for clients without an API key. Unlimited if not set
+    pub daily_quota: Option<u32>,
+    /// IP addresses of reverse proxies. Clients connecting through one of them are limited by the
+    /// address in the forwarding headers, all other clients by the address they connect from
+    #[serde(default)]
+    pub trusted_proxies: Vec<String>,
+    #[serde(default)]
+    pub api_keys: Vec<ApiKeyConfig>,
+}
+
+/// An issued API key with its own limits
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ApiKeyConfig {
+    pub key: String,
+    /// Name of the keys owner. Only used for logging
+    pub name: String,
+    /// Unlimited if not set
+    pub requests_per_minute: Option<u32>,
+    /// Unlimited if not set
+    pub daily_quota: Option<u32>,
+}
+
+/// Cross origin and frame embedding policy of all routes starting with `path`. The rule with the
+/// longest matching path applies, eg.
+///
+/// ```toml
+/// [[cors]]
+/// path = "/api"
+/// origins = ["https://example.com"]
+/// methods = ["GET", "POST"]
+///
+/// [[cors]]
+/// path = "/"
+/// frame_ancestors = ["'self'"]
+///
+/// # Lookup widget which other sites embed
+/// [[cors]]
+/// path = "/embed"
+/// frame_ancestors = ["*"]
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CorsRule {
+    pub path: String,
+    /// Origins allowed to access the routes. `*` allows all origins. Cross origin requests are
+    /// blocked if empty
+    #[serde(default)]
+    pub origins: Vec<String>,
+    /// Methods cross origin requests are allowed to use
+    #[serde(default = "CorsRule::default_methods")]
+    pub methods: Vec<String>,
+    /// Request headers cross origin requests are allowed to send. `*` allows all headers a
+    /// preflight request asks for. Only `Content-Type` and `X-API-Key` are allowed if not set
+    pub headers: Option<Vec<String>>,
+    /// Sources allowed to embed the routes in frames, eg. `'self'` or `https://example.com`.
+    /// Embedding is forbidden if empty and allowed everywhere if not set
+    pub frame_ancestors: Option<Vec<String>>,
+}
+
+impl CorsRule {
+    fn default_methods() -> Vec<String> {
+        vec!["GET".to_string(), "POST".to_string()]
+    }
+
+    /// Rules used if none are configured. Allows all origins to use the API and assets
+    fn defaults() -> Vec<CorsRule> {
+        ["/api", "/assets"]
+            .into_iter()
+            .map(|path| CorsRule {
+                path: path.to_string(),
+                origins: vec!["*".to_string()],
+                methods: Self::default_methods(),
+                headers: None,
+                frame_ancestors: None,
+            })
+            .collect()
+    }
+
+    /// Returns `true` if the rule applies to `path`
+    pub fn matches(&self, path: &str) -> bool {
+        let prefix = self.path.trim_end_matches('/');
+        path.strip_prefix(prefix)
+            .map_or(false, |rest| rest.is_empty() || rest.starts_with('/'))
+    }
+
+    /// Returns `true` if requests from `origin` are allowed
+    pub fn allows_origin(&self, origin: &str) -> bool {
+        self.origins.iter().any(|i| i == "*" || i == origin)
+    }
+
+    /// Returns `true` if all request headers asked for in preflight requests are allowed
+    pub fn allows_all_headers(&self) -> bool {
+        self.headers
+            .as_ref()
+            .map_or(false, |headers| headers.iter().any(|i| i == "*"))
+    }
+
+    /// Returns `true` if cross origin requests are allowed to use `method`
+    pub fn allows_method(&self, method: &str) -> bool {
+        method == "OPTIONS" || self.methods.iter().any(|i| i.eq_ignore_ascii_case(method))
+    }
+}
+
+impl RateLimitConfig {
+    /// Returns the configuration of the API key `key`
+    pub fn find_key(&self, key: &str) -> Option<&ApiKeyConfig> {
+        self.api_keys.iter().find(|i| i.key == key)
+    }
+
+    /// Returns `true` if the forwarding headers of requests from `ip` can be trusted
+    pub fn is_trusted_proxy(&self, ip: &str) -> bool {
+        self.trusted_proxies.iter().any(|i| i == ip)
+    }
 }
 
 impl Config {
+    /// Returns the configured cors rules or the default ones if none are configured
+    pub fn get_cors_rules(&self) -> Vec<CorsRule> {
+        if self.cors.is_empty() {
+            return CorsRule::defaults();
+        }
+        self.cors.clone()
+    }
+
     /// Returns the configured index source files or its default value if not set
     pub fn get_indexes_source(&self) -> &str {
         self.search
@@ -63,6 +347,58 @@ impl Config {
             .unwrap_or("./resources/suggestions")
     }
 
+    /// Returns the configured max amount of cached suggestion responses
+    pub fn get_suggestion_cache_size(&self) -> usize {
+        self.search
+            .as_ref()
+            .and_then(|i| i.suggestion_cache_size)
+            .unwrap_or(10_000)
+    }
+
+    /// Returns the configured time after which cached suggestion responses expire
+    pub fn get_suggestion_cache_ttl(&self) -> Duration {
+        let ttl = self
+            .search
+            .as_ref()
+            .and_then(|i| i.suggestion_cache_ttl)
+            .unwrap_or(600);
+
+        Duration::from_secs(ttl)
+    }
+
+    /// Returns the configured path of the warm-up queries
+    #[inline]
+    pub fn get_warmup_queries(&self) -> Option<&str> {
+        self.search.as_ref()?.warmup_queries.as_deref()
+    }
+
+    /// Returns the configured amount of warm-up queries to run
+    #[inline]
+    pub fn get_warmup_count(&self) -> usize {
+        self.search
+            .as_ref()
+            .and_then(|i| i.warmup_count)
+            .unwrap_or(500)
+    }
+
+    /// Returns the configured engine settings
+    #[inline]
+    pub fn get_engines(&self) -> EnginesConfig {
+        self.search
+            .as_ref()
+            .and_then(|i| i.engines.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the configured merge strategies
+    #[inline]
+    pub fn get_merge(&self) -> MergeConfig {
+        self.search
+            .as_ref()
+            .and_then(|i| i.merge)
+            .unwrap_or_default()
+    }
+
     /// Returns the configured query report timeout
     pub fn get_query_report_timeout(&self) -> Duration {
         let timeout = self
@@ -74,6 +410,12 @@ impl Config {
         Duration::from_secs(timeout)
     }
 
+    /// Returns the configured source files of the importers
+    #[inline]
+    pub fn get_import(&self) -> ImportConfig {
+        self.import.clone().unwrap_or_default()
+    }
+
     /// Returns the configured (or default) path for storage data
     pub fn get_storage_data_path(&self) -> String {
         self.server
@@ -126,7 +468,14 @@ impl Default for ServerConfig {
             img_upload_dir: Some(String::from("./img_scan_tmp")),
             unidic_dict: Some(String::from("./resources/unidic-mecab")),
             tess_data: None,
+            ocr_command: None,
+            tts_command: None,
+            tts_cache: Some(String::from("./resources/tts_cache")),
             news_folder: Some(String::from("./resources/news")),
+            textbook_folder: Some(String::from("./resources/textbooks")),
+            overlay_folder: Some(String::from("./resources/overlays")),
+            resource_segments: None,
+            languages: None,
             debug_mode: Some(false),
             internal_api_key: "ReplaceMe!!!!".to_string(),
         }
@@ -149,6 +498,39 @@ impl ServerConfig {
     pub fn get_news_folder(&self) -> &str {
         self.news_folder.as_deref().unwrap_or("./resources/news")
     }
+
+    pub fn get_textbook_folder(&self) -> &str {
+        self.textbook_folder
+            .as_deref()
+            .unwrap_or("./resources/textbooks")
+    }
+
+    pub fn get_tts_cache(&self) -> &str {
+        self.tts_cache.as_deref().unwrap_or("./resources/tts_cache")
+    }
+
+    /// Returns `true` if audio for words without pre-recorded audio files gets synthesized
+    #[inline]
+    pub fn tts_enabled(&self) -> bool {
+        self.tts_command.is_some()
+    }
+
+    pub fn get_overlay_folder(&self) -> &str {
+        self.overlay_folder
+            .as_deref()
+            .unwrap_or("./resources/overlays")
+    }
+
+    #[inline]
+    pub fn get_resource_segments(&self) -> Option<&str> {
+        self.resource_segments.as_deref()
+    }
+
+    /// Returns the gloss languages to load or `None` if all languages should be loaded
+    #[inline]
+    pub fn get_languages(&self) -> Option<&[String]> {
+        self.languages.as_deref()
+    }
 }
 
 impl Config {
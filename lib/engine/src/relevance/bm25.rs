@@ -0,0 +1,129 @@
+use sparse_vec::{SpVec32, VecExt};
+
+/// Term frequency saturation. Higher values let repeated terms count more
+pub const K1: f32 = 1.2;
+
+/// Influence of the document length. 0.0 ignores the length, 1.0 fully normalizes by it
+pub const B: f32 = 0.75;
+
+/// Collection statistics BM25 scores are based on
+pub trait CollectionStats {
+    /// Total amount of documents in the collection
+    fn doc_count(&self) -> usize;
+
+    /// Average amount of terms within a document
+    fn avg_doc_len(&self) -> f32;
+
+    /// Amount of documents containing the term with the given id
+    fn doc_freq(&self, term: u32) -> usize;
+}
+
+/// Inverse document frequency of a term which occurs in `doc_freq` of `doc_count` documents.
+/// Never gets negative, so very common terms still count a little
+#[inline]
+pub fn idf(doc_count: usize, doc_freq: usize) -> f32 {
+    let n = doc_count as f32;
+    let df = doc_freq as f32;
+    (1.0 + (n - df + 0.5) / (df + 0.5)).ln()
+}
+
+/// BM25 score of `doc` for `query`, normalized to 0.0..=1.0 by dividing through the score of a
+/// document containing all query terms and being of average length. Term weights of the query
+/// are applied to the score of each term. Terms within `doc` are counted once, since their
+/// weights aren't term frequencies
+pub fn score<S: CollectionStats>(query: &SpVec32, doc: &SpVec32, stats: &S) -> f32 {
+    let doc_len = doc.as_vec().len() as f32;
+    let avg_doc_len = stats.avg_doc_len().max(1.0);
+    let len_norm = 1.0 - B + B * doc_len / avg_doc_len;
+
+    let mut score = 0.0;
+    let mut max_score = 0.0;
+
+    for (term, weight) in query.as_vec() {
+        let term_idf = idf(stats.doc_count(), stats.doc_freq(*term)) * weight;
+        max_score += term_idf;
+
+        if doc.as_vec().iter().any(|i| i.0 == *term) {
+            score += term_idf * (K1 + 1.0) / (1.0 + K1 * len_norm);
+        }
+    }
+
+    if max_score <= 0.0 {
+        return 0.0;
+    }
+
+    // A document of average length containing all terms scores `max_score`
+    (score / max_score).min(1.0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Collection of 100 documents with an average length of 2 terms. Term 1 occurs in one
+    /// document and all other terms in 50
+    struct Stats;
+
+    impl CollectionStats for Stats {
+        fn doc_count(&self) -> usize {
+            100
+        }
+
+        fn avg_doc_len(&self) -> f32 {
+            2.0
+        }
+
+        fn doc_freq(&self, term: u32) -> usize {
+            if term == 1 {
+                1
+            } else {
+                50
+            }
+        }
+    }
+
+    fn vec(terms: &[u32]) -> SpVec32 {
+        SpVec32::create_new_raw(terms.iter().map(|i| (*i, 1.0)))
+    }
+
+    #[test]
+    fn test_idf() {
+        assert!(idf(100, 1) > idf(100, 50));
+        assert!(idf(100, 100) > 0.0);
+    }
+
+    #[test]
+    fn test_score_all_terms() {
+        let score = score(&vec(&[1, 2]), &vec(&[1, 2]), &Stats);
+        assert!((score - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_score_no_terms() {
+        assert_eq!(score(&vec(&[1, 2]), &vec(&[3, 4]), &Stats), 0.0);
+        assert_eq!(score(&vec(&[]), &vec(&[1, 2]), &Stats), 0.0);
+    }
+
+    #[test]
+    fn test_score_rare_terms() {
+        let rare = score(&vec(&[1, 2]), &vec(&[1, 3]), &Stats);
+        let common = score(&vec(&[1, 2]), &vec(&[2, 3]), &Stats);
+        assert!(rare > common);
+        assert!(rare < 1.0);
+    }
+
+    #[test]
+    fn test_score_doc_len() {
+        let short = score(&vec(&[2]), &vec(&[2]), &Stats);
+        let long = score(&vec(&[2]), &vec(&[2, 3, 4, 5]), &Stats);
+        assert!(short > long);
+    }
+
+    #[test]
+    fn test_score_weights() {
+        let query = SpVec32::create_new_raw([(1, 0.1), (2, 1.0)].into_iter());
+        let light = score(&query, &vec(&[1, 3]), &Stats);
+        let heavy = score(&query, &vec(&[2, 3]), &Stats);
+        assert!(heavy > light);
+    }
+}
@@ -1,3 +1,4 @@
+pub mod bm25;
 pub mod data;
 pub mod item;
 
@@ -1,11 +1,12 @@
 use crate::{
-    pushable::{MaxCounter, PushMod, Pushable},
+    pushable::{MaxCounter, PushFn, PushMod, Pushable},
     relevance::{data::SortData, RelevanceEngine},
     relevance::{item::RelItem, RelEngineInit},
     result::SearchResult,
     Engine,
 };
 use priority_container::StableUniquePrioContainerMax;
+use rayon::prelude::*;
 use std::marker::PhantomData;
 use types::jotoba::{
     language::Language,
@@ -36,6 +37,9 @@ pub struct SearchTask<'index, E: Engine<'index>> {
     /// Max distance to max item
     max_dist: Option<f32>,
 
+    /// Max amount of documents retrieved from the index
+    vector_limit: Option<usize>,
+
     limit: usize,
     offset: usize,
     est_limit: usize,
@@ -68,6 +72,14 @@ where
         self.query_lang.is_some()
     }
 
+    /// Sets the max amount of documents retrieved from the index. Retrieval stops once it's
+    /// reached, regardless of how many results were found
+    #[inline]
+    pub fn with_vector_limit(mut self, vector_limit: usize) -> Self {
+        self.vector_limit = Some(vector_limit);
+        self
+    }
+
     /// Set the total limit. This is the max amount of vectors which will be loaded and processed
     #[inline]
     pub fn with_limit(mut self, total_limit: usize) -> Self {
@@ -150,6 +162,40 @@ where
         self.find_to_inner(out, true)
     }
 
+    /// Runs a search task for each item in `inputs` in parallel and pushes their results into
+    /// `out`. Results get pushed in the order of `inputs`, so `out` ends up the same as when
+    /// running the tasks one after another. Since [`SearchTask`]s can't be sent to other threads,
+    /// they get created by `make_task` within the worker threads
+    pub fn find_parallel_to<T, F, O>(inputs: &[T], make_task: F, out: &mut O)
+    where
+        T: Sync,
+        F: Fn(&T) -> Self + Sync,
+        O: Pushable<Item = RelItem<E::Output>>,
+        E::Output: Send,
+    {
+        if let [input] = inputs {
+            make_task(input).find_to(out);
+            return;
+        }
+
+        let results: Vec<Vec<RelItem<E::Output>>> = inputs
+            .par_iter()
+            .map(|input| {
+                let mut items = vec![];
+                let mut collect = PushFn::new(|i| {
+                    items.push(i);
+                    true
+                });
+                make_task(input).find_to(&mut collect);
+                items
+            })
+            .collect();
+
+        for item in results.into_iter().flatten() {
+            out.push(item);
+        }
+    }
+
     /// Estimates the amount of results efficiently. This 'guess' is defined as follows:
     ///
     /// Be 'm' the amount of items a full search would return.
@@ -201,14 +247,29 @@ where
     where
         O: Pushable<Item = RelItem<E::Output>>,
     {
+        let span = tracing::trace_span!(
+            "search_task",
+            engine = std::any::type_name::<E>(),
+            query = %self.query_str,
+            lang = ?self.query_lang,
+            pushed = tracing::field::Empty
+        )
+        .entered();
+
         let query = E::make_query(&self.query_str, self.query_lang)?;
 
         let mut retr: E::Retriever =
             E::retrieve_for(&query, &self.query_str, self.query_lang).get();
 
         let mut pushed = 0;
+        let mut retrieved = 0;
 
         loop {
+            if self.vector_limit.map_or(false, |limit| retrieved >= limit) {
+                break;
+            }
+            retrieved += 1;
+
             let (index_item, out_items) = match self.retrieve_next(&mut retr) {
                 Some(v) => v,
                 None => break,
@@ -233,6 +294,7 @@ where
             }
         }
 
+        span.record("pushed", pushed);
         Some(pushed)
     }
 
@@ -327,6 +389,7 @@ impl<'a, T: Engine<'a>> Default for SearchTask<'a, T> {
             est_limit: 100,
             phantom: PhantomData,
             max_dist: None,
+            vector_limit: None,
         }
     }
 }
@@ -53,6 +53,9 @@ pub enum RestError {
 
     #[error("Unauthorized")]
     Unauthorized,
+
+    #[error("Too many requests")]
+    TooManyRequests,
 }
 
 /// Error response format. Used as json encoding structure
@@ -74,6 +77,7 @@ impl RestError {
             Self::NoTextFound => "NoTextFound".to_string(),
             Self::FormatNotSupported => "FormatNotSupported".to_string(),
             Self::Unauthorized => "Unauthtorized".to_string(),
+            Self::TooManyRequests => "TooManyRequests".to_string(),
             _ => "InternalError".to_string(),
         }
     }
@@ -90,6 +94,7 @@ impl ResponseError for RestError {
             Self::FormatNotSupported => StatusCode::BAD_REQUEST,
             Self::NoTextFound => StatusCode::SEE_OTHER,
             Self::Unauthorized => StatusCode::UNAUTHORIZED,
+            Self::TooManyRequests => StatusCode::TOO_MANY_REQUESTS,
             _ => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
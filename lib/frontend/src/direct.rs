@@ -18,7 +18,7 @@ use crate::{
     search_ep::redirect_home,
     templates, user_settings,
     web_error::{self, Error},
-    BaseData, ResultData, SearchResult,
+    BaseData, ResultData,
 };
 
 /// Endpoint to perform a search
@@ -56,21 +56,19 @@ pub async fn direct_ep(
     Ok(HttpResponse::Ok().body(render!(templates::base, base_data).render()))
 }
 
+/// Sets the og tags of sentences. Words and names get the tags of their entry
 fn set_og_tag(base_data: &mut BaseData, query_type: SearchTarget) {
-    let search_result = base_data.site.as_search_result().unwrap();
-    let mut search_res_og = og_tags::TagSet::with_capacity(5);
+    if query_type != SearchTarget::Sentences {
+        return;
+    }
 
-    let title = match query_type {
-        SearchTarget::Kanji => return,
-        SearchTarget::Sentences => "Jotoba sentence".to_string(),
-        SearchTarget::Names => format!("{} - Jotoba name", search_res_val(&search_result).unwrap()),
-        SearchTarget::Words => format!("{} - Jotoba word", search_res_val(&search_result).unwrap()),
-    };
+    let mut search_res_og = og_tags::TagSet::with_capacity(5);
 
+    let title = "Jotoba sentence";
     let descrption = "Jotoba entry. See more...";
 
-    search_res_og.add_og(TagKeyName::Title, &title);
-    search_res_og.add_twitter(TagKeyName::Title, &title);
+    search_res_og.add_og(TagKeyName::Title, title);
+    search_res_og.add_twitter(TagKeyName::Title, title);
     search_res_og.add_og(TagKeyName::Description, descrption);
     search_res_og.add_twitter(TagKeyName::Description, descrption);
     search_res_og.add_twitter(TagKeyName::Card, "summary");
@@ -78,14 +76,6 @@ fn set_og_tag(base_data: &mut BaseData, query_type: SearchTarget) {
     base_data.set_og_tags(search_res_og);
 }
 
-fn search_res_val(res: &SearchResult) -> Option<String> {
-    Some(match &res.result {
-        ResultData::Word(w) => w.items[0].get_reading().reading.clone(),
-        ResultData::Name(n) => n[0].kanji.as_ref().unwrap_or(&n[0].kana).to_string(),
-        _ => return None,
-    })
-}
-
 /// Find direct word
 pub async fn find_direct_word(id: &str, settings: &UserSettings) -> Result<ResultData, Error> {
     let sequence_id: u32 = id.parse().map_err(|_| Error::NotFound)?;
@@ -131,7 +121,11 @@ pub async fn find_direct_name(id: &str) -> Result<ResultData, Error> {
         .by_sequence(sequence_id)
         .ok_or(web_error::Error::NotFound)?;
 
-    Ok(ResultData::Name(vec![res_word]))
+    use search::executor::search_result::SearchResult as SearchResult2;
+    Ok(ResultData::Name(SearchResult2::with_other_default(
+        vec![res_word],
+        1,
+    )))
 }
 
 /// Find direct sentence
@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use config::Config;
+use localization::TranslationDict;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use search::{query::parser::QueryParser, SearchExecutor};
+use serde::Deserialize;
+use types::jotoba::search::SearchTarget;
+
+use crate::{templates, user_settings, web_error, BaseData};
+
+/// Max amount of senses shown in the widget
+pub const MAX_SENSES: usize = 3;
+
+#[derive(Deserialize)]
+pub struct EmbedQuery {
+    /// The word to look up
+    #[serde(default)]
+    pub q: String,
+}
+
+/// Minimal page showing the best matching word of a lookup, meant to be embedded in iframes
+pub async fn embed(
+    query: web::Query<EmbedQuery>,
+    locale_dict: web::Data<Arc<TranslationDict>>,
+    config: web::Data<Config>,
+    request: HttpRequest,
+) -> Result<HttpResponse, web_error::Error> {
+    let settings = user_settings::parse(&request);
+    let lookup = query.into_inner().q.trim().to_string();
+
+    let query = QueryParser::new(lookup.clone(), SearchTarget::Words, settings)
+        .with_page(1)
+        .parse();
+
+    let word = match query {
+        Some(query) => {
+            web::block(move || {
+                let search = search::word::Search::new(&query);
+                SearchExecutor::new(search).run().items.into_iter().next()
+            })
+            .await?
+        }
+        None => None,
+    };
+
+    // Link to the full search result
+    let search_url = format!("/search/{}", utf8_percent_encode(&lookup, NON_ALPHANUMERIC));
+
+    let base_data = BaseData::new(&locale_dict, settings, &config.asset_hash, &config);
+    Ok(HttpResponse::Ok().body(render!(templates::embed, &base_data, &search_url, word).render()))
+}
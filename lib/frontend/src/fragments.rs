@@ -0,0 +1,99 @@
+//! Endpoints rendering heavy sections of word result pages as HTML fragments. Result pages load
+//! them after they got displayed, so these sections don't delay the results
+
+use std::sync::Arc;
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use config::Config;
+use localization::TranslationDict;
+use serde::Deserialize;
+use types::jotoba::words::Word;
+
+use crate::{
+    templ_utils::example_sentence,
+    templates, user_settings,
+    web_error::{self, Error},
+    BaseData,
+};
+
+/// Max amount of IDs a single fragment can be requested for
+const MAX_IDS: usize = 100;
+
+#[derive(Deserialize)]
+pub struct IdList {
+    /// Comma separated IDs
+    #[serde(default)]
+    pub ids: String,
+}
+
+impl IdList {
+    /// Returns the valid IDs of the list
+    fn parse(&self) -> Vec<u32> {
+        self.ids
+            .split(',')
+            .filter_map(|i| i.trim().parse().ok())
+            .take(MAX_IDS)
+            .collect()
+    }
+}
+
+/// Kanji of the words with the given sequence IDs
+pub async fn kanji(
+    query: web::Query<IdList>,
+    locale_dict: web::Data<Arc<TranslationDict>>,
+    config: web::Data<Config>,
+    request: HttpRequest,
+) -> Result<HttpResponse, web_error::Error> {
+    let settings = user_settings::parse(&request);
+
+    let word_storage = resources::get().words();
+    let words: Vec<Word> = query
+        .parse()
+        .into_iter()
+        .filter_map(|i| word_storage.by_sequence(i).cloned())
+        .collect();
+
+    let kanji = web::block(move || search::word::kanji::load_word_kanji_info(&words)).await?;
+
+    let data = BaseData::new(&locale_dict, settings, &config.asset_hash, &config);
+    Ok(HttpResponse::Ok().body(render!(templates::fragments::word_kanji, &data, kanji).render()))
+}
+
+/// Example sentences with the given IDs, translated into the users language
+pub async fn sentences(
+    query: web::Query<IdList>,
+    request: HttpRequest,
+) -> Result<HttpResponse, web_error::Error> {
+    let settings = user_settings::parse(&request);
+
+    let sentences: Vec<_> = query
+        .parse()
+        .into_iter()
+        .filter_map(|id| {
+            let (furigana, translation) = example_sentence(id, &settings.user_lang)?;
+            Some((id, furigana, translation))
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok()
+        .body(render!(templates::fragments::example_sentences, sentences).render()))
+}
+
+/// Collocations of the word with the given sequence ID
+pub async fn collocations(
+    sequence: web::Path<u32>,
+    locale_dict: web::Data<Arc<TranslationDict>>,
+    config: web::Data<Config>,
+    request: HttpRequest,
+) -> Result<HttpResponse, web_error::Error> {
+    let settings = user_settings::parse(&request);
+
+    let word = resources::get()
+        .words()
+        .by_sequence(*sequence)
+        .ok_or(Error::NotFound)?;
+
+    let data = BaseData::new(&locale_dict, settings, &config.asset_hash, &config);
+    Ok(HttpResponse::Ok()
+        .body(render!(templates::fragments::collocation_table, &data, word).render()))
+}
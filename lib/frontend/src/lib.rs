@@ -5,6 +5,8 @@ mod actix_ructe;
 
 pub mod about;
 pub mod direct;
+pub mod embed;
+pub mod fragments;
 pub mod help_page;
 pub mod index;
 pub mod liveness;
@@ -13,6 +15,7 @@ pub mod og_tags;
 pub mod search_ep;
 //pub mod search_help;
 mod session;
+pub mod structured_data;
 pub mod templ_utils;
 pub mod unescaped;
 mod url_query;
@@ -30,13 +33,14 @@ use localization::{
 use news::NewsEntry;
 use og_tags::TagKeyName;
 use search::{executor::search_result::SearchResult as SearchResult2, query::Query};
+use structured_data::Entry;
 
 use search::{kanji::result::Item as KanjiItem, query::UserSettings};
 use types::jotoba::{
     names::Name,
     pagination::Pagination,
     search::{help::SearchHelp, SearchTarget},
-    words::Word,
+    words::{reference::Reference, Word},
 };
 use unescaped::{UnescapedStr, UnescapedString};
 
@@ -49,6 +53,11 @@ pub struct BaseData<'a> {
     pub asset_hash: &'a str,
     pub config: &'a Config,
     pub og_tags: Option<og_tags::TagSet>,
+    /// Whether the page gets rendered for printing
+    pub print: bool,
+    /// Whether heavy sections of result pages get rendered inline instead of being loaded as
+    /// fragments by JS. Set for printing and for searches of clients without JS
+    pub inline_sections: bool,
 }
 
 /// The site to display
@@ -74,7 +83,7 @@ pub struct SearchResult<'a> {
 pub enum ResultData {
     Word(SearchResult2<Word, search::word::result::AddResData>),
     KanjiInfo(Vec<KanjiItem>),
-    Name(Vec<&'static Name>),
+    Name(SearchResult2<&'static Name, search::name::result::ResData>),
     Sentence(SearchResult2<search::sentence::result::Sentence, search::sentence::result::ResData>),
 }
 
@@ -94,6 +103,8 @@ impl<'a> BaseData<'a> {
             asset_hash,
             config,
             og_tags: None,
+            print: false,
+            inline_sections: false,
         }
     }
 
@@ -152,7 +163,7 @@ impl<'a> BaseData<'a> {
             };
         }
 
-        0
+        self.user_settings.default_target().get_type_id()
     }
 
     #[inline]
@@ -166,7 +177,16 @@ impl<'a> BaseData<'a> {
             };
         }
 
-        self.gettext("Words").as_str()
+        self.gettext(self.user_settings.default_target()).as_str()
+    }
+
+    /// Returns the IDs and names of the result sections in the order the user prefers
+    pub fn search_sections(&self) -> Vec<(u8, UnescapedStr<'a>)> {
+        self.user_settings
+            .section_order
+            .iter()
+            .map(|i| (i.get_type_id(), self.gettext(*i)))
+            .collect()
     }
 
     #[inline]
@@ -215,6 +235,13 @@ impl<'a> BaseData<'a> {
         self.pagination.as_ref().map(|i| i.get_last()).unwrap_or(0) > 1
     }
 
+    /// Returns the URL reproducing the current search result view
+    #[inline]
+    pub fn share_url(&self) -> Option<String> {
+        let query = self.site.as_search_result()?.query;
+        Some(url_query::share_url(query))
+    }
+
     /// Sets og tags which will overwrite the site-defaults if existing
     pub fn set_og_tags(&mut self, tags: og_tags::TagSet) {
         self.og_tags = Some(tags);
@@ -227,6 +254,12 @@ impl<'a> BaseData<'a> {
         }
         self.site.og_tags()
     }
+
+    /// Returns the schema.org JSON-LD of the displayed entry, if the site displays a single entry
+    pub fn get_structured_data(&self) -> Option<UnescapedString> {
+        let entry = self.site.as_search_result()?.entry()?;
+        Some(entry.json_ld())
+    }
 }
 
 impl<'a> Site<'a> {
@@ -255,7 +288,7 @@ impl ResultData {
         match self {
             ResultData::Word(w) => w.items.is_empty() && w.sentence.is_none(),
             ResultData::KanjiInfo(k) => k.is_empty(),
-            ResultData::Name(n) => n.is_empty(),
+            ResultData::Name(n) => n.items.is_empty() && n.transliterations.is_empty(),
             ResultData::Sentence(s) => s.items.is_empty(),
         }
     }
@@ -263,6 +296,10 @@ impl ResultData {
 
 impl<'a> SearchResult<'a> {
     pub fn og_tags(&self) -> og_tags::TagSet {
+        if let Some(entry) = self.entry() {
+            return entry.og_tags();
+        }
+
         let mut tags = og_tags::TagSet::with_capacity(5);
 
         let search_type_name = self.search_type_ogg();
@@ -279,6 +316,25 @@ impl<'a> SearchResult<'a> {
         tags
     }
 
+    /// Returns the only item of the result if it consists of a single word, kanji or name
+    pub fn entry(&self) -> Option<Entry> {
+        match &self.result {
+            ResultData::Word(w) if w.sentence.is_none() => match w.items.as_slice() {
+                [word] => Some(Entry::Word(word)),
+                _ => None,
+            },
+            ResultData::KanjiInfo(k) => match k.as_slice() {
+                [item] => Some(Entry::Kanji(&item.kanji)),
+                _ => None,
+            },
+            ResultData::Name(n) => match n.items.as_slice() {
+                [name] => Some(Entry::Name(name)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
     pub(crate) fn og_tag_description(&self) -> String {
         format!("{} results. See more...", self.result_count())
     }
@@ -296,7 +352,7 @@ impl<'a> SearchResult<'a> {
         match &self.result {
             ResultData::Word(w) => w.items.len(),
             ResultData::KanjiInfo(k) => k.len(),
-            ResultData::Name(n) => n.len(),
+            ResultData::Name(n) => n.items.len(),
             ResultData::Sentence(s) => s.items.len(),
         }
     }
@@ -412,6 +468,23 @@ impl<'a> BaseData<'a> {
             .into()
     }
 
+    /// Like [`Self::gt_search_link`] but links to the referenced word directly if `reference`
+    /// could be resolved
+    #[inline]
+    pub fn gt_reference_link<T: Translatable>(
+        &self,
+        t: T,
+        value: &str,
+        reference: Option<&Reference>,
+    ) -> UnescapedString {
+        let link = match reference {
+            Some(reference) => format_reference_link(value, reference),
+            None => format_search_link(value),
+        };
+        t.gettext_fmt(&self.dict, &[link], Some(self.get_lang()))
+            .into()
+    }
+
     #[inline]
     pub fn gt_search_links<T: Translatable, V: Display + Sized + Clone>(
         &self,
@@ -440,6 +513,19 @@ impl<'a> BaseData<'a> {
     }
 }
 
+/// Formats a link to the word referenced by `reference`. References to a specific sense show its
+/// number, eg. "開ける (2)"
+fn format_reference_link(text: &str, reference: &Reference) -> String {
+    let text = match reference.target_sense {
+        Some(nr) => format!("{text} ({nr})"),
+        None => text.to_string(),
+    };
+    format!(
+        "<a class='clickable no-align green' href='/direct/0/{}'>{}</a>",
+        reference.target, text
+    )
+}
+
 fn format_search_link<V: Display + Sized + Clone>(input: V) -> String {
     format!(
         "<a class='clickable no-align green' href='/search/{}'>{}</a>",
@@ -1,9 +1,95 @@
-use actix_web::HttpResponse;
+use std::path::Path;
 
-pub async fn ready() -> HttpResponse {
-    HttpResponse::Ok().finish()
+use actix_web::{web, HttpResponse};
+use config::Config;
+use search::warmup::Progress;
+use serde::Serialize;
+
+/// Health probe for load balancers. Only checks the data required for searching, so an instance
+/// without optional data (eg. audio files) is still considered healthy
+pub async fn healthz() -> HttpResponse {
+    let checks = vec![
+        Check::new("resources", resources::is_loaded()),
+        Check::new("indexes", indexes_ready()),
+    ];
+    respond(Status::new(checks))
+}
+
+/// Readiness probe. Checks all dependencies required for serving requests and reports them
+/// individually. Responds with 503 if at least one of them is not available
+pub async fn readyz(config: web::Data<Config>) -> HttpResponse {
+    let warmup = search::warmup::progress();
+    let checks = vec![
+        Check::new("resources", resources::is_loaded()),
+        Check::new("indexes", indexes_ready()),
+        Check::new("suggestions", suggestions_ready()),
+        Check::new("audio", audio_available(&config)),
+        Check::new("warmup", warmup.finished),
+    ];
+
+    let mut status = Status::new(checks);
+    status.warmup = Some(warmup);
+    respond(status)
+}
+
+/// Responds with 503 if `status` is not ok
+fn respond(status: Status) -> HttpResponse {
+    if status.ok {
+        HttpResponse::Ok().json(status)
+    } else {
+        HttpResponse::ServiceUnavailable().json(status)
+    }
+}
+
+/// Overall health status along with the results of all executed checks
+#[derive(Serialize)]
+struct Status {
+    ok: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    checks: Vec<Check>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    warmup: Option<Progress>,
+}
+
+impl Status {
+    fn new(checks: Vec<Check>) -> Self {
+        let ok = checks.iter().all(|i| i.ok);
+        Self {
+            ok,
+            checks,
+            warmup: None,
+        }
+    }
+}
+
+/// Result of a single dependency check
+#[derive(Serialize)]
+struct Check {
+    name: &'static str,
+    ok: bool,
+}
+
+impl Check {
+    #[inline]
+    fn new(name: &'static str, ok: bool) -> Self {
+        Self { name, ok }
+    }
+}
+
+#[inline]
+fn indexes_ready() -> bool {
+    indexes::storage::is_loaded() && indexes::get().check()
+}
+
+#[inline]
+fn suggestions_ready() -> bool {
+    indexes::storage::suggestions::is_loaded() && indexes::get_suggestions().check()
 }
 
-pub async fn healthy() -> HttpResponse {
-    HttpResponse::Ok().finish()
+/// Returns `true` if the audio folder exists and contains the mp3 files or audio gets synthesized
+fn audio_available(config: &Config) -> bool {
+    config.server.tts_enabled()
+        || Path::new(config.server.get_audio_files())
+            .join("mp3")
+            .is_dir()
 }
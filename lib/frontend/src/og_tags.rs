@@ -109,7 +109,13 @@ impl Tag {
             TagKey::Og(og) => format!("property=\"og:{}\"", og.as_ref()),
             TagKey::Twitter(twitter) => format!("property=\"twitter:{}\"", twitter.as_ref()),
         };
-        format!("<meta {key_attr} content=\"{}\"/>", self.value)
+        let value = self
+            .value
+            .replace('&', "&amp;")
+            .replace('"', "&quot;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;");
+        format!("<meta {key_attr} content=\"{value}\"/>")
     }
 }
 
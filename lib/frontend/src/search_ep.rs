@@ -26,7 +26,7 @@ pub async fn search_ep_no_js(
     request: HttpRequest,
 ) -> Result<HttpResponse, web_error::Error> {
     let (query_data, query) = query_data.0.to_query_struct();
-    search(query, query_data, locale_dict, config, request).await
+    search(query, query_data, true, locale_dict, config, request).await
 }
 
 /// Endpoint to perform a search
@@ -38,22 +38,28 @@ pub async fn search_ep(
     request: HttpRequest,
 ) -> Result<HttpResponse, web_error::Error> {
     let query = percent_decode(query.as_bytes()).decode_utf8()?.to_string();
-    search(query, query_data.0, locale_dict, config, request).await
+    search(query, query_data.0, false, locale_dict, config, request).await
 }
 
+/// Performs a search and renders its results. Searches of the no-JS form get their heavy sections
+/// rendered inline, since they can't be loaded as fragments
 async fn search(
     query: String,
     query_data: QueryStruct,
+    no_js: bool,
     locale_dict: web::Data<Arc<TranslationDict>>,
     config: web::Data<Config>,
     request: HttpRequest,
 ) -> Result<HttpResponse, web_error::Error> {
-    let settings = user_settings::parse(&request);
+    let mut settings = user_settings::parse(&request);
+    query_data.overwrite_settings(&mut settings);
+    let print = query_data.print;
 
     // Parse query and redirect to home on error
     let query = match query_data
         .adjust(query.to_string())
         .as_query_parser(settings)
+        .with_filter_presets(user_settings::filter_presets(&request))
         .parse()
     {
         Some(k) => k,
@@ -63,7 +69,10 @@ async fn search(
     let start = Instant::now();
 
     // Log search duration if too long and available
-    let search_result = do_search(query.target, &locale_dict, settings, &query, &config).await?;
+    let mut search_result =
+        do_search(query.target, &locale_dict, settings, &query, &config).await?;
+    search_result.print = print;
+    search_result.inline_sections = print || no_js;
 
     log::debug!(
         "{:?} search for {:?} took {:?}",
@@ -141,7 +150,7 @@ async fn name_search<'a>(base_data: &mut BaseData<'a>, query: &'a Query) -> SRes
     .await?;
 
     base_data.with_pages(result.total as u32, query.page as u32);
-    Ok(ResultData::Name(result.items))
+    Ok(ResultData::Name(result))
 }
 
 /// Perform a word search
@@ -0,0 +1,132 @@
+//! OpenGraph tags and schema.org `DefinedTerm` JSON-LD of entry pages, showing the reading and the
+//! first gloss of a word, kanji or name in shared links and search engines
+
+use crate::{
+    og_tags::{TagKeyName, TagSet},
+    unescaped::UnescapedString,
+};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use serde_json::json;
+use types::jotoba::{kanji::Kanji, names::Name, words::Word};
+
+/// URL of the site, prefixed to all URLs of entries
+pub const BASE_URL: &str = "https://jotoba.de";
+
+/// A single word, kanji or name displayed on its own page
+#[derive(Clone, Copy)]
+pub enum Entry<'a> {
+    Word(&'a Word),
+    Kanji(&'a Kanji),
+    Name(&'a Name),
+}
+
+impl<'a> Entry<'a> {
+    /// Returns the written form of the entry
+    pub fn term(&self) -> String {
+        match self {
+            Entry::Word(word) => word.get_reading().reading.clone(),
+            Entry::Kanji(kanji) => kanji.literal.to_string(),
+            Entry::Name(name) => name.get_reading().to_string(),
+        }
+    }
+
+    /// Returns the reading of the entry. This is the kana reading for words and names and the
+    /// first on or kun reading for kanji
+    pub fn reading(&self) -> Option<&'a str> {
+        match self {
+            Entry::Word(word) => Some(word.get_kana()),
+            Entry::Kanji(kanji) => kanji
+                .onyomi
+                .first()
+                .or_else(|| kanji.kunyomi.first())
+                .map(|i| i.as_str()),
+            Entry::Name(name) => Some(name.kana.as_str()),
+        }
+    }
+
+    /// Returns the first gloss of the entry
+    pub fn gloss(&self) -> Option<&'a str> {
+        match self {
+            Entry::Word(word) => {
+                let sense = word.senses.first()?;
+                Some(sense.glosses.first()?.gloss.as_str())
+            }
+            Entry::Kanji(kanji) => kanji.meanings.first().map(|i| i.as_str()),
+            Entry::Name(name) => Some(name.transcription.as_str()),
+        }
+    }
+
+    /// Returns the absolute URL of the page of the entry
+    pub fn url(&self) -> String {
+        match self {
+            Entry::Word(word) => format!("{BASE_URL}/direct/0/{}", word.sequence),
+            Entry::Kanji(kanji) => {
+                let literal = kanji.literal.to_string();
+                let literal = utf8_percent_encode(&literal, NON_ALPHANUMERIC);
+                format!("{BASE_URL}/search/{literal}?t=1")
+            }
+            Entry::Name(name) => format!("{BASE_URL}/direct/3/{}", name.sequence),
+        }
+    }
+
+    /// Returns the page title of the entry, eg. `食べる - Jotoba word`
+    pub fn title(&self) -> String {
+        let kind = match self {
+            Entry::Word(_) => "word",
+            Entry::Kanji(_) => "kanji",
+            Entry::Name(_) => "name",
+        };
+        format!("{} - Jotoba {kind}", self.term())
+    }
+
+    /// Returns a short description of the entry made of its reading and first gloss, eg.
+    /// `【たべる】 to eat`
+    pub fn description(&self) -> String {
+        let reading = self
+            .reading()
+            .filter(|i| *i != self.term())
+            .map(|i| format!("【{i}】 "))
+            .unwrap_or_default();
+        let gloss = self.gloss().unwrap_or("See more...");
+        format!("{reading}{gloss}")
+    }
+
+    /// Returns the OpenGraph and Twitter card tags of the entry
+    pub fn og_tags(&self) -> TagSet {
+        let mut tags = TagSet::with_capacity(8);
+
+        let title = self.title();
+        let description = self.description();
+
+        tags.add_og(TagKeyName::Title, &title);
+        tags.add_og(TagKeyName::Description, &description);
+        tags.add_og(TagKeyName::Type, "website");
+        tags.add_og(TagKeyName::URL, self.url());
+        tags.add_twitter(TagKeyName::Title, &title);
+        tags.add_twitter(TagKeyName::Description, &description);
+        tags.add_twitter(TagKeyName::Card, "summary");
+
+        tags
+    }
+
+    /// Renders the schema.org `DefinedTerm` of the entry as JSON-LD
+    pub fn json_ld(&self) -> UnescapedString {
+        let mut term = json!({
+            "@context": "https://schema.org",
+            "@type": "DefinedTerm",
+            "name": self.term(),
+            "url": self.url(),
+            "inDefinedTermSet": BASE_URL,
+        });
+
+        if let Some(reading) = self.reading().filter(|i| *i != self.term()) {
+            term["alternateName"] = reading.into();
+        }
+        if let Some(gloss) = self.gloss() {
+            term["description"] = gloss.into();
+        }
+
+        // Prevent glosses from closing the surrounding script tag
+        term.to_string().replace("</", "<\\/").into()
+    }
+}
@@ -1,38 +1,46 @@
+use indexes::kanji::reading_freq::Reading;
 use itertools::Itertools;
-use jp_utils::furigana::{self, reading_part_ref::ReadingPartRef};
+use jp_utils::furigana::{self, as_part::AsPart, reading_part_ref::ReadingPartRef};
 use localization::{traits::Translatable, TranslationDict};
-use search::executor::search_result::SearchResult;
+use search::{executor::search_result::SearchResult, query::UserSettings};
 use types::jotoba::{
     kanji::Kanji,
     language::{param::AsLangParam, Language},
-    names::Name,
-    words::{filter_languages, sense::Sense, Word},
+    names::{alias::Alias, Name},
+    words::{
+        filter_languages,
+        reference::{Reference, ReferenceKind},
+        sense::Sense,
+        Word,
+    },
 };
 
 use crate::unescaped::UnescapedString;
 
-/// Returns a list of all collocations of a word
-pub fn get_collocations(word: &Word, lang: impl AsLangParam) -> Vec<(String, String)> {
+/// Max amount of collocations to display for a word
+pub const MAX_COLLOCATIONS: usize = 10;
+
+/// Max amount of readings to display in the reading statistics of a kanji
+pub const MAX_READING_STATS: usize = 5;
+
+/// Returns a list of the most frequent collocations of a word along with their corpus frequency
+pub fn get_collocations(word: &Word, lang: impl AsLangParam) -> Vec<(String, String, Option<u32>)> {
     if !word.has_collocations() {
         return vec![];
     }
 
-    let word_storage = resources::get().words();
-
-    let mut words = word
-        .collocations
-        .as_ref()
-        .unwrap()
-        .iter()
-        .filter_map(|i| word_storage.by_sequence(*i))
-        .cloned()
+    let mut words = resources::get()
+        .words()
+        .collocations(word, MAX_COLLOCATIONS)
+        .into_iter()
+        .map(|(word, freq)| (word.clone(), freq))
         .collect::<Vec<_>>();
 
-    filter_languages(words.iter_mut(), lang);
+    filter_languages(words.iter_mut().map(|i| &mut i.0), lang);
 
     words
         .into_iter()
-        .map(|word| {
+        .map(|(word, freq)| {
             let senses: Vec<String> = word
                 .get_senses_with_en()
                 .into_iter()
@@ -45,11 +53,95 @@ pub fn get_collocations(word: &Word, lang: impl AsLangParam) -> Vec<(String, Str
 
             let reading = word.reading.kanji.unwrap_or(word.reading.kana).reading;
 
-            (reading, senses.join(", "))
+            (reading, senses.join(", "), freq)
         })
         .collect()
 }
 
+/// Returns words which are written and read the same way as `name` along with their glosses
+pub fn get_name_words(name: &Name, lang: impl AsLangParam) -> Vec<(String, String)> {
+    let word_storage = resources::get().words();
+
+    let mut words = resources::get()
+        .names()
+        .linked_words(name.sequence)
+        .iter()
+        .filter_map(|i| word_storage.by_sequence(*i))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    filter_languages(words.iter_mut(), lang);
+
+    words
+        .into_iter()
+        .map(|word| (word.get_reading_str().to_string(), word.glosses_pretty()))
+        .collect()
+}
+
+/// Returns historical and alternative names of `name`
+#[inline]
+pub fn get_name_aliases(name: &Name) -> &'static [Alias] {
+    resources::get().names().aliases(name.sequence)
+}
+
+/// Returns names which are written and read the same way as `word`
+pub fn get_word_names(word: &Word) -> Vec<&'static Name> {
+    let name_storage = resources::get().names();
+
+    resources::get()
+        .words()
+        .linked_names(word.sequence)
+        .iter()
+        .filter_map(|i| name_storage.by_sequence(*i))
+        .collect()
+}
+
+/// Returns the resolved reference of `kind` of `sense` of `word`, if any
+#[inline]
+pub fn get_sense_reference(
+    word: &Word,
+    sense: &Sense,
+    kind: ReferenceKind,
+) -> Option<&'static Reference> {
+    resources::get()
+        .words()
+        .sense_reference(word.sequence, sense.id, kind)
+}
+
+/// Returns the WaniKani level of `word` if the user wants to see WaniKani levels
+#[inline]
+pub fn get_word_wk_level(word: &Word, settings: &UserSettings) -> Option<u8> {
+    if !settings.show_wanikani {
+        return None;
+    }
+    resources::get().wanikani().word_level(word.sequence)
+}
+
+/// Returns the WaniKani level of the kanji `literal` if the user wants to see WaniKani levels
+#[inline]
+pub fn get_kanji_wk_level(literal: char, settings: &UserSettings) -> Option<u8> {
+    if !settings.show_wanikani {
+        return None;
+    }
+    resources::get().wanikani().kanji_level(literal)
+}
+
+/// Returns the readings of the kanji `literal` most commonly used within words along with the
+/// percentage of words using them
+pub fn get_kanji_reading_stats(literal: char) -> Vec<(&'static Reading, u32)> {
+    let freq_data = match indexes::get().kanji().reading_freq().get(literal) {
+        Some(freq_data) => freq_data,
+        None => return vec![],
+    };
+
+    freq_data
+        .most_common()
+        .into_iter()
+        .take(MAX_READING_STATS)
+        .map(|i| (i, (i.count as usize * 100 / freq_data.total()) as u32))
+        .collect()
+}
+
 #[inline]
 pub fn unescaped_string<T: ToString>(s: T) -> UnescapedString {
     UnescapedString::new(s)
@@ -71,13 +163,21 @@ pub fn get_intransitive_counterpart(word: &Word) -> Option<Word> {
 
 /// Returns an example sentences of a `sense` if existing.
 /// tries to use a sentence written in `language` or falls back to english
+#[inline]
 pub fn ext_sentence(
     sense: &Sense,
     language: &Language,
 ) -> Option<(Vec<ReadingPartRef<'static>>, &'static str)> {
-    let sentence = resources::get()
-        .sentences()
-        .by_id(sense.example_sentence?)?;
+    example_sentence(sense.example_sentence?, language)
+}
+
+/// Returns the sentence with the ID `id` along with its translation into `language`, falling
+/// back to english
+pub fn example_sentence(
+    id: u32,
+    language: &Language,
+) -> Option<(Vec<ReadingPartRef<'static>>, &'static str)> {
+    let sentence = resources::get().sentences().by_id(id)?;
 
     let translation = sentence
         .translation_for(*language)
@@ -87,6 +187,45 @@ pub fn ext_sentence(
     Some((furigana, translation))
 }
 
+/// A piece of furigana text rendered as one ruby element. `reading` is `None` for kana
+pub struct RubySegment<'a> {
+    pub text: &'a str,
+    pub reading: Option<&'a str>,
+}
+
+/// Splits furigana into the segments of its parts, so readings which are stored for single kanji
+/// are aligned over their kanji, eg. `[振|ふ]り[仮|が][名|な]` into `振|ふ`, `り`, `仮|が` and
+/// `名|な`. Blocks which aren't split in the furigana, like `今日|きょう`, stay a single segment
+pub fn ruby_segments<'a>(parts: &'a [ReadingPartRef<'a>]) -> Vec<RubySegment<'a>> {
+    parts
+        .iter()
+        .filter(|i| !i.is_empty())
+        .flat_map(|part| {
+            part.reading_iter().map(move |(text, reading)| RubySegment {
+                text,
+                reading: reading.filter(|_| part.is_kanji()),
+            })
+        })
+        .collect()
+}
+
+/// Returns the comma separated IDs of the example sentences of `words` or `None` if they don't
+/// have any. Used to load the sentences after the result page got displayed
+pub fn example_sentence_ids(words: &[Word]) -> Option<String> {
+    let ids = words
+        .iter()
+        .flat_map(|i| i.senses.iter())
+        .filter_map(|i| i.example_sentence)
+        .unique()
+        .join(",");
+    (!ids.is_empty()).then(|| ids)
+}
+
+/// Returns the comma separated sequence IDs of `words`
+pub fn word_sequences(words: &[Word]) -> String {
+    words.iter().map(|i| i.sequence).join(",")
+}
+
 pub fn get_types_humanized(
     name: &Name,
     dict: &TranslationDict,
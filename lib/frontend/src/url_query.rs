@@ -1,8 +1,9 @@
 use std::str::FromStr;
 
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use search::{
     self,
-    query::{parser::QueryParser, UserSettings},
+    query::{parser::QueryParser, user_settings::MAX_PAGE_SIZE, Query, UserSettings},
 };
 use serde::{Deserialize, Deserializer};
 use types::jotoba::{language::Language, search::SearchTarget};
@@ -19,13 +20,20 @@ pub struct QueryStruct {
     #[serde(default, rename = "l", deserialize_with = "deserialize_lang")]
     pub lang_overwrite: Option<Language>,
 
+    /// Overwrites the page size of the user settings
+    #[serde(rename = "ps")]
+    pub page_size: Option<u32>,
+
+    /// Render the results for printing
+    #[serde(default)]
+    pub print: bool,
+
     #[serde(skip)]
     pub query_str: String,
 }
 
 impl QueryStruct {
-    /// Adjusts the search query trim and map empty search queries to Option::None.
-    /// Ensures `search_type` is always 'Some()'
+    /// Adjusts the search query trim and map empty search queries to Option::None
     pub fn adjust(&self, query_str: String) -> Self {
         let query_str = query_str.trim().to_string();
 
@@ -37,23 +45,32 @@ impl QueryStruct {
 
         QueryStruct {
             query_str,
-            search_type: Some(self.search_type.unwrap_or_default()),
+            search_type: self.search_type,
             page,
             word_index: self.word_index,
             lang_overwrite: self.lang_overwrite,
+            page_size: self.page_size,
+            print: self.print,
+        }
+    }
+
+    /// Applies the settings overwritten by the query to `settings`
+    pub fn overwrite_settings(&self, settings: &mut UserSettings) {
+        if let Some(page_size) = self.page_size {
+            settings.page_size = page_size.clamp(1, MAX_PAGE_SIZE);
         }
     }
 
-    /// Returns a [`QueryParser`] of the query
+    /// Returns a [`QueryParser`] of the query. Queries without a search type search the first
+    /// result section of the users section order
     #[inline]
     pub fn as_query_parser(&self, user_settings: UserSettings) -> QueryParser {
-        let mut q_parser = QueryParser::new(
-            self.query_str.clone(),
-            self.search_type.unwrap_or_default(),
-            user_settings,
-        )
-        .with_page(self.page)
-        .with_word_index(self.word_index.unwrap_or_default());
+        let target = self
+            .search_type
+            .unwrap_or_else(|| user_settings.default_target());
+        let mut q_parser = QueryParser::new(self.query_str.clone(), target, user_settings)
+            .with_page(self.page)
+            .with_word_index(self.word_index.unwrap_or_default());
 
         if let Some(lang) = self.lang_overwrite {
             q_parser = q_parser.with_lang_overwrite(lang);
@@ -63,6 +80,28 @@ impl QueryStruct {
     }
 }
 
+/// Returns the URL of the result view of `query`. It encodes the whole query state, including
+/// tags, the search language, the page and the page size, so opening it shows the same results
+/// for everyone
+pub fn share_url(query: &Query) -> String {
+    let lang = query.cust_lang.unwrap_or(query.settings.user_lang);
+
+    let mut url = format!(
+        "/search/{}?t={}&l={}&p={}&ps={}",
+        utf8_percent_encode(&query.raw_query, NON_ALPHANUMERIC),
+        query.target.get_type_id(),
+        lang.as_ref(),
+        query.page.max(1),
+        query.settings.page_size
+    );
+
+    if query.word_index > 0 {
+        url.push_str(&format!("&i={}", query.word_index));
+    }
+
+    url
+}
+
 #[inline]
 fn default_page() -> usize {
     1
@@ -83,6 +122,12 @@ pub struct NoJSQueryStruct {
 
     #[serde(default, rename = "l", deserialize_with = "deserialize_lang")]
     pub lang_overwrite: Option<Language>,
+
+    #[serde(rename = "ps")]
+    pub page_size: Option<u32>,
+
+    #[serde(default)]
+    pub print: bool,
 }
 
 impl NoJSQueryStruct {
@@ -94,6 +139,8 @@ impl NoJSQueryStruct {
             search_type: self.search_type,
             query_str: String::new(),
             lang_overwrite: self.lang_overwrite,
+            page_size: self.page_size,
+            print: self.print,
         };
 
         (query_struct, self.query)
@@ -1,55 +1,23 @@
-use std::str::FromStr;
-
 use actix_web::HttpRequest;
+use percent_encoding::percent_decode_str;
 use search::query::UserSettings;
-use types::jotoba::language::Language;
+use types::jotoba::search::filter_preset::FilterPreset;
 
-/// Parses user settings from a `HttpRequest`
+/// Parses user settings from a `HttpRequest`. Cookie values are percent-encoded by the frontend
+/// (eg. commas of lists as `%2C`)
+#[inline]
 pub(super) fn parse(request: &HttpRequest) -> UserSettings {
-    let show_english = request
-        .cookie("show_english")
-        .and_then(|i| i.value().parse().ok())
-        .unwrap_or_else(|| UserSettings::default().show_english);
-
-    let user_lang = request
-        .cookie("default_lang")
-        .and_then(|i| Language::from_str(i.value()).ok())
-        .unwrap_or_default();
-
-    let page_lang = request
-        .cookie("page_lang")
-        .and_then(|i| localization::language::Language::from_str(i.value()).ok())
-        .unwrap_or_default();
-
-    let english_on_top = request
-        .cookie("show_english_on_top")
-        .and_then(|i| i.value().parse().ok())
-        .unwrap_or_else(|| UserSettings::default().english_on_top)
-        && show_english;
-
-    let items_per_page = request
-        .cookie("items_per_page")
-        .and_then(|i| i.value().parse().ok())
-        .unwrap_or_else(|| UserSettings::default().page_size);
-
-    let example_sentences_enabled = request
-        .cookie("show_sentences")
-        .and_then(|i| Some(i.value() == "true"))
-        .unwrap_or_else(|| UserSettings::default().show_example_sentences);
-
-    let sentence_furigana = request
-        .cookie("sentence_furigana")
-        .and_then(|i| Some(i.value() == "true"))
-        .unwrap_or_else(|| UserSettings::default().sentence_furigana);
+    UserSettings::from_values(|key| {
+        let cookie = request.cookie(key)?;
+        let value = percent_decode_str(cookie.value()).decode_utf8_lossy();
+        Some(value.to_string())
+    })
+}
 
-    UserSettings {
-        user_lang,
-        show_english,
-        english_on_top,
-        page_lang,
-        page_size: items_per_page,
-        show_example_sentences: example_sentences_enabled,
-        sentence_furigana,
-        ..Default::default()
-    }
+/// Parses the users filter presets from a `HttpRequest`
+pub(super) fn filter_presets(request: &HttpRequest) -> Vec<FilterPreset> {
+    request
+        .cookie("filter_presets")
+        .map(|i| FilterPreset::parse_list(&percent_decode_str(i.value()).decode_utf8_lossy()))
+        .unwrap_or_default()
 }
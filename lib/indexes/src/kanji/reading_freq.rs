@@ -17,11 +17,45 @@ pub struct FreqData {
 pub struct Reading {
     pub reading: String,
     pub count: u32,
+    /// Occurrences by [`Position`] within words
+    pub positions: [u32; 3],
+}
+
+/// Position of a kanji within a word
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Position {
+    Initial,
+    Medial,
+    Final,
+}
+
+impl Position {
+    /// Returns the position of a kanji spanning the characters `start..end` of a word with `len`
+    /// characters. Kanji at the start are initial, even if the word consists of only them
+    pub fn new(start: usize, end: usize, len: usize) -> Self {
+        if start == 0 {
+            Self::Initial
+        } else if end >= len {
+            Self::Final
+        } else {
+            Self::Medial
+        }
+    }
 }
 
 impl Reading {
     pub fn new(reading: String, count: u32) -> Self {
-        Self { reading, count }
+        Self {
+            reading,
+            count,
+            positions: [0; 3],
+        }
+    }
+
+    /// Returns how often the reading is used at `position` within words
+    #[inline]
+    pub fn at(&self, position: Position) -> u32 {
+        self.positions[position as usize]
     }
 }
 
@@ -39,6 +73,22 @@ impl FreqData {
         self.total
     }
 
+    /// Counts an occurrence of the reading at `index` at `position` within a word
+    #[inline]
+    pub fn count(&mut self, index: usize, position: Position) {
+        let reading = &mut self.readings[index];
+        reading.count += 1;
+        reading.positions[position as usize] += 1;
+        self.total += 1;
+    }
+
+    /// Returns the used readings, the most common first
+    pub fn most_common(&self) -> Vec<&Reading> {
+        let mut readings: Vec<_> = self.readings.iter().filter(|i| i.count > 0).collect();
+        readings.sort_by(|a, b| b.count.cmp(&a.count));
+        readings
+    }
+
     #[inline]
     pub fn get_reading<S: AsRef<str>>(&self, r: S) -> Option<u32> {
         self.readings
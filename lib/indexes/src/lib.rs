@@ -6,6 +6,7 @@ pub mod radical;
 pub mod regex;
 pub mod sentences;
 pub mod storage;
+pub mod substring;
 pub mod term_freq;
 pub mod words;
 
@@ -3,3 +3,13 @@ pub type ForeignIndex = ngindex::NgramIndex<FOREIGN_NGRAM, u32>;
 
 pub const NATIVE_NGRAM: usize = 3;
 pub type NativeIndex = ngindex::NgramIndex<NATIVE_NGRAM, u32>;
+
+/// Formats a transcription or query for the foreign name index by lowercasing it and replacing
+/// all special characters with spaces
+pub fn format_foreign(inp: &str) -> String {
+    let mut out = inp.to_lowercase();
+    for i in ".,[]() \t\"'\\/-;:".chars() {
+        out = out.replace(i, " ");
+    }
+    out
+}
@@ -1,5 +1,109 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use types::jotoba::language::Language;
 use vsm::presets::VSMIndexSimple;
 
 // Shortcut for type of index
 pub type NativeIndex = VSMIndexSimple<u32>;
 pub type ForeignIndex = VSMIndexSimple<u32>;
+
+/// Index of the sentences which were appended by a sentence sync after the vector indexes have
+/// been generated. Maps the terms of those sentences to their IDs, so they can be found by text
+/// until the vector indexes get regenerated
+#[derive(Serialize, Deserialize, Default)]
+pub struct SyncIndex {
+    native: HashMap<String, Vec<u32>>,
+    foreign: HashMap<Language, HashMap<String, Vec<u32>>>,
+}
+
+impl SyncIndex {
+    /// Creates a new empty index
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the japanese `term` of the sentence `id`
+    pub fn add_native(&mut self, id: u32, term: &str) {
+        push_id(self.native.entry(term.to_string()).or_default(), id);
+    }
+
+    /// Adds the `term` of the translation in `language` of the sentence `id`
+    pub fn add_foreign(&mut self, id: u32, language: Language, term: &str) {
+        let terms = self.foreign.entry(language).or_default();
+        push_id(terms.entry(term.to_string()).or_default(), id);
+    }
+
+    /// Returns the IDs of all sentences containing the japanese `term`
+    #[inline]
+    pub fn native(&self, term: &str) -> &[u32] {
+        self.native
+            .get(term)
+            .map(|i| i.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns the IDs of all sentences with a translation in `language` containing `term`
+    #[inline]
+    pub fn foreign(&self, language: Language, term: &str) -> &[u32] {
+        self.foreign
+            .get(&language)
+            .and_then(|i| i.get(term))
+            .map(|i| i.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Removes the terms of all translations in languages for which `keep` returns `false`
+    pub fn retain_languages<F: Fn(Language) -> bool>(&mut self, keep: F) {
+        self.foreign.retain(|lang, _| keep(*lang));
+    }
+
+    /// Returns `true` if no sentence has been added
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.native.is_empty() && self.foreign.is_empty()
+    }
+}
+
+/// Adds `id` to `ids` unless it's already the last one. Terms of a sentence are added one after
+/// another, so this keeps the IDs unique
+#[inline]
+fn push_id(ids: &mut Vec<u32>, id: u32) {
+    if ids.last() != Some(&id) {
+        ids.push(id);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sync_index() {
+        let mut index = SyncIndex::new();
+        assert!(index.is_empty());
+
+        index.add_native(1, "猫");
+        index.add_native(1, "猫");
+        index.add_native(2, "猫");
+        index.add_foreign(1, Language::English, "cat");
+
+        assert_eq!(index.native("猫"), &[1, 2]);
+        assert_eq!(index.native("犬"), &[] as &[u32]);
+        assert_eq!(index.foreign(Language::English, "cat"), &[1]);
+        assert_eq!(index.foreign(Language::German, "cat"), &[] as &[u32]);
+    }
+
+    #[test]
+    fn test_retain_languages() {
+        let mut index = SyncIndex::new();
+        index.add_native(1, "猫");
+        index.add_foreign(1, Language::English, "cat");
+        index.add_foreign(1, Language::German, "katze");
+
+        index.retain_languages(|i| i == Language::English);
+        assert_eq!(index.native("猫"), &[1]);
+        assert_eq!(index.foreign(Language::English, "cat"), &[1]);
+        assert_eq!(index.foreign(Language::German, "katze"), &[] as &[u32]);
+    }
+}
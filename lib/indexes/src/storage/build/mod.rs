@@ -0,0 +1,307 @@
+//! Builds the indexes from the resources, so they are always written in the format the loaders
+//! expect.
+
+pub mod names;
+pub mod sentences;
+pub mod suggestions;
+pub mod words;
+
+use super::{
+    kanji::K_READINGS_FREQ_FILE,
+    name, sentence,
+    sentence::SYNC_FILE,
+    suggestions::SuggestionStorage,
+    word::{self, FOREIGN_PREFIX, KANJI_READING_INDEX, SUBSTRING_FILE},
+};
+use crate::{
+    kanji::reading_freq::{FrequencyIndex, Position},
+    sentences::SyncIndex,
+    substring::SubstringIndex,
+};
+use index_framework::{backend::memory::build::MemIndexBuilder, traits::build::IndexBuilder};
+use japanese::ToKanaExt;
+use jp_utils::JapaneseExt;
+use ngindex::{builder::NGIndexBuilder, NgramIndex};
+use serde::Serialize;
+use sparse_vec::{SpVec32, VecExt};
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fs::File,
+    io::BufWriter,
+    path::{Path, PathBuf},
+};
+use types::jotoba::{
+    kanji::Kanji, language::Language, names::Name, sentences::Sentence, words::Word,
+};
+use vsm::{dict_term::DictTerm, doc_vec::DocVector, presets::VSMIndexSimple};
+
+/// Resources to build the indexes from
+pub struct Sources<'a> {
+    pub words: &'a [&'a Word],
+    pub kanji: &'a [Kanji],
+    pub names: &'a [&'a Name],
+    pub sentences: &'a [&'a Sentence],
+
+    /// Returns the terms a japanese sentence text is looked up by
+    pub native_terms: &'a dyn Fn(&str) -> HashSet<String>,
+
+    /// Returns the terms a translation of a sentence is looked up by
+    pub foreign_terms: &'a dyn Fn(&str) -> HashSet<String>,
+}
+
+/// Builds all word, kanji, name and sentence indexes from `sources` and writes them into `out`.
+/// Returns the paths of the written files
+pub fn build_all(
+    out: &Path,
+    sources: &Sources,
+) -> Result<Vec<PathBuf>, Box<dyn Error + Send + Sync>> {
+    std::fs::create_dir_all(out)?;
+
+    let word_iter = || sources.words.iter().copied();
+    let substring = substring_index(word_iter());
+    let native = words::native(word_iter());
+    let kreading = words::kanji_reading(sources.kanji, word_iter());
+    let kreading_freq = kanji_reading_freq(sources.kanji, word_iter());
+
+    let mut files = vec![
+        write(out, SUBSTRING_FILE, &substring)?,
+        write(out, word::NATIVE_FILE, &native)?,
+        write(out, KANJI_READING_INDEX, &kreading)?,
+        write(out, K_READINGS_FREQ_FILE, &kreading_freq)?,
+    ];
+
+    for language in Language::iter_word() {
+        let foreign = match words::foreign(word_iter(), language) {
+            Some(foreign) => foreign,
+            None => continue,
+        };
+        let name = format!("{FOREIGN_PREFIX}{}", language.to_query_format());
+        files.push(write(out, &name, &foreign)?);
+    }
+
+    files.extend(write_names(out, sources.names)?);
+
+    let sentence_iter = || sources.sentences.iter().copied();
+    let native = sentences::native(sentence_iter(), sources.native_terms);
+    files.push(write(out, sentence::NATIVE_FILE, &native)?);
+    for language in Language::iter() {
+        let foreign = sentences::foreign(sentence_iter(), language, sources.foreign_terms);
+        let foreign = match foreign {
+            Some(foreign) => foreign,
+            None => continue,
+        };
+        let name = format!("{}{}", sentence::FOREIGN_PREFIX, language.to_query_format());
+        files.push(write(out, &name, &foreign)?);
+    }
+
+    Ok(files)
+}
+
+/// Writes the index of synced sentences into `out`, replacing the previous one
+pub fn write_sync(out: &Path, index: &SyncIndex) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
+    write(out, SYNC_FILE, index)
+}
+
+/// Writes the name search indexes of `names` into `out`, replacing the previous ones
+pub fn write_names(
+    out: &Path,
+    names: &[&Name],
+) -> Result<Vec<PathBuf>, Box<dyn Error + Send + Sync>> {
+    let name_iter = || names.iter().copied();
+    Ok(vec![
+        write(out, name::NATIVE_FILE, &names::native(name_iter()))?,
+        write(out, name::FOREIGN_FILE, &names::foreign(name_iter()))?,
+    ])
+}
+
+/// Writes the suggestion indexes `storage` into the file `path`, replacing the previous ones
+pub fn write_suggestions(
+    path: &Path,
+    storage: &SuggestionStorage,
+) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
+    let out = path.parent().unwrap_or_else(|| Path::new(""));
+    let name = path
+        .file_name()
+        .and_then(|i| i.to_str())
+        .ok_or("Invalid path")?;
+    write(out, name, storage)
+}
+
+/// Builds the character n-gram index over the readings of `words`, used to find regex search
+/// candidates
+pub fn substring_index<'a, W>(words: W) -> SubstringIndex
+where
+    W: Iterator<Item = &'a Word>,
+{
+    let mut index = SubstringIndex::new();
+
+    for word in words {
+        for reading in word.reading_iter(true) {
+            index.add_term(&reading.reading, word.sequence);
+        }
+    }
+
+    index.finish();
+    index
+}
+
+/// Builds the index of how often each reading of a kanji is used within the furigana of `words`
+pub fn kanji_reading_freq<'a, W>(kanji: &[Kanji], words: W) -> FrequencyIndex
+where
+    W: Iterator<Item = &'a Word>,
+{
+    let mut index = FrequencyIndex::new(kanji);
+
+    for furigana in words.filter_map(|i| i.furigana.as_deref()) {
+        let len: usize = jp_utils::furigana::parse::unchecked(furigana)
+            .map(|part| {
+                part.reading_iter()
+                    .map(|(main, _)| main.chars().count())
+                    .sum::<usize>()
+            })
+            .sum();
+
+        let mut offset = 0;
+        for part in jp_utils::furigana::parse::unchecked(furigana) {
+            for (main, reading) in part.reading_iter() {
+                let start = offset;
+                offset += main.chars().count();
+
+                let (literal, reading) = match (single_kanji(main), reading) {
+                    (Some(literal), Some(reading)) => (literal, reading),
+                    _ => continue,
+                };
+
+                let freq_data = match index.data.get_mut(&literal) {
+                    Some(freq_data) => freq_data,
+                    None => continue,
+                };
+
+                let pos = freq_data
+                    .readings
+                    .iter()
+                    .position(|i| reading_matches(&i.reading, reading));
+                if let Some(pos) = pos {
+                    freq_data.count(pos, Position::new(start, offset, len));
+                }
+            }
+        }
+    }
+
+    index
+}
+
+/// Builds a vector space index with `postings` postings over `docs`, each being a document and
+/// all of its terms along with the posting they're mapped in. Terms are weighted equally within a
+/// document, with the document vectors having a length of 1
+fn vsm_index<D>(postings: u32, docs: D) -> VSMIndexSimple<u32>
+where
+    D: IntoIterator<Item = (u32, Vec<(u32, String)>)>,
+{
+    let mut builder = MemIndexBuilder::new(postings);
+    let mut ids: HashMap<String, u32> = HashMap::new();
+
+    for (doc, mut terms) in docs {
+        terms.sort_unstable();
+        terms.dedup();
+        if terms.is_empty() {
+            continue;
+        }
+
+        let term_ids: Vec<(u32, u32)> = terms
+            .into_iter()
+            .map(|(posting, term)| {
+                let id = *ids
+                    .entry(term.clone())
+                    .or_insert_with(|| builder.insert_term(DictTerm::from(term)));
+                (posting, id)
+            })
+            .collect();
+
+        let mut dims: Vec<u32> = term_ids.iter().map(|i| i.1).collect();
+        dims.sort_unstable();
+        dims.dedup();
+        let weight = 1.0 / (dims.len() as f32).sqrt();
+
+        let vec = SpVec32::create_new_raw(dims.into_iter().map(|i| (i, weight)));
+        let doc_id = builder.insert_doc(DocVector::new(doc, vec));
+        for (posting, term_id) in term_ids {
+            builder.map(posting, term_id, doc_id);
+        }
+    }
+
+    builder.build()
+}
+
+/// Builds an n-gram index over `docs`, each being an item and all terms it's found by
+fn ngram_index<const N: usize, D>(docs: D) -> NgramIndex<N, u32>
+where
+    D: IntoIterator<Item = (u32, Vec<String>)>,
+{
+    let mut builder = NGIndexBuilder::new();
+
+    for (item, mut terms) in docs {
+        terms.retain(|i| !i.is_empty());
+        terms.sort_unstable();
+        terms.dedup();
+        if !terms.is_empty() {
+            builder.insert(&terms, item);
+        }
+    }
+
+    builder.build()
+}
+
+/// Returns the kanji if `s` consists of exactly one kanji
+fn single_kanji(s: &str) -> Option<char> {
+    let mut chars = s.chars();
+    let c = chars.next()?;
+    (chars.next().is_none() && c.is_kanji()).then(|| c)
+}
+
+/// Returns `true` if the dictionary reading of a kanji (eg. "ショク" or "た.べる") matches the
+/// `used` kana reading within a word
+fn reading_matches(reading: &str, used: &str) -> bool {
+    let stem = reading
+        .split('.')
+        .next()
+        .unwrap_or(reading)
+        .trim_matches('-');
+    !stem.is_empty() && stem.to_hiragana() == used.to_hiragana()
+}
+
+/// Serializes `index` into the file `name` within `out`
+fn write<T: Serialize>(
+    out: &Path,
+    name: &str,
+    index: &T,
+) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
+    let path = out.join(name);
+    let mut writer = BufWriter::new(File::create(&path)?);
+    ::utils::format::write_header(&mut writer, super::FORMAT_VERSION)?;
+    bincode::serialize_into(writer, index)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("ショク", "しょく", true; "Onyomi")]
+    #[test_case("た.べる", "た", true; "Kunyomi with okurigana")]
+    #[test_case("-ぶ", "ぶ", true; "Suffix")]
+    #[test_case("た.べる", "たべ", false; "Different reading")]
+    fn test_reading_matches(reading: &str, used: &str, expected: bool) {
+        assert_eq!(reading_matches(reading, used), expected);
+    }
+
+    #[test_case(0, 1, 1, Position::Initial; "Single kanji")]
+    #[test_case(0, 1, 3, Position::Initial; "Initial")]
+    #[test_case(1, 2, 3, Position::Medial; "Medial")]
+    #[test_case(2, 3, 3, Position::Final; "Final")]
+    fn test_position(start: usize, end: usize, len: usize, expected: Position) {
+        assert_eq!(Position::new(start, end, len), expected);
+    }
+}
@@ -0,0 +1,32 @@
+use super::ngram_index;
+use crate::names::{self, ForeignIndex, NativeIndex};
+use types::jotoba::names::Name;
+
+/// Builds the japanese index of the kana and kanji of `names`
+pub fn native<'a, N>(names: N) -> NativeIndex
+where
+    N: Iterator<Item = &'a Name>,
+{
+    let docs = names.map(|name| {
+        let terms = std::iter::once(&name.kana)
+            .chain(name.kanji.iter())
+            .cloned()
+            .collect();
+        (name.sequence, terms)
+    });
+    ngram_index(docs)
+}
+
+/// Builds the index of the transcriptions of `names`
+pub fn foreign<'a, N>(names: N) -> ForeignIndex
+where
+    N: Iterator<Item = &'a Name>,
+{
+    let docs = names.map(|name| {
+        (
+            name.sequence,
+            vec![names::format_foreign(&name.transcription)],
+        )
+    });
+    ngram_index(docs)
+}
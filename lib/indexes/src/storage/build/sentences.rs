@@ -0,0 +1,58 @@
+use super::vsm_index;
+use crate::sentences::{ForeignIndex, NativeIndex};
+use std::collections::HashSet;
+use types::jotoba::{language::Language, sentences::Sentence};
+
+/// Builds the japanese index of `sentences`, split into terms by `terms`. Each sentence is mapped
+/// in the postings of the languages it has a translation in
+pub fn native<'a, S>(sentences: S, terms: &dyn Fn(&str) -> HashSet<String>) -> NativeIndex
+where
+    S: Iterator<Item = &'a Sentence>,
+{
+    let docs = sentences.map(|sentence| {
+        let terms = terms(&sentence.japanese);
+        let terms = sentence
+            .translations
+            .iter()
+            .flat_map(|tr| terms.iter().map(|i| (tr.language as u32, i.clone())))
+            .collect();
+        (sentence.id, terms)
+    });
+
+    vsm_index(postings(), docs)
+}
+
+/// Builds the index of the translations in `language` of `sentences`, split into terms by
+/// `terms`. Returns `None` if none of the sentences has a translation in `language`
+pub fn foreign<'a, S>(
+    sentences: S,
+    language: Language,
+    terms: &dyn Fn(&str) -> HashSet<String>,
+) -> Option<ForeignIndex>
+where
+    S: Iterator<Item = &'a Sentence>,
+{
+    let docs: Vec<_> = sentences
+        .filter_map(|sentence| {
+            let terms: Vec<_> = sentence
+                .translations
+                .iter()
+                .filter(|tr| tr.language == language)
+                .flat_map(|tr| terms(&tr.text).into_iter().map(|i| (0, i)))
+                .collect();
+            (!terms.is_empty()).then(|| (sentence.id, terms))
+        })
+        .collect();
+
+    if docs.is_empty() {
+        return None;
+    }
+
+    Some(vsm_index(1, docs))
+}
+
+/// Amount of postings of the japanese sentence index, one for each language
+#[inline]
+fn postings() -> u32 {
+    Language::iter().count() as u32
+}
@@ -0,0 +1,46 @@
+use crate::storage::suggestions::{FG_NAMES_NGRAM, JP_NAMES_NGRAM};
+use autocompletion::index::{
+    basic::{self, BasicIndex},
+    japanese::{self, JapaneseIndex},
+};
+use std::collections::HashMap;
+use types::jotoba::{names::Name, sentences::lang_mask, words::Word};
+
+/// Builds the bitmaps of the languages `words` have glosses in, by their sequence ids
+pub fn gloss_langs<'a, W>(words: W) -> HashMap<u32, u16>
+where
+    W: Iterator<Item = &'a Word>,
+{
+    words
+        .map(|word| {
+            let langs = word.senses.iter().map(|i| i.language);
+            (word.sequence, lang_mask(langs))
+        })
+        .collect()
+}
+
+/// Builds the suggestion indexes of the kana and kanji as well as of the transcriptions of `names`
+pub fn names<'a, N>(names: N) -> (JapaneseIndex<JP_NAMES_NGRAM>, BasicIndex<FG_NAMES_NGRAM>)
+where
+    N: Iterator<Item = &'a Name>,
+{
+    let mut native = vec![];
+    let mut foreign = vec![];
+
+    for name in names {
+        native.push(japanese::Item::new(
+            name.kana.clone(),
+            name.kanji.clone(),
+            0,
+        ));
+
+        let reading = name.kanji.clone().unwrap_or_else(|| name.kana.clone());
+        foreign.push(basic::Item::new(
+            name.transcription.clone(),
+            Some(reading),
+            0,
+        ));
+    }
+
+    (JapaneseIndex::new(native), BasicIndex::new(foreign))
+}
@@ -0,0 +1,124 @@
+use super::{ngram_index, reading_matches, single_kanji, vsm_index};
+use crate::{
+    kanji::reading::Index as ReadingIndex,
+    ng_freq::NgFreqIndex,
+    words::{analyzer::Analyzer, foreign::NG_FREQ_N, native, ForeignIndex, NativeIndex},
+};
+use index_framework::{backend::memory::build::MemIndexBuilder, traits::build::IndexBuilder};
+use std::collections::HashMap;
+use types::jotoba::{kanji::Kanji, language::Language, words::Word};
+
+/// Builds the japanese index of the readings of `words`
+pub fn native<'a, W>(words: W) -> NativeIndex
+where
+    W: Iterator<Item = &'a Word>,
+{
+    let mut tf_index = NgFreqIndex::new(native::N);
+
+    let docs: Vec<_> = words
+        .map(|word| {
+            let readings: Vec<String> =
+                word.reading_iter(true).map(|i| i.reading.clone()).collect();
+            for reading in readings.iter() {
+                tf_index.insert(reading);
+            }
+            (word.sequence, readings)
+        })
+        .collect();
+
+    NativeIndex::new(ngram_index(docs), tf_index)
+}
+
+/// Builds the index of the words using a reading of a kanji. Terms are the literal of the kanji
+/// followed by its reading, eg. "食た.べる"
+pub fn kanji_reading<'a, W>(kanji: &[Kanji], words: W) -> ReadingIndex
+where
+    W: Iterator<Item = &'a Word>,
+{
+    let by_literal: HashMap<char, &Kanji> = kanji.iter().map(|i| (i.literal, i)).collect();
+
+    let mut builder = MemIndexBuilder::new(1);
+    let mut term_ids: HashMap<String, u32> = HashMap::new();
+
+    for word in words {
+        let furigana = match word.furigana.as_deref() {
+            Some(furigana) => furigana,
+            None => continue,
+        };
+
+        let mut terms = vec![];
+        for part in jp_utils::furigana::parse::unchecked(furigana) {
+            for (main, used) in part.reading_iter() {
+                let literal = single_kanji(main).and_then(|i| by_literal.get(&i));
+                let (kanji, used) = match (literal, used) {
+                    (Some(kanji), Some(used)) => (kanji, used),
+                    _ => continue,
+                };
+
+                let reading = kanji
+                    .kunyomi
+                    .iter()
+                    .chain(kanji.onyomi.iter())
+                    .find(|i| reading_matches(i, used));
+                if let Some(reading) = reading {
+                    terms.push(format!("{}{reading}", kanji.literal));
+                }
+            }
+        }
+
+        terms.sort_unstable();
+        terms.dedup();
+        if terms.is_empty() {
+            continue;
+        }
+
+        let doc_id = builder.insert_doc(word.sequence);
+        for term in terms {
+            let term_id = *term_ids
+                .entry(term.clone())
+                .or_insert_with(|| builder.insert_term(term));
+            builder.map(0, term_id, doc_id);
+        }
+    }
+
+    builder.build()
+}
+
+/// Builds the foreign index of the glosses in `language` of `words`. Glosses get split into terms
+/// by the [`Analyzer`] of `language`, the same way queries do. Returns `None` if none of the words
+/// has a gloss in `language`
+pub fn foreign<'a, W>(words: W, language: Language) -> Option<ForeignIndex>
+where
+    W: Iterator<Item = &'a Word>,
+{
+    let analyzer = Analyzer::new(language);
+    let mut ng_index = NgFreqIndex::new(NG_FREQ_N);
+
+    let mut docs = vec![];
+    for word in words {
+        let mut terms: Vec<String> = vec![];
+        for gloss in word.gloss_iter_by_lang(language) {
+            ng_index.insert(&gloss.to_lowercase());
+
+            // Whole glosses are looked up with the highest weight, so they have to be normalized
+            // exactly like whole queries
+            terms.push(analyzer.normalize(gloss));
+            terms.extend(analyzer.terms(gloss));
+        }
+
+        let terms: Vec<_> = terms
+            .into_iter()
+            .filter(|i| !i.is_empty())
+            .map(|i| (0, i))
+            .collect();
+        if !terms.is_empty() {
+            docs.push((word.sequence, terms));
+        }
+    }
+
+    if docs.is_empty() {
+        return None;
+    }
+
+    Some(ForeignIndex::new(vsm_index(1, docs), ng_index))
+}
@@ -1,5 +1,6 @@
+use super::utils;
 use crate::kanji::reading_freq::FrequencyIndex;
-use std::{error::Error, fs::File, io::BufReader, path::Path};
+use std::{error::Error, path::Path};
 
 pub const K_READINGS_FREQ_FILE: &str = "kreading_freq_index";
 
@@ -20,8 +21,8 @@ impl KanjiStore {
 }
 
 pub(crate) fn load<P: AsRef<Path>>(path: P) -> Result<KanjiStore, Box<dyn Error + Send + Sync>> {
-    let kread_file = Path::new(path.as_ref()).join(K_READINGS_FREQ_FILE);
+    // The index generator writes reading frequencies without positions
     let kread_frequency: FrequencyIndex =
-        bincode::deserialize_from(BufReader::new(File::open(kread_file)?))?;
+        utils::deser_file_versioned(path.as_ref(), K_READINGS_FREQ_FILE)?;
     Ok(KanjiStore::new(kread_frequency))
 }
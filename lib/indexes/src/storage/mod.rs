@@ -1,3 +1,4 @@
+pub mod build;
 pub mod kanji;
 pub mod name;
 pub mod radical;
@@ -6,15 +7,27 @@ pub mod suggestions;
 pub(crate) mod utils;
 pub mod word;
 
+use arc_swap::ArcSwapOption;
 use once_cell::sync::OnceCell;
-use std::{error::Error, path::Path};
+use std::{error::Error, path::Path, str::FromStr, sync::Arc};
+use types::jotoba::language::Language;
 use {
     kanji::KanjiStore, name::NameStore, radical::RadicalStore, sentence::SentenceStore,
     word::WordStore,
 };
 
-/// In-memory store for all indexes
-pub(crate) static INDEX_STORE: OnceCell<IndexStore> = OnceCell::new();
+/// Version of the format of index files written by the index builder. Has to be increased on every
+/// change to the format of an index. Index files without a version are built by the external index
+/// generator and get loaded as they are, unless the builder writes the index in a different format
+pub const FORMAT_VERSION: u32 = 3;
+
+/// In-memory store for all indexes. It gets swapped atomically when replacing the indexes. Indexes
+/// are handed out with a `'static` lifetime, so replaced stores get retired and are only freed once
+/// all readers pinned with [`::utils::epoch::pin`] are done with them
+static INDEX_STORE: ArcSwapOption<IndexStore> = ArcSwapOption::const_empty();
+
+/// Gets set once the first index store is loaded
+static LOADED: OnceCell<()> = OnceCell::new();
 
 /// Store for all indexes
 pub struct IndexStore {
@@ -57,46 +70,127 @@ impl IndexStore {
     }
 }
 
+/// Options for loading index files
+#[derive(Clone, Debug, Default)]
+pub struct LoadOptions {
+    /// Languages of the foreign word, sentence and suggestion indexes to load. All are loaded if
+    /// `None`
+    languages: Option<Vec<Language>>,
+}
+
+impl LoadOptions {
+    /// Only loads the foreign indexes of the languages named in `names`, eg. `["eng", "ger"]`.
+    /// Unknown names get ignored. All languages are loaded if `names` is `None`
+    pub fn with_language_names(names: Option<&[String]>) -> Self {
+        let names = match names {
+            Some(names) => names,
+            None => return Self::default(),
+        };
+
+        let languages = names
+            .iter()
+            .filter_map(|i| match Language::from_str(i) {
+                Ok(lang) => Some(lang),
+                Err(_) => {
+                    log::warn!("Unknown language in config: {i}");
+                    None
+                }
+            })
+            .collect();
+        Self::default().languages(languages)
+    }
+
+    /// Only loads the foreign indexes of `languages`. English indexes are always loaded
+    #[inline]
+    pub fn languages(mut self, languages: Vec<Language>) -> Self {
+        self.languages = Some(languages);
+        self
+    }
+
+    /// Returns the languages whose foreign indexes get loaded or `None` if all get loaded
+    #[inline]
+    pub fn get_languages(&self) -> Option<&[Language]> {
+        self.languages.as_deref()
+    }
+
+    /// Returns `true` if the foreign indexes of `language` should be loaded
+    #[inline]
+    pub(crate) fn use_language(&self, language: Language) -> bool {
+        language == Language::English
+            || self
+                .languages
+                .as_ref()
+                .map_or(true, |i| i.contains(&language))
+    }
+}
+
 /// Returns an IndexStore which can be used to retrieve all indexes
 #[inline(always)]
 pub fn get() -> &'static IndexStore {
-    unsafe { INDEX_STORE.get_unchecked() }
+    let store = INDEX_STORE.load();
+    let store = Arc::as_ptr(store.as_ref().expect("Indexes not loaded"));
+
+    // Safety: INDEX_STORE never gets unset. Replaced stores are retired and only freed after all
+    // pinned readers are done
+    unsafe { &*store }
 }
 
 /// Loads all indexes
+#[inline]
 pub fn load<P: AsRef<Path>>(index_folder: P) -> Result<bool, Box<dyn Error + Send + Sync>> {
+    load_with_options(index_folder, &LoadOptions::default())
+}
+
+/// Loads all indexes using the given [`LoadOptions`]
+pub fn load_with_options<P: AsRef<Path>>(
+    index_folder: P,
+    options: &LoadOptions,
+) -> Result<bool, Box<dyn Error + Send + Sync>> {
     if is_loaded() {
         return Ok(true);
     }
 
-    let store = load_raw(index_folder)?;
+    let store = load_raw(index_folder, options)?;
 
     if !store.check() {
         return Ok(false);
     }
 
-    INDEX_STORE.set(store).ok();
+    let prev = INDEX_STORE.compare_and_swap(&None::<Arc<_>>, Some(Arc::new(store)));
+    if prev.is_none() {
+        LOADED.set(()).ok();
+    }
 
     Ok(true)
 }
 
+/// Replaces the loaded indexes with `store` without blocking readers. Requests which are currently
+/// running keep using the old indexes, which get freed once all of them are done
+pub fn replace(store: IndexStore) {
+    if let Some(old) = INDEX_STORE.swap(Some(Arc::new(store))) {
+        ::utils::epoch::retire(old);
+    }
+    LOADED.set(()).ok();
+}
+
 pub fn is_loaded() -> bool {
-    INDEX_STORE.get().is_some()
+    INDEX_STORE.load().is_some()
 }
 
 /// Needed for tests only
 pub fn wait() {
-    INDEX_STORE.wait();
+    LOADED.wait();
 }
 
 pub fn load_raw<P: AsRef<Path>>(
     index_folder: P,
+    options: &LoadOptions,
 ) -> Result<IndexStore, Box<dyn Error + Send + Sync>> {
     log::debug!("Loading word index");
-    let word = word::load(index_folder.as_ref())?;
+    let word = word::load(index_folder.as_ref(), options)?;
 
     log::debug!("Loading sentence index");
-    let sentence = sentence::load(index_folder.as_ref())?;
+    let sentence = sentence::load(index_folder.as_ref(), options)?;
 
     log::debug!("Loading name index");
     let name = name::load(index_folder.as_ref())?;
@@ -1,25 +1,43 @@
-use super::utils;
-use crate::sentences::{ForeignIndex, NativeIndex};
-use std::{error::Error, path::Path};
+use super::{utils, LoadOptions};
+use crate::sentences::{ForeignIndex, NativeIndex, SyncIndex};
+use log::debug;
+use std::{collections::HashMap, error::Error, path::Path, str::FromStr};
+use types::jotoba::language::Language;
 
 pub const NATIVE_FILE: &str = "sentences_jp_index";
-pub const FOREIGN_FILE: &str = "sentences_fg_index";
+pub const FOREIGN_PREFIX: &str = "sentences_fg_index_";
+pub const SYNC_FILE: &str = "sentences_sync_index";
 
 /// Store for sentence indexes
 pub struct SentenceStore {
     native: NativeIndex,
-    foreign: ForeignIndex,
+    foreign: HashMap<Language, ForeignIndex>,
+    sync: SyncIndex,
 }
 
 impl SentenceStore {
-    pub(crate) fn new(native: NativeIndex, foreign: ForeignIndex) -> Self {
-        Self { foreign, native }
+    pub(crate) fn new(
+        native: NativeIndex,
+        foreign: HashMap<Language, ForeignIndex>,
+        sync: SyncIndex,
+    ) -> Self {
+        Self {
+            foreign,
+            native,
+            sync,
+        }
     }
 
     /// Returns the foreign index for the given language or `None` if not loaded
     #[inline(always)]
-    pub fn foreign(&self) -> &ForeignIndex {
-        &self.foreign
+    pub fn foreign(&self, language: Language) -> Option<&ForeignIndex> {
+        self.foreign.get(&language)
+    }
+
+    /// Returns an iterator over the languages of all loaded foreign indexes
+    #[inline]
+    pub fn languages(&self) -> impl Iterator<Item = Language> + '_ {
+        self.foreign.keys().copied()
     }
 
     /// Returns the japanese sentence index
@@ -28,13 +46,54 @@ impl SentenceStore {
         &self.native
     }
 
+    /// Returns the index of sentences appended after the other sentence indexes were generated
+    #[inline(always)]
+    pub fn sync(&self) -> &SyncIndex {
+        &self.sync
+    }
+
     pub(crate) fn check(&self) -> bool {
         true
     }
 }
 
-pub(crate) fn load<P: AsRef<Path>>(path: P) -> Result<SentenceStore, Box<dyn Error + Send + Sync>> {
+pub(crate) fn load<P: AsRef<Path>>(
+    path: P,
+    options: &LoadOptions,
+) -> Result<SentenceStore, Box<dyn Error + Send + Sync>> {
     let native = utils::deser_file(path.as_ref(), NATIVE_FILE)?;
-    let foreign = utils::deser_file(path.as_ref(), FOREIGN_FILE)?;
-    Ok(SentenceStore::new(native, foreign))
+    let foreign = load_foreign(path.as_ref(), options)?;
+
+    let mut sync = load_sync(path.as_ref())?;
+    sync.retain_languages(|i| options.use_language(i));
+
+    Ok(SentenceStore::new(native, foreign, sync))
+}
+
+fn load_foreign<P: AsRef<Path>>(
+    path: P,
+    options: &LoadOptions,
+) -> Result<HashMap<Language, ForeignIndex>, Box<dyn Error + Send + Sync>> {
+    utils::load_by_language(path, FOREIGN_PREFIX, |p| {
+        let file_name = p
+            .file_name()
+            .and_then(|i| i.to_str())
+            .and_then(|i| i.strip_prefix(FOREIGN_PREFIX))
+            .ok_or("Invalid sentence index file name")?;
+        let lang = Language::from_str(file_name)?;
+        if !options.use_language(lang) {
+            debug!("Skipping sentence index {lang:?}");
+            return Ok(None);
+        }
+        Ok(Some((lang, utils::deser_file(p, "")?)))
+    })
+}
+
+/// Loads the index of synced sentences from the index folder `path`. Returns an empty index if no
+/// sentences have been synced yet
+pub fn load_sync<P: AsRef<Path>>(path: P) -> Result<SyncIndex, Box<dyn Error + Send + Sync>> {
+    if !path.as_ref().join(SYNC_FILE).exists() {
+        return Ok(SyncIndex::new());
+    }
+    utils::deser_file_versioned(path.as_ref(), SYNC_FILE)
 }
@@ -1,10 +1,10 @@
 use crate::hashtag::HashTagIndex;
 
-use super::utils;
+use super::{utils, LoadOptions};
+use arc_swap::ArcSwapOption;
 use autocompletion::index::{basic::BasicIndex, japanese::JapaneseIndex};
-use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, error::Error, path::Path};
+use std::{collections::HashMap, error::Error, path::Path, sync::Arc};
 use types::jotoba::language::Language;
 
 pub const K_MEANING_NGRAM: usize = 3;
@@ -17,8 +17,10 @@ pub const JP_NAMES_NGRAM: usize = 2;
 
 pub const SUGGESTION_FILE: &str = "suggestions";
 
-/// In-memory store for all suggestion indexes
-pub(crate) static SUGGESTION_STORE: OnceCell<SuggestionStorage> = OnceCell::new();
+/// In-memory store for all suggestion indexes. The store gets replaced as a whole when reloading.
+/// References to it are handed out with a `'static` lifetime, so replaced stores get retired and
+/// are only freed once all readers pinned with [`::utils::epoch::pin`] are done with them
+pub(crate) static SUGGESTION_STORE: ArcSwapOption<SuggestionStorage> = ArcSwapOption::const_empty();
 
 /// Contains all suggestion index data
 #[derive(Serialize, Deserialize)]
@@ -32,6 +34,16 @@ pub struct SuggestionStorage {
     names_foreign: BasicIndex<FG_NAMES_NGRAM>,
 
     hashtag: HashTagIndex,
+
+    /// Bitmaps of the languages the suggested words have glosses in, by word sequence id. The
+    /// index files don't contain them, so they get built from the resources when loading the
+    /// indexes and are replaced along with them
+    #[serde(skip)]
+    gloss_langs: HashMap<u32, u16>,
+
+    /// Options the indexes have been loaded with
+    #[serde(skip)]
+    options: LoadOptions,
 }
 
 impl SuggestionStorage {
@@ -50,6 +62,8 @@ impl SuggestionStorage {
             names_native,
             names_foreign,
             hashtag,
+            gloss_langs: HashMap::new(),
+            options: LoadOptions::default(),
         }
     }
 
@@ -78,13 +92,39 @@ impl SuggestionStorage {
         &self.names_foreign
     }
 
+    /// Replaces the name suggestion indexes, eg. after names have been imported
+    pub fn set_names(
+        &mut self,
+        native: JapaneseIndex<JP_NAMES_NGRAM>,
+        foreign: BasicIndex<FG_NAMES_NGRAM>,
+    ) {
+        self.names_native = native;
+        self.names_foreign = foreign;
+    }
+
     #[inline]
     pub fn hashtags(&self) -> &HashTagIndex {
         &self.hashtag
     }
 
+    /// Returns the bitmap of the languages the word suggested with `word_id` has glosses in
+    #[inline]
+    pub fn gloss_langs(&self, word_id: u32) -> Option<u16> {
+        self.gloss_langs.get(&word_id).copied()
+    }
+
     pub fn check(&self) -> bool {
-        utils::check_lang_map(&self.foreign_words)
+        Language::iter_word()
+            .filter(|i| self.options.use_language(*i))
+            .all(|i| self.foreign_words.contains_key(&i))
+    }
+
+    /// Removes the foreign word suggestion indexes of all languages which shouldn't be loaded
+    /// according to `options`
+    pub fn retain_languages(&mut self, options: &LoadOptions) {
+        self.foreign_words
+            .retain(|lang, _| options.use_language(*lang));
+        self.options = options.clone();
     }
 }
 
@@ -94,12 +134,40 @@ pub fn load_raw<P: AsRef<Path>>(
     utils::deser_file(file, "")
 }
 
-pub fn load<P: AsRef<Path>>(path: P) -> Result<bool, Box<dyn Error + Sync + Send>> {
-    let store = load_raw(path)?;
-    Ok(SUGGESTION_STORE.set(store).is_ok())
+/// Loads the suggestion indexes from `path` and replaces the currently loaded ones. `gloss_langs`
+/// are the bitmaps of the languages words have glosses in (see [`build::suggestions::gloss_langs`]).
+/// Returns `true` if no suggestion indexes were loaded before
+///
+/// [`build::suggestions::gloss_langs`]: super::build::suggestions::gloss_langs
+pub fn load<P: AsRef<Path>>(
+    path: P,
+    options: &LoadOptions,
+    gloss_langs: HashMap<u32, u16>,
+) -> Result<bool, Box<dyn Error + Sync + Send>> {
+    let mut store = load_raw(path)?;
+    store.retain_languages(options);
+    store.gloss_langs = gloss_langs;
+    let prev = SUGGESTION_STORE.swap(Some(Arc::new(store)));
+    let first = prev.is_none();
+    if let Some(prev) = prev {
+        ::utils::epoch::retire(prev);
+    }
+    Ok(first)
 }
 
+/// Returns `true` if the suggestion store is loaded
+#[inline]
+pub fn is_loaded() -> bool {
+    SUGGESTION_STORE.load().is_some()
+}
+
+/// Returns the currently loaded suggestion indexes. Panics if they haven't been loaded yet
 #[inline]
 pub fn get_suggestions() -> &'static SuggestionStorage {
-    unsafe { SUGGESTION_STORE.get_unchecked() }
+    let store = SUGGESTION_STORE.load();
+    let store = Arc::as_ptr(store.as_ref().expect("Suggestions not loaded"));
+
+    // Safety: SUGGESTION_STORE never gets unset. Replaced stores are retired and only freed after
+    // all pinned readers are done
+    unsafe { &*store }
 }
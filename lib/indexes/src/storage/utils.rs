@@ -1,3 +1,5 @@
+use super::FORMAT_VERSION;
+use ::utils::format::{self, VersionError};
 use serde::de::DeserializeOwned;
 use std::{
     collections::HashMap,
@@ -8,17 +10,38 @@ use std::{
 };
 use types::jotoba::language::Language;
 
-/// Deserializes a file from `path` with `name`
+/// Deserializes a file from `path` with `name`. Files without a format version are loaded as
+/// they are
+#[inline]
 pub fn deser_file<O: DeserializeOwned, P: AsRef<Path>>(
     path: P,
     name: &str,
+) -> Result<O, Box<dyn Error + Send + Sync>> {
+    load_file(path, name, false)
+}
+
+/// Deserializes a file from `path` with `name` which has to be written in the current format
+/// version. Used for indexes whose format differs from the one of the external index generator
+#[inline]
+pub fn deser_file_versioned<O: DeserializeOwned, P: AsRef<Path>>(
+    path: P,
+    name: &str,
+) -> Result<O, Box<dyn Error + Send + Sync>> {
+    load_file(path, name, true)
+}
+
+fn load_file<O: DeserializeOwned, P: AsRef<Path>>(
+    path: P,
+    name: &str,
+    versioned: bool,
 ) -> Result<O, Box<dyn Error + Send + Sync>> {
     let path = if name.is_empty() {
         path.as_ref().to_path_buf()
     } else {
         path.as_ref().join(name)
     };
-    Ok(fast_deser(path)?)
+
+    fast_deser(path, versioned)
 }
 
 pub fn load_by_language<O, F, P: AsRef<Path>>(
@@ -59,19 +82,38 @@ where
     Language::from_str(lang_str).ok()
 } */
 
-/// Returns true if `map` has an entry for all language keys
-pub fn check_lang_map<T>(map: &HashMap<Language, T>) -> bool {
-    Language::iter_word().all(|i| map.contains_key(&i))
-}
-
 // A bit faster. Who cares about memory consumption anyways
 fn fast_deser<O: DeserializeOwned, P: AsRef<Path>>(
     file_path: P,
+    versioned: bool,
 ) -> Result<O, Box<dyn Error + Sync + Send>> {
-    let file = File::open(file_path)?;
+    let file = File::open(file_path.as_ref())?;
     let len = file.metadata()?.len();
     let mut buf = vec![0u8; len as usize];
     let mut reader = BufReader::new(file);
     reader.read_exact(&mut buf)?;
-    Ok(bincode::deserialize(&buf)?)
+    deser_versioned(file_path.as_ref(), &buf, versioned)
+}
+
+/// Deserializes the index `data` of the file `path`. Fails if the index was written in a
+/// different format version or if it has no format version but `versioned` is set
+fn deser_versioned<O: DeserializeOwned>(
+    path: &Path,
+    data: &[u8],
+    versioned: bool,
+) -> Result<O, Box<dyn Error + Sync + Send>> {
+    let data = match format::split_header(data) {
+        Some((FORMAT_VERSION, data)) => data,
+        Some((version, _)) => {
+            return Err(VersionError::new(path.display(), Some(version), FORMAT_VERSION).into())
+        }
+        None if versioned => {
+            return Err(VersionError::new(path.display(), None, FORMAT_VERSION).into())
+        }
+        None => {
+            log::debug!("{} has no format version", path.display());
+            data
+        }
+    };
+    Ok(bincode::deserialize(data)?)
 }
@@ -1,7 +1,7 @@
-use super::utils;
+use super::{utils, LoadOptions};
 use crate::{
     kanji,
-    regex::RegexSearchIndex,
+    substring::SubstringIndex,
     words::{ForeignIndex, NativeIndex},
 };
 use log::debug;
@@ -10,7 +10,7 @@ use types::jotoba::language::Language;
 
 pub const FOREIGN_PREFIX: &str = "word_index_";
 pub const NATIVE_FILE: &str = "jp_index";
-pub const REGEX_FILE: &str = "regex_index";
+pub const SUBSTRING_FILE: &str = "substring_index";
 pub const KANJI_READING_INDEX: &str = "word_kr_index";
 
 /// Store for words
@@ -18,23 +18,32 @@ pub struct WordStore {
     foreign: HashMap<Language, ForeignIndex>,
     native: NativeIndex,
 
-    regex: RegexSearchIndex,
+    /// Character n-grams of all word readings
+    substring: SubstringIndex,
 
     k_reading: kanji::reading::Index,
+
+    /// Languages whose foreign index has to be loaded
+    languages: Vec<Language>,
 }
 
 impl WordStore {
     pub(crate) fn new(
         foreign: HashMap<Language, ForeignIndex>,
         native: NativeIndex,
-        regex: RegexSearchIndex,
+        substring: SubstringIndex,
         k_reading: kanji::reading::Index,
+        options: &LoadOptions,
     ) -> Self {
+        let languages = Language::iter_word()
+            .filter(|i| options.use_language(*i))
+            .collect();
         Self {
             foreign,
             native,
-            regex,
+            substring,
             k_reading,
+            languages,
         }
     }
 
@@ -44,9 +53,25 @@ impl WordStore {
         self.foreign.get(&language)
     }
 
+    /// Returns an iterator over the languages of all loaded foreign indexes
+    #[inline]
+    pub fn languages(&self) -> impl Iterator<Item = Language> + '_ {
+        self.foreign.keys().copied()
+    }
+
+    /// Returns `language` if its foreign index is loaded and English otherwise
+    #[inline]
+    pub fn loaded_language(&self, language: Language) -> Language {
+        if self.foreign.contains_key(&language) {
+            language
+        } else {
+            Language::English
+        }
+    }
+
     #[inline]
-    pub fn regex(&self) -> &RegexSearchIndex {
-        &self.regex
+    pub fn substring(&self) -> &SubstringIndex {
+        &self.substring
     }
 
     #[inline]
@@ -60,39 +85,45 @@ impl WordStore {
     }
 
     pub(crate) fn check(&self) -> bool {
-        utils::check_lang_map(&self.foreign)
+        self.languages.iter().all(|i| self.foreign.contains_key(i))
     }
 }
 
 #[cfg(not(feature = "parallel"))]
-pub(crate) fn load<P: AsRef<Path>>(path: P) -> Result<WordStore, Box<dyn Error + Sync + Send>> {
+pub(crate) fn load<P: AsRef<Path>>(
+    path: P,
+    options: &LoadOptions,
+) -> Result<WordStore, Box<dyn Error + Sync + Send>> {
     let start = std::time::Instant::now();
-    let foreign = load_foreign(path.as_ref())?;
+    let foreign = load_foreign(path.as_ref(), options)?;
     let native = utils::deser_file(path.as_ref(), NATIVE_FILE)?;
-    let regex = utils::deser_file(path.as_ref(), REGEX_FILE)?;
+    let substring = utils::deser_file(path.as_ref(), SUBSTRING_FILE)?;
     let k_reading = utils::deser_file(path.as_ref(), KANJI_READING_INDEX)?;
     debug!("Loading indexes sync took: {:?}", start.elapsed());
-    Ok(WordStore::new(foreign, native, regex, k_reading))
+    Ok(WordStore::new(
+        foreign, native, substring, k_reading, options,
+    ))
 }
 
 #[cfg(feature = "parallel")]
 pub(crate) fn load<P: AsRef<Path> + Send + Sync>(
     path: P,
+    options: &LoadOptions,
 ) -> Result<WordStore, Box<dyn Error + Send + Sync>> {
     let start = std::time::Instant::now();
     let mut foreign = None;
     let mut native = None;
-    let mut regex: Option<Result<RegexSearchIndex, Box<dyn Error + Send + Sync>>> = None;
+    let mut substring: Option<Result<SubstringIndex, Box<dyn Error + Send + Sync>>> = None;
     let mut k_reading = None;
     rayon::scope(|s| {
         s.spawn(|_| {
-            foreign = Some(load_foreign(path.as_ref()));
+            foreign = Some(load_foreign(path.as_ref(), options));
         });
         s.spawn(|_| {
             native = Some(utils::deser_file(path.as_ref(), NATIVE_FILE));
         });
         s.spawn(|_| {
-            regex = Some(utils::deser_file(path.as_ref(), REGEX_FILE));
+            substring = Some(utils::deser_file(path.as_ref(), SUBSTRING_FILE));
         });
         s.spawn(|_| {
             k_reading = Some(utils::deser_file(path.as_ref(), KANJI_READING_INDEX));
@@ -100,18 +131,19 @@ pub(crate) fn load<P: AsRef<Path> + Send + Sync>(
     });
     let foreign = foreign.unwrap()?;
     let native = native.unwrap()?;
-    let regex = regex.unwrap()?;
+    let substring = substring.unwrap()?;
     let k_reading = k_reading.unwrap()?;
     debug!("Loading indexes parallel took: {:?}", start.elapsed());
-    Ok(WordStore::new(foreign, native, regex, k_reading))
+    Ok(WordStore::new(
+        foreign, native, substring, k_reading, options,
+    ))
 }
 
 fn load_foreign<P: AsRef<Path>>(
     path: P,
+    options: &LoadOptions,
 ) -> Result<HashMap<Language, ForeignIndex>, Box<dyn Error + Send + Sync>> {
     utils::load_by_language(path, FOREIGN_PREFIX, |p| {
-        //let index = ForeignIndex::open(p)?;
-        let index: ForeignIndex = utils::deser_file(p, "").unwrap();
         let file_name = p
             .file_name()
             .unwrap()
@@ -120,6 +152,12 @@ fn load_foreign<P: AsRef<Path>>(
             .strip_prefix(FOREIGN_PREFIX)
             .unwrap();
         let lang = Language::from_str(file_name).unwrap();
+        if !options.use_language(lang) {
+            debug!("Skipping word index {lang:?}");
+            return Ok(None);
+        }
+        //let index = ForeignIndex::open(p)?;
+        let index: ForeignIndex = utils::deser_file(p, "").unwrap();
         //let lang = index.get_metadata().language;
         Ok(Some((lang, index)))
     })
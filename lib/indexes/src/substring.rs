@@ -0,0 +1,129 @@
+use crate::regex::Indexable;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Length of the character n-grams within the index
+pub const N: usize = 2;
+
+/// Character n-gram index over terms, used to find candidates for contains-style queries (eg.
+/// wildcard searches). Compared to an index of single characters, the n-grams also take the
+/// order of characters into account and result in way less candidates for longer query fragments
+#[derive(Serialize, Deserialize, Default)]
+pub struct SubstringIndex {
+    /// Sorted ids of all terms containing an n-gram. Also contains single characters to find
+    /// fragments shorter than `N`
+    grams: HashMap<String, Vec<u32>>,
+}
+
+impl SubstringIndex {
+    /// Creates a new empty index
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a new term to the index. [`SubstringIndex::finish`] has to be called after all
+    /// terms got added
+    pub fn add_term(&mut self, term: &str, id: u32) {
+        let chars: Vec<char> = term.chars().collect();
+
+        for c in &chars {
+            self.grams.entry(c.to_string()).or_default().push(id);
+        }
+
+        for gram in chars.windows(N) {
+            self.grams
+                .entry(gram.iter().collect())
+                .or_default()
+                .push(id);
+        }
+    }
+
+    /// Sorts and deduplicates all postings
+    pub fn finish(&mut self) {
+        for ids in self.grams.values_mut() {
+            ids.sort_unstable();
+            ids.dedup();
+            ids.shrink_to_fit();
+        }
+        self.grams.shrink_to_fit();
+    }
+
+    /// Returns an iterator over all n-grams and the ids of the terms containing them
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[u32])> {
+        self.grams
+            .iter()
+            .map(|(gram, ids)| (gram.as_str(), ids.as_slice()))
+    }
+
+    /// Returns the n-grams of `fragment`, or the fragment itself if it's shorter than `N`
+    fn fragment_grams(fragment: &str) -> Vec<String> {
+        let chars: Vec<char> = fragment.chars().collect();
+        if chars.len() < N {
+            return vec![fragment.to_string()];
+        }
+
+        chars.windows(N).map(|i| i.iter().collect()).collect()
+    }
+}
+
+impl Indexable for SubstringIndex {
+    fn candidates(&self, fragments: &[&str]) -> Vec<u32> {
+        let mut postings = Vec::new();
+
+        for gram in fragments
+            .iter()
+            .filter(|i| !i.is_empty())
+            .flat_map(|i| Self::fragment_grams(i))
+        {
+            match self.grams.get(&gram) {
+                Some(ids) => postings.push(ids),
+                // No term contains this n-gram
+                None => return vec![],
+            }
+        }
+
+        // Intersect starting with the shortest posting
+        postings.sort_by_key(|i| i.len());
+        let mut postings = postings.into_iter();
+
+        let mut out = match postings.next() {
+            Some(first) => first.clone(),
+            None => return vec![],
+        };
+
+        for ids in postings {
+            out.retain(|i| ids.binary_search(i).is_ok());
+            if out.is_empty() {
+                break;
+            }
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    fn index() -> SubstringIndex {
+        let mut index = SubstringIndex::new();
+        index.add_term("宇宙飛行士", 1);
+        index.add_term("飛行機", 2);
+        index.add_term("行士飛", 3);
+        index.finish();
+        index
+    }
+
+    #[test_case(&["宇宙", "行士"], &[1]; "Two fragments")]
+    #[test_case(&["飛行"], &[1, 2]; "Bigram")]
+    #[test_case(&["行"], &[1, 2, 3]; "Single character")]
+    #[test_case(&["士飛"], &[3]; "Order matters")]
+    #[test_case(&["宇宙人"], &[]; "Unknown n-gram")]
+    fn test_candidates(fragments: &[&str], expected: &[u32]) {
+        assert_eq!(index().candidates(fragments), expected);
+    }
+}
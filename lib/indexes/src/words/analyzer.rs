@@ -0,0 +1,247 @@
+use types::jotoba::language::Language;
+
+/// Min length of both parts of a split compound
+const MIN_COMPOUND_PART: usize = 3;
+
+/// Linking elements between the parts of german and dutch compounds (eg. "Arbeit-s-zimmer")
+const LINKING_ELEMENTS: &[&str] = &["s", "es", "n", "en", "e"];
+
+/// Characters separating the words of a gloss
+const SEPARATORS: &str = ".,[]() \t\"'’\\/-;:・";
+
+/// Language specific processing of gloss terms. Takes care of stopwords, french elisions,
+/// german and dutch compounds as well as inflectional suffixes. Has to be used for building the
+/// foreign word indexes as well as for queries, so both of them end up with the same terms
+#[derive(Clone, Copy, Debug)]
+pub struct Analyzer {
+    language: Language,
+}
+
+impl Analyzer {
+    #[inline]
+    pub fn new(language: Language) -> Self {
+        Self { language }
+    }
+
+    /// Normalizes a whole gloss or query `text` into the term it gets indexed and looked up as.
+    /// Lowercases `text`, strips elisions and the english infinitive marker and replaces all
+    /// separators with single spaces (eg. "To take (sth) off" => "take sth off")
+    pub fn normalize(&self, text: &str) -> String {
+        let words = self.words(text);
+        let words = match words.split_first() {
+            Some((first, rest))
+                if first == "to" && self.language == Language::English && !rest.is_empty() =>
+            {
+                rest
+            }
+            _ => &words[..],
+        };
+        words.join(" ")
+    }
+
+    /// Returns all words of the gloss or query `text` which aren't stopwords, normalized like in
+    /// [`Analyzer::normalize`]
+    pub fn terms(&self, text: &str) -> Vec<String> {
+        self.words(text)
+            .into_iter()
+            .filter(|i| !self.is_stopword(i))
+            .collect()
+    }
+
+    /// Returns `true` if `term` is a stopword which doesn't help finding a gloss
+    pub fn is_stopword(&self, term: &str) -> bool {
+        stopwords(self.language).contains(&term)
+    }
+
+    /// Returns the variants of the normalized `term` to look up, starting with `term` itself.
+    /// `is_term` has to return `true` for terms which exist in the index. Other variants than
+    /// `term` itself are only returned if they exist
+    pub fn variants<F>(&self, term: &str, is_term: F) -> Vec<String>
+    where
+        F: Fn(&str) -> bool,
+    {
+        let mut out = vec![term.to_string()];
+
+        for stem in self.stems(term) {
+            if is_term(&stem) && !out.contains(&stem) {
+                out.push(stem);
+            }
+        }
+
+        if !is_term(term) {
+            for part in self.split_compound(term, &is_term) {
+                if !out.contains(&part) {
+                    out.push(part);
+                }
+            }
+        }
+
+        out
+    }
+
+    /// Splits the lowercase `text` into its words, removing elisions and separators
+    fn words(&self, text: &str) -> Vec<String> {
+        // Elisions have to be removed before the apostrophes get treated as separators
+        text.to_lowercase()
+            .split_whitespace()
+            .map(|i| self.strip_elision(i))
+            .flat_map(|i| i.split(|c| SEPARATORS.contains(c)))
+            .filter(|i| !i.is_empty())
+            .map(|i| i.to_string())
+            .collect()
+    }
+
+    /// Removes elided articles and pronouns (eg. "l'eau" => "eau")
+    fn strip_elision<'a>(&self, term: &'a str) -> &'a str {
+        if self.language != Language::French {
+            return term;
+        }
+
+        const ELISIONS: &[&str] = &["l", "d", "j", "m", "n", "s", "t", "c", "qu"];
+        for apostrophe in ['\'', '’'] {
+            if let Some((prefix, rest)) = term.split_once(apostrophe) {
+                if ELISIONS.contains(&prefix) && !rest.is_empty() {
+                    return rest;
+                }
+            }
+        }
+
+        term
+    }
+
+    /// Returns possible stems of `term` by removing inflectional suffixes
+    fn stems(&self, term: &str) -> Vec<String> {
+        let suffixes: &[(&str, &str)] = match self.language {
+            Language::English => &[("ies", "y"), ("es", ""), ("s", "")],
+            Language::German => &[("en", ""), ("er", ""), ("e", ""), ("n", ""), ("s", "")],
+            Language::French => &[("aux", "al"), ("s", ""), ("x", "")],
+            Language::Spanish => &[("ces", "z"), ("es", ""), ("s", "")],
+            Language::Dutch => &[("en", ""), ("s", "")],
+            Language::Swedish => &[
+                ("arna", ""),
+                ("orna", ""),
+                ("na", ""),
+                ("ar", ""),
+                ("or", ""),
+            ],
+            _ => &[],
+        };
+
+        suffixes
+            .iter()
+            .filter_map(|(suffix, replacement)| {
+                let stem = term.strip_suffix(suffix)?;
+                (stem.chars().count() >= MIN_COMPOUND_PART).then(|| format!("{stem}{replacement}"))
+            })
+            .collect()
+    }
+
+    /// Splits german and dutch compounds into two known terms (eg. "haustür" => "haus", "tür")
+    fn split_compound<F>(&self, term: &str, is_term: F) -> Vec<String>
+    where
+        F: Fn(&str) -> bool,
+    {
+        if !matches!(self.language, Language::German | Language::Dutch) {
+            return vec![];
+        }
+
+        let positions = term
+            .char_indices()
+            .map(|i| i.0)
+            .filter(|i| *i > 0)
+            .collect::<Vec<_>>();
+
+        // Prefer long heads, since the last part of a compound usually determines its meaning
+        for pos in positions.into_iter().rev() {
+            let (head, tail) = term.split_at(pos);
+            if head.chars().count() < MIN_COMPOUND_PART || tail.chars().count() < MIN_COMPOUND_PART
+            {
+                continue;
+            }
+
+            if !is_term(tail) {
+                continue;
+            }
+
+            if is_term(head) {
+                return vec![head.to_string(), tail.to_string()];
+            }
+
+            let linked = LINKING_ELEMENTS
+                .iter()
+                .filter_map(|i| head.strip_suffix(i))
+                .find(|i| i.chars().count() >= MIN_COMPOUND_PART && is_term(i));
+            if let Some(head) = linked {
+                return vec![head.to_string(), tail.to_string()];
+            }
+        }
+
+        vec![]
+    }
+}
+
+/// Returns the most common function words of `language`
+fn stopwords(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::English => &["a", "an", "the", "of", "to", "and", "or", "in", "on", "for"],
+        Language::German => &[
+            "der", "die", "das", "den", "dem", "des", "ein", "eine", "einen", "und", "oder", "zu",
+            "von", "sich", "etw", "jmdn", "jmdm",
+        ],
+        Language::French => &[
+            "le", "la", "les", "un", "une", "des", "de", "du", "et", "ou", "à", "se", "qch", "qn",
+        ],
+        Language::Spanish => &[
+            "el", "la", "los", "las", "un", "una", "de", "del", "y", "o", "a", "se", "algo",
+        ],
+        Language::Dutch => &["de", "het", "een", "van", "en", "of", "te", "zich", "iets"],
+        Language::Swedish => &["en", "ett", "och", "eller", "att", "av", "sig", "något"],
+        Language::Russian => &["и", "или", "в", "на", "с", "к", "что-л", "кого-л"],
+        Language::Hungarian => &["a", "az", "egy", "és", "vagy", "valami", "valamit"],
+        Language::Slovenian => &["in", "ali", "se", "kaj", "koga"],
+        _ => &[],
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    const TERMS: &[&str] = &["haus", "tür", "arbeit", "zimmer", "eau", "dog", "city"];
+
+    fn is_term(term: &str) -> bool {
+        TERMS.contains(&term)
+    }
+
+    #[test_case(Language::German, "haustür", &["haustür", "haus", "tür"]; "German compound")]
+    #[test_case(Language::German, "arbeitszimmer", &["arbeitszimmer", "arbeit", "zimmer"]; "Linking element")]
+    #[test_case(Language::English, "dogs", &["dogs", "dog"]; "English plural")]
+    #[test_case(Language::English, "cities", &["cities", "city"]; "English ies plural")]
+    #[test_case(Language::English, "haustür", &["haustür"]; "No decompounding")]
+    fn test_variants(language: Language, term: &str, expected: &[&str]) {
+        let variants = Analyzer::new(language).variants(term, is_term);
+        assert_eq!(variants, expected);
+    }
+
+    #[test_case(Language::English, "To take (sth) off", "take sth off"; "English infinitive")]
+    #[test_case(Language::English, "to", "to"; "Single to")]
+    #[test_case(Language::French, "L'eau-de-vie", "eau de vie"; "French elision")]
+    #[test_case(Language::French, "qu’importe", "importe"; "Typographic apostrophe")]
+    #[test_case(Language::English, "don't", "don t"; "No elision")]
+    fn test_normalize(language: Language, text: &str, expected: &str) {
+        assert_eq!(Analyzer::new(language).normalize(text), expected);
+    }
+
+    #[test_case(Language::French, "l'eau et le vin", &["eau", "vin"]; "French")]
+    #[test_case(Language::German, "Die Tür des Hauses", &["tür", "hauses"]; "German")]
+    fn test_terms(language: Language, text: &str, expected: &[&str]) {
+        assert_eq!(Analyzer::new(language).terms(text), expected);
+    }
+
+    #[test]
+    fn test_stopwords() {
+        assert!(Analyzer::new(Language::German).is_stopword("der"));
+        assert!(!Analyzer::new(Language::German).is_stopword("haus"));
+    }
+}
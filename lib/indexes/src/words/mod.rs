@@ -1,3 +1,4 @@
+pub mod analyzer;
 pub mod foreign;
 pub mod native;
 
@@ -6,6 +6,24 @@ use jp_utils::JapaneseExt;
 
 use crate::ToKanaExt;
 
+/// Converts a furigana encoded text (eg. "[音楽|おん|がく]が[好|す]き") into kana only text, using
+/// the furigana of each kanji as its reading
+pub fn to_kana(furigana: &str) -> String {
+    let mut out = String::with_capacity(furigana.len());
+    for part in jp_utils::furigana::parse::unchecked(furigana) {
+        for (main, reading) in part.reading_iter() {
+            out.push_str(reading.unwrap_or(main));
+        }
+    }
+    out
+}
+
+/// Converts a furigana encoded text into romaji, using the furigana of each kanji as its reading
+#[inline]
+pub fn to_romaji(furigana: &str) -> String {
+    wana_kana::to_romaji::to_romaji(&to_kana(furigana))
+}
+
 /// Generates all kanji readins from a kanji and kana string an returns them (kanji, kana)
 fn map_readings(kanji: &str, kana: &str) -> Option<Vec<(String, String)>> {
     let kana = kana.chars().filter(|s| !s.is_symbol()).collect::<Vec<_>>();
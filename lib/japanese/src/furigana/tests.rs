@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use crate::furigana::map_readings;
+    use crate::furigana::{map_readings, to_kana};
     //use resources::LAZY_STORAGE;
     use test_case::test_case;
 
@@ -23,4 +23,12 @@ mod tests {
             .collect::<Vec<_>>();
         assert_eq!(parsed, expected);
     }
+
+    #[test_case("", ""; "Empty")]
+    #[test_case("おはよう", "おはよう"; "Kana only")]
+    #[test_case("[好|す]き", "すき"; "Simple")]
+    #[test_case("[音楽|おん|がく]が[好|す]き", "おんがくがすき"; "Multiple kanji")]
+    fn test_to_kana(furigana: &str, expected: &str) {
+        assert_eq!(to_kana(furigana), expected);
+    }
 }
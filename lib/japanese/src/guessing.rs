@@ -2,6 +2,13 @@ use jp_utils::JapaneseExt;
 
 use crate::ToKanaExt;
 
+/// Romaji which can start a mora but doesn't form one on its own
+const ONSETS: &[&str] = &[
+    "k", "s", "t", "n", "h", "m", "y", "r", "w", "g", "z", "d", "b", "p", "j", "f", "c", "v", "ky",
+    "sh", "sy", "ch", "cy", "ts", "ty", "th", "ny", "hy", "my", "ry", "gy", "zy", "jy", "dy", "dh",
+    "by", "py",
+];
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -47,6 +54,23 @@ mod test {
         test("this is a laptop", false);
     }
 
+    #[test]
+    fn test_partial_romaji() {
+        let partial = |kana: &str, pending: &str| Some((kana.to_string(), pending.to_string()));
+        assert_eq!(partial_romaji("tabe"), partial("たべ", ""));
+        assert_eq!(partial_romaji("tabem"), partial("たべ", "m"));
+        assert_eq!(partial_romaji("tabemo"), partial("たべも", ""));
+        assert_eq!(partial_romaji("tan"), partial("た", "n"));
+        assert_eq!(partial_romaji("kank"), partial("かん", "k"));
+        assert_eq!(partial_romaji("kannk"), partial("かん", "k"));
+        assert_eq!(partial_romaji("gakk"), partial("がっ", "k"));
+        assert_eq!(partial_romaji("kaish"), partial("かい", "sh"));
+        assert_eq!(partial_romaji("k"), None);
+        assert_eq!(partial_romaji("konnan"), partial("こんな", "n"));
+        assert_eq!(partial_romaji("gx"), None);
+        assert_eq!(partial_romaji("tabe mono"), None);
+    }
+
     fn test(inp: &str, assert: bool) {
         if could_be_romaji(inp) != assert {
             panic!("{:?} should be {}", inp, assert);
@@ -70,3 +94,54 @@ pub fn is_romaji_repl(inp: &str) -> Option<String> {
     }
     inp.to_hiragana().is_japanese().then(|| inp)
 }
+
+/// Converts romaji which is still being typed (eg. "tabem") into kana. Returns the kana of all
+/// completely typed morae and the romaji of the mora currently being typed, eg. ("たべ", "m").
+/// Returns `None` if `inp` isn't (partially typed) romaji
+pub fn partial_romaji(inp: &str) -> Option<(String, String)> {
+    let inp = inp.trim().to_lowercase();
+    if !inp.chars().all(|c| c.is_ascii_alphabetic() || c == '-') {
+        return None;
+    }
+
+    let tail_start = inp
+        .rfind(|c: char| is_vowel(c) || c == '-')
+        .map_or(0, |i| i + 1);
+    let (head, mut tail) = inp.split_at(tail_start);
+    let mut head = head.to_string();
+
+    // Moraic n followed by another consonant (eg. "kank")
+    if tail.len() > 1 && tail.starts_with('n') && !tail[1..].starts_with('y') {
+        head.push_str("nn");
+        tail = &tail[1..];
+        if tail.len() > 1 && tail.starts_with('n') {
+            tail = &tail[1..];
+        }
+    }
+
+    // Gemination (eg. "gakk")
+    let sokuon = tail.len() > 1 && tail[..1] == tail[1..2];
+    if sokuon {
+        tail = &tail[1..];
+    }
+
+    if !tail.is_empty() && !ONSETS.contains(&tail) {
+        return None;
+    }
+
+    let mut kana = head.to_hiragana();
+    if sokuon {
+        kana.push('っ');
+    }
+
+    if kana.is_empty() || !kana.is_japanese() {
+        return None;
+    }
+
+    Some((kana, tail.to_string()))
+}
+
+#[inline]
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
+}
@@ -1,6 +1,8 @@
 pub mod furigana;
 pub mod guessing;
+pub mod normalize;
 pub mod radicals;
+pub mod transliterate;
 
 pub trait ToKanaExt {
     fn to_hiragana(&self) -> String;
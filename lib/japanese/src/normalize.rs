@@ -0,0 +1,83 @@
+//! Unicode normalization applied to imported data as well as incoming queries, so both always
+//! end up in the same representation.
+//!
+//! The policy is NFKC, with the following exceptions:
+//! - Spacing (semi-)voiced sound marks (゛゜) following a kana get combined with it (か゛ → が).
+//!   Standalone ones are kept as they are, instead of turning them into a space and a combining
+//!   mark.
+//! - Ellipses (…‥) and the fullwidth tilde (～) are kept, since NFKC replaces them with ascii
+//!   characters which have a different meaning in japanese text.
+
+use jp_utils::JapaneseExt;
+use unicode_normalization::UnicodeNormalization;
+
+/// Characters which are kept as they are
+const PRESERVED: &[char] = &['゛', '゜', '…', '‥', '～'];
+
+/// Normalizes `inp` following the policy described above
+pub fn normalize(inp: &str) -> String {
+    if inp.is_ascii() {
+        return inp.to_string();
+    }
+
+    let mut out = String::with_capacity(inp.len());
+    let mut segment = String::new();
+    let mut prev: Option<char> = None;
+
+    for c in inp.chars() {
+        let c = match c {
+            '゛' if prev.map_or(false, |p| p.is_kana()) => '\u{3099}',
+            '゜' if prev.map_or(false, |p| p.is_kana()) => '\u{309A}',
+            c => c,
+        };
+        prev = Some(c);
+
+        if PRESERVED.contains(&c) {
+            out.extend(segment.nfkc());
+            segment.clear();
+            out.push(c);
+            continue;
+        }
+
+        segment.push(c);
+    }
+
+    out.extend(segment.nfkc());
+    out
+}
+
+/// Normalizes `inp` in place
+pub fn normalize_mut(inp: &mut String) {
+    if inp.is_ascii() {
+        return;
+    }
+
+    let normalized = normalize(inp);
+    if normalized != *inp {
+        *inp = normalized;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("", ""; "Empty")]
+    #[test_case("dog", "dog"; "Ascii")]
+    #[test_case("音楽", "音楽"; "Already normalized")]
+    #[test_case("か゛", "が"; "Spacing dakuten")]
+    #[test_case("は゜", "ぱ"; "Spacing handakuten")]
+    #[test_case("か\u{3099}", "が"; "Combining dakuten")]
+    #[test_case("ウ゛ァイオリン", "ヴァイオリン"; "Spacing dakuten katakana")]
+    #[test_case("ｶﾞｯｺｳ", "ガッコウ"; "Halfwidth katakana")]
+    #[test_case("ＡＢＣ１２３", "ABC123"; "Fullwidth ascii")]
+    #[test_case("＃ｎ５ 犬", "#n5 犬"; "Fullwidth tag")]
+    #[test_case("㍿", "株式会社"; "Compatibility character")]
+    #[test_case("゛", "゛"; "Standalone dakuten")]
+    #[test_case("えっと…", "えっと…"; "Ellipsis")]
+    #[test_case("１０～２０", "10～20"; "Fullwidth tilde")]
+    fn test_normalize(inp: &str, expected: &str) {
+        assert_eq!(normalize(inp), expected);
+    }
+}
@@ -0,0 +1,193 @@
+use itertools::Itertools;
+
+/// Max amount of katakana renderings generated for a single name
+const MAX_CANDIDATES: usize = 4;
+
+/// Spelling rules mapping graphemes of (mostly english) names to their romanized japanese
+/// pronunciation. Longer graphemes have to be placed before their prefixes and the first
+/// alternative is the most common one
+const RULES: &[(&str, &[&str])] = &[
+    ("tch", &["ch"]),
+    ("sch", &["sh"]),
+    ("chr", &["kr"]),
+    ("ch", &["ch", "k"]),
+    ("ph", &["f"]),
+    ("th", &["s", "t"]),
+    ("sh", &["sh"]),
+    ("ck", &["kk"]),
+    ("qu", &["kw"]),
+    ("ce", &["se"]),
+    ("ci", &["shi"]),
+    ("cy", &["shi"]),
+    ("ti", &["thi", "chi"]),
+    ("di", &["dhi", "ji"]),
+    ("ee", &["ii"]),
+    ("ea", &["ii"]),
+    ("oo", &["uu"]),
+    ("ya", &["ya"]),
+    ("yo", &["yo"]),
+    ("yu", &["yu"]),
+    ("ye", &["ie"]),
+    ("wa", &["wa"]),
+    ("wi", &["ui"]),
+    ("we", &["ue"]),
+    ("wo", &["uo"]),
+    ("c", &["k"]),
+    ("x", &["ks"]),
+    ("q", &["k"]),
+    ("l", &["r"]),
+    ("v", &["b", "v"]),
+    ("w", &["u"]),
+    ("y", &["i"]),
+];
+
+/// Spelling rules which are only applied at the end of a name
+const END_RULES: &[(&str, &[&str])] = &[
+    ("er", &["aa", "a"]),
+    ("or", &["aa", "a"]),
+    ("ar", &["aa", "a"]),
+    ("ie", &["ii", "i"]),
+    ("y", &["ii", "i"]),
+    ("e", &["", "e"]),
+];
+
+/// Converts a foreign name (eg. "Christopher") into plausible katakana renderings of it (eg.
+/// "クリストファー"), using grapheme-to-kana rules. The most plausible rendering comes first.
+/// Returns an empty vec if `name` isn't written in latin letters only
+pub fn to_katakana_candidates(name: &str) -> Vec<String> {
+    let name = name.trim().to_lowercase();
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphabetic()) {
+        return vec![];
+    }
+
+    let mut candidates = vec![String::new()];
+
+    let mut pos = 0;
+    while pos < name.len() {
+        let (len, alternatives) = find_rule(&name[pos..]);
+
+        candidates = candidates
+            .iter()
+            .flat_map(|c| alternatives.iter().map(move |a| format!("{c}{a}")))
+            .take(MAX_CANDIDATES)
+            .collect();
+
+        pos += len;
+    }
+
+    candidates
+        .into_iter()
+        .map(|i| romaji_to_katakana(&i))
+        .unique()
+        .collect()
+}
+
+/// Returns the length of the grapheme at the beginning of `rest` and its pronunciations
+fn find_rule(rest: &str) -> (usize, Vec<&str>) {
+    if let Some((pat, alts)) = END_RULES.iter().find(|i| i.0 == rest) {
+        return (pat.len(), alts.to_vec());
+    }
+
+    if let Some((pat, alts)) = RULES.iter().find(|i| rest.starts_with(i.0)) {
+        return (pat.len(), alts.to_vec());
+    }
+
+    (1, vec![&rest[..1]])
+}
+
+/// Converts romanized pronunciation into katakana, adding vowels to consonants which aren't
+/// followed by one since japanese doesn't know standalone consonants (except ン)
+fn romaji_to_katakana(romaji: &str) -> String {
+    let chars: Vec<char> = romaji.chars().collect();
+    let mut out = String::with_capacity(romaji.len() * 2);
+
+    for (i, c) in chars.iter().copied().enumerate() {
+        let prev = i.checked_sub(1).map(|p| chars[p]);
+        let next = chars.get(i + 1).copied();
+
+        if is_vowel(c) {
+            // Long vowel
+            if prev == Some(c) {
+                out.push('-');
+            } else {
+                out.push(c);
+            }
+            continue;
+        }
+
+        // Silent h (eg. "john")
+        if c == 'h' && prev.map_or(false, is_vowel) && !next.map_or(false, is_vowel) {
+            continue;
+        }
+
+        // Double consonants which can't be geminated are pronounced as single ones
+        if Some(c) == next && !"kstpn".contains(c) {
+            continue;
+        }
+
+        if c == 'n' && !next.map_or(false, |n| is_vowel(n) || n == 'y') {
+            out.push_str("nn");
+            continue;
+        }
+
+        out.push(c);
+
+        if needs_vowel(c, next) {
+            out.push(epenthetic_vowel(c, prev));
+        }
+    }
+
+    wana_kana::to_katakana::to_katakana(&out).replace('-', "ー")
+}
+
+/// Returns `true` if the consonant `c` requires a vowel to be pronounceable in japanese
+fn needs_vowel(c: char, next: Option<char>) -> bool {
+    let next = match next {
+        Some(n) => n,
+        None => return true,
+    };
+
+    if is_vowel(next) || next == 'y' {
+        return false;
+    }
+
+    // Gemination (eg. "kk") and digraphs (eg. "sh")
+    !(next == c || (next == 'h' && "sctd".contains(c)))
+}
+
+/// Returns the vowel to add after a consonant which can't stand on its own
+#[inline]
+fn epenthetic_vowel(c: char, prev: Option<char>) -> char {
+    match c {
+        't' | 'd' => 'o',
+        'h' if prev == Some('c') => 'i',
+        _ => 'u',
+    }
+}
+
+#[inline]
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("Christopher", "クリストファー"; "Christopher")]
+    #[test_case("Anna", "アンナ"; "Anna")]
+    #[test_case("John", "ジョン"; "John")]
+    #[test_case("Harry", "ハリー"; "Harry")]
+    fn test_to_katakana(name: &str, expected: &str) {
+        let candidates = to_katakana_candidates(name);
+        assert_eq!(candidates.first().map(|i| i.as_str()), Some(expected));
+    }
+
+    #[test_case(""; "Empty")]
+    #[test_case("山田"; "Japanese")]
+    #[test_case("R2D2"; "Digits")]
+    fn test_no_candidates(name: &str) {
+        assert!(to_katakana_candidates(name).is_empty());
+    }
+}
@@ -0,0 +1,63 @@
+//! Import of historical and alternative names of places and eras from tab separated files. Each
+//! line reads as "`name` <kind> `alias`": `<name>\t<kind>\t<alias>`, eg. `東京\tformerly\t江戸`.
+//! Valid kinds are `formerly`, `later_known_as` and `also_known_as`. Empty lines and lines
+//! starting with '#' are ignored
+
+use super::{diff, frequency::read_list, ImportStats, Progress};
+use crate::ResourceStorage;
+use japanese::normalize::normalize;
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    path::Path,
+};
+use types::jotoba::names::alias::AliasKind;
+
+/// Imports the aliases of `source`, replacing the aliases of all names in `storage`
+pub fn import<S: AsRef<Path>>(
+    storage: &mut ResourceStorage,
+    source: S,
+    on_progress: impl FnMut(Progress),
+) -> Result<ImportStats, Box<dyn Error>> {
+    let names: HashSet<String> = storage
+        .names
+        .entries()
+        .iter()
+        .map(|i| i.get_reading().to_string())
+        .collect();
+
+    let mut aliases = vec![];
+    let mut skipped = 0;
+    let entries = read_list(source.as_ref(), '\t', on_progress, |_, cols| {
+        let alias = match cols {
+            [name, kind, alias, ..] => parse_kind(kind).map(|kind| (*name, kind, *alias)),
+            _ => None,
+        };
+
+        match alias {
+            Some((name, kind, alias))
+                if names.contains(&normalize(name)) || names.contains(&normalize(alias)) =>
+            {
+                aliases.push((name.to_string(), alias.to_string(), kind));
+            }
+            _ => skipped += 1,
+        }
+    })?;
+
+    let current: HashMap<_, _> = storage.names.aliases.drain().collect();
+    storage.names.insert_aliases(aliases);
+
+    let mut stats = diff(&current, &storage.names.aliases);
+    stats.entries = entries;
+    stats.skipped = skipped;
+    Ok(stats)
+}
+
+fn parse_kind(s: &str) -> Option<AliasKind> {
+    Some(match s {
+        "formerly" => AliasKind::Formerly,
+        "later_known_as" => AliasKind::LaterKnownAs,
+        "also_known_as" => AliasKind::AlsoKnownAs,
+        _ => return None,
+    })
+}
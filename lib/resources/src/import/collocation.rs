@@ -0,0 +1,361 @@
+//! Extraction of word collocations from the tokenized japanese sentences of a Tatoeba export
+//! (`jpn_indices.csv`, see [`super::tatoeba`]). Pairs of tokens occurring close to each other
+//! get scored by their pointwise mutual information (PMI). If the tokens of a strongly associated
+//! pair are written like a dictionary entry, eg. `雨 が 降る` like `雨が降る`, this entry becomes a
+//! collocation of the words of both tokens. The occurrences of the tokens become the corpus
+//! frequencies of their words, which collocations get ordered by
+
+use super::{
+    diff,
+    tatoeba::{parse_b_word, read_tsv, INDICES_FILE},
+    ImportStats, Progress, WordMatcher,
+};
+use crate::ResourceStorage;
+use japanese::normalize::normalize;
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fs,
+    path::Path,
+    str::FromStr,
+};
+use types::jotoba::words::part_of_speech::PosSimple;
+
+/// Max distance of two tokens of a sentence to be counted as pair
+pub const WINDOW: usize = 2;
+
+/// Min amount of occurrences of a pair to get scored
+pub const MIN_COUNT: u32 = 3;
+
+/// Min PMI of a pair to be a collocation
+pub const MIN_PMI: f64 = 3.0;
+
+/// Words which never get collocations assigned
+const IGNORED_POS: &[PosSimple] = &[PosSimple::Particle, PosSimple::Auxilary];
+
+/// How extracted collocations get combined with the ones in the storage
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Mode {
+    /// Keeps existing collocations and adds the extracted ones
+    #[default]
+    Augment,
+    /// Replaces all collocations with the extracted ones
+    Replace,
+}
+
+impl FromStr for Mode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "augment" => Ok(Self::Augment),
+            "replace" => Ok(Self::Replace),
+            _ => Err(format!("Unknown collocation mode: {s}")),
+        }
+    }
+}
+
+/// Summary of a collocation extraction
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CollocationStats {
+    /// Changes of the words collocations. `entries` is the amount of sentences and `skipped` the
+    /// amount of scored pairs not written like any dictionary entry
+    pub import: ImportStats,
+    /// Amount of pairs scored above [`MIN_PMI`]
+    pub pairs: usize,
+    /// Amount of words occurring in the sentences
+    pub frequencies: usize,
+}
+
+/// A single token of a sentence
+struct Token {
+    /// Index of the tokens headword and reading in the vocabulary
+    id: u32,
+    /// How the token is written in the sentence
+    surface: String,
+}
+
+/// Extracts collocations and corpus frequencies from the sentences of the Tatoeba export in `dir`
+/// and writes them into the words of `storage`. Corpus frequencies are always replaced
+pub fn extract<P: AsRef<Path>>(
+    storage: &mut ResourceStorage,
+    dir: P,
+    mode: Mode,
+    mut on_progress: impl FnMut(Progress),
+) -> Result<CollocationStats, Box<dyn Error>> {
+    let indices_file = dir.as_ref().join(INDICES_FILE);
+    let mut progress = Progress {
+        total: fs::metadata(&indices_file)?.len(),
+        ..Default::default()
+    };
+
+    // Headwords and readings of all tokens
+    let mut vocab: Vec<(String, Option<String>)> = vec![];
+    let mut vocab_ids: HashMap<(String, Option<String>), u32> = HashMap::new();
+    let mut sentences: Vec<Vec<Token>> = vec![];
+
+    read_tsv(&indices_file, &mut progress, &mut on_progress, |cols| {
+        let b_line = match cols {
+            [_, _, b_line, ..] => b_line,
+            _ => return,
+        };
+
+        let tokens = b_line
+            .split_whitespace()
+            .filter_map(|word| {
+                let (headword, reading) = parse_b_word(word)?;
+                let key = (normalize(headword), reading.map(normalize));
+                let id = *vocab_ids.entry(key.clone()).or_insert_with(|| {
+                    vocab.push(key);
+                    vocab.len() as u32 - 1
+                });
+                let surface = b_word_surface(word).unwrap_or(headword).to_string();
+                Some(Token { id, surface })
+            })
+            .collect::<Vec<_>>();
+
+        if !tokens.is_empty() {
+            sentences.push(tokens);
+        }
+    })?;
+    drop(vocab_ids);
+
+    let mut stats = CollocationStats::default();
+    stats.import.entries = sentences.len();
+
+    let token_counts = count_tokens(&sentences, vocab.len());
+    let pairs = score_pairs(&sentences, &token_counts);
+    stats.pairs = pairs.len();
+
+    let matcher = WordMatcher::new(&storage.words);
+    let words = &storage.words.words;
+    let mut frequencies: HashMap<u32, u32> = HashMap::new();
+    let token_words: Vec<Vec<u32>> = vocab
+        .iter()
+        .zip(token_counts)
+        .map(|((headword, reading), count)| {
+            let seqs = matcher.find(headword, reading.as_deref());
+            for seq in &seqs {
+                *frequencies.entry(*seq).or_default() += count;
+            }
+            seqs.into_iter()
+                .filter(|seq| !words.get(*seq).unwrap().has_pos(IGNORED_POS))
+                .collect()
+        })
+        .collect();
+    stats.frequencies = frequencies.len();
+
+    let mut collocations: HashMap<u32, HashSet<u32>> = HashMap::new();
+    let mut matched_pairs: HashSet<(u32, u32)> = HashSet::new();
+    for tokens in &sentences {
+        for_each_pair(tokens, |start, end| {
+            let (first, last) = (&tokens[start], &tokens[end]);
+            if !pairs.contains(&(first.id, last.id)) {
+                return;
+            }
+
+            // Dictionary form of the whole span
+            let mut written: String = tokens[start..end].iter().map(|i| &*i.surface).collect();
+            written.push_str(&vocab[last.id as usize].0);
+
+            let pair_words = || {
+                token_words[first.id as usize]
+                    .iter()
+                    .chain(&token_words[last.id as usize])
+            };
+            for collocation in matcher.find(&written, None) {
+                if pair_words().any(|i| *i == collocation) {
+                    continue;
+                }
+
+                matched_pairs.insert((first.id, last.id));
+                for word in pair_words() {
+                    collocations.entry(*word).or_default().insert(collocation);
+                }
+            }
+        });
+    }
+    drop(matcher);
+    stats.import.skipped = pairs.len() - matched_pairs.len();
+
+    let current: HashMap<u32, Vec<u32>> = storage
+        .words
+        .words
+        .iter()
+        .filter_map(|(seq, word)| Some((*seq, sorted(word.collocations.clone()?))))
+        .collect();
+
+    let mut new = match mode {
+        Mode::Augment => current.clone(),
+        Mode::Replace => HashMap::new(),
+    };
+    for (seq, extracted) in collocations {
+        let list = new.entry(seq).or_default();
+        list.extend(extracted);
+        *list = sorted(std::mem::take(list));
+    }
+
+    stats.import = ImportStats {
+        entries: stats.import.entries,
+        skipped: stats.import.skipped,
+        ..diff(&current, &new)
+    };
+
+    storage.words.insert_frequencies(frequencies);
+    storage.words.set_collocations(new);
+    Ok(stats)
+}
+
+/// Returns how often each token of the vocabulary occurs in `sentences`
+fn count_tokens(sentences: &[Vec<Token>], vocab_len: usize) -> Vec<u32> {
+    let mut token_counts = vec![0u32; vocab_len];
+    for token in sentences.iter().flatten() {
+        token_counts[token.id as usize] += 1;
+    }
+    token_counts
+}
+
+/// Returns all ordered pairs of token ids which occur at least [`MIN_COUNT`] times and have a
+/// PMI of at least [`MIN_PMI`]
+fn score_pairs(sentences: &[Vec<Token>], token_counts: &[u32]) -> HashSet<(u32, u32)> {
+    let mut pair_counts: HashMap<(u32, u32), u32> = HashMap::new();
+
+    for tokens in sentences {
+        for_each_pair(tokens, |start, end| {
+            let (first, last) = (tokens[start].id, tokens[end].id);
+            if first != last {
+                *pair_counts.entry((first, last)).or_default() += 1;
+            }
+        });
+    }
+
+    let token_total = token_counts.iter().map(|i| *i as f64).sum::<f64>();
+    let pair_total = pair_counts.values().map(|i| *i as f64).sum::<f64>();
+
+    pair_counts
+        .into_iter()
+        .filter(|(_, count)| *count >= MIN_COUNT)
+        .filter(|((first, last), count)| {
+            let p_pair = *count as f64 / pair_total;
+            let p_first = token_counts[*first as usize] as f64 / token_total;
+            let p_last = token_counts[*last as usize] as f64 / token_total;
+            (p_pair / (p_first * p_last)).log2() >= MIN_PMI
+        })
+        .map(|(pair, _)| pair)
+        .collect()
+}
+
+/// Calls `f` with the indices of all pairs of tokens at most [`WINDOW`] tokens apart
+fn for_each_pair<F: FnMut(usize, usize)>(tokens: &[Token], mut f: F) {
+    for start in 0..tokens.len() {
+        for end in (start + 1)..tokens.len().min(start + WINDOW + 1) {
+            f(start, end);
+        }
+    }
+}
+
+/// Returns the form a word of a B-line is written in within the sentence, eg. `彼の` for
+/// `彼(かれ)[01]{彼の}~`
+fn b_word_surface(s: &str) -> Option<&str> {
+    let (_, rest) = s.split_once('{')?;
+    Some(rest.split_once('}')?.0).filter(|i| !i.is_empty())
+}
+
+fn sorted(mut list: Vec<u32>) -> Vec<u32> {
+    list.sort_unstable();
+    list.dedup();
+    list
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::import::test::word;
+
+    /// Writes a Tatoeba export with the B-lines `sentences` and returns its folder
+    fn export(name: &str, sentences: &[&str]) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("jotoba_import_collocation_{name}"));
+        fs::create_dir_all(&dir).unwrap();
+
+        let lines: String = sentences
+            .iter()
+            .enumerate()
+            .map(|(id, b_line)| format!("{id}\t{id}\t{b_line}\n"))
+            .collect();
+        fs::write(dir.join(INDICES_FILE), lines).unwrap();
+        dir
+    }
+
+    /// Sentences in which `雨 が 降る` is a strongly associated pair, along with unrelated
+    /// sentences mentioning 雨具 ten times
+    fn sentences() -> Vec<String> {
+        let mut sentences = vec!["雨 が 降る".to_string(); 3];
+        sentences.extend((0..10).map(|i| format!("雨具 x{i}")));
+        sentences
+    }
+
+    fn storage() -> ResourceStorage {
+        let mut storage = ResourceStorage::new();
+        storage.words.insert_words(vec![
+            word(1, "雨", "あめ"),
+            word(2, "降る", "ふる"),
+            word(3, "雨が降る", "あめがふる"),
+            word(4, "雨具", "あまぐ"),
+        ]);
+        storage
+    }
+
+    fn collocations(storage: &ResourceStorage, seq: u32) -> Option<Vec<u32>> {
+        storage.words.words.get(seq).unwrap().collocations.clone()
+    }
+
+    #[test]
+    fn test_extract() {
+        let mut storage = storage();
+        let sentences = sentences();
+        let sentences: Vec<_> = sentences.iter().map(|i| i.as_str()).collect();
+        let dir = export("extract", &sentences);
+
+        let stats = extract(&mut storage, &dir, Mode::Replace, |_| ()).unwrap();
+        assert_eq!(stats.import.entries, 13);
+        assert_eq!(stats.import.added, 2);
+        assert_eq!(stats.frequencies, 3);
+
+        assert_eq!(collocations(&storage, 1), Some(vec![3]));
+        assert_eq!(collocations(&storage, 2), Some(vec![3]));
+        assert_eq!(collocations(&storage, 3), None);
+
+        let words = &storage.words;
+        assert_eq!(words.frequencies.get(1), Some(&3));
+        assert_eq!(words.frequencies.get(2), Some(&3));
+        assert_eq!(words.frequencies.get(4), Some(&10));
+    }
+
+    #[test]
+    fn test_extract_modes() {
+        let mut storage = storage();
+        storage
+            .words
+            .set_collocations(HashMap::from([(1, vec![4])]));
+        let sentences = sentences();
+        let sentences: Vec<_> = sentences.iter().map(|i| i.as_str()).collect();
+        let dir = export("modes", &sentences);
+
+        // Existing collocations are kept and the more frequent 雨具 comes first
+        let stats = extract(&mut storage, &dir, Mode::Augment, |_| ()).unwrap();
+        assert_eq!(stats.import.added, 1);
+        assert_eq!(stats.import.updated, 1);
+        assert_eq!(collocations(&storage, 1), Some(vec![4, 3]));
+
+        let stats = extract(&mut storage, &dir, Mode::Replace, |_| ()).unwrap();
+        assert_eq!(stats.import.updated, 1);
+        assert_eq!(collocations(&storage, 1), Some(vec![3]));
+    }
+
+    #[test]
+    fn test_b_word_surface() {
+        assert_eq!(b_word_surface("彼(かれ)[01]{彼の}~"), Some("彼の"));
+        assert_eq!(b_word_surface("彼(かれ)[01]~"), None);
+        assert_eq!(b_word_surface("彼{}"), None);
+    }
+}
@@ -0,0 +1,233 @@
+//! Import of word and kanji frequency lists. Lists contain one entry per line, ordered by
+//! frequency with the most frequent entry first. Word lists can contain the kana reading of a
+//! word in a second, tab separated column to tell apart words written the same way, and the
+//! frequency of the word in the last column. Words without a frequency get one estimated from
+//! their rank. Empty lines and lines starting with '#' are ignored
+
+use super::{diff, ImportStats, Progress, WordMatcher, BATCH_SIZE};
+use crate::{
+    storage::{kanji::KanjiStorage, word::estimate_frequency},
+    ResourceStorage,
+};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs::{self, File},
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+/// Imports the word frequency list `source`, replacing the frequencies of all words in `storage`,
+/// including corpus frequencies extracted from sentences
+pub fn import_words<S: AsRef<Path>>(
+    storage: &mut ResourceStorage,
+    source: S,
+    on_progress: impl FnMut(Progress),
+) -> Result<ImportStats, Box<dyn Error>> {
+    let matcher = WordMatcher::new(&storage.words);
+
+    let mut frequencies: HashMap<u32, u32> = HashMap::new();
+    let mut skipped = 0;
+    let entries = read_list(source.as_ref(), '\t', on_progress, |rank, cols| {
+        let (cols, freq) = match cols.split_last() {
+            Some((last, rest)) if !rest.is_empty() => match last.parse::<u32>() {
+                Ok(freq) => (rest, freq),
+                Err(_) => (cols, estimate_frequency(rank)),
+            },
+            _ => (cols, estimate_frequency(rank)),
+        };
+
+        let seqs = matcher.find(cols[0], cols.get(1).copied());
+        if seqs.is_empty() {
+            skipped += 1;
+        }
+
+        for seq in seqs {
+            // Words listed multiple times keep their highest frequency
+            let entry = frequencies.entry(seq).or_default();
+            *entry = (*entry).max(freq);
+        }
+    })?;
+    drop(matcher);
+
+    let current: HashMap<u32, u32> = storage
+        .words
+        .frequencies
+        .iter()
+        .map(|i| (*i.0, *i.1))
+        .collect();
+    let mut stats = diff(&current, &frequencies);
+    stats.entries = entries;
+    stats.skipped = skipped;
+    storage.words.insert_frequencies(frequencies);
+    Ok(stats)
+}
+
+/// Imports the kanji frequency list `source`. Kanji without a frequency rank in KANJIDIC get their
+/// rank in the list, replacing the ranks of a previously imported list
+pub fn import_kanji<S: AsRef<Path>>(
+    storage: &mut ResourceStorage,
+    source: S,
+    on_progress: impl FnMut(Progress),
+) -> Result<ImportStats, Box<dyn Error>> {
+    let mut ranks: HashMap<char, u16> = HashMap::new();
+    let entries = read_list(source.as_ref(), '\t', on_progress, |rank, cols| {
+        if let Some(literal) = cols[0].chars().next() {
+            let rank = rank.min(u16::MAX as u32) as u16;
+            ranks.entry(literal).or_insert(rank);
+        }
+    })?;
+
+    let kanji = &mut storage.kanji;
+    let listed = ranks.len();
+    ranks.retain(|lit, _| kanji.literal_index.get(*lit as u32).is_some());
+    let skipped = listed - ranks.len();
+
+    let current = list_ranks(kanji);
+    kanji.set_list_frequencies(&ranks);
+
+    let mut stats = diff(&current, &list_ranks(kanji));
+    stats.entries = entries;
+    stats.skipped = skipped;
+    Ok(stats)
+}
+
+/// Returns the frequency ranks of all kanji whose rank was taken from a frequency list
+fn list_ranks(kanji: &KanjiStorage) -> HashMap<char, u16> {
+    kanji
+        .listed_frequencies
+        .iter()
+        .filter_map(|lit| Some((*lit, kanji.literal_index.get(*lit as u32)?.frequency?)))
+        .collect()
+}
+
+/// Calls `on_entry` with the rank and the columns, separated by `sep`, of each entry of the list
+/// `source`. Returns the amount of entries
+pub(crate) fn read_list<F>(
+    source: &Path,
+    sep: char,
+    mut on_progress: impl FnMut(Progress),
+    mut on_entry: F,
+) -> Result<usize, Box<dyn Error>>
+where
+    F: FnMut(u32, &[&str]),
+{
+    let mut progress = Progress {
+        total: fs::metadata(source)?.len(),
+        ..Default::default()
+    };
+
+    for line in BufReader::new(File::open(source)?).lines() {
+        let line = line?;
+        progress.read += line.len() as u64 + 1;
+
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        progress.entries += 1;
+        let cols: Vec<_> = line.split(sep).map(|i| i.trim()).collect();
+        on_entry(progress.entries as u32, &cols);
+
+        if progress.entries % BATCH_SIZE == 0 {
+            on_progress(progress);
+        }
+    }
+
+    on_progress(progress);
+    Ok(progress.entries)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::import::test::{kanji, temp_file, word};
+
+    fn storage() -> ResourceStorage {
+        let mut storage = ResourceStorage::new();
+        storage.words.insert_words(vec![
+            word(1, "日本", "にほん"),
+            word(2, "日本", "にっぽん"),
+            word(3, "猫", "ねこ"),
+        ]);
+        storage.kanji.insert_kanji(vec![
+            kanji('日', Some(1)),
+            kanji('猫', None),
+            kanji('犬', None),
+        ]);
+        storage
+    }
+
+    #[test]
+    fn test_import_words_ranks() {
+        let mut storage = storage();
+        let source = temp_file(
+            "frequency_words_ranks",
+            "# comment\n猫\n日本\tにっぽん\n知らない\n",
+        );
+
+        let stats = import_words(&mut storage, &source, |_| ()).unwrap();
+        assert_eq!(stats.entries, 3);
+        assert_eq!(stats.added, 2);
+        assert_eq!(stats.skipped, 1);
+
+        let words = &storage.words;
+        assert_eq!(words.frequencies.get(3), Some(&estimate_frequency(1)));
+        assert_eq!(words.frequencies.get(2), Some(&estimate_frequency(2)));
+        assert_eq!(words.frequencies.get(1), None);
+        assert_eq!(words.frequency_order, vec![3, 2]);
+    }
+
+    #[test]
+    fn test_import_words_frequencies() {
+        let mut storage = storage();
+        storage.words.insert_frequencies([(1, 10), (2, 10)]);
+        let source = temp_file("frequency_words", "日本\tにほん\t500\n猫\t200\n");
+
+        let stats = import_words(&mut storage, &source, |_| ()).unwrap();
+        assert_eq!(stats.added, 1);
+        assert_eq!(stats.updated, 1);
+        assert_eq!(stats.removed, 1);
+
+        let words = &storage.words;
+        assert_eq!(words.frequencies.get(1), Some(&500));
+        assert_eq!(words.frequencies.get(2), None);
+        assert_eq!(words.frequencies.get(3), Some(&200));
+        assert_eq!(words.frequency_order, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_import_kanji() {
+        let mut storage = storage();
+        let source = temp_file("frequency_kanji", "猫\n日\n人\n");
+
+        let stats = import_kanji(&mut storage, &source, |_| ()).unwrap();
+        assert_eq!(stats.entries, 3);
+        assert_eq!(stats.added, 1);
+        assert_eq!(stats.skipped, 1);
+
+        let frequency = |storage: &ResourceStorage, lit: char| {
+            storage
+                .kanji
+                .literal_index
+                .get(lit as u32)
+                .unwrap()
+                .frequency
+        };
+
+        // KANJIDIC ranks are kept
+        assert_eq!(frequency(&storage, '日'), Some(1));
+        assert_eq!(frequency(&storage, '猫'), Some(1));
+        assert_eq!(frequency(&storage, '犬'), None);
+
+        // Ranks of the previous list get replaced
+        let source = temp_file("frequency_kanji_update", "犬\n");
+        let stats = import_kanji(&mut storage, &source, |_| ()).unwrap();
+        assert_eq!(stats.added, 1);
+        assert_eq!(stats.removed, 1);
+        assert_eq!(frequency(&storage, '日'), Some(1));
+        assert_eq!(frequency(&storage, '猫'), None);
+        assert_eq!(frequency(&storage, '犬'), Some(1));
+    }
+}
@@ -0,0 +1,262 @@
+//! Import of the source languages of loanwords (`lsource` elements) from JMdict XML files, eg.
+//! `<lsource xml:lang="ger">Arbeit</lsource>` for アルバイト. Sources become the gairaigo of the
+//! english senses of the words. Senses of other languages aren't aligned with the JMdict senses
+//! and only get a source if all sources of the entry are the same
+
+use super::{diff, sense_sentences::english_senses, ImportStats, Progress, BATCH_SIZE};
+use crate::ResourceStorage;
+use quick_xml::{
+    events::{BytesStart, Event},
+    Reader,
+};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs::{self, File},
+    io::{BufRead, BufReader},
+    path::Path,
+    str::FromStr,
+};
+use types::jotoba::words::{foreign_language::ForeignLanguage, sense::Gairaigo};
+
+/// Language of an `lsource` element without `xml:lang` attribute
+const DEFAULT_LANGUAGE: &str = "eng";
+
+/// Loanword sources of a JMdict entry
+#[derive(Debug, Default)]
+struct Entry {
+    sequence: u32,
+    /// Sources by sense, in the order of the JMdict senses
+    senses: Vec<Vec<Source>>,
+}
+
+/// A single `lsource` element
+#[derive(Debug)]
+struct Source {
+    language: String,
+    /// `ls_type="part"` marks sources the word is only partially derived from
+    fully_derived: bool,
+    original: String,
+}
+
+/// Imports the loanword sources of the JMdict file `source`, replacing the gairaigo of all
+/// senses. `entries` of the returned stats is the amount of sources and `skipped` the amount of
+/// them belonging to unknown words or having an unknown language
+pub fn import<S: AsRef<Path>>(
+    storage: &mut ResourceStorage,
+    source: S,
+    mut on_progress: impl FnMut(Progress),
+) -> Result<ImportStats, Box<dyn Error>> {
+    let source = source.as_ref();
+    let total = fs::metadata(source)?.len();
+
+    let mut stats = ImportStats::default();
+    let mut entry_count = 0;
+
+    // Gairaigo by sequence id and position of the sense within the word
+    let mut new: HashMap<(u32, usize), Gairaigo> = HashMap::new();
+
+    let mut entries = Entries::new(BufReader::new(File::open(source)?));
+    while let Some(entry) = entries.next() {
+        let entry = entry?;
+        entry_count += 1;
+
+        if entry_count % BATCH_SIZE == 0 {
+            on_progress(Progress {
+                entries: entry_count,
+                read: entries.position(),
+                total,
+            });
+        }
+
+        let source_count = entry.senses.iter().map(|i| i.len()).sum::<usize>();
+        if source_count == 0 {
+            continue;
+        }
+        stats.entries += source_count;
+
+        let word = match storage.words.words.get(entry.sequence) {
+            Some(word) => word,
+            None => {
+                stats.skipped += source_count;
+                continue;
+            }
+        };
+
+        // Only the first source with a known language is used for each sense
+        let senses: Vec<Option<Gairaigo>> = entry
+            .senses
+            .iter()
+            .map(|sources| {
+                let gairaigo = sources.iter().find_map(to_gairaigo);
+                stats.skipped += sources.len() - gairaigo.is_some() as usize;
+                gairaigo
+            })
+            .collect();
+
+        let english = english_senses(word);
+        for (nr, pos) in english.iter().enumerate() {
+            if let Some(gairaigo) = senses.get(nr).cloned().flatten() {
+                new.insert((entry.sequence, *pos), gairaigo);
+            }
+        }
+
+        let mut distinct = senses.iter().flatten();
+        let shared = distinct
+            .next()
+            .filter(|first| distinct.all(|i| i == *first));
+        if let Some(shared) = shared {
+            for pos in (0..word.senses.len()).filter(|i| !english.contains(i)) {
+                new.insert((entry.sequence, pos), shared.clone());
+            }
+        }
+    }
+
+    on_progress(Progress {
+        entries: entry_count,
+        read: total,
+        total,
+    });
+
+    let words = &mut storage.words.words;
+    let mut current = HashMap::new();
+    for (seq, word) in words.iter() {
+        for (pos, sense) in word.senses.iter().enumerate() {
+            if let Some(gairaigo) = &sense.gairaigo {
+                current.insert((*seq, pos), gairaigo.clone());
+            }
+        }
+    }
+
+    stats = ImportStats {
+        entries: stats.entries,
+        skipped: stats.skipped,
+        ..diff(&current, &new)
+    };
+
+    for (seq, word) in words.iter_mut() {
+        for (pos, sense) in word.senses.iter_mut().enumerate() {
+            sense.gairaigo = new.remove(&(*seq, pos));
+        }
+    }
+
+    Ok(stats)
+}
+
+/// Converts a source to a gairaigo. Returns `None` if its language is unknown
+fn to_gairaigo(source: &Source) -> Option<Gairaigo> {
+    Some(Gairaigo {
+        language: ForeignLanguage::from_str(&source.language).ok()?,
+        fully_derived: source.fully_derived,
+        original: source.original.clone(),
+    })
+}
+
+/// Iterator over the entries of a JMdict XML file, only reading the loanword sources
+struct Entries<R: BufRead> {
+    reader: Reader<R>,
+    buf: Vec<u8>,
+}
+
+impl<R: BufRead> Entries<R> {
+    fn new(reader: R) -> Self {
+        let mut reader = Reader::from_reader(reader);
+        reader.trim_text(true);
+        Self {
+            reader,
+            buf: vec![],
+        }
+    }
+
+    /// Returns the amount of bytes read
+    #[inline]
+    fn position(&self) -> u64 {
+        self.reader.buffer_position() as u64
+    }
+
+    fn read_entry(&mut self) -> Result<Option<Entry>, Box<dyn Error>> {
+        let mut entry: Option<Entry> = None;
+        let mut tag = String::new();
+
+        loop {
+            self.buf.clear();
+            match self.reader.read_event_into(&mut self.buf)? {
+                Event::Start(e) => {
+                    tag = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                    if let Some(entry) = entry.as_mut() {
+                        add_element(entry, &e)?;
+                    } else if tag == "entry" {
+                        entry = Some(Entry::default());
+                    }
+                }
+                Event::Empty(e) => {
+                    if let Some(entry) = entry.as_mut() {
+                        add_element(entry, &e)?;
+                    }
+                }
+                Event::Text(text) => {
+                    let entry = match entry.as_mut() {
+                        Some(entry) => entry,
+                        None => continue,
+                    };
+                    match tag.as_str() {
+                        "ent_seq" => entry.sequence = String::from_utf8_lossy(&text).parse()?,
+                        "lsource" => {
+                            let source = entry.senses.last_mut().and_then(|i| i.last_mut());
+                            if let Some(source) = source {
+                                let text = String::from_utf8_lossy(&text);
+                                source.original = quick_xml::escape::unescape(&text)?.into_owned();
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+                Event::End(e) => {
+                    if e.name().as_ref() == b"entry" {
+                        return Ok(entry);
+                    }
+                    tag.clear();
+                }
+                Event::Eof => return Ok(None),
+                _ => (),
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Entries<R> {
+    type Item = Result<Entry, Box<dyn Error>>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_entry().transpose()
+    }
+}
+
+/// Adds the opened element `e` to `entry` if it's a sense or a loanword source
+fn add_element(entry: &mut Entry, e: &BytesStart) -> Result<(), Box<dyn Error>> {
+    match e.name().as_ref() {
+        b"sense" => entry.senses.push(vec![]),
+        b"lsource" => {
+            let mut source = Source {
+                language: DEFAULT_LANGUAGE.to_string(),
+                fully_derived: true,
+                original: String::new(),
+            };
+            for attr in e.attributes() {
+                let attr = attr?;
+                let value = attr.unescape_value()?;
+                match attr.key.as_ref() {
+                    b"xml:lang" => source.language = value.into_owned(),
+                    b"ls_type" => source.fully_derived = value != "part",
+                    _ => (),
+                }
+            }
+            if let Some(sense) = entry.senses.last_mut() {
+                sense.push(source);
+            }
+        }
+        _ => (),
+    }
+    Ok(())
+}
@@ -0,0 +1,130 @@
+//! Import of JLPT levels from CSV files, replacing the levels of all words or kanji. Each line
+//! holds the level (eg. `N5` or `5`) followed by the entry:
+//!
+//! - Words: `<level>,<written>[,<kana>]` or `<level>,<sequence id>`
+//! - Kanji: `<level>,<kanji>`
+//!
+//! Empty lines, lines starting with '#' and a header line are ignored. Entries listed multiple
+//! times keep the first level. Sentence levels are estimated while generating the resources and
+//! don't get updated
+
+use super::{diff, frequency::read_list, ImportStats, Progress, WordMatcher};
+use crate::ResourceStorage;
+use std::{collections::HashMap, error::Error, path::Path};
+
+/// Valid JLPT levels
+const LEVELS: std::ops::RangeInclusive<u8> = 1..=5;
+
+/// Imports the JLPT levels of words from `source`
+pub fn import_words<S: AsRef<Path>>(
+    storage: &mut ResourceStorage,
+    source: S,
+    on_progress: impl FnMut(Progress),
+) -> Result<ImportStats, Box<dyn Error>> {
+    let matcher = WordMatcher::new(&storage.words);
+
+    let mut levels: HashMap<u32, u8> = HashMap::new();
+    let mut skipped = 0;
+    let entries = read_csv(source.as_ref(), on_progress, |level, cols| {
+        let seqs = match cols[0].parse::<u32>() {
+            Ok(seq) if storage.words.words.get(seq).is_some() => vec![seq],
+            _ => matcher.find(cols[0], cols.get(1).copied()),
+        };
+
+        if seqs.is_empty() {
+            skipped += 1;
+        }
+
+        for seq in seqs {
+            levels.entry(seq).or_insert(level);
+        }
+    })?;
+    drop(matcher);
+
+    let current: HashMap<u32, u8> = storage
+        .words
+        .words
+        .iter()
+        .filter_map(|(seq, w)| Some((*seq, w.get_jlpt_lvl()?)))
+        .collect();
+
+    let mut stats = diff(&current, &levels);
+    stats.entries = entries;
+    stats.skipped = skipped;
+    storage.words.set_jlpt_levels(levels);
+    Ok(stats)
+}
+
+/// Imports the JLPT levels of kanji from `source`
+pub fn import_kanji<S: AsRef<Path>>(
+    storage: &mut ResourceStorage,
+    source: S,
+    on_progress: impl FnMut(Progress),
+) -> Result<ImportStats, Box<dyn Error>> {
+    let kanji = &storage.kanji.literal_index;
+
+    let mut levels: HashMap<char, u8> = HashMap::new();
+    let mut skipped = 0;
+    let entries = read_csv(source.as_ref(), on_progress, |level, cols| {
+        let literal = cols[0]
+            .chars()
+            .next()
+            .filter(|i| kanji.get(*i as u32).is_some());
+        match literal {
+            Some(literal) => {
+                levels.entry(literal).or_insert(level);
+            }
+            None => skipped += 1,
+        }
+    })?;
+
+    let current: HashMap<char, u8> = kanji
+        .iter()
+        .filter_map(|(_, k)| Some((k.literal, k.jlpt?)))
+        .collect();
+
+    let mut stats = diff(&current, &levels);
+    stats.entries = entries;
+    stats.skipped = skipped;
+    storage.kanji.set_jlpt_levels(levels);
+    Ok(stats)
+}
+
+/// Calls `on_entry` with the level and the remaining columns of each line in `source`
+fn read_csv<F>(
+    source: &Path,
+    on_progress: impl FnMut(Progress),
+    mut on_entry: F,
+) -> Result<usize, Box<dyn Error>>
+where
+    F: FnMut(u8, &[&str]),
+{
+    let mut invalid: Option<String> = None;
+
+    let entries = read_list(source, ',', on_progress, |pos, cols| {
+        if cols.len() < 2 || cols[1].is_empty() {
+            return;
+        }
+
+        match parse_level(cols[0]) {
+            Some(level) => on_entry(level, &cols[1..]),
+            // Header
+            None if pos == 1 => (),
+            None => {
+                invalid.get_or_insert_with(|| cols[0].to_string());
+            }
+        }
+    })?;
+
+    if let Some(level) = invalid {
+        return Err(format!("Invalid JLPT level: {level}").into());
+    }
+
+    Ok(entries)
+}
+
+/// Parses levels like "N5", "n5" or "5"
+fn parse_level(s: &str) -> Option<u8> {
+    let s = s.strip_prefix(['N', 'n']).unwrap_or(s);
+    s.parse().ok().filter(|i| LEVELS.contains(i))
+}
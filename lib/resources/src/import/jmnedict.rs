@@ -0,0 +1,237 @@
+//! Import of names from JMnedict XML files
+
+use super::{journal::Journal, ImportStats, Progress, BATCH_SIZE};
+use crate::ResourceStorage;
+use japanese::normalize::normalize_mut;
+use quick_xml::{events::Event, Reader};
+use std::{
+    collections::HashSet,
+    error::Error,
+    fs::{self, File},
+    io::{BufRead, BufReader},
+    path::Path,
+    str::FromStr,
+};
+use types::{
+    jotoba::names::{name_type::NameType, Name},
+    raw::jmnedict::NameEntry,
+};
+
+/// Name of the import journal
+const JOURNAL: &str = "jmnedict";
+
+/// Imports the JMnedict file `source` into `storage`, which has been loaded from `storage_path`.
+/// Only names which are new or differ from the ones in `storage` are updated and names which
+/// are not in `source` anymore get removed. The returned journal, holding the new and changed
+/// names, has to be removed after `storage` got stored
+pub fn import<P: AsRef<Path>, S: AsRef<Path>>(
+    storage: &mut ResourceStorage,
+    storage_path: P,
+    source: S,
+    mut on_progress: impl FnMut(Progress),
+) -> Result<(ImportStats, Journal<Name>), Box<dyn Error>> {
+    let source = source.as_ref();
+    let total = fs::metadata(source)?.len();
+
+    let mut journal = Journal::open(storage_path, JOURNAL, source)?;
+    let mut stats = ImportStats {
+        resumed: journal.restored_entries(),
+        ..Default::default()
+    };
+
+    let mut entries = Entries::new(BufReader::new(File::open(source)?));
+    let mut seen = HashSet::new();
+
+    while let Some(entry) = entries.next() {
+        let name = to_name(entry?);
+        seen.insert(name.sequence);
+        stats.entries += 1;
+
+        // Entries processed by an interrupted run are already in the journal
+        if stats.entries > stats.resumed {
+            let changed = storage
+                .names
+                .names
+                .get(&name.sequence)
+                .map_or(true, |old| !same_name(old, &name));
+            if changed {
+                journal.push(name);
+            }
+
+            if stats.entries % BATCH_SIZE == 0 {
+                journal.commit(stats.entries)?;
+            }
+        }
+
+        if stats.entries % BATCH_SIZE == 0 {
+            on_progress(Progress {
+                entries: stats.entries,
+                read: entries.position(),
+                total,
+            });
+        }
+    }
+
+    journal.commit(stats.entries)?;
+    on_progress(Progress {
+        entries: stats.entries,
+        read: total,
+        total,
+    });
+
+    for name in journal.changes() {
+        match storage.names.names.insert(name.sequence, name.clone()) {
+            Some(_) => stats.updated += 1,
+            None => stats.added += 1,
+        }
+    }
+
+    let names = &mut storage.names;
+    let before = names.names.len();
+    names.names.retain(|seq, _| seen.contains(seq));
+    names.aliases.retain(|seq, _| seen.contains(seq));
+    stats.removed = before - names.names.len();
+
+    if stats.added + stats.updated + stats.removed > 0 {
+        storage.link_names_words();
+    }
+
+    Ok((stats, journal))
+}
+
+/// Converts a parsed entry to a name the same way names get inserted into the storage
+fn to_name(entry: NameEntry) -> Name {
+    let mut name = Name {
+        sequence: entry.sequence as u32,
+        kana: entry.kana_element,
+        kanji: entry.kanji_element,
+        transcription: entry.transcription,
+        name_type: entry.name_type,
+        xref: entry.xref,
+    };
+
+    normalize_mut(&mut name.kana);
+    if let Some(kanji) = name.kanji.as_mut() {
+        normalize_mut(kanji);
+    }
+
+    name
+}
+
+/// Returns `true` if both names have the same content. `Name`s `PartialEq` only compares the
+/// sequence ids
+fn same_name(a: &Name, b: &Name) -> bool {
+    a.kana == b.kana
+        && a.kanji == b.kanji
+        && a.transcription == b.transcription
+        && a.name_type == b.name_type
+        && a.xref == b.xref
+}
+
+/// Iterator over the entries of a JMnedict XML file. Only the first kanji and kana element and
+/// the first translation of each entry are used
+pub struct Entries<R: BufRead> {
+    reader: Reader<R>,
+    buf: Vec<u8>,
+}
+
+impl<R: BufRead> Entries<R> {
+    pub fn new(reader: R) -> Self {
+        let mut reader = Reader::from_reader(reader);
+        reader.trim_text(true);
+        Self {
+            reader,
+            buf: vec![],
+        }
+    }
+
+    /// Returns the amount of bytes read
+    #[inline]
+    pub fn position(&self) -> u64 {
+        self.reader.buffer_position() as u64
+    }
+
+    fn read_entry(&mut self) -> Result<Option<NameEntry>, Box<dyn Error>> {
+        let mut entry: Option<NameEntry> = None;
+        let mut tag = String::new();
+
+        loop {
+            self.buf.clear();
+            match self.reader.read_event_into(&mut self.buf)? {
+                Event::Start(e) => {
+                    tag = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                    if tag == "entry" {
+                        entry = Some(NameEntry::default());
+                    }
+                }
+                Event::Text(text) => {
+                    let entry = match entry.as_mut() {
+                        Some(entry) => entry,
+                        None => continue,
+                    };
+                    let text = unescape(&String::from_utf8_lossy(&text))?;
+                    add_text(entry, &tag, text)?;
+                }
+                Event::End(e) => {
+                    if e.name().as_ref() == b"entry" {
+                        return Ok(entry);
+                    }
+                    tag.clear();
+                }
+                Event::Eof => return Ok(None),
+                _ => (),
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Entries<R> {
+    type Item = Result<NameEntry, Box<dyn Error>>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_entry().transpose()
+    }
+}
+
+/// Sets the value of the element `tag` of `entry` to `text`
+fn add_text(entry: &mut NameEntry, tag: &str, text: String) -> Result<(), Box<dyn Error>> {
+    match tag {
+        "ent_seq" => entry.sequence = text.parse()?,
+        "keb" if entry.kanji_element.is_none() => entry.kanji_element = Some(text),
+        "reb" if entry.kana_element.is_empty() => entry.kana_element = text,
+        "trans_det" if entry.transcription.is_empty() => entry.transcription = text,
+        "xref" if entry.xref.is_none() => entry.xref = Some(text),
+        "name_type" => {
+            // New name types of JMnedict releases shouldn't fail the whole import
+            let name_type = match NameType::from_str(&text) {
+                Ok(name_type) => name_type,
+                Err(_) => {
+                    log::warn!(
+                        "Skipping unknown name type {text} of name {}",
+                        entry.sequence
+                    );
+                    return Ok(());
+                }
+            };
+            let types = entry.name_type.get_or_insert_with(Vec::new);
+            if !types.contains(&name_type) {
+                types.push(name_type);
+            }
+        }
+        _ => (),
+    }
+    Ok(())
+}
+
+/// Unescapes XML text. Entities defined in the JMnedict DTD (eg. `&surname;`) are replaced by
+/// their name, which matches the serialized `NameType`s
+fn unescape(text: &str) -> Result<String, Box<dyn Error>> {
+    if let Some(entity) = text.strip_prefix('&').and_then(|i| i.strip_suffix(';')) {
+        if !entity.contains(['&', ';']) {
+            return Ok(entity.to_string());
+        }
+    }
+
+    Ok(quick_xml::escape::unescape(text)?.into_owned())
+}
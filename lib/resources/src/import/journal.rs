@@ -0,0 +1,155 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    error::Error,
+    fs::{self, File, OpenOptions},
+    io::{BufReader, BufWriter, Seek, Write},
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+/// Journal of an import, written next to the storage. It holds the changes of all processed
+/// entries so an interrupted import continues where it stopped instead of starting over. The
+/// journal only gets used as long as the source file didn't change
+pub struct Journal<C> {
+    path: PathBuf,
+    out: BufWriter<File>,
+    /// Changes which have not been written yet
+    pending: Vec<C>,
+    /// Changes read from a previous run
+    restored: Vec<C>,
+    /// Amount of entries of the source processed by a previous run
+    restored_entries: usize,
+}
+
+/// Identifies the source file a journal belongs to
+#[derive(Serialize, Deserialize, PartialEq, Eq)]
+struct Header {
+    source: String,
+    len: u64,
+    modified: u64,
+}
+
+/// A batch of changes
+#[derive(Serialize, Deserialize)]
+struct Record<C> {
+    /// Total amount of entries processed after this record
+    entries: usize,
+    changes: Vec<C>,
+}
+
+impl<C: Serialize + DeserializeOwned> Journal<C> {
+    /// Opens the journal `name` for `storage`. Changes of a previous, interrupted import of the
+    /// same `source` file are restored
+    pub fn open<P: AsRef<Path>, S: AsRef<Path>>(
+        storage: P,
+        name: &str,
+        source: S,
+    ) -> Result<Self, Box<dyn Error>> {
+        let path = journal_path(storage.as_ref(), name);
+        let header = Header::new(source.as_ref())?;
+
+        let (restored, restored_entries) = match Self::restore(&path, &header) {
+            Some(restored) => restored,
+            None => {
+                let mut out = BufWriter::new(File::create(&path)?);
+                bincode::serialize_into(&mut out, &header)?;
+                out.flush()?;
+                (vec![], 0)
+            }
+        };
+
+        let out = BufWriter::new(OpenOptions::new().append(true).open(&path)?);
+        Ok(Self {
+            path,
+            out,
+            pending: vec![],
+            restored,
+            restored_entries,
+        })
+    }
+
+    /// Reads all complete records of the journal at `path` if it belongs to `header`
+    fn restore(path: &Path, header: &Header) -> Option<(Vec<C>, usize)> {
+        let mut reader = BufReader::new(File::open(path).ok()?);
+        let file_header: Header = bincode::deserialize_from(&mut reader).ok()?;
+        if file_header != *header {
+            return None;
+        }
+
+        let mut changes = vec![];
+        let mut entries = 0;
+        let mut len = reader.stream_position().ok()?;
+
+        // A record which is cut off was written while the import got interrupted and gets
+        // processed again
+        while let Ok(record) = bincode::deserialize_from::<_, Record<C>>(&mut reader) {
+            changes.extend(record.changes);
+            entries = record.entries;
+            len = reader.stream_position().ok()?;
+        }
+
+        // Drop the incomplete tail so new records follow the last complete one
+        let file = OpenOptions::new().write(true).open(path).ok()?;
+        file.set_len(len).ok()?;
+
+        Some((changes, entries))
+    }
+
+    /// Amount of entries of the source which were processed by a previous run and can be
+    /// skipped
+    #[inline]
+    pub fn restored_entries(&self) -> usize {
+        self.restored_entries
+    }
+
+    /// Adds a change
+    #[inline]
+    pub fn push(&mut self, change: C) {
+        self.pending.push(change);
+    }
+
+    /// Writes all pending changes to the journal. `entries` is the total amount of processed
+    /// entries
+    pub fn commit(&mut self, entries: usize) -> Result<(), Box<dyn Error>> {
+        let record = Record {
+            entries,
+            changes: std::mem::take(&mut self.pending),
+        };
+        bincode::serialize_into(&mut self.out, &record)?;
+        self.out.flush()?;
+        self.restored.extend(record.changes);
+        Ok(())
+    }
+
+    /// Returns all changes of the import
+    #[inline]
+    pub fn changes(&self) -> &[C] {
+        &self.restored
+    }
+
+    /// Deletes the journal. Has to be called once the changes have been stored
+    pub fn remove(self) -> Result<(), Box<dyn Error>> {
+        drop(self.out);
+        fs::remove_file(self.path)?;
+        Ok(())
+    }
+}
+
+impl Header {
+    fn new(source: &Path) -> Result<Self, Box<dyn Error>> {
+        let meta = fs::metadata(source)?;
+        let modified = meta.modified()?.duration_since(UNIX_EPOCH)?.as_secs();
+        Ok(Self {
+            source: source.to_string_lossy().to_string(),
+            len: meta.len(),
+            modified,
+        })
+    }
+}
+
+/// Returns the path of the journal `name` of `storage`
+fn journal_path(storage: &Path, name: &str) -> PathBuf {
+    let mut file = storage.as_os_str().to_owned();
+    file.push(format!(".{name}.journal"));
+    PathBuf::from(file)
+}
@@ -0,0 +1,40 @@
+//! Import of kanji mnemonics from tab separated files. Each line holds a kanji followed by the
+//! text of its mnemonic: `<kanji>\t<mnemonic>`. Empty lines and lines starting with '#' are
+//! ignored. Kanji listed multiple times keep the first mnemonic
+
+use super::{diff, frequency::read_list, ImportStats, Progress};
+use crate::ResourceStorage;
+use std::{collections::HashMap, error::Error, path::Path};
+
+/// Imports the mnemonics of `source`, replacing the mnemonics of all kanji in `storage`
+pub fn import<S: AsRef<Path>>(
+    storage: &mut ResourceStorage,
+    source: S,
+    on_progress: impl FnMut(Progress),
+) -> Result<ImportStats, Box<dyn Error>> {
+    let kanji = &storage.kanji.literal_index;
+
+    let mut mnemonics: HashMap<char, String> = HashMap::new();
+    let mut skipped = 0;
+    let entries = read_list(source.as_ref(), '\t', on_progress, |_, cols| {
+        let entry = match cols {
+            [literal, text, ..] if !text.is_empty() => literal.chars().next().map(|i| (i, text)),
+            _ => None,
+        };
+
+        match entry.filter(|(literal, _)| kanji.get(*literal as u32).is_some()) {
+            Some((literal, text)) => {
+                mnemonics.entry(literal).or_insert_with(|| text.to_string());
+            }
+            None => skipped += 1,
+        }
+    })?;
+
+    let mut stats = diff(&storage.kanji.mnemonics, &mnemonics);
+    stats.entries = entries;
+    stats.skipped = skipped;
+    storage
+        .kanji
+        .insert_mnemonics(mnemonics.into_iter().collect());
+    Ok(stats)
+}
@@ -0,0 +1,193 @@
+//! Importers updating an existing resource storage from upstream data files. Imports only
+//! apply the differences to the current storage and can be resumed if they get interrupted (see
+//! [`journal::Journal`])
+
+pub mod aliases;
+pub mod collocation;
+pub mod frequency;
+pub mod gairaigo;
+pub mod jlpt;
+pub mod jmnedict;
+pub mod journal;
+pub mod mnemonics;
+pub mod pitch;
+pub mod radkfile;
+pub mod references;
+pub mod revisions;
+pub mod sense_sentences;
+pub mod tatoeba;
+pub mod transitivity;
+pub mod wanikani;
+
+use crate::storage::word::WordStorage;
+use japanese::normalize::normalize;
+use std::{collections::HashMap, hash::Hash};
+
+/// Amount of entries processed between two journal writes
+pub const BATCH_SIZE: usize = 1000;
+
+/// Progress of a running import
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Progress {
+    /// Amount of entries processed
+    pub entries: usize,
+    /// Bytes of the source file read
+    pub read: u64,
+    /// Size of the source file in bytes
+    pub total: u64,
+}
+
+impl Progress {
+    /// Returns the progress in percent
+    #[inline]
+    pub fn percent(&self) -> f32 {
+        if self.total == 0 {
+            return 100.0;
+        }
+        (self.read as f32 / self.total as f32 * 100.0).min(100.0)
+    }
+}
+
+/// Summary of a finished import
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ImportStats {
+    /// Amount of entries in the source file
+    pub entries: usize,
+    pub added: usize,
+    pub updated: usize,
+    pub removed: usize,
+    /// Amount of entries which were already processed by an interrupted import
+    pub resumed: usize,
+    /// Amount of entries for which the sources disagreed
+    pub conflicts: usize,
+    /// Amount of entries which don't belong to any entry of the storage
+    pub skipped: usize,
+}
+
+/// Compares the values assigned to entries before and after an import
+pub(crate) fn diff<K: Eq + Hash, V: PartialEq>(
+    old: &HashMap<K, V>,
+    new: &HashMap<K, V>,
+) -> ImportStats {
+    let mut stats = ImportStats::default();
+    for (id, value) in new {
+        match old.get(id) {
+            Some(old) if old == value => (),
+            Some(_) => stats.updated += 1,
+            None => stats.added += 1,
+        }
+    }
+    stats.removed = old.keys().filter(|i| !new.contains_key(i)).count();
+    stats
+}
+
+/// Finds words by the way they're written in data files
+pub(crate) struct WordMatcher<'a> {
+    words: &'a WordStorage,
+    by_main: HashMap<&'a str, Vec<u32>>,
+    by_any: HashMap<&'a str, Vec<u32>>,
+}
+
+impl<'a> WordMatcher<'a> {
+    pub(crate) fn new(words: &'a WordStorage) -> Self {
+        let mut by_main: HashMap<&str, Vec<u32>> = HashMap::new();
+        let mut by_any: HashMap<&str, Vec<u32>> = HashMap::new();
+        for word in words.words.iter().map(|i| i.1) {
+            by_main
+                .entry(word.get_reading_str())
+                .or_default()
+                .push(word.sequence);
+            for reading in word.reading_iter(true) {
+                by_any
+                    .entry(reading.reading.as_str())
+                    .or_default()
+                    .push(word.sequence);
+            }
+        }
+
+        Self {
+            words,
+            by_main,
+            by_any,
+        }
+    }
+
+    /// Returns the sequence ids of all words written as `written`, preferring words having it as
+    /// main reading. If `kana` is set, only words with this kana reading are returned
+    pub(crate) fn find(&self, written: &str, kana: Option<&str>) -> Vec<u32> {
+        let written = normalize(written);
+        let kana = kana.filter(|i| !i.is_empty()).map(normalize);
+
+        self.by_main
+            .get(written.as_str())
+            .or_else(|| self.by_any.get(written.as_str()))
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|seq| {
+                let word = self.words.words.get(*seq).unwrap();
+                kana.as_ref().map_or(true, |kana| word.get_kana() == kana)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::path::PathBuf;
+    use types::jotoba::{
+        kanji::Kanji,
+        words::{dict::Dict, reading::Reading, Word},
+    };
+
+    /// Returns a word written as `kanji` and read as `kana`
+    pub(crate) fn word(sequence: u32, kanji: &str, kana: &str) -> Word {
+        let dict = |reading: &str, kanji| Dict {
+            reading: reading.to_string(),
+            kanji,
+            is_main: true,
+            ..Default::default()
+        };
+        Word {
+            sequence,
+            reading: Reading {
+                kana: dict(kana, false),
+                kanji: Some(dict(kanji, true)),
+                alternative: vec![],
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Returns a kanji without any data besides its KANJIDIC `frequency` rank
+    pub(crate) fn kanji(literal: char, frequency: Option<u16>) -> Kanji {
+        Kanji {
+            literal,
+            grade: None,
+            stroke_count: 1,
+            frequency,
+            jlpt: None,
+            variant: vec![],
+            onyomi: vec![],
+            nanori: vec![],
+            kunyomi: vec![],
+            chinese: vec![],
+            korean_r: vec![],
+            korean_h: vec![],
+            vietnamese: vec![],
+            kun_dicts: vec![],
+            on_dicts: vec![],
+            similar_kanji: vec![],
+            meanings: vec![],
+            radical: Default::default(),
+            parts: vec![],
+        }
+    }
+
+    /// Writes `content` into the temporary file `name` and returns its path
+    pub(crate) fn temp_file(name: &str, content: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("jotoba_import_{name}"));
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+}
@@ -0,0 +1,208 @@
+//! Import of pitch accents from tab separated files in the format of Kanjium's `accents.txt`:
+//! `<written>\t<kana>\t<drops>`. `kana` is empty for words written in kana only and `drops` is
+//! a comma separated list of downsteps which may be annotated, eg. `0,2` or `(名)0,(副)1`
+
+use super::{ImportStats, Progress, BATCH_SIZE};
+use crate::ResourceStorage;
+use japanese::normalize::normalize;
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs::{self, File},
+    io::{BufRead, BufReader},
+    path::Path,
+    str::FromStr,
+};
+use types::jotoba::words::pitch::raw_data::PitchValues;
+
+/// Max amount of accents a reading can have
+const MAX_ACCENTS: usize = 4;
+
+/// Highest downstep which can be stored
+const MAX_DROP: u8 = 6;
+
+/// How the accents of a reading get chosen if multiple sources have accents for it. Accents
+/// already in the storage are treated like a source with the lowest priority
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Resolution {
+    /// Uses the accents of the first source having the reading
+    #[default]
+    First,
+    /// Uses the accents of all sources, ordered by the sources
+    Union,
+}
+
+impl FromStr for Resolution {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "first" => Ok(Self::First),
+            "union" => Ok(Self::Union),
+            _ => Err(format!("Unknown resolution: {s}")),
+        }
+    }
+}
+
+/// A single line of a pitch accent file
+#[derive(Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub written: String,
+    pub kana: String,
+    pub drops: Vec<u8>,
+}
+
+impl Entry {
+    /// Parses a line of a pitch accent file
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut cols = line.split('\t');
+        let written = normalize(cols.next()?.trim());
+        let kana = cols.next()?.trim();
+        let kana = if kana.is_empty() {
+            written.clone()
+        } else {
+            normalize(kana)
+        };
+
+        let drops = cols
+            .next()?
+            .split(',')
+            .filter_map(|i| {
+                let digits: String = i.chars().filter(|c| c.is_ascii_digit()).collect();
+                digits.parse().ok()
+            })
+            .collect::<Vec<u8>>();
+
+        if written.is_empty() || drops.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            written,
+            kana,
+            drops,
+        })
+    }
+}
+
+/// Imports the pitch accents of all `sources` into the words of `storage`. Sources are ordered
+/// by their priority, highest first. Entries get assigned to all words having a reading written
+/// like the entry together with the entries kana reading
+pub fn import<S: AsRef<Path>>(
+    storage: &mut ResourceStorage,
+    sources: &[S],
+    resolution: Resolution,
+    mut on_progress: impl FnMut(Progress),
+) -> Result<ImportStats, Box<dyn Error>> {
+    let total = sources
+        .iter()
+        .map(|i| fs::metadata(i).map(|i| i.len()))
+        .sum::<Result<u64, _>>()?;
+    let mut progress = Progress {
+        total,
+        ..Default::default()
+    };
+
+    let mut by_written: HashMap<&str, Vec<u32>> = HashMap::new();
+    for word in storage.words.words.iter().map(|i| i.1) {
+        for reading in word.reading_iter(true) {
+            by_written
+                .entry(reading.reading.as_str())
+                .or_default()
+                .push(word.sequence);
+        }
+    }
+
+    // Accents of each source for word readings, in order of the sources
+    let mut found: HashMap<(u32, String), Vec<(usize, Vec<u8>)>> = HashMap::new();
+
+    for (src_id, source) in sources.iter().enumerate() {
+        for line in BufReader::new(File::open(source)?).lines() {
+            let line = line?;
+            progress.read += line.len() as u64 + 1;
+            progress.entries += 1;
+            if progress.entries % BATCH_SIZE == 0 {
+                on_progress(progress);
+            }
+
+            let entry = match Entry::parse(&line) {
+                Some(entry) => entry,
+                None => continue,
+            };
+
+            let seqs = match by_written.get(entry.written.as_str()) {
+                Some(seqs) => seqs,
+                None => continue,
+            };
+
+            for seq in seqs {
+                let accents = found.entry((*seq, entry.kana.clone())).or_default();
+                // Only the first entry of each source counts
+                if accents.last().map_or(true, |i| i.0 != src_id) {
+                    accents.push((src_id, entry.drops.clone()));
+                }
+            }
+        }
+    }
+    on_progress(Progress {
+        read: total,
+        ..progress
+    });
+    drop(by_written);
+
+    let mut stats = ImportStats {
+        entries: progress.entries,
+        ..Default::default()
+    };
+
+    let words = &mut storage.words;
+    for ((seq, kana), accents) in found {
+        let current = words.get_accents(seq, &kana).unwrap_or_default();
+
+        if accents.iter().skip(1).any(|i| i.1 != accents[0].1) {
+            stats.conflicts += 1;
+        }
+
+        let drops = resolve(resolution, &accents, current);
+        if drops.is_empty() {
+            continue;
+        }
+
+        let new = PitchValues::new(&drops);
+        if new == current || !words.set_accents(seq, &kana, new) {
+            continue;
+        }
+
+        if current.is_empty() {
+            stats.added += 1;
+        } else {
+            stats.updated += 1;
+        }
+    }
+
+    Ok(stats)
+}
+
+/// Chooses the accents of a reading out of the accents of all sources and the `current` accents
+fn resolve(resolution: Resolution, accents: &[(usize, Vec<u8>)], current: PitchValues) -> Vec<u8> {
+    let current: Vec<u8> = current.iter().collect();
+    let all = accents
+        .iter()
+        .map(|i| &i.1)
+        .chain(std::iter::once(&current));
+
+    let mut out: Vec<u8> = vec![];
+    for drops in all {
+        for drop in drops.iter().copied().filter(|i| *i <= MAX_DROP) {
+            if out.len() < MAX_ACCENTS && !out.contains(&drop) {
+                out.push(drop);
+            }
+        }
+
+        if resolution == Resolution::First && !out.is_empty() {
+            break;
+        }
+    }
+
+    out
+}
@@ -0,0 +1,171 @@
+//! Import of the radicals used to search kanji. Accepts UTF-8 encoded files in the format of the
+//! radkfile, listing the kanji of each radical:
+//!
+//! ```text
+//! $ 一 1
+//! 一七万丈三上下不与...
+//! ```
+//!
+//! or in the format of the kradfile, listing the radicals of each kanji:
+//!
+//! ```text
+//! 亜 : ｜ 一 口
+//! ```
+//!
+//! Lines starting with '#' are ignored. Radicals replaced by placeholder kanji in the original
+//! radkfile are mapped to the radicals used by the search
+
+use super::{diff, ImportStats, Progress};
+use crate::ResourceStorage;
+use japanese::radicals::is_radical;
+use std::{
+    collections::{BTreeSet, HashMap},
+    error::Error,
+    fs,
+    path::Path,
+};
+
+/// Placeholder kanji of the radkfile for radicals not in JIS X 0208 and the radicals they stand
+/// for
+const PLACEHOLDERS: &[(char, char)] = &[
+    ('化', '⺅'),
+    ('个', '𠆢'),
+    ('并', '丷'),
+    ('刈', '⺉'),
+    ('乞', '𠂉'),
+    ('込', '⻌'),
+    ('尚', '⺌'),
+    ('忙', '⺖'),
+    ('扎', '⺘'),
+    ('汁', '⺡'),
+    ('犯', '⺨'),
+    ('艾', '⺾'),
+    ('邦', '⻏'),
+    ('阡', '⻖'),
+    ('老', '⺹'),
+    ('杰', '⺣'),
+    ('礼', '⺭'),
+    ('疔', '疒'),
+    ('禹', '禸'),
+    ('初', '⻂'),
+    ('買', '罒'),
+    ('滴', '啇'),
+];
+
+/// Summary of a radical import
+#[derive(Clone, Debug, Default)]
+pub struct RadicalStats {
+    pub import: ImportStats,
+    /// Radicals of the source which can't be used to search kanji
+    pub unknown: BTreeSet<char>,
+}
+
+/// Imports the decompositions of `source`, replacing the radicals of all kanji in `storage`. The
+/// storage is left untouched if the source doesn't contain any known kanji
+pub fn import<S: AsRef<Path>>(
+    storage: &mut ResourceStorage,
+    source: S,
+    mut on_progress: impl FnMut(Progress),
+) -> Result<RadicalStats, Box<dyn Error>> {
+    let content = fs::read_to_string(source.as_ref())
+        .map_err(|err| format!("Failed to read radical file (has to be UTF-8): {err}"))?;
+
+    let mut progress = Progress {
+        total: content.len() as u64,
+        ..Default::default()
+    };
+
+    let is_kradfile = content
+        .lines()
+        .find(|i| !i.starts_with('#') && !i.trim().is_empty())
+        .map(|i| i.contains(" : "))
+        .unwrap_or(false);
+
+    let mut stats = RadicalStats::default();
+    let mut parts: HashMap<char, Vec<char>> = HashMap::new();
+    let mut add = |literal: char, radical: char| {
+        let radical = placeholder_radical(radical);
+        if !is_radical(radical) {
+            stats.unknown.insert(radical);
+            return;
+        }
+        parts.entry(literal).or_default().push(radical);
+    };
+
+    let mut radical: Option<char> = None;
+    for line in content.lines() {
+        progress.read += line.len() as u64 + 1;
+        if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+        progress.entries += 1;
+
+        if is_kradfile {
+            let (literal, radicals) = line
+                .split_once(" : ")
+                .ok_or_else(|| format!("Invalid kradfile line: {line}"))?;
+            let literal = literal.trim().chars().next().ok_or("Missing kanji")?;
+            for rad in radicals.split_whitespace().filter_map(|i| i.chars().next()) {
+                add(literal, rad);
+            }
+        } else if let Some(header) = line.strip_prefix('$') {
+            let rad = header
+                .split_whitespace()
+                .next()
+                .and_then(|i| i.chars().next());
+            radical = Some(rad.ok_or_else(|| format!("Invalid radical line: {line}"))?);
+        } else {
+            let rad = radical.ok_or("Kanji listed before the first radical")?;
+            for literal in line.chars().filter(|i| !i.is_whitespace()) {
+                add(literal, rad);
+            }
+        }
+
+        on_progress(progress);
+    }
+
+    let kanji = &storage.kanji.literal_index;
+    let before = parts.len();
+    parts.retain(|literal, _| kanji.contains_key(*literal as u32));
+    stats.import.skipped = before - parts.len();
+
+    if parts.is_empty() {
+        return Err("The radical file doesn't contain any known kanji".into());
+    }
+
+    for (_, v) in parts.iter_mut() {
+        v.sort_unstable();
+        v.dedup();
+    }
+
+    let current: HashMap<char, Vec<char>> = kanji
+        .iter()
+        .filter(|i| !i.1.parts.is_empty())
+        .map(|i| (i.1.literal, sorted(&i.1.parts)))
+        .collect();
+    let entries = parts.len();
+    stats.import = ImportStats {
+        entries,
+        skipped: stats.import.skipped,
+        ..diff(&current, &parts)
+    };
+
+    storage.kanji.set_radicals(parts);
+    Ok(stats)
+}
+
+/// Returns the radical represented by `c` if it's a placeholder of the radkfile
+fn placeholder_radical(c: char) -> char {
+    PLACEHOLDERS
+        .iter()
+        .find(|i| i.0 == c)
+        .map(|i| i.1)
+        .unwrap_or(c)
+}
+
+fn sorted(chars: &[char]) -> Vec<char> {
+    let mut chars = chars.to_vec();
+    chars.sort_unstable();
+    chars.dedup();
+    chars
+}
@@ -0,0 +1,70 @@
+//! Resolves the cross references (`xref`) and antonyms (`ant`) of senses, which JMdict states by
+//! the written form of the referenced word, to the sequence ids of the referenced words
+
+use super::{diff, ImportStats, WordMatcher};
+use crate::ResourceStorage;
+use std::collections::HashMap;
+use types::jotoba::words::reference::{Reference, ReferenceKind};
+
+/// Replaces the resolved references of all words with the references of their senses. `entries`
+/// of the returned stats is the amount of references and `skipped` the amount of them not
+/// matching any word
+pub fn link(storage: &mut ResourceStorage) -> ImportStats {
+    let matcher = WordMatcher::new(&storage.words);
+    let mut stats = ImportStats::default();
+
+    let mut references: HashMap<u32, Vec<Reference>> = HashMap::new();
+    for (seq, word) in storage.words.words.iter() {
+        for sense in &word.senses {
+            let raw = [
+                (ReferenceKind::SeeAlso, &sense.xref),
+                (ReferenceKind::Antonym, &sense.antonym),
+            ];
+
+            for (kind, raw) in raw {
+                let (written, reading, target_sense) =
+                    match raw.as_deref().and_then(Reference::parse_raw) {
+                        Some(parsed) => parsed,
+                        None => continue,
+                    };
+                stats.entries += 1;
+
+                // Words written the same way as the referenced one are ambiguous, so the one
+                // preferred by the matcher gets linked
+                let target = matcher
+                    .find(written, reading)
+                    .into_iter()
+                    .find(|i| *i != *seq);
+                let target = match target {
+                    Some(target) => target,
+                    None => {
+                        stats.skipped += 1;
+                        continue;
+                    }
+                };
+
+                let reference = Reference {
+                    kind,
+                    sense: sense.id,
+                    target,
+                    target_sense,
+                };
+                let list = references.entry(*seq).or_default();
+                if !list.contains(&reference) {
+                    list.push(reference);
+                }
+            }
+        }
+    }
+    drop(matcher);
+
+    let current = storage.words.references.clone();
+    stats = ImportStats {
+        entries: stats.entries,
+        skipped: stats.skipped,
+        ..diff(&current, &references)
+    };
+
+    storage.words.set_references(references);
+    stats
+}
@@ -0,0 +1,204 @@
+//! Import of the revision dates of words from JMdict XML releases. Each entry gets compared with
+//! the same entry of the previously imported release and entries which were added or changed get
+//! the date of the release (`<!-- JMdict created: 2023-01-14 -->`) as revision date
+
+use super::{ImportStats, Progress, BATCH_SIZE};
+use crate::ResourceStorage;
+use intmap::IntMap;
+use quick_xml::{events::Event, Reader};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs::{self, File},
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+/// Summary of an import of revision dates
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RevisionStats {
+    pub import: ImportStats,
+    /// Date (yyyymmdd) of the imported release
+    pub release: u32,
+    /// `true` if no release has been imported before. The first import only records the entries
+    /// the next releases get compared with and doesn't assign any revision dates
+    pub baseline: bool,
+}
+
+/// Imports the revision dates of words from the JMdict release `source`. `added` and `updated` of
+/// the returned stats are the amount of words which were added or changed in this release
+pub fn import<S: AsRef<Path>>(
+    storage: &mut ResourceStorage,
+    source: S,
+    mut on_progress: impl FnMut(Progress),
+) -> Result<RevisionStats, Box<dyn Error>> {
+    let source = source.as_ref();
+    let total = fs::metadata(source)?.len();
+
+    let words = &storage.words;
+    let baseline = words.revision_hashes.is_empty();
+
+    let mut stats = ImportStats::default();
+    let mut hashes = IntMap::new();
+    let mut dates = HashMap::new();
+
+    let mut entries = Entries::new(BufReader::new(File::open(source)?));
+    while let Some(entry) = entries.next() {
+        let (seq, hash) = entry?;
+        let release = entries.release.ok_or("JMdict release date not found")?;
+        stats.entries += 1;
+
+        if stats.entries % BATCH_SIZE == 0 {
+            on_progress(Progress {
+                entries: stats.entries,
+                read: entries.position(),
+                total,
+            });
+        }
+
+        if words.words.get(seq).is_none() {
+            stats.skipped += 1;
+            continue;
+        }
+
+        hashes.insert(seq, hash);
+
+        match words.revision_hashes.get(seq) {
+            Some(old) if *old == hash => {
+                if let Some(date) = words.revision_dates.get(seq) {
+                    dates.insert(seq, *date);
+                }
+            }
+            Some(_) => {
+                dates.insert(seq, release);
+                stats.updated += 1;
+            }
+            None if baseline => (),
+            None => {
+                dates.insert(seq, release);
+                stats.added += 1;
+            }
+        }
+    }
+
+    on_progress(Progress {
+        entries: stats.entries,
+        read: total,
+        total,
+    });
+
+    let release = entries.release.ok_or("JMdict release date not found")?;
+
+    let words = &mut storage.words;
+    stats.removed = words
+        .revision_hashes
+        .iter()
+        .filter(|i| hashes.get(*i.0).is_none())
+        .count();
+    words.revision_hashes = hashes;
+    words.insert_revisions(dates);
+
+    Ok(RevisionStats {
+        import: stats,
+        release,
+        baseline,
+    })
+}
+
+/// Iterator over the entries of a JMdict XML file, returning the sequence id and a fingerprint
+/// of the content of each entry
+struct Entries<R: BufRead> {
+    reader: Reader<R>,
+    buf: Vec<u8>,
+    /// Date of the release, read from the comment in front of the entries
+    release: Option<u32>,
+}
+
+impl<R: BufRead> Entries<R> {
+    fn new(reader: R) -> Self {
+        let mut reader = Reader::from_reader(reader);
+        reader.trim_text(true);
+        Self {
+            reader,
+            buf: vec![],
+            release: None,
+        }
+    }
+
+    /// Returns the amount of bytes read
+    #[inline]
+    fn position(&self) -> u64 {
+        self.reader.buffer_position() as u64
+    }
+
+    fn read_entry(&mut self) -> Result<Option<(u32, u64)>, Box<dyn Error>> {
+        let mut in_entry = false;
+        let mut in_seq = false;
+        let mut seq = None;
+        let mut hash = FNV_OFFSET;
+
+        loop {
+            self.buf.clear();
+            match self.reader.read_event_into(&mut self.buf)? {
+                Event::Comment(text) if !in_entry => {
+                    if self.release.is_none() {
+                        self.release = parse_release(&String::from_utf8_lossy(&text));
+                    }
+                }
+                Event::Start(e) if e.name().as_ref() == b"entry" => in_entry = true,
+                Event::End(e) if e.name().as_ref() == b"entry" => {
+                    let seq = seq.ok_or("JMdict entry without sequence id")?;
+                    return Ok(Some((seq, hash)));
+                }
+                _ if !in_entry => (),
+                Event::Start(e) => {
+                    in_seq = e.name().as_ref() == b"ent_seq";
+                    hash = fnv(fnv(hash, b"<"), &e);
+                }
+                Event::Empty(e) => hash = fnv(fnv(hash, b"/"), &e),
+                Event::Text(text) => {
+                    if in_seq {
+                        seq = Some(String::from_utf8_lossy(&text).parse()?);
+                    }
+                    hash = fnv(fnv(hash, b"\""), &text);
+                }
+                Event::End(e) => {
+                    in_seq = false;
+                    hash = fnv(fnv(hash, b">"), e.name().as_ref());
+                }
+                Event::Eof => return Ok(None),
+                _ => (),
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Entries<R> {
+    type Item = Result<(u32, u64), Box<dyn Error>>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_entry().transpose()
+    }
+}
+
+/// Parses the date of a `JMdict created: yyyy-mm-dd` comment into yyyymmdd
+fn parse_release(comment: &str) -> Option<u32> {
+    let date = comment.trim().strip_prefix("JMdict created:")?.trim();
+    let date: String = date.chars().filter(|i| *i != '-').collect();
+    if date.len() != 8 {
+        return None;
+    }
+    date.parse().ok()
+}
+
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// FNV-1a hash of `bytes`, continuing from `hash`. Unlike std's hashers it is stable across
+/// builds, which is required for comparing fingerprints of different imports
+fn fnv(hash: u64, bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(hash, |hash, b| (hash ^ *b as u64).wrapping_mul(FNV_PRIME))
+}
@@ -0,0 +1,217 @@
+//! Import of the Tanaka corpus word indices (Tatoeba's `jpn_indices.csv`:
+//! `<sentence id>\t<meaning id>\t<B-line>`). Words of a B-line can state the number of the
+//! JMdict sense they're used in, eg. `彼(かれ)[01]{彼の}~`, and are used in their first sense
+//! otherwise. Words are marked with `~` if the sentence is a checked example of this sense. The
+//! sentences become the example sentences of the english senses of the words, replacing the
+//! existing ones of these senses. Senses without any sentence keep their example sentences, just
+//! like senses of other languages which aren't aligned with the JMdict sense numbers
+
+use super::{
+    diff, frequency::read_list, tatoeba::parse_b_word, ImportStats, Progress, WordMatcher,
+};
+use crate::ResourceStorage;
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{self, Display},
+    path::Path,
+};
+use types::jotoba::{language::Language, words::Word};
+
+/// Max amount of examples printed per kind of inconsistency
+const MAX_EXAMPLES: usize = 10;
+
+/// A sense annotation of the indices which couldn't be applied
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Inconsistency {
+    /// The word has less senses than the annotation states
+    UnknownSense { sentence: u32, word: u32, sense: u8 },
+    /// The annotated word matches multiple words
+    Ambiguous { sentence: u32, written: String },
+    /// The annotated sentence isn't in the storage
+    MissingSentence { sentence: u32 },
+}
+
+impl Inconsistency {
+    /// Short name of the kind of the inconsistency
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Inconsistency::UnknownSense { .. } => "unknown senses",
+            Inconsistency::Ambiguous { .. } => "ambiguous words",
+            Inconsistency::MissingSentence { .. } => "missing sentences",
+        }
+    }
+}
+
+impl Display for Inconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Inconsistency::UnknownSense {
+                sentence,
+                word,
+                sense,
+            } => write!(f, "sentence {sentence} uses sense {sense} of word {word}"),
+            Inconsistency::Ambiguous { sentence, written } => {
+                write!(f, "sentence {sentence} uses {written}")
+            }
+            Inconsistency::MissingSentence { sentence } => write!(f, "sentence {sentence}"),
+        }
+    }
+}
+
+/// Summary of a sense alignment import
+#[derive(Clone, Debug, Default)]
+pub struct AlignmentReport {
+    /// Changes of the example sentences of senses. `entries` is the amount of sense annotations
+    /// and `skipped` the amount of annotated words not matching any word
+    pub import: ImportStats,
+    pub inconsistencies: Vec<Inconsistency>,
+}
+
+impl Display for AlignmentReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut by_kind: Vec<(&str, Vec<&Inconsistency>)> = vec![];
+        for inconsistency in &self.inconsistencies {
+            match by_kind.iter_mut().find(|i| i.0 == inconsistency.kind()) {
+                Some(kind) => kind.1.push(inconsistency),
+                None => by_kind.push((inconsistency.kind(), vec![inconsistency])),
+            }
+        }
+
+        for (kind, inconsistencies) in by_kind {
+            writeln!(f, "{} {kind}", inconsistencies.len())?;
+            for inconsistency in inconsistencies.iter().take(MAX_EXAMPLES) {
+                writeln!(f, "  {inconsistency}")?;
+            }
+            if inconsistencies.len() > MAX_EXAMPLES {
+                writeln!(f, "  ... and {} more", inconsistencies.len() - MAX_EXAMPLES)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Imports the word indices `source`, replacing the example sentences of all english senses used
+/// by a sentence
+pub fn import<S: AsRef<Path>>(
+    storage: &mut ResourceStorage,
+    source: S,
+    on_progress: impl FnMut(Progress),
+) -> Result<AlignmentReport, Box<dyn Error>> {
+    let matcher = WordMatcher::new(&storage.words);
+    let sentences = storage.sentences();
+
+    let mut report = AlignmentReport::default();
+
+    // Example sentences by sequence id and english sense number, along with whether they're
+    // checked examples
+    let mut examples: HashMap<(u32, u8), (u32, bool)> = HashMap::new();
+
+    read_list(source.as_ref(), '\t', on_progress, |_, cols| {
+        let (sentence, b_line) = match cols {
+            [id, _, b_line, ..] => match id.parse::<u32>() {
+                Ok(id) => (id, b_line),
+                Err(_) => return,
+            },
+            _ => return,
+        };
+
+        let mut exists = None;
+        for b_word in b_line.split_whitespace() {
+            let sense = match b_word_sense(b_word) {
+                Some(sense) => sense,
+                None => continue,
+            };
+            let (written, reading) = match parse_b_word(b_word) {
+                Some(word) => word,
+                None => continue,
+            };
+            report.import.entries += 1;
+
+            if !*exists.get_or_insert_with(|| sentences.by_id(sentence).is_some()) {
+                report
+                    .inconsistencies
+                    .push(Inconsistency::MissingSentence { sentence });
+                break;
+            }
+
+            let seq = match matcher.find(written, reading).as_slice() {
+                [] => {
+                    report.import.skipped += 1;
+                    continue;
+                }
+                [seq] => *seq,
+                _ => {
+                    let written = written.to_string();
+                    report
+                        .inconsistencies
+                        .push(Inconsistency::Ambiguous { sentence, written });
+                    continue;
+                }
+            };
+
+            let word = storage.words.words.get(seq).unwrap();
+            if english_senses(word).len() < sense as usize {
+                report.inconsistencies.push(Inconsistency::UnknownSense {
+                    sentence,
+                    word: seq,
+                    sense,
+                });
+                continue;
+            }
+
+            // Checked examples replace unchecked ones
+            let checked = b_word.ends_with('~');
+            let example = examples.entry((seq, sense)).or_insert((sentence, checked));
+            if checked && !example.1 {
+                *example = (sentence, checked);
+            }
+        }
+    })?;
+    drop(matcher);
+
+    let new: HashMap<(u32, u8), u32> = examples.into_iter().map(|(k, v)| (k, v.0)).collect();
+
+    let words = &mut storage.words.words;
+    let mut current = HashMap::new();
+    for &(seq, nr) in new.keys() {
+        let word = words.get(seq).unwrap();
+        let pos = english_senses(word)[nr as usize - 1];
+        if let Some(sentence) = word.senses[pos].example_sentence {
+            current.insert((seq, nr), sentence);
+        }
+    }
+
+    report.import = ImportStats {
+        entries: report.import.entries,
+        skipped: report.import.skipped,
+        ..diff(&current, &new)
+    };
+
+    for (&(seq, nr), sentence) in new.iter() {
+        let word = words.get_mut(seq).unwrap();
+        let pos = english_senses(word)[nr as usize - 1];
+        word.senses[pos].example_sentence = Some(*sentence);
+    }
+
+    Ok(report)
+}
+
+/// Returns the positions of all english senses of `word`. The n-th english sense is the n-th
+/// sense of the JMdict entry
+pub(crate) fn english_senses(word: &Word) -> Vec<usize> {
+    (0..word.senses.len())
+        .filter(|i| word.senses[*i].language == Language::English)
+        .collect()
+}
+
+/// Returns the JMdict sense number of a word of a B-line, eg. `2` for `彼(かれ)[02]{彼の}~`.
+/// Words without a sense number are used in their first sense
+fn b_word_sense(s: &str) -> Option<u8> {
+    let rest = match s.split_once('[') {
+        Some((_, rest)) => rest,
+        None => return Some(1),
+    };
+    rest.split_once(']')?.0.parse().ok().filter(|i| *i > 0)
+}
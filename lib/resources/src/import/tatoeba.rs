@@ -0,0 +1,427 @@
+//! Sync of the sentence storage with Tatoeba exports. Japanese sentences which are not in the
+//! storage yet get appended along with their translations. The following files of an extracted
+//! export are read from a folder:
+//!
+//! - `sentences.csv`: `<id>\t<lang>\t<text>`
+//! - `links.csv`: `<sentence id>\t<translation id>`
+//! - `jpn_indices.csv` (optional): `<sentence id>\t<meaning id>\t<B-line>`, the words used in
+//!   japanese sentences. Their readings are used for the furigana of new sentences
+//! - `tags.csv` (optional): `<sentence id>\t<tag>`, used to skip sentences flagged by
+//!   [`QUALITY_TAGS`] and for the tags of new sentences
+//!
+//! The files can be downloaded with [`download`]. The appended sentences are returned in
+//! [`SyncStats::ids`], so they can be added to the sentence indexes
+
+use super::{Progress, WordMatcher, BATCH_SIZE};
+use crate::ResourceStorage;
+use bzip2::read::BzDecoder;
+use japanese::{furigana::generate, ToKanaExt};
+use jp_utils::JapaneseExt;
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    ffi::OsStr,
+    fs::{self, File},
+    io::{self, BufRead, BufReader},
+    path::Path,
+    str::FromStr,
+};
+use types::jotoba::{
+    language::Language,
+    sentences::{lang_mask, tag::Tag, translation::Translation, Sentence},
+};
+
+pub const SENTENCES_FILE: &str = "sentences.csv";
+pub const LINKS_FILE: &str = "links.csv";
+pub const INDICES_FILE: &str = "jpn_indices.csv";
+pub const TAGS_FILE: &str = "tags.csv";
+
+/// Folder containing the weekly Tatoeba exports. Each file is available as `<name>.tar.bz2`
+pub const EXPORTS_URL: &str = "https://downloads.tatoeba.org/exports";
+
+/// Tatoeba tags marking sentences which need to be checked or corrected
+pub const QUALITY_TAGS: &[&str] = &[
+    "@needs native check",
+    "@check",
+    "@change",
+    "@delete",
+    "@translation check",
+];
+
+/// Summary of a sentence sync
+#[derive(Clone, Debug, Default)]
+pub struct SyncStats {
+    /// Amount of japanese sentences in the export
+    pub sentences: usize,
+    /// Amount of appended sentences
+    pub added: usize,
+    /// Amount of new sentences skipped due to quality flags
+    pub flagged: usize,
+    /// Amount of new sentences skipped since they don't have a translation in a supported
+    /// language
+    pub untranslated: usize,
+    /// Amount of links between new sentences and words
+    pub word_links: usize,
+    /// IDs of the appended sentences
+    pub ids: Vec<u32>,
+}
+
+/// Downloads the latest Tatoeba export into `dir`, replacing the files of a previous download.
+/// Files are only replaced once they've been downloaded completely
+pub fn download<P: AsRef<Path>>(dir: P) -> Result<(), Box<dyn Error>> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+
+    for file in [SENTENCES_FILE, LINKS_FILE, INDICES_FILE, TAGS_FILE] {
+        let url = format!("{EXPORTS_URL}/{}.tar.bz2", file.trim_end_matches(".csv"));
+        let response = ureq::get(&url).call()?;
+        let mut archive = tar::Archive::new(BzDecoder::new(response.into_reader()));
+
+        let mut found = false;
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            if entry.path()?.file_name() != Some(OsStr::new(file)) {
+                continue;
+            }
+
+            let tmp = dir.join(format!("{file}.tmp"));
+            io::copy(&mut entry, &mut File::create(&tmp)?)?;
+            fs::rename(&tmp, dir.join(file))?;
+            found = true;
+        }
+
+        if !found {
+            return Err(format!("{url} doesn't contain {file}").into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Appends all japanese sentences of the export in `dir` which are not in `storage` yet
+pub fn sync<P: AsRef<Path>>(
+    storage: &mut ResourceStorage,
+    dir: P,
+    mut on_progress: impl FnMut(Progress),
+) -> Result<SyncStats, Box<dyn Error>> {
+    let dir = dir.as_ref();
+    let sentences_file = dir.join(SENTENCES_FILE);
+    let links_file = dir.join(LINKS_FILE);
+    let indices_file = dir.join(INDICES_FILE);
+    let tags_file = dir.join(TAGS_FILE);
+
+    let mut progress = Progress {
+        total: fs::metadata(&sentences_file)?.len() * 2 + fs::metadata(&links_file)?.len(),
+        ..Default::default()
+    };
+    if indices_file.exists() {
+        progress.total += fs::metadata(&indices_file)?.len();
+    }
+
+    let mut stats = SyncStats::default();
+
+    let (flagged, mut tags) = if tags_file.exists() {
+        read_tags(&tags_file)?
+    } else {
+        Default::default()
+    };
+
+    // New japanese sentences
+    let mut new: HashMap<u32, String> = HashMap::new();
+    read_tsv(&sentences_file, &mut progress, &mut on_progress, |cols| {
+        let (id, lang, text) = match cols {
+            [id, lang, text, ..] => (id, lang, text),
+            _ => return,
+        };
+        if *lang != "jpn" {
+            return;
+        }
+        stats.sentences += 1;
+
+        let id: u32 = match id.parse() {
+            Ok(id) => id,
+            Err(_) => return,
+        };
+        if storage.sentences.sentences.get(id).is_some() {
+            return;
+        }
+
+        if flagged.contains(&id) {
+            stats.flagged += 1;
+            return;
+        }
+
+        new.insert(id, text.to_string());
+    })?;
+
+    // Translations of the new sentences
+    let mut translation_of: HashMap<u32, Vec<u32>> = HashMap::new();
+    read_tsv(&links_file, &mut progress, &mut on_progress, |cols| {
+        let (from, to) = match cols {
+            [from, to, ..] => (from.parse::<u32>(), to.parse::<u32>()),
+            _ => return,
+        };
+        if let (Ok(from), Ok(to)) = (from, to) {
+            if new.contains_key(&from) && !flagged.contains(&to) {
+                translation_of.entry(to).or_default().push(from);
+            }
+        }
+    })?;
+
+    let mut translations: HashMap<u32, Vec<Translation>> = HashMap::new();
+    read_tsv(&sentences_file, &mut progress, &mut on_progress, |cols| {
+        let (id, lang, text) = match cols {
+            [id, lang, text, ..] => (id, lang, text),
+            _ => return,
+        };
+        let sentences = match id.parse().ok().and_then(|i: u32| translation_of.get(&i)) {
+            Some(sentences) => sentences,
+            None => return,
+        };
+        let language = match Language::from_str(lang) {
+            Ok(lang) if lang != Language::Japanese => lang,
+            _ => return,
+        };
+
+        for sentence in sentences {
+            let translation = Translation::from((text.to_string(), language));
+            translations.entry(*sentence).or_default().push(translation);
+        }
+    })?;
+
+    // Words used in the new sentences and the furigana generated from them
+    let mut word_langs: HashMap<u32, u16> = HashMap::new();
+    let mut furigana: HashMap<u32, String> = HashMap::new();
+    if indices_file.exists() {
+        let matcher = WordMatcher::new(&storage.words);
+        read_tsv(&indices_file, &mut progress, &mut on_progress, |cols| {
+            let (id, b_line) = match cols {
+                [id, _, b_line, ..] => match id.parse::<u32>() {
+                    Ok(id) => (id, b_line),
+                    Err(_) => return,
+                },
+                _ => return,
+            };
+            let langs = match translations.get(&id) {
+                Some(translations) => lang_mask(translations.iter().map(|i| i.language)),
+                None => return,
+            };
+
+            // Sentences can have multiple B-lines, the first one is used
+            if let Some(text) = new.get(&id) {
+                furigana
+                    .entry(id)
+                    .or_insert_with(|| sentence_furigana(storage, &matcher, text, b_line));
+            }
+
+            for (word, reading) in b_line.split_whitespace().filter_map(parse_b_word) {
+                for seq in matcher.find(word, reading) {
+                    *word_langs.entry(seq).or_default() |= langs;
+                    stats.word_links += 1;
+                }
+            }
+        })?;
+    }
+
+    for (id, text) in new {
+        let translations = match translations.remove(&id) {
+            Some(translations) => translations,
+            None => {
+                stats.untranslated += 1;
+                continue;
+            }
+        };
+
+        let furigana = furigana.remove(&id).unwrap_or_else(|| text.clone());
+        let tags = tags.remove(&id).unwrap_or_default();
+        for tag in tags.iter() {
+            storage.sentences.tag_map.entry(*tag).or_default().push(id);
+        }
+
+        let sentence = Sentence::new(id, text, furigana, translations, tags);
+        storage.sentences.sentences.insert(id, sentence);
+        stats.ids.push(id);
+        stats.added += 1;
+    }
+
+    for ids in storage.sentences.tag_map.values_mut() {
+        ids.sort_unstable();
+        ids.dedup();
+    }
+    stats.ids.sort_unstable();
+
+    for (seq, langs) in word_langs {
+        if let Some(word) = storage.words.words.get_mut(seq) {
+            word.sentences_available |= langs;
+        }
+    }
+
+    if stats.added > 0 {
+        storage.compute_sentence_stats();
+        storage.words.update_sentence_mapping();
+    }
+
+    Ok(stats)
+}
+
+/// Tags of sentences by their ID
+type SentenceTags = HashMap<u32, Vec<Tag>>;
+
+/// Returns the IDs of all sentences tagged with one of the [`QUALITY_TAGS`] and the known
+/// [`Tag`]s of all sentences
+fn read_tags(tags_file: &Path) -> Result<(HashSet<u32>, SentenceTags), Box<dyn Error>> {
+    let mut flagged = HashSet::new();
+    let mut tags: SentenceTags = HashMap::new();
+    for line in BufReader::new(File::open(tags_file)?).lines() {
+        let line = line?;
+        let (id, tag) = match line.split_once('\t') {
+            Some((id, tag)) => match id.parse() {
+                Ok(id) => (id, tag.trim()),
+                Err(_) => continue,
+            },
+            None => continue,
+        };
+
+        if QUALITY_TAGS.contains(&tag) {
+            flagged.insert(id);
+        } else if let Ok(tag) = Tag::from_str(&tag.to_lowercase()) {
+            tags.entry(id).or_default().push(tag);
+        }
+    }
+    Ok((flagged, tags))
+}
+
+/// Generates the furigana of the japanese sentence `text` from the words of its B-line. Words
+/// without a reading in the B-line get the reading of the word they're written like, if all of
+/// these words are read the same way. Words whose reading is unknown are kept without furigana
+fn sentence_furigana(
+    storage: &ResourceStorage,
+    matcher: &WordMatcher,
+    text: &str,
+    b_line: &str,
+) -> String {
+    let kanji = storage.kanji();
+    let mut out = String::with_capacity(text.len() * 2);
+    let mut rest = text;
+
+    for b_word in b_line.split_whitespace() {
+        let (word, reading) = match parse_b_word(b_word) {
+            Some(word) => word,
+            None => continue,
+        };
+        let surface = b_word_surface(b_word).unwrap_or(word);
+        if !surface.has_kanji() {
+            continue;
+        }
+
+        // Words are listed in the order they're used in
+        let pos = match rest.find(surface) {
+            Some(pos) => pos,
+            None => continue,
+        };
+        out.push_str(&rest[..pos]);
+        rest = &rest[pos + surface.len()..];
+
+        let kana = match reading {
+            Some(reading) => Some(reading.to_string()),
+            None => unique_kana(storage, matcher, word),
+        };
+        match kana.and_then(|kana| surface_reading(word, &kana, surface)) {
+            Some(kana) => out.push_str(&generate::checked(&kanji, surface, &kana)),
+            None => out.push_str(surface),
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Returns the kana reading of all words written as `word` if they're all read the same way
+fn unique_kana(storage: &ResourceStorage, matcher: &WordMatcher, word: &str) -> Option<String> {
+    let mut kana = matcher
+        .find(word, None)
+        .into_iter()
+        .filter_map(|seq| storage.words.words.get(seq))
+        .map(|i| i.get_kana());
+    let first = kana.next()?;
+    kana.all(|i| i == first).then(|| first.to_string())
+}
+
+/// Returns the reading of `surface`, the form `word` with the kana `reading` is used in within a
+/// sentence. Inflected forms have to share the kanji of `word` and only differ in their kana
+/// ending, eg. 食べた for 食べる(たべる)
+fn surface_reading(word: &str, reading: &str, surface: &str) -> Option<String> {
+    if surface == word {
+        return Some(reading.to_string());
+    }
+
+    let stem_len: usize = word
+        .chars()
+        .zip(surface.chars())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a.len_utf8())
+        .sum();
+    let (word_end, surface_end) = (&word[stem_len..], &surface[stem_len..]);
+    if stem_len == 0 || !is_kana(word_end) || !is_kana(surface_end) {
+        return None;
+    }
+
+    let reading = reading.to_hiragana();
+    let stem = reading.strip_suffix(word_end.to_hiragana().as_str())?;
+    Some(format!("{stem}{surface_end}"))
+}
+
+#[inline]
+fn is_kana(s: &str) -> bool {
+    s.chars().all(|c| c.is_kana())
+}
+
+/// Returns the form a word of a B-line is used in within the sentence, eg. `彼の` for
+/// `彼(かれ)[01]{彼の}~`. Words without a form are used as they're written
+fn b_word_surface(s: &str) -> Option<&str> {
+    let rest = s.split_once('{')?.1;
+    Some(rest.split_once('}')?.0).filter(|i| !i.is_empty())
+}
+
+/// Parses a single word of a B-line, eg. `彼(かれ)[01]{彼の}~`, into the headword and its reading
+pub(crate) fn parse_b_word(s: &str) -> Option<(&str, Option<&str>)> {
+    let end = s.find(['(', '[', '{', '~']).unwrap_or(s.len());
+    let word = &s[..end];
+    if word.is_empty() {
+        return None;
+    }
+
+    let reading = s[end..]
+        .strip_prefix('(')
+        .and_then(|i| i.split_once(')'))
+        .map(|i| i.0);
+
+    Some((word, reading))
+}
+
+/// Calls `on_line` with the columns of each line of the tab separated file `file`
+pub(crate) fn read_tsv<F>(
+    file: &Path,
+    progress: &mut Progress,
+    on_progress: &mut impl FnMut(Progress),
+    mut on_line: F,
+) -> Result<(), Box<dyn Error>>
+where
+    F: FnMut(&[&str]),
+{
+    for line in BufReader::new(File::open(file)?).lines() {
+        let line = line?;
+        progress.read += line.len() as u64 + 1;
+        progress.entries += 1;
+
+        let cols: Vec<_> = line.split('\t').collect();
+        on_line(&cols);
+
+        if progress.entries % (BATCH_SIZE * 100) == 0 {
+            on_progress(*progress);
+        }
+    }
+
+    on_progress(*progress);
+    Ok(())
+}
@@ -0,0 +1,179 @@
+//! Detection of transitive and intransitive verb pairs, eg. `開く` and `開ける`. Verbs written with
+//! the same kanji get paired if their readings follow one of the [`PATTERNS`] or if a sense of
+//! one of them references the other one
+
+use super::ImportStats;
+use crate::{storage::word::WordStorage, ResourceStorage};
+use japanese::furigana::to_romaji;
+use jp_utils::JapaneseExt;
+use std::{
+    collections::{HashMap, HashSet},
+    num::NonZeroU32,
+};
+use types::jotoba::words::{part_of_speech::PosSimple, Word};
+
+/// Endings of intransitive verbs and their transitive counterparts in romaji, after removing
+/// the part both readings share, eg. `ag-aru` and `ag-eru`
+pub const PATTERNS: &[(&str, &str)] = &[
+    ("aru", "eru"),
+    ("aru", "u"),
+    ("u", "eru"),
+    ("u", "asu"),
+    ("eru", "u"),
+    ("eru", "asu"),
+    ("eru", "yasu"),
+    ("iru", "asu"),
+    ("iru", "osu"),
+    ("ru", "su"),
+    ("ru", "seru"),
+    ("reru", "su"),
+    ("chiru", "tosu"),
+    ("ieru", "esu"),
+];
+
+/// A detected transitivity pair
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Pair {
+    pub intransitive: u32,
+    pub transitive: u32,
+}
+
+/// Links all detected transitivity pairs of verbs which aren't linked yet. Existing links are
+/// kept. `entries` of the returned stats is the amount of detected pairs and `conflicts` the
+/// amount of them disagreeing with existing links
+pub fn link(storage: &mut ResourceStorage) -> ImportStats {
+    let pairs = detect(&storage.words);
+    let words = &mut storage.words.words;
+
+    let mut stats = ImportStats {
+        entries: pairs.len(),
+        ..Default::default()
+    };
+
+    for pair in pairs {
+        let intr = words.get(pair.intransitive).unwrap().transive_version;
+        let tr = words.get(pair.transitive).unwrap().intransive_version;
+
+        match (intr, tr) {
+            (None, None) => (),
+            (Some(intr), Some(tr))
+                if intr.get() == pair.transitive && tr.get() == pair.intransitive => {}
+            _ => {
+                stats.conflicts += 1;
+                continue;
+            }
+        }
+
+        if intr.is_some() {
+            continue;
+        }
+
+        words.get_mut(pair.intransitive).unwrap().transive_version =
+            NonZeroU32::new(pair.transitive);
+        words.get_mut(pair.transitive).unwrap().intransive_version =
+            NonZeroU32::new(pair.intransitive);
+        stats.added += 1;
+    }
+
+    stats
+}
+
+/// Returns all transitivity pairs of verbs in `words`. Each verb is part of at most one pair
+pub fn detect(words: &WordStorage) -> Vec<Pair> {
+    // Transitive verbs by their kanji stem
+    let mut transitive: HashMap<&str, Vec<&Word>> = HashMap::new();
+    for word in words.entries().iter() {
+        if word.has_pos(&[PosSimple::Transitive]) {
+            if let Some(stem) = kanji_stem(word) {
+                transitive.entry(stem).or_default().push(word);
+            }
+        }
+    }
+
+    let mut candidates: Vec<(u8, Pair)> = vec![];
+    for word in words.entries().iter() {
+        if !word.has_pos(&[PosSimple::Intransitive]) {
+            continue;
+        }
+
+        let stem = match kanji_stem(word) {
+            Some(stem) => stem,
+            None => continue,
+        };
+
+        for other in transitive.get(stem).into_iter().flatten() {
+            if other.sequence == word.sequence {
+                continue;
+            }
+
+            let score = references(word, other) as u8 * 2 + reading_pattern(word, other) as u8;
+            if score > 0 {
+                let pair = Pair {
+                    intransitive: word.sequence,
+                    transitive: other.sequence,
+                };
+                candidates.push((score, pair));
+            }
+        }
+    }
+
+    // Best matches first
+    candidates.sort_by(|a, b| {
+        b.0.cmp(&a.0)
+            .then(a.1.intransitive.cmp(&b.1.intransitive))
+            .then(a.1.transitive.cmp(&b.1.transitive))
+    });
+
+    let mut paired = HashSet::new();
+    let mut out = vec![];
+    for (_, pair) in candidates {
+        if paired.contains(&pair.intransitive) || paired.contains(&pair.transitive) {
+            continue;
+        }
+        paired.insert(pair.intransitive);
+        paired.insert(pair.transitive);
+        out.push(pair);
+    }
+
+    out
+}
+
+/// Returns the kanji at the start of the main reading of `word`, eg. `開` for `開ける`
+fn kanji_stem(word: &Word) -> Option<&str> {
+    let kanji = &word.reading.kanji.as_ref()?.reading;
+    let len: usize = kanji
+        .chars()
+        .take_while(|i| i.is_kanji())
+        .map(|i| i.len_utf8())
+        .sum();
+    (len > 0).then(|| &kanji[..len])
+}
+
+/// Returns `true` if the readings of `intr` and `tr` differ by one of the [`PATTERNS`]
+fn reading_pattern(intr: &Word, tr: &Word) -> bool {
+    let intr = to_romaji(intr.get_kana());
+    let tr = to_romaji(tr.get_kana());
+
+    let shared: usize = intr
+        .chars()
+        .zip(tr.chars())
+        .take_while(|(a, b)| a == b)
+        .map(|i| i.0.len_utf8())
+        .sum();
+    if shared == 0 {
+        return false;
+    }
+
+    PATTERNS.contains(&(&intr[shared..], &tr[shared..]))
+}
+
+/// Returns `true` if a sense of one of the words references the other one
+fn references(a: &Word, b: &Word) -> bool {
+    let refs = |from: &Word, to: &Word| {
+        from.senses
+            .iter()
+            .filter_map(|i| i.get_xref())
+            .any(|i| to.has_main_reading(i))
+    };
+    refs(a, b) || refs(b, a)
+}
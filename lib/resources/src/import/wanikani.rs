@@ -0,0 +1,117 @@
+//! Import of a WaniKani subject mapping, replacing the WaniKani levels of all words and kanji.
+//! Each line maps a subject to a JMdict entry or kanji:
+//!
+//! - Kanji: `<subject id>,kanji,<level>,<kanji>`
+//! - Words: `<subject id>,vocabulary,<level>,<written>[,<kana>]` or
+//!   `<subject id>,vocabulary,<level>,<sequence id>`
+//!
+//! Subjects of other types (eg. radicals), empty lines, lines starting with '#' and a header line
+//! are ignored. Entries mapped by multiple subjects keep the lowest level
+
+use super::{diff, frequency::read_list, ImportStats, Progress, WordMatcher};
+use crate::ResourceStorage;
+use std::{collections::HashMap, error::Error, path::Path};
+
+/// Valid WaniKani levels
+pub const LEVELS: std::ops::RangeInclusive<u8> = 1..=60;
+
+/// Summary of a WaniKani import
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WaniKaniStats {
+    pub words: ImportStats,
+    pub kanji: ImportStats,
+}
+
+/// Imports the WaniKani levels of words and kanji from the subject mapping `source`
+pub fn import<S: AsRef<Path>>(
+    storage: &mut ResourceStorage,
+    source: S,
+    on_progress: impl FnMut(Progress),
+) -> Result<WaniKaniStats, Box<dyn Error>> {
+    let matcher = WordMatcher::new(&storage.words);
+    let kanji = &storage.kanji.literal_index;
+
+    let mut stats = WaniKaniStats::default();
+    let mut word_levels: HashMap<u32, u8> = HashMap::new();
+    let mut kanji_levels: HashMap<char, u8> = HashMap::new();
+    let mut invalid: Option<String> = None;
+
+    read_list(source.as_ref(), ',', on_progress, |pos, cols| {
+        if cols.len() < 4 || cols[3].is_empty() {
+            return;
+        }
+
+        let level = match cols[2].parse::<u8>().ok().filter(|i| LEVELS.contains(i)) {
+            Some(level) => level,
+            // Header
+            None if pos == 1 => return,
+            None => {
+                invalid.get_or_insert_with(|| cols[2].to_string());
+                return;
+            }
+        };
+
+        match cols[1] {
+            "kanji" => {
+                stats.kanji.entries += 1;
+                let literal = cols[3]
+                    .chars()
+                    .next()
+                    .filter(|i| kanji.get(*i as u32).is_some());
+                match literal {
+                    Some(literal) => insert_min(&mut kanji_levels, literal, level),
+                    None => stats.kanji.skipped += 1,
+                }
+            }
+            "vocabulary" | "kana_vocabulary" => {
+                stats.words.entries += 1;
+                let seqs = match cols[3].parse::<u32>() {
+                    Ok(seq) if storage.words.words.get(seq).is_some() => vec![seq],
+                    _ => matcher.find(cols[3], cols.get(4).copied()),
+                };
+
+                if seqs.is_empty() {
+                    stats.words.skipped += 1;
+                }
+
+                for seq in seqs {
+                    insert_min(&mut word_levels, seq, level);
+                }
+            }
+            _ => (),
+        }
+    })?;
+    drop(matcher);
+
+    if let Some(level) = invalid {
+        return Err(format!("Invalid WaniKani level: {level}").into());
+    }
+
+    let current_words: HashMap<u32, u8> = storage
+        .wanikani
+        .word_levels
+        .iter()
+        .map(|(seq, level)| (*seq, *level))
+        .collect();
+    stats.words = ImportStats {
+        entries: stats.words.entries,
+        skipped: stats.words.skipped,
+        ..diff(&current_words, &word_levels)
+    };
+
+    stats.kanji = ImportStats {
+        entries: stats.kanji.entries,
+        skipped: stats.kanji.skipped,
+        ..diff(&storage.wanikani.kanji_levels, &kanji_levels)
+    };
+
+    storage.wanikani.set_word_levels(word_levels);
+    storage.wanikani.set_kanji_levels(kanji_levels);
+    Ok(stats)
+}
+
+/// Inserts `level` for `key`, keeping the lower level if there already is one
+fn insert_min<K: Eq + std::hash::Hash>(levels: &mut HashMap<K, u8>, key: K, level: u8) {
+    let entry = levels.entry(key).or_insert(level);
+    *entry = (*entry).min(level);
+}
@@ -0,0 +1,374 @@
+//! Integrity checks of a loaded resource storage. Finds references between entries which can't
+//! be resolved and data which can't be rendered, eg. after an incomplete import
+
+use crate::{import::transitivity, ResourceStorage};
+use jp_utils::JapaneseExt;
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::{self, Display},
+};
+use types::jotoba::{
+    language::Language,
+    sentences::{lang_mask, parse_lang_mask},
+    words::{
+        part_of_speech::PosSimple,
+        pitch::{raw_data::PitchValues, split_kana},
+    },
+};
+
+/// Max amount of examples printed per issue kind
+const MAX_EXAMPLES: usize = 10;
+
+/// A single problem found in the resources
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Issue {
+    /// A word references a collocation which doesn't exist
+    DanglingCollocation { word: u32, collocation: u32 },
+    /// A word references a transitivity pair which doesn't exist
+    DanglingTransitivity { word: u32, pair: u32 },
+    /// A word is linked to a transitivity pair which isn't a verb of the opposite transitivity or
+    /// isn't linked back
+    InvalidTransitivity { word: u32, pair: u32 },
+    /// A resolved cross reference or antonym of a word points to a word which doesn't exist
+    DanglingReference { word: u32, target: u32 },
+    /// Two verbs form a transitivity pair but aren't linked
+    MissingTransitivity { intransitive: u32, transitive: u32 },
+    /// A word is marked to have sentences in a language no sentence is translated into
+    MissingSentences { word: u32, language: Language },
+    /// A sense of a word has an example sentence which doesn't exist
+    DanglingExample { word: u32, sentence: u32 },
+    /// Sentence search data references a sentence which doesn't exist
+    DanglingSentence { sentence: u32 },
+    /// A word is linked to a name which doesn't exist or vice versa
+    DanglingNameLink { word: u32, name: u32 },
+    /// A word is written with a kanji which is missing in the kanji data
+    MissingKanji { literal: char, word: u32 },
+    /// A reading has a downstep after its last mora
+    InvalidAccent {
+        word: u32,
+        kana: String,
+        accents: PitchValues,
+    },
+}
+
+impl Issue {
+    /// Short name of the kind of the issue
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Issue::DanglingCollocation { .. } => "dangling collocations",
+            Issue::DanglingTransitivity { .. } => "dangling transitivity pairs",
+            Issue::InvalidTransitivity { .. } => "invalid transitivity pairs",
+            Issue::MissingTransitivity { .. } => "unlinked transitivity pairs",
+            Issue::DanglingReference { .. } => "dangling references",
+            Issue::MissingSentences { .. } => "missing sentence links",
+            Issue::DanglingExample { .. } => "dangling example sentences",
+            Issue::DanglingSentence { .. } => "dangling sentence references",
+            Issue::DanglingNameLink { .. } => "dangling name links",
+            Issue::MissingKanji { .. } => "kanji missing in kanjidic",
+            Issue::InvalidAccent { .. } => "accents of wrong length",
+        }
+    }
+
+    /// Returns a hint on how to fix the issue
+    pub fn fix(&self) -> &'static str {
+        match self {
+            Issue::DanglingCollocation { .. } | Issue::DanglingTransitivity { .. } => {
+                "Regenerate the storage data from a single JMdict release"
+            }
+            Issue::InvalidTransitivity { .. } | Issue::MissingTransitivity { .. } => {
+                "Link the transitivity pairs with --link-transitivity"
+            }
+            Issue::DanglingReference { .. } => "Re-resolve the references with --link-references",
+            Issue::MissingSentences { .. } => {
+                "Re-run the sentence update with the links of all languages or regenerate the storage data"
+            }
+            Issue::DanglingExample { .. } => {
+                "Re-import the example sentences of senses with --import-sense-sentences"
+            }
+            Issue::DanglingSentence { .. } => {
+                "Regenerate the storage data after updating sentences"
+            }
+            Issue::DanglingNameLink { .. } => "Re-run the name import to link names and words",
+            Issue::MissingKanji { .. } => {
+                "Use a kanjidic release which is at least as new as the JMdict release"
+            }
+            Issue::InvalidAccent { .. } => {
+                "Re-import the pitch accents with --import-pitch from a source matching the readings"
+            }
+        }
+    }
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::DanglingCollocation { word, collocation } => {
+                write!(f, "word {word} has unknown collocation {collocation}")
+            }
+            Issue::DanglingTransitivity { word, pair } => {
+                write!(f, "word {word} has unknown transitivity pair {pair}")
+            }
+            Issue::InvalidTransitivity { word, pair } => {
+                write!(f, "word {word} is paired with {pair}")
+            }
+            Issue::MissingTransitivity {
+                intransitive,
+                transitive,
+            } => write!(f, "words {intransitive} and {transitive}"),
+            Issue::DanglingReference { word, target } => {
+                write!(f, "word {word} references unknown word {target}")
+            }
+            Issue::MissingSentences { word, language } => {
+                write!(f, "word {word} claims sentences in {language:?}")
+            }
+            Issue::DanglingExample { word, sentence } => {
+                write!(f, "word {word} has unknown example sentence {sentence}")
+            }
+            Issue::DanglingSentence { sentence } => write!(f, "unknown sentence {sentence}"),
+            Issue::DanglingNameLink { word, name } => {
+                write!(
+                    f,
+                    "word {word} and name {name} are linked but one is missing"
+                )
+            }
+            Issue::MissingKanji { literal, word } => {
+                write!(f, "kanji {literal} used by word {word}")
+            }
+            Issue::InvalidAccent {
+                word,
+                kana,
+                accents,
+            } => write!(f, "word {word} reading {kana} has accents {accents:?}"),
+        }
+    }
+}
+
+/// Result of an integrity check
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    pub issues: Vec<Issue>,
+}
+
+impl Report {
+    /// Returns `true` if no issues were found
+    #[inline]
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns the issues grouped by their kind
+    pub fn by_kind(&self) -> BTreeMap<&'static str, Vec<&Issue>> {
+        let mut out: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for issue in &self.issues {
+            out.entry(issue.kind()).or_default().push(issue);
+        }
+        out
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_ok() {
+            return writeln!(f, "No issues found");
+        }
+
+        for (kind, issues) in self.by_kind() {
+            writeln!(f, "{} {kind}", issues.len())?;
+            for issue in issues.iter().take(MAX_EXAMPLES) {
+                writeln!(f, "  {issue}")?;
+            }
+            if issues.len() > MAX_EXAMPLES {
+                writeln!(f, "  ... and {} more", issues.len() - MAX_EXAMPLES)?;
+            }
+            writeln!(f, "  Fix: {}", issues[0].fix())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks references between words, kanji, names and sentences of `storage`
+pub fn check(storage: &ResourceStorage) -> Report {
+    let mut issues = vec![];
+    words(storage, &mut issues);
+    transitivity(storage, &mut issues);
+    sentences(storage, &mut issues);
+    names(storage, &mut issues);
+    Report { issues }
+}
+
+fn words(storage: &ResourceStorage, issues: &mut Vec<Issue>) {
+    let words = storage.words();
+    let kanji = storage.kanji();
+    let sentences = storage.sentences();
+
+    // Languages of all sentence translations
+    let sentence_langs = lang_mask(
+        sentences
+            .iter()
+            .flat_map(|i| i.translations.iter().map(|j| j.language)),
+    );
+
+    for word in words.iter() {
+        let seq = word.sequence;
+
+        for collocation in word.collocations.iter().flatten() {
+            if words.by_sequence(*collocation).is_none() {
+                issues.push(Issue::DanglingCollocation {
+                    word: seq,
+                    collocation: *collocation,
+                });
+            }
+        }
+
+        for pair in [word.transive_version, word.intransive_version]
+            .into_iter()
+            .flatten()
+        {
+            if words.by_sequence(pair.get()).is_none() {
+                issues.push(Issue::DanglingTransitivity {
+                    word: seq,
+                    pair: pair.get(),
+                });
+            }
+        }
+
+        for reference in words.references(seq) {
+            if words.by_sequence(reference.target).is_none() {
+                issues.push(Issue::DanglingReference {
+                    word: seq,
+                    target: reference.target,
+                });
+            }
+        }
+
+        for language in parse_lang_mask(word.sentences_available & !sentence_langs) {
+            issues.push(Issue::MissingSentences {
+                word: seq,
+                language,
+            });
+        }
+
+        for sentence in word.senses.iter().filter_map(|i| i.example_sentence) {
+            if sentences.by_id(sentence).is_none() {
+                issues.push(Issue::DanglingExample {
+                    word: seq,
+                    sentence,
+                });
+            }
+        }
+
+        let literals: HashSet<char> = word
+            .reading_iter(false)
+            .flat_map(|i| i.reading.chars())
+            .filter(|i| i.is_kanji())
+            .collect();
+        for literal in literals {
+            if kanji.by_literal(literal).is_none() {
+                issues.push(Issue::MissingKanji { literal, word: seq });
+            }
+        }
+
+        let alternatives = storage
+            .words
+            .reading_accents
+            .get(&seq)
+            .into_iter()
+            .flatten();
+        for (kana, accents) in std::iter::once((word.get_kana(), &word.accents))
+            .chain(alternatives.map(|i| (i.0.as_str(), &i.1)))
+        {
+            if !valid_accents(kana, *accents) {
+                issues.push(Issue::InvalidAccent {
+                    word: seq,
+                    kana: kana.to_string(),
+                    accents: *accents,
+                });
+            }
+        }
+    }
+}
+
+fn transitivity(storage: &ResourceStorage, issues: &mut Vec<Issue>) {
+    let words = storage.words();
+
+    for word in words.iter() {
+        let links = [
+            (word.transive_version, PosSimple::Transitive),
+            (word.intransive_version, PosSimple::Intransitive),
+        ];
+        for (pair, pos) in links {
+            let pair = match pair.and_then(|i| words.by_sequence(i.get())) {
+                Some(pair) => pair,
+                None => continue,
+            };
+
+            let linked_back = match pos {
+                PosSimple::Transitive => pair.intransive_version,
+                _ => pair.transive_version,
+            };
+            if !pair.has_pos(&[pos]) || linked_back.map(|i| i.get()) != Some(word.sequence) {
+                issues.push(Issue::InvalidTransitivity {
+                    word: word.sequence,
+                    pair: pair.sequence,
+                });
+            }
+        }
+    }
+
+    for pair in transitivity::detect(&storage.words) {
+        let linked = words
+            .by_sequence(pair.intransitive)
+            .and_then(|i| i.transive_version)
+            .map(|i| i.get());
+        if linked.is_none() {
+            issues.push(Issue::MissingTransitivity {
+                intransitive: pair.intransitive,
+                transitive: pair.transitive,
+            });
+        }
+    }
+}
+
+fn sentences(storage: &ResourceStorage, issues: &mut Vec<Issue>) {
+    let retrieve = storage.sentences();
+    let sentences = &storage.sentences;
+
+    let referenced = sentences
+        .tag_map
+        .values()
+        .chain(sentences.jlpt_map.values())
+        .flatten()
+        .copied()
+        .chain(sentences.script_stats.iter().map(|i| *i.0));
+
+    let mut checked = HashSet::new();
+    for id in referenced {
+        if checked.insert(id) && retrieve.by_id(id).is_none() {
+            issues.push(Issue::DanglingSentence { sentence: id });
+        }
+    }
+}
+
+fn names(storage: &ResourceStorage, issues: &mut Vec<Issue>) {
+    let words = storage.words();
+    let names = storage.names();
+
+    let name_links = storage.words.name_links.iter().map(|(w, n)| (*w, n, true));
+    let word_links = storage.names.word_links.iter().map(|(n, w)| (*n, w, false));
+
+    for (id, links, from_word) in name_links.chain(word_links) {
+        for link in links {
+            let (word, name) = if from_word { (id, *link) } else { (*link, id) };
+            if words.by_sequence(word).is_none() || names.by_sequence(name).is_none() {
+                issues.push(Issue::DanglingNameLink { word, name });
+            }
+        }
+    }
+}
+
+/// Returns `true` if all downsteps of `accents` lie within the morae of `kana`
+fn valid_accents(kana: &str, accents: PitchValues) -> bool {
+    let morae = split_kana(kana).count() as u8;
+    accents.iter().all(|drop| drop <= morae)
+}
@@ -1,19 +1,32 @@
+pub mod import;
+pub mod integrity;
+pub mod overlay;
 pub mod retrieve;
 pub mod storage;
+pub mod textbooks;
 
 pub use storage::{feature::Feature, ResourceStorage};
 
+use arc_swap::ArcSwapOption;
 use once_cell::sync::{Lazy, OnceCell};
 use std::{
     error::Error,
     fs::File,
-    io::{BufReader, Write},
+    io::{BufReader, Read, Write},
     path::Path,
+    sync::Arc,
 };
+use storage::word::WordStorageV2;
+use utils::format::{self, VersionError};
 
 /// Static git hash of current build
 pub const GIT_HASH: &str = env!("GIT_HASH");
 
+/// Version of the serialized resource storage. Has to be increased on every change to the format
+/// of the storage. If older files can still be converted, a migration has to be added to
+/// [`migrate`]
+pub const FORMAT_VERSION: u32 = 3;
+
 /// List of features that are required for Jotoba to run properly
 pub const REQUIRED_FEATURES: &[Feature] = &[
     Feature::Words,
@@ -24,8 +37,13 @@ pub const REQUIRED_FEATURES: &[Feature] = &[
     Feature::RadicalData,
 ];
 
-/// InMemory storage for all data
-static STORAGE: OnceCell<ResourceStorage> = OnceCell::new();
+/// InMemory storage for all data. It gets swapped atomically when replacing the resources. Entries
+/// of the storage are handed out with a `'static` lifetime, so replaced storages get retired and
+/// are only freed once all readers pinned with [`utils::epoch::pin`] are done with them
+static STORAGE: ArcSwapOption<ResourceStorage> = ArcSwapOption::const_empty();
+
+/// Gets set once the first storage is loaded
+static LOADED: OnceCell<()> = OnceCell::new();
 
 /// Lazy resource storage for tests
 pub static LAZY_STORAGE: Lazy<ResourceStorage> = Lazy::new(|| {
@@ -36,22 +54,72 @@ pub static LAZY_STORAGE: Lazy<ResourceStorage> = Lazy::new(|| {
 /// Get loaded storage data
 #[inline(always)]
 pub fn get() -> &'static ResourceStorage {
+    let storage = STORAGE.load();
+    let storage = Arc::as_ptr(storage.as_ref().expect("Resources not loaded"));
+
     // Safety:
-    // The STORAGE cell gets initialized once at the beginning which is absolutely necessary for
-    // the program to work. It won't be unset so its always safe
-    unsafe { STORAGE.get_unchecked() }
+    // STORAGE gets initialized once at the beginning which is absolutely necessary for the program
+    // to work. Replaced storages are retired and only freed after all pinned readers are done
+    unsafe { &*storage }
 }
 
 /// Returns `true` if the storage is loaded
 #[inline(always)]
 pub fn is_loaded() -> bool {
-    STORAGE.get().is_some()
+    STORAGE.load().is_some()
 }
 
 /// Load the resource storage and returns it
 pub fn load_raw<P: AsRef<Path>>(path: P) -> Result<ResourceStorage, Box<dyn Error>> {
-    let mut reader = BufReader::new(File::open(path)?);
-    Ok(bincode::deserialize_from(&mut reader)?)
+    let mut reader = BufReader::new(File::open(path.as_ref())?);
+    let mut storage: ResourceStorage = match format::read_header(&mut reader)? {
+        Some(FORMAT_VERSION) => bincode::deserialize_from(&mut reader)?,
+        version => migrate(path.as_ref(), version, reader)?,
+    };
+
+    // Storages built by older versions or other tools don't contain links between names and words
+    if storage.words.name_links.is_empty() && storage.names.word_links.is_empty() {
+        storage.link_names_words();
+    }
+
+    Ok(storage)
+}
+
+/// Loads a resource storage written in an older format `version`
+fn migrate<R: Read>(
+    path: &Path,
+    version: Option<u32>,
+    reader: R,
+) -> Result<ResourceStorage, Box<dyn Error>> {
+    match version {
+        // Version 1 didn't have WaniKani levels
+        Some(1) => {
+            let (words, kanji, names, sentences): (WordStorageV2, _, _, _) =
+                bincode::deserialize_from(reader)?;
+            Ok(ResourceStorage {
+                words: words.into(),
+                kanji,
+                names,
+                sentences,
+                wanikani: Default::default(),
+                overlays: vec![],
+            })
+        }
+        // Version 2 didn't have references between words
+        Some(2) => {
+            let (words, kanji, names, sentences, wanikani): (WordStorageV2, _, _, _, _) =
+                bincode::deserialize_from(reader)?;
+            Ok(ResourceStorage {
+                words: words.into(),
+                kanji,
+                names,
+                sentences,
+                wanikani,
+                overlays: vec![],
+            })
+        }
+        _ => Err(VersionError::new(path.display(), version, FORMAT_VERSION).into()),
+    }
 }
 
 /// Load the resource storage from a file. Returns `true` if it wasn't loaded before
@@ -59,19 +127,34 @@ pub fn load<P: AsRef<Path>>(path: P) -> Result<bool, Box<dyn Error>> {
     if is_loaded() {
         return Ok(true);
     }
-    Ok(STORAGE.set(load_raw(path)?).is_ok())
+    set(load_raw(path)?);
+    Ok(true)
 }
 
 /// Serializes a ResourceStorage into `output`
-pub fn store<W: Write>(output: W, storage: &ResourceStorage) -> Result<(), Box<dyn Error>> {
+pub fn store<W: Write>(mut output: W, storage: &ResourceStorage) -> Result<(), Box<dyn Error>> {
+    format::write_header(&mut output, FORMAT_VERSION)?;
     bincode::serialize_into(output, storage)?;
     Ok(())
 }
 
+/// Sets the storage if no storage is loaded yet
 pub fn set(res_storage: ResourceStorage) {
-    STORAGE.set(res_storage).ok();
+    let prev = STORAGE.compare_and_swap(&None::<Arc<_>>, Some(Arc::new(res_storage)));
+    if prev.is_none() {
+        LOADED.set(()).ok();
+    }
+}
+
+/// Replaces the loaded storage with `res_storage` without blocking readers. Requests which are
+/// currently running keep using the old storage, which gets freed once all of them are done
+pub fn replace(res_storage: ResourceStorage) {
+    if let Some(old) = STORAGE.swap(Some(Arc::new(res_storage))) {
+        utils::epoch::retire(old);
+    }
+    LOADED.set(()).ok();
 }
 
 pub fn wait() {
-    STORAGE.wait();
+    LOADED.wait();
 }
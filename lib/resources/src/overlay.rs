@@ -0,0 +1,174 @@
+//! Operator maintained patches applied on top of the resource storage at load time, so broken
+//! entries can be fixed without waiting for upstream dictionary releases.
+//!
+//! Overlays are display-only: the search, suggestion and regex indexes are built from the
+//! unpatched data and don't get updated, so added readings and changed glosses are shown but
+//! words can't be found by them. Changes which should be searchable have to go into the storage
+//! data before building the indexes.
+//!
+//! Patches are read from all `.toml` files within the overlay folder, eg.
+//!
+//! ```toml
+//! [[words]]
+//! sequence = 1358280
+//! add_readings = ["喰べる"]
+//!
+//! [[words.glosses]]
+//! sense = 0
+//! gloss = 1
+//! language = "English"
+//! text = "to eat"
+//! ```
+
+use crate::ResourceStorage;
+use japanese::normalize::normalize;
+use jp_utils::JapaneseExt;
+use serde::{Deserialize, Serialize};
+use std::{error::Error, fs, path::Path};
+use types::jotoba::{
+    language::Language,
+    words::{dict::Dict, Word},
+};
+
+/// A single overlay file
+#[derive(Deserialize, Default)]
+pub struct Overlay {
+    #[serde(default)]
+    pub words: Vec<WordPatch>,
+}
+
+/// Patches for a single word
+#[derive(Deserialize)]
+pub struct WordPatch {
+    pub sequence: u32,
+    /// Readings which are added as alternative readings
+    #[serde(default)]
+    pub add_readings: Vec<String>,
+    #[serde(default)]
+    pub glosses: Vec<GlossPatch>,
+}
+
+/// Replaces the text of a single gloss
+#[derive(Deserialize)]
+pub struct GlossPatch {
+    /// ID of the sense
+    pub sense: u8,
+    /// ID of the gloss within the sense
+    pub gloss: u8,
+    #[serde(default)]
+    pub language: Language,
+    pub text: String,
+}
+
+/// Information about an applied overlay file
+#[derive(Serialize, Clone, Debug)]
+pub struct AppliedOverlay {
+    pub file: String,
+    /// Amount of patches which have been applied
+    pub applied: usize,
+    /// Amount of patches which couldn't be applied since their target doesn't exist or since
+    /// they don't change anything
+    pub skipped: usize,
+}
+
+/// Applies all overlay files within `dir` to `storage` and adds them to its applied overlays.
+/// Files are applied in alphabetical order
+pub fn apply_dir<P: AsRef<Path>>(
+    storage: &mut ResourceStorage,
+    dir: P,
+) -> Result<Vec<AppliedOverlay>, Box<dyn Error>> {
+    let mut files: Vec<_> = fs::read_dir(dir)?
+        .map(|i| i.map(|i| i.path()))
+        .collect::<Result<_, _>>()?;
+    files.retain(|i| i.extension().map_or(false, |e| e == "toml"));
+    files.sort();
+
+    let mut out = vec![];
+    for file in files {
+        let overlay: Overlay = toml::from_str(&fs::read_to_string(&file)?)?;
+        let (applied, skipped) = apply(storage, &overlay);
+        let file = file.file_name().unwrap().to_string_lossy().to_string();
+        out.push(AppliedOverlay {
+            file,
+            applied,
+            skipped,
+        });
+    }
+
+    storage.overlays.extend(out.iter().cloned());
+    Ok(out)
+}
+
+/// Applies `overlay` to `storage` and returns the amount of applied and skipped patches. Indexes
+/// aren't updated (see the module documentation)
+pub fn apply(storage: &mut ResourceStorage, overlay: &Overlay) -> (usize, usize) {
+    let mut applied = 0;
+    let mut skipped = 0;
+
+    for patch in &overlay.words {
+        let word = match storage.words.get_mut(patch.sequence) {
+            Some(word) => word,
+            None => {
+                skipped += patch.add_readings.len() + patch.glosses.len();
+                continue;
+            }
+        };
+
+        for reading in &patch.add_readings {
+            if add_reading(word, reading) {
+                applied += 1;
+            } else {
+                skipped += 1;
+            }
+        }
+
+        for gloss in &patch.glosses {
+            if patch_gloss(word, gloss) {
+                applied += 1;
+            } else {
+                skipped += 1;
+            }
+        }
+    }
+
+    (applied, skipped)
+}
+
+/// Returns all overlay files applied to the loaded storage
+#[inline]
+pub fn applied() -> &'static [AppliedOverlay] {
+    &crate::get().overlays
+}
+
+/// Adds `reading` as alternative reading of `word`. Returns `false` if `word` already has it
+fn add_reading(word: &mut Word, reading: &str) -> bool {
+    let reading = normalize(reading);
+    if word.has_reading(&reading) {
+        return false;
+    }
+
+    let dict = Dict {
+        kanji: reading.has_kanji(),
+        reading,
+        ..Dict::default()
+    };
+    word.reading.alternative.push(dict);
+    true
+}
+
+fn patch_gloss(word: &mut Word, patch: &GlossPatch) -> bool {
+    let gloss = word
+        .senses
+        .iter_mut()
+        .filter(|i| i.id == patch.sense && i.language == patch.language)
+        .flat_map(|i| i.glosses.iter_mut())
+        .find(|i| i.id == patch.gloss);
+
+    match gloss {
+        Some(gloss) => {
+            gloss.gloss = patch.text.clone();
+            true
+        }
+        None => false,
+    }
+}
@@ -2,7 +2,7 @@ use ids_parser::IDS;
 use sorted_intersection::SortedIntersection;
 use types::jotoba::kanji::{radical::DetailedRadical, Kanji};
 
-use super::super::storage::kanji::KanjiStorage;
+use super::super::storage::kanji::{mnemonic_terms, KanjiStorage};
 
 #[derive(Clone, Copy)]
 pub struct KanjiRetrieve<'a> {
@@ -58,6 +58,12 @@ impl<'a> KanjiRetrieve<'a> {
         self.storage.radical_data.iter().map(|i| i.1)
     }
 
+    /// Returns the detailed data of the radical `literal`
+    #[inline]
+    pub fn radical(&self, literal: char) -> Option<&'a DetailedRadical> {
+        self.storage.radical_data.get(&literal)
+    }
+
     /// Returns a list of kanji taught in given genki_lesson
     #[inline]
     pub fn by_genki_lesson(&self, genki_lektion: u8) -> Option<&'a Vec<char>> {
@@ -79,6 +85,33 @@ impl<'a> KanjiRetrieve<'a> {
         self.storage.ids_index.get(&kanji_lit)
     }
 
+    /// Returns the mnemonic text of a kanji
+    #[inline]
+    pub fn mnemonic(&self, kanji_lit: char) -> Option<&'a str> {
+        self.storage.mnemonics.get(&kanji_lit).map(|i| i.as_str())
+    }
+
+    /// Returns all kanji whose mnemonic texts contain all terms of `query`
+    pub fn by_mnemonic(&self, query: &str) -> Vec<&'a Kanji> {
+        let index = &self.storage.mnemonic_index;
+
+        let mut maps = vec![];
+        for term in mnemonic_terms(query) {
+            match index.get(&term) {
+                Some(kanji) => maps.push(kanji.iter()),
+                None => return vec![],
+            }
+        }
+
+        if maps.is_empty() {
+            return vec![];
+        }
+
+        SortedIntersection::new(&mut maps)
+            .filter_map(|i| self.by_literal(*i))
+            .collect::<Vec<_>>()
+    }
+
     /// Returns the count of kanji
     #[inline]
     pub fn count(&self) -> usize {
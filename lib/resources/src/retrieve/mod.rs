@@ -1,4 +1,5 @@
 pub mod kanji;
 pub mod name;
 pub mod sentence;
+pub mod wanikani;
 pub mod word;
@@ -1,5 +1,5 @@
 use super::super::storage::name::NameStorage;
-use types::jotoba::names::Name;
+use types::jotoba::names::{alias::Alias, Name};
 
 #[derive(Clone, Copy)]
 pub struct NameRetrieve<'a> {
@@ -15,18 +15,38 @@ impl<'a> NameRetrieve<'a> {
     /// Get a name by its sequence id
     #[inline]
     pub fn by_sequence(&self, seq_id: u32) -> Option<&'a Name> {
-        self.storage.names.get(&seq_id)
+        self.storage.entries().get(seq_id)
+    }
+
+    /// Returns the sequence ids of words written and read the same way as the given name
+    #[inline]
+    pub fn linked_words(&self, seq_id: u32) -> &'a [u32] {
+        self.storage
+            .word_links
+            .get(&seq_id)
+            .map(|i| i.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns historical and alternative names of the given name
+    #[inline]
+    pub fn aliases(&self, seq_id: u32) -> &'a [Alias] {
+        self.storage
+            .aliases
+            .get(&seq_id)
+            .map(|i| i.as_slice())
+            .unwrap_or_default()
     }
 
     /// Returns the amount of names
     #[inline]
     pub fn count(&self) -> usize {
-        self.storage.names.len()
+        self.storage.entries().len()
     }
 
     /// Returns an iterator over all names
     #[inline]
     pub fn iter(&self) -> impl Iterator<Item = &'a Name> {
-        self.storage.names.iter().map(|i| i.1)
+        self.storage.entries().iter()
     }
 }
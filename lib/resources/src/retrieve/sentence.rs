@@ -1,5 +1,5 @@
 use super::super::storage::sentence::SentenceStorage;
-use types::jotoba::sentences::{tag::Tag, Sentence};
+use types::jotoba::sentences::{stats::ScriptStats, tag::Tag, Sentence};
 
 #[derive(Clone, Copy)]
 pub struct SentenceRetrieve<'a> {
@@ -15,7 +15,13 @@ impl<'a> SentenceRetrieve<'a> {
     /// Returns a sentence by its id or `None` if no sentence for the given ID exists
     #[inline]
     pub fn by_id(&self, id: u32) -> Option<&'a Sentence> {
-        self.storage.sentences.get(id)
+        self.storage.entries().get(id)
+    }
+
+    /// Returns the script statistics of the sentence with the given id
+    #[inline]
+    pub fn script_stats(&self, id: u32) -> Option<ScriptStats> {
+        self.storage.script_stats.get(id).copied()
     }
 
     /// Returns an iterator over all sentences with given `jlpt` level
@@ -53,11 +59,11 @@ impl<'a> SentenceRetrieve<'a> {
 
     #[inline]
     pub fn count(&self) -> usize {
-        self.storage.sentences.len()
+        self.storage.entries().len()
     }
 
     #[inline]
     pub fn iter(&self) -> impl Iterator<Item = &'a Sentence> {
-        self.storage.sentences.iter().map(|i| i.1)
+        self.storage.entries().iter()
     }
 }
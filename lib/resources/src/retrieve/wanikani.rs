@@ -0,0 +1,37 @@
+use super::super::storage::wanikani::WaniKaniStorage;
+
+#[derive(Clone, Copy)]
+pub struct WaniKaniRetrieve<'a> {
+    storage: &'a WaniKaniStorage,
+}
+
+impl<'a> WaniKaniRetrieve<'a> {
+    #[inline(always)]
+    pub(crate) fn new(storage: &'a WaniKaniStorage) -> Self {
+        WaniKaniRetrieve { storage }
+    }
+
+    /// Returns the WaniKani level of the word with the given sequence id
+    #[inline]
+    pub fn word_level(&self, seq_id: u32) -> Option<u8> {
+        self.storage.word_levels.get(seq_id).copied()
+    }
+
+    /// Returns the sequence ids of all words taught in the WaniKani level `level`
+    #[inline]
+    pub fn words_by_level(&self, level: u8) -> Option<&'a Vec<u32>> {
+        self.storage.word_level_map.get(&level)
+    }
+
+    /// Returns the WaniKani level of the kanji `literal`
+    #[inline]
+    pub fn kanji_level(&self, literal: char) -> Option<u8> {
+        self.storage.kanji_levels.get(&literal).copied()
+    }
+
+    /// Returns all kanji taught in the WaniKani level `level`
+    #[inline]
+    pub fn kanji_by_level(&self, level: u8) -> Option<&'a Vec<char>> {
+        self.storage.kanji_level_map.get(&level)
+    }
+}
@@ -1,5 +1,12 @@
 use super::super::storage::word::WordStorage;
-use types::jotoba::words::{misc::Misc, part_of_speech::PosSimple, Word};
+use types::jotoba::words::{
+    dialect::Dialect,
+    misc::Misc,
+    part_of_speech::PosSimple,
+    pitch::raw_data::PitchValues,
+    reference::{Reference, ReferenceKind},
+    Word,
+};
 
 #[derive(Clone, Copy)]
 pub struct WordRetrieve<'a> {
@@ -15,13 +22,20 @@ impl<'a> WordRetrieve<'a> {
     /// Get a word by its sequence id
     #[inline]
     pub fn by_sequence(&self, seq_id: u32) -> Option<&'a Word> {
-        self.storage.words.get(seq_id)
+        self.storage.entries().get(seq_id)
     }
 
     /// Returns an iterator over all words
     #[inline]
     pub fn iter(&self) -> impl Iterator<Item = &'a Word> {
-        self.storage.words.iter().map(|i| i.1)
+        self.storage.entries().iter()
+    }
+
+    /// Returns the pitch accents of the kana reading `kana` of the word `seq_id`, including
+    /// alternative kana readings
+    #[inline]
+    pub fn accents(&self, seq_id: u32, kana: &str) -> Option<PitchValues> {
+        self.storage.get_accents(seq_id, kana)
     }
 
     /// returns an iterator over all katakana words
@@ -44,6 +58,12 @@ impl<'a> WordRetrieve<'a> {
             .filter_map(|seq| self.by_sequence(seq))
     }
 
+    /// Returns `true` if the word with the given sequence id is an irregular ichidan word
+    #[inline]
+    pub fn is_irregular_ichidan(&self, seq_id: u32) -> bool {
+        self.storage.irregular_ichidan.contains(&seq_id)
+    }
+
     /// Returns the amount of irregular ichidan words that have been indexed
     #[inline]
     pub fn irregular_ichidan_len(&self) -> usize {
@@ -116,6 +136,180 @@ impl<'a> WordRetrieve<'a> {
         self.storage.misc_map.get(&(*misc as u8)).map(|i| i.len())
     }
 
+    /// Returns an iterator over all words with a sense used in `dialect`
+    #[inline]
+    pub fn by_dialect<'b>(
+        &'b self,
+        dialect: Dialect,
+    ) -> impl Iterator<Item = &'a Word> + 'b + DoubleEndedIterator {
+        self.storage
+            .dialect_map
+            .get(&(dialect as u8))
+            .into_iter()
+            .flatten()
+            .filter_map(move |i| self.by_sequence(*i))
+    }
+
+    /// Returns the amount of words indexed for `dialect`
+    #[inline]
+    pub fn dialect_len(&self, dialect: Dialect) -> Option<usize> {
+        self.storage
+            .dialect_map
+            .get(&(dialect as u8))
+            .map(|i| i.len())
+    }
+
+    /// Returns the date (yyyymmdd) of the JMdict release in which the word was added or last
+    /// changed
+    #[inline]
+    pub fn revision_date(&self, seq_id: u32) -> Option<u32> {
+        self.storage.revision_dates.get(seq_id).copied()
+    }
+
+    /// Returns the dates of the `n` newest JMdict releases, newest first
+    #[inline]
+    pub fn releases(&self, n: usize) -> &'a [u32] {
+        let releases = &self.storage.releases;
+        &releases[..n.min(releases.len())]
+    }
+
+    /// Returns `true` if the word was added or changed within the `releases` newest releases
+    #[inline]
+    pub fn is_recent(&self, seq_id: u32, releases: usize) -> bool {
+        match (self.revision_date(seq_id), self.oldest_release(releases)) {
+            (Some(date), Some(oldest)) => date >= oldest,
+            _ => false,
+        }
+    }
+
+    /// Returns an iterator over all words which were added or changed within the `releases`
+    /// newest releases, ordered by their revision date (oldest first)
+    #[inline]
+    pub fn recent(
+        &self,
+        releases: usize,
+    ) -> impl Iterator<Item = &'a Word> + DoubleEndedIterator + 'a {
+        let retrieve = *self;
+        self.recent_seqs(releases)
+            .iter()
+            .filter_map(move |i| retrieve.by_sequence(*i))
+    }
+
+    /// Returns the amount of words added or changed within the `releases` newest releases
+    #[inline]
+    pub fn recent_len(&self, releases: usize) -> usize {
+        self.recent_seqs(releases).len()
+    }
+
+    /// Returns the sequence ids of all words added or changed within the `releases` newest
+    /// releases
+    fn recent_seqs(&self, releases: usize) -> &'a [u32] {
+        let order = &self.storage.revision_order;
+        let oldest = match self.oldest_release(releases) {
+            Some(oldest) => oldest,
+            None => return &[],
+        };
+
+        let start = order.partition_point(|i| self.revision_date(*i).unwrap_or(0) < oldest);
+        &order[start..]
+    }
+
+    /// Returns the date of the oldest release within the `n` newest ones
+    #[inline]
+    fn oldest_release(&self, n: usize) -> Option<u32> {
+        self.releases(n).last().copied()
+    }
+
+    /// Returns the frequency of the word with the given sequence id
+    #[inline]
+    pub fn frequency(&self, seq_id: u32) -> Option<u32> {
+        self.storage.frequencies.get(seq_id).copied()
+    }
+
+    /// Returns `true` if the word with the given sequence id is at least as frequent as the `n`th
+    /// most frequent word
+    pub fn is_top_ranked(&self, seq_id: u32, n: usize) -> bool {
+        let order = &self.storage.frequency_order;
+        let last = match n.min(order.len()).checked_sub(1) {
+            Some(last) => order[last],
+            None => return false,
+        };
+        self.frequency(seq_id) >= self.frequency(last)
+    }
+
+    /// Returns the `n` most frequent words, ordered by their frequency
+    pub fn top_ranked(&self, n: usize) -> impl Iterator<Item = &'a Word> + DoubleEndedIterator {
+        let retrieve = *self;
+        let order = &self.storage.frequency_order;
+        order[..n.min(order.len())]
+            .iter()
+            .filter_map(move |i| retrieve.by_sequence(*i))
+    }
+
+    /// Returns the amount of words within the `n` most frequent words
+    #[inline]
+    pub fn top_ranked_len(&self, n: usize) -> usize {
+        n.min(self.storage.frequency_order.len())
+    }
+
+    /// Returns the `limit` most frequent collocations of `word` along with their corpus frequency,
+    /// ordered by the frequency with the most frequent collocation first
+    pub fn collocations(&self, word: &Word, limit: usize) -> Vec<(&'a Word, Option<u32>)> {
+        let mut collocations = word
+            .collocations
+            .iter()
+            .flatten()
+            .filter_map(|seq| Some((self.by_sequence(*seq)?, self.frequency(*seq))))
+            .collect::<Vec<_>>();
+        collocations.sort_by(|a, b| b.1.cmp(&a.1));
+        collocations.truncate(limit);
+        collocations
+    }
+
+    /// Returns the resolved cross references and antonyms of the senses of the given word
+    #[inline]
+    pub fn references(&self, seq_id: u32) -> &'a [Reference] {
+        self.storage
+            .references
+            .get(&seq_id)
+            .map(|i| i.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns the sequence ids of all words referenced by the senses of the given word as `kind`
+    pub fn referenced(&self, seq_id: u32, kind: ReferenceKind) -> Vec<u32> {
+        let mut out: Vec<u32> = vec![];
+        for reference in self.references(seq_id).iter().filter(|i| i.kind == kind) {
+            if !out.contains(&reference.target) {
+                out.push(reference.target);
+            }
+        }
+        out
+    }
+
+    /// Returns the resolved reference of `kind` of the sense `sense_id` of the given word
+    #[inline]
+    pub fn sense_reference(
+        &self,
+        seq_id: u32,
+        sense_id: u8,
+        kind: ReferenceKind,
+    ) -> Option<&'a Reference> {
+        self.references(seq_id)
+            .iter()
+            .find(|i| i.sense == sense_id && i.kind == kind)
+    }
+
+    /// Returns the sequence ids of names written and read the same way as the given word
+    #[inline]
+    pub fn linked_names(&self, seq_id: u32) -> &'a [u32] {
+        self.storage
+            .name_links
+            .get(&seq_id)
+            .map(|i| i.as_slice())
+            .unwrap_or_default()
+    }
+
     /// Returns the total count of words
     #[inline]
     pub fn count(&self) -> usize {
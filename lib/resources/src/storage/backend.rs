@@ -0,0 +1,343 @@
+use arc_swap::ArcSwapOption;
+use intmap::IntMap;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+use utils::{
+    epoch,
+    format::{self, VersionError},
+};
+
+/// Amount of consecutive ids stored within a single segment
+const SEGMENT_SIZE: u32 = 10_000;
+
+/// Max amount of segments of a single backend kept in memory
+const MAX_LOADED: usize = 32;
+
+/// File within a segment folder holding its [`Meta`]. The segments of a folder are always written
+/// together with the meta file, so only the meta file carries the format version
+const META_FILE: &str = "meta";
+
+/// Source of the entries (words, names or sentences) of a storage. Returned references have to
+/// stay valid as long as the reader is pinned (see [`utils::epoch`]), so backends which unload
+/// entries have to retire them instead of freeing them
+pub trait EntryBackend<T>: Send + Sync {
+    /// Returns the entry with the given id
+    fn get(&self, id: u32) -> Option<&T>;
+
+    /// Returns a mutable reference to the entry with the given id. Changes have to be visible to
+    /// all following reads
+    fn get_mut(&mut self, id: u32) -> Option<&mut T>;
+
+    /// Returns the amount of entries
+    fn len(&self) -> usize;
+
+    /// Returns an iterator over all entries
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = &'a T> + 'a>;
+
+    /// Returns `true` if there are no entries
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T: Send + Sync> EntryBackend<T> for IntMap<T> {
+    #[inline]
+    fn get(&self, id: u32) -> Option<&T> {
+        IntMap::get(self, id)
+    }
+
+    #[inline]
+    fn get_mut(&mut self, id: u32) -> Option<&mut T> {
+        IntMap::get_mut(self, id)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        IntMap::len(self)
+    }
+
+    #[inline]
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = &'a T> + 'a> {
+        Box::new(IntMap::iter(self).map(|i| i.1))
+    }
+}
+
+impl<T: Send + Sync> EntryBackend<T> for HashMap<u32, T> {
+    #[inline]
+    fn get(&self, id: u32) -> Option<&T> {
+        HashMap::get(self, &id)
+    }
+
+    #[inline]
+    fn get_mut(&mut self, id: u32) -> Option<&mut T> {
+        HashMap::get_mut(self, &id)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        HashMap::len(self)
+    }
+
+    #[inline]
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = &'a T> + 'a> {
+        Box::new(self.values())
+    }
+}
+
+/// Backend storing entries on disk in segments of `SEGMENT_SIZE` consecutive ids. Segments get
+/// loaded on first access and at most `MAX_LOADED` of them are kept in memory. The least recently
+/// used segment gets evicted when loading another one, but stays alive until all readers pinned at
+/// the time of the eviction are unpinned (see [`utils::epoch`])
+pub struct Segmented<T> {
+    dir: PathBuf,
+    len: usize,
+    segments: BTreeMap<u32, Slot<T>>,
+    /// Applied to every entry read from disk
+    filter: Option<Filter<T>>,
+    /// Entries which have been changed after opening the segments. They're kept in memory and
+    /// take precedence over the entries on disk
+    overrides: IntMap<T>,
+    /// Numbers of all loaded segments. Loading and evicting segments happens while holding this
+    /// lock
+    loaded: Mutex<Vec<u32>>,
+    /// Increased on every access, used to find the least recently used segment
+    clock: AtomicU64,
+}
+
+/// Function changing entries after reading them from disk
+type Filter<T> = Box<dyn Fn(&mut T) + Send + Sync>;
+
+/// A single segment which may or may not be loaded
+struct Slot<T> {
+    entries: ArcSwapOption<IntMap<T>>,
+    last_used: AtomicU64,
+}
+
+/// Contents of a segment folder
+#[derive(Serialize, Deserialize)]
+struct Meta {
+    len: usize,
+    segments: Vec<u32>,
+}
+
+impl<T: DeserializeOwned + Clone + Send + Sync + 'static> Segmented<T> {
+    /// Opens the segments written to `dir` by [`Segmented::write`]
+    pub fn open<P: AsRef<Path>>(dir: P) -> Result<Self, Box<dyn Error>> {
+        let dir = dir.as_ref().to_path_buf();
+        let meta_path = dir.join(META_FILE);
+        let mut reader = BufReader::new(File::open(&meta_path)?);
+        let version = format::read_header(&mut reader)?;
+        if version != Some(crate::FORMAT_VERSION) {
+            let err = VersionError::new(meta_path.display(), version, crate::FORMAT_VERSION);
+            return Err(err.into());
+        }
+        let meta: Meta = bincode::deserialize_from(reader)?;
+
+        let segments = meta
+            .segments
+            .into_iter()
+            .map(|i| {
+                let slot = Slot {
+                    entries: ArcSwapOption::empty(),
+                    last_used: AtomicU64::new(0),
+                };
+                (i, slot)
+            })
+            .collect();
+
+        Ok(Self {
+            dir,
+            len: meta.len,
+            segments,
+            filter: None,
+            overrides: IntMap::new(),
+            loaded: Mutex::new(vec![]),
+            clock: AtomicU64::new(0),
+        })
+    }
+
+    /// Applies `filter` to all entries read from disk, eg. to drop translations which aren't
+    /// needed
+    pub fn with_filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&mut T) + Send + Sync + 'static,
+    {
+        self.filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Returns the segment with number `nr`, loading it if it isn't in memory. Returns `None` if
+    /// there is no such segment or it can't be read
+    fn segment(&self, nr: u32) -> Option<&IntMap<T>> {
+        let slot = self.segments.get(&nr)?;
+        let now = self.clock.fetch_add(1, Ordering::Relaxed);
+        slot.last_used.store(now, Ordering::Relaxed);
+
+        if let Some(segment) = slot.entries.load_full() {
+            return Some(self.extend(&segment));
+        }
+
+        let mut loaded = self.loaded.lock().unwrap();
+
+        // Another thread may have loaded the segment while waiting for the lock
+        if let Some(segment) = slot.entries.load_full() {
+            return Some(self.extend(&segment));
+        }
+
+        let segment = Arc::new(self.read_logged(nr)?);
+        slot.entries.store(Some(segment.clone()));
+        loaded.push(nr);
+
+        while loaded.len() > MAX_LOADED {
+            let lru = loaded
+                .iter()
+                .enumerate()
+                .filter(|i| *i.1 != nr)
+                .min_by_key(|i| self.segments[i.1].last_used.load(Ordering::Relaxed))
+                .map(|i| i.0);
+            let lru = match lru {
+                Some(lru) => lru,
+                None => break,
+            };
+            let evicted = loaded.swap_remove(lru);
+            if let Some(evicted) = self.segments[&evicted].entries.swap(None) {
+                epoch::retire(evicted);
+            }
+        }
+
+        Some(self.extend(&segment))
+    }
+
+    /// Returns a reference to a segment which lives as long as the backend
+    #[inline]
+    fn extend(&self, segment: &Arc<IntMap<T>>) -> &IntMap<T> {
+        // Safety: Loaded segments are owned by their slot until they get evicted. Evicted
+        // segments are retired and only get freed once no reader which might have been handed out
+        // one of its entries is pinned anymore
+        unsafe { &*Arc::as_ptr(segment) }
+    }
+
+    /// Reads the segment with number `nr` from disk, logging errors
+    fn read_logged(&self, nr: u32) -> Option<IntMap<T>> {
+        match self.read(nr) {
+            Ok(segment) => Some(segment),
+            Err(err) => {
+                log::error!(
+                    "Failed to load resource segment {nr} of {}: {err}",
+                    self.dir.display()
+                );
+                None
+            }
+        }
+    }
+
+    /// Reads the segment with number `nr` from disk
+    fn read(&self, nr: u32) -> Result<IntMap<T>, Box<dyn Error>> {
+        let file = File::open(self.dir.join(nr.to_string()))?;
+        let entries: Vec<(u32, T)> = bincode::deserialize_from(BufReader::new(file))?;
+
+        let mut segment = IntMap::new();
+        for (id, mut entry) in entries {
+            if let Some(ref filter) = self.filter {
+                filter(&mut entry);
+            }
+            segment.insert(id, entry);
+        }
+        Ok(segment)
+    }
+
+    /// Returns the segment with number `nr` for iterating over it. Segments which aren't loaded
+    /// get read without being kept in memory, so iterating over all entries doesn't fill the
+    /// memory with segments
+    fn segment_once(&self, nr: u32) -> Option<&IntMap<T>> {
+        if let Some(segment) = self.segments.get(&nr)?.entries.load_full() {
+            return Some(self.extend(&segment));
+        }
+
+        let segment = Arc::new(self.read_logged(nr)?);
+        let entries = self.extend(&segment);
+        epoch::retire(segment);
+        Some(entries)
+    }
+}
+impl<T: Serialize> Segmented<T> {
+    /// Writes `entries` as segments into `dir`
+    pub fn write<'a, P, I>(dir: P, entries: I) -> Result<(), Box<dyn Error>>
+    where
+        P: AsRef<Path>,
+        I: Iterator<Item = (u32, &'a T)>,
+        T: 'a,
+    {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+
+        let mut segments: BTreeMap<u32, Vec<(u32, &T)>> = BTreeMap::new();
+        let mut len = 0;
+        for (id, entry) in entries {
+            segments
+                .entry(id / SEGMENT_SIZE)
+                .or_default()
+                .push((id, entry));
+            len += 1;
+        }
+
+        for (nr, entries) in segments.iter() {
+            let writer = BufWriter::new(File::create(dir.join(nr.to_string()))?);
+            bincode::serialize_into(writer, entries)?;
+        }
+
+        let meta = Meta {
+            len,
+            segments: segments.keys().copied().collect(),
+        };
+        let mut writer = BufWriter::new(File::create(dir.join(META_FILE))?);
+        format::write_header(&mut writer, crate::FORMAT_VERSION)?;
+        bincode::serialize_into(writer, &meta)?;
+        Ok(())
+    }
+}
+
+impl<T: DeserializeOwned + Clone + Send + Sync + 'static> EntryBackend<T> for Segmented<T> {
+    #[inline]
+    fn get(&self, id: u32) -> Option<&T> {
+        if let Some(entry) = self.overrides.get(id) {
+            return Some(entry);
+        }
+        self.segment(id / SEGMENT_SIZE)?.get(id)
+    }
+
+    /// Copies the entry into memory, so changes to it are kept when its segment gets evicted
+    fn get_mut(&mut self, id: u32) -> Option<&mut T> {
+        if self.overrides.get(id).is_none() {
+            let entry = self.segment(id / SEGMENT_SIZE)?.get(id)?.clone();
+            self.overrides.insert(id, entry);
+        }
+        self.overrides.get_mut(id)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = &'a T> + 'a> {
+        let iter = self
+            .segments
+            .keys()
+            .filter_map(|nr| self.segment_once(*nr))
+            .flat_map(|segment| segment.iter())
+            .map(|(id, entry)| self.overrides.get(*id).unwrap_or(entry));
+        Box::new(iter)
+    }
+}
@@ -19,6 +19,7 @@ pub enum Feature {
     // Sentences
     SentenceJLPT,
     SentenceTags,
+    SentenceScriptStats,
 
     // Words
     WordIrregularIchidan,
@@ -26,11 +27,21 @@ pub enum Feature {
     WordPitch,
     SentenceAvailable,
     WordJlpt,
+    WordFrequency,
+    WordDialects,
+    WordRevisions,
+    WordWaniKani,
+    WordReferences,
+
+    // Names
+    NameAliases,
 
     // Kanji
     GenkiTags,
     SimilarKanji,
     KanjiDecompositions,
+    KanjiMnemonics,
+    KanjiWaniKani,
 }
 
 impl Feature {
@@ -26,6 +26,16 @@ pub struct KanjiStorage {
     /// IDS index for kanji decomposition graph
     pub ids_index: HashMap<char, IDS>,
 
+    /// Optional mnemonic texts of kanji
+    pub mnemonics: HashMap<char, String>,
+
+    /// Index mapping terms of mnemonic texts to the kanji whose mnemonic contains them
+    pub mnemonic_index: HashMap<String, Vec<char>>,
+
+    /// Kanji without a KANJIDIC frequency rank whose rank was taken from an imported frequency
+    /// list instead
+    pub listed_frequencies: Vec<char>,
+
     has_similar_kanji: bool,
 }
 
@@ -38,6 +48,7 @@ impl KanjiStorage {
     pub fn insert_kanji(&mut self, kanji: Vec<Kanji>) {
         self.literal_index.clear();
         self.jlpt_data.clear();
+        self.listed_frequencies.clear();
 
         for kanji in kanji {
             if let Some(jlpt) = kanji.jlpt {
@@ -51,6 +62,67 @@ impl KanjiStorage {
         }
     }
 
+    /// Sets the JLPT levels of all kanji, replacing all existing levels. Kanji without a level in
+    /// `levels` don't have one afterwards
+    pub fn set_jlpt_levels<I: IntoIterator<Item = (char, u8)>>(&mut self, levels: I) {
+        for (_, kanji) in self.literal_index.iter_mut() {
+            kanji.jlpt = None;
+        }
+        self.jlpt_data.clear();
+
+        for (literal, level) in levels {
+            let kanji = match self.literal_index.get_mut(literal as u32) {
+                Some(kanji) => kanji,
+                None => continue,
+            };
+            kanji.jlpt = Some(level);
+            self.jlpt_data.entry(level).or_default().push(literal);
+        }
+    }
+
+    /// Sets the frequency ranks of kanji from a frequency list, replacing the ranks of a previously
+    /// imported list. Ranks of KANJIDIC are kept, so only kanji without one get a rank of `ranks`
+    pub fn set_list_frequencies(&mut self, ranks: &HashMap<char, u16>) {
+        for literal in self.listed_frequencies.drain(..) {
+            if let Some(kanji) = self.literal_index.get_mut(literal as u32) {
+                kanji.frequency = None;
+            }
+        }
+
+        for (_, kanji) in self.literal_index.iter_mut() {
+            if kanji.frequency.is_some() {
+                continue;
+            }
+            if let Some(rank) = ranks.get(&kanji.literal) {
+                kanji.frequency = Some(*rank);
+                self.listed_frequencies.push(kanji.literal);
+            }
+        }
+        self.listed_frequencies.sort_unstable();
+    }
+
+    /// Sets the radicals of all kanji and rebuilds the radical search mapping. Kanji without an
+    /// entry in `parts` can't be found by radicals afterwards
+    pub fn set_radicals(&mut self, parts: HashMap<char, Vec<char>>) {
+        let mut radical_map: HashMap<char, Vec<char>> = HashMap::new();
+        for (literal, radicals) in parts.iter() {
+            for radical in radicals {
+                radical_map.entry(*radical).or_default().push(*literal);
+            }
+        }
+
+        // Required to intersect the kanji of multiple radicals
+        for (_, v) in radical_map.iter_mut() {
+            v.sort_unstable();
+            v.dedup();
+        }
+
+        for (_, kanji) in self.literal_index.iter_mut() {
+            kanji.parts = parts.get(&kanji.literal).cloned().unwrap_or_default();
+        }
+        self.radical_map = radical_map;
+    }
+
     /// Insert radical detail data
     pub fn insert_radicals(&mut self, radicals: Vec<DetailedRadical>) {
         self.radical_data.clear();
@@ -59,6 +131,24 @@ impl KanjiStorage {
         }
     }
 
+    /// Insert mnemonic texts of kanji and builds the index to search them
+    pub fn insert_mnemonics(&mut self, mnemonics: Vec<(char, String)>) {
+        self.mnemonics.clear();
+        self.mnemonic_index.clear();
+
+        for (literal, text) in mnemonics {
+            for term in mnemonic_terms(&text) {
+                self.mnemonic_index.entry(term).or_default().push(literal);
+            }
+            self.mnemonics.insert(literal, text);
+        }
+
+        for (_, v) in self.mnemonic_index.iter_mut() {
+            v.sort_unstable();
+            v.dedup();
+        }
+    }
+
     pub fn get_features(&self) -> Vec<Feature> {
         let mut out = vec![];
 
@@ -86,6 +176,17 @@ impl KanjiStorage {
             out.push(Feature::KanjiDecompositions);
         }
 
+        if !self.mnemonics.is_empty() {
+            out.push(Feature::KanjiMnemonics);
+        }
+
         out
     }
 }
+
+/// Splits a mnemonic text (or a query for it) into lowercased, indexable terms
+pub fn mnemonic_terms(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|i| i.chars().count() > 1)
+        .map(|i| i.to_lowercase())
+}
@@ -1,19 +1,36 @@
+pub mod backend;
 pub mod feature;
 pub mod kanji;
 pub mod name;
 pub mod sentence;
+pub mod wanikani;
 pub mod word;
 
-use super::retrieve::{
-    kanji::KanjiRetrieve, name::NameRetrieve, sentence::SentenceRetrieve, word::WordRetrieve,
+use super::{
+    overlay::AppliedOverlay,
+    retrieve::{
+        kanji::KanjiRetrieve, name::NameRetrieve, sentence::SentenceRetrieve,
+        wanikani::WaniKaniRetrieve, word::WordRetrieve,
+    },
 };
 
 use self::{
-    feature::Feature, kanji::KanjiStorage, name::NameStorage, sentence::SentenceStorage,
-    word::WordStorage,
+    backend::Segmented, feature::Feature, kanji::KanjiStorage, name::NameStorage,
+    sentence::SentenceStorage, wanikani::WaniKaniStorage, word::WordStorage,
 };
+use intmap::IntMap;
+use jp_utils::JapaneseExt;
 use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, error::Error, path::Path, sync::Arc};
 use strum::IntoEnumIterator;
+use types::jotoba::{
+    language::Language,
+    sentences::{
+        stats::{ScriptStats, RARE_KANJI_GRADE},
+        Sentence,
+    },
+    words::Word,
+};
 
 /// Storage holding all data of Jotoba
 #[derive(Serialize, Deserialize, Default, Clone)]
@@ -22,6 +39,10 @@ pub struct ResourceStorage {
     pub kanji: KanjiStorage,
     pub names: NameStorage,
     pub sentences: SentenceStorage,
+    pub wanikani: WaniKaniStorage,
+    /// Overlay files which have been applied to the storage after loading it
+    #[serde(skip)]
+    pub overlays: Vec<AppliedOverlay>,
 }
 
 impl ResourceStorage {
@@ -30,6 +51,153 @@ impl ResourceStorage {
         Self::default()
     }
 
+    /// Links names to words which are written and read the same way (eg. 翼) and vice versa.
+    /// Has to be called after words and names have been inserted
+    pub fn link_names_words(&mut self) {
+        let mut by_reading: HashMap<(&str, &str), Vec<u32>> = HashMap::new();
+        for word in self.words.entries().iter() {
+            by_reading
+                .entry((word.get_reading_str(), word.get_kana()))
+                .or_default()
+                .push(word.sequence);
+        }
+
+        let mut word_links: HashMap<u32, Vec<u32>> = HashMap::new();
+        let mut name_links: HashMap<u32, Vec<u32>> = HashMap::new();
+
+        for name in self.names.entries().iter() {
+            let words = match by_reading.get(&(name.get_reading(), name.kana.as_str())) {
+                Some(words) => words,
+                None => continue,
+            };
+
+            for word in words {
+                name_links.entry(*word).or_default().push(name.sequence);
+            }
+            word_links.insert(name.sequence, words.clone());
+        }
+
+        for (_, v) in name_links.iter_mut() {
+            v.sort_unstable();
+        }
+
+        self.names.word_links = word_links;
+        self.words.name_links = name_links;
+    }
+
+    /// Computes the script statistics of all sentences. Has to be called after sentences and
+    /// kanji have been inserted
+    pub fn compute_sentence_stats(&mut self) {
+        let mut stats = IntMap::new();
+
+        for sentence in self.sentences.entries().iter() {
+            let mut jp_chars = 0;
+            let mut katakana = 0;
+            let mut max_grade = 0;
+
+            for c in sentence.japanese.chars().filter(|i| i.is_japanese()) {
+                jp_chars += 1;
+
+                if c.is_katakana() {
+                    katakana += 1;
+                } else if c.is_kanji() {
+                    let grade = self
+                        .kanji
+                        .literal_index
+                        .get(c as u32)
+                        .and_then(|i| i.grade)
+                        .unwrap_or(RARE_KANJI_GRADE);
+                    max_grade = max_grade.max(grade);
+                }
+            }
+
+            let katakana_percent = (katakana * 100).checked_div(jp_chars).unwrap_or(0) as u8;
+            stats.insert(sentence.id, ScriptStats::new(katakana_percent, max_grade));
+        }
+
+        self.sentences.script_stats = stats;
+    }
+
+    /// Removes all senses and sentence translations in languages other than `languages` and
+    /// English. Words and sentences kept in segments get filtered when they're read instead (see
+    /// [`ResourceStorage::open_segments`])
+    pub fn retain_languages(&mut self, languages: &[Language]) {
+        for (_, word) in self.words.words.iter_mut() {
+            word.senses.retain(|i| keep_language(languages, i.language));
+        }
+
+        for (_, sentence) in self.sentences.sentences.iter_mut() {
+            sentence
+                .translations
+                .retain(|i| keep_language(languages, i.language));
+        }
+    }
+
+    /// Writes words, names and sentences as lazily loaded segments into `dir` and removes them
+    /// from the storage. The storage has to be stored afterwards and can only be used together
+    /// with the segments (see [`ResourceStorage::open_segments`])
+    pub fn split_segments<P: AsRef<Path>>(&mut self, dir: P) -> Result<(), Box<dyn Error>> {
+        let dir = dir.as_ref();
+        Segmented::write(
+            dir.join("words"),
+            self.words.words.iter().map(|i| (*i.0, i.1)),
+        )?;
+        Segmented::write(
+            dir.join("names"),
+            self.names.names.iter().map(|i| (*i.0, i.1)),
+        )?;
+        Segmented::write(
+            dir.join("sentences"),
+            self.sentences.sentences.iter().map(|i| (*i.0, i.1)),
+        )?;
+
+        self.words.words = Default::default();
+        self.names.names = Default::default();
+        self.sentences.sentences = Default::default();
+        Ok(())
+    }
+
+    /// Uses the segments written by [`ResourceStorage::split_segments`] into `dir` as backends
+    /// for words, names and sentences. Only segments which are accessed get loaded into memory.
+    /// If `languages` is set, senses and translations get filtered like in
+    /// [`ResourceStorage::retain_languages`] when reading them
+    pub fn open_segments<P: AsRef<Path>>(
+        &mut self,
+        dir: P,
+        languages: Option<&[Language]>,
+    ) -> Result<(), Box<dyn Error>> {
+        let dir = dir.as_ref();
+
+        let mut words = Segmented::open(dir.join("words"))?;
+        let mut sentences = Segmented::open(dir.join("sentences"))?;
+        if let Some(languages) = languages {
+            let word_languages = languages.to_vec();
+            words = words.with_filter(move |word: &mut Word| {
+                word.senses
+                    .retain(|i| keep_language(&word_languages, i.language));
+            });
+
+            let sentence_languages = languages.to_vec();
+            sentences = sentences.with_filter(move |sentence: &mut Sentence| {
+                sentence
+                    .translations
+                    .retain(|i| keep_language(&sentence_languages, i.language));
+            });
+        }
+
+        self.words.set_backend(Arc::new(words));
+        self.names
+            .set_backend(Arc::new(Segmented::open(dir.join("names"))?));
+        self.sentences.set_backend(Arc::new(sentences));
+        Ok(())
+    }
+
+    /// Returns `true` if words, names and sentences are kept in segments
+    #[inline]
+    pub fn is_segmented(&self) -> bool {
+        self.words.has_backend()
+    }
+
     /// Returns `true` if all necessary features are present
     pub fn check(&self) -> bool {
         self.missing_but_required().is_empty()
@@ -76,10 +244,17 @@ impl ResourceStorage {
         out.extend(self.kanji.get_features());
         out.extend(self.names.get_features());
         out.extend(self.sentences.get_features());
+        out.extend(self.wanikani.get_features());
         out
     }
 }
 
+/// Returns `true` if translations in `language` are kept when only keeping `languages`
+#[inline]
+fn keep_language(languages: &[Language], language: Language) -> bool {
+    language == Language::English || languages.contains(&language)
+}
+
 // Retrieve functions
 // `ResourceStorage::check` is supposed to be called at the begininng to ensure
 // those fields are not unset
@@ -107,4 +282,10 @@ impl ResourceStorage {
     pub fn sentences(&self) -> SentenceRetrieve {
         SentenceRetrieve::new(&self.sentences)
     }
+
+    /// Get a reference to the resource storage's WaniKani levels.
+    #[inline(always)]
+    pub fn wanikani(&self) -> WaniKaniRetrieve {
+        WaniKaniRetrieve::new(&self.wanikani)
+    }
 }
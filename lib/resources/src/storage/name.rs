@@ -1,14 +1,28 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
-use super::feature::Feature;
+use super::{backend::EntryBackend, feature::Feature};
+use japanese::normalize::normalize_mut;
 use serde::{Deserialize, Serialize};
-use types::jotoba::names::Name;
+use types::jotoba::names::{
+    alias::{Alias, AliasKind},
+    Name,
+};
 
 /// Storage containing all data related to names
 #[derive(Serialize, Deserialize, Default, Clone)]
 pub struct NameStorage {
     /// Index mapping name id to its `Name` value
     pub names: HashMap<u32, Name>,
+
+    /// Maps name sequence ids to words written and read the same way
+    pub word_links: HashMap<u32, Vec<u32>>,
+
+    /// Historical and alternative names of places and eras
+    pub aliases: HashMap<u32, Vec<Alias>>,
+
+    /// Backend providing the names instead of `names` if set
+    #[serde(skip)]
+    backend: Option<Arc<dyn EntryBackend<Name>>>,
 }
 
 impl NameStorage {
@@ -16,20 +30,79 @@ impl NameStorage {
         Self::default()
     }
 
+    /// Returns the backend providing the names
+    #[inline(always)]
+    pub fn entries(&self) -> &dyn EntryBackend<Name> {
+        match self.backend {
+            Some(ref backend) => backend.as_ref(),
+            None => &self.names,
+        }
+    }
+
+    /// Replaces the in-memory names with `backend`
+    pub fn set_backend(&mut self, backend: Arc<dyn EntryBackend<Name>>) {
+        self.names = Default::default();
+        self.backend = Some(backend);
+    }
+
     /// Insert names into the NameStorage
     pub fn insert_names(&mut self, names: Vec<Name>) {
         self.names.clear();
 
-        for name in names {
+        for mut name in names {
+            normalize_mut(&mut name.kana);
+            if let Some(kanji) = name.kanji.as_mut() {
+                normalize_mut(kanji);
+            }
             self.names.insert(name.sequence, name);
         }
     }
 
+    /// Insert aliases of names. Each entry `(name, alias, kind)` reads as "`name` <kind> `alias`",
+    /// eg. ("東京", "江戸", Formerly). Aliases get assigned to all names written that way and the
+    /// inverse relation is added to the names written like the alias. Has to be called after
+    /// names have been inserted
+    pub fn insert_aliases(&mut self, aliases: Vec<(String, String, AliasKind)>) {
+        self.aliases.clear();
+
+        let mut by_writing: HashMap<String, Vec<u32>> = HashMap::new();
+        for name in self.entries().iter() {
+            by_writing
+                .entry(name.get_reading().to_string())
+                .or_default()
+                .push(name.sequence);
+        }
+
+        for (mut name, mut alias, kind) in aliases {
+            normalize_mut(&mut name);
+            normalize_mut(&mut alias);
+
+            let entries = [(&name, &alias, kind), (&alias, &name, kind.inverse())];
+            for (from, to, kind) in entries {
+                let seqs = match by_writing.get(from.as_str()) {
+                    Some(seqs) => seqs,
+                    None => continue,
+                };
+
+                for seq in seqs {
+                    let aliases = self.aliases.entry(*seq).or_default();
+                    let alias = Alias::new(kind, to.clone());
+                    if !aliases.contains(&alias) {
+                        aliases.push(alias);
+                    }
+                }
+            }
+        }
+    }
+
     pub fn get_features(&self) -> Vec<Feature> {
         let mut out = vec![];
-        if !self.names.is_empty() {
+        if !self.entries().is_empty() {
             out.push(Feature::Names);
         }
+        if !self.aliases.is_empty() {
+            out.push(Feature::NameAliases);
+        }
         out
     }
 }
@@ -1,8 +1,8 @@
-use super::feature::Feature;
+use super::{backend::EntryBackend, feature::Feature};
 use intmap::IntMap;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use types::jotoba::sentences::{tag::Tag, Sentence};
+use std::{collections::HashMap, sync::Arc};
+use types::jotoba::sentences::{stats::ScriptStats, tag::Tag, Sentence};
 
 /// Storage for sentence related data
 #[derive(Serialize, Deserialize, Clone, Default)]
@@ -15,6 +15,13 @@ pub struct SentenceStorage {
 
     // Search tags
     pub jlpt_map: HashMap<u8, Vec<u32>>,
+
+    /// Script statistics of sentences by their ID
+    pub script_stats: IntMap<ScriptStats>,
+
+    /// Backend providing the sentences instead of `sentences` if set
+    #[serde(skip)]
+    backend: Option<Arc<dyn EntryBackend<Sentence>>>,
 }
 
 impl SentenceStorage {
@@ -22,10 +29,25 @@ impl SentenceStorage {
         Self::default()
     }
 
+    /// Returns the backend providing the sentences
+    #[inline(always)]
+    pub fn entries(&self) -> &dyn EntryBackend<Sentence> {
+        match self.backend {
+            Some(ref backend) => backend.as_ref(),
+            None => &self.sentences,
+        }
+    }
+
+    /// Replaces the in-memory sentences with `backend`
+    pub fn set_backend(&mut self, backend: Arc<dyn EntryBackend<Sentence>>) {
+        self.sentences = Default::default();
+        self.backend = Some(backend);
+    }
+
     pub fn get_features(&self) -> Vec<Feature> {
         let mut out = vec![];
 
-        if !self.sentences.is_empty() {
+        if !self.entries().is_empty() {
             out.push(Feature::Sentences);
         }
 
@@ -37,6 +59,10 @@ impl SentenceStorage {
             out.push(Feature::SentenceJLPT);
         }
 
+        if !self.script_stats.is_empty() {
+            out.push(Feature::SentenceScriptStats);
+        }
+
         out
     }
 }
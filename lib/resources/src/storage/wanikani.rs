@@ -0,0 +1,71 @@
+use intmap::IntMap;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::feature::Feature;
+
+/// Storage containing the WaniKani levels of words and kanji
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct WaniKaniStorage {
+    /// WaniKani levels of words by sequence id
+    pub word_levels: IntMap<u8>,
+    /// Maps levels to the sequence ids of all words taught in the level
+    pub word_level_map: HashMap<u8, Vec<u32>>,
+
+    /// WaniKani levels of kanji by their literal
+    pub kanji_levels: HashMap<char, u8>,
+    /// Maps levels to all kanji taught in the level
+    pub kanji_level_map: HashMap<u8, Vec<char>>,
+}
+
+impl WaniKaniStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the WaniKani levels of all words, replacing all existing levels
+    pub fn set_word_levels<I: IntoIterator<Item = (u32, u8)>>(&mut self, levels: I) {
+        self.word_levels.clear();
+        self.word_level_map.clear();
+
+        for (seq_id, level) in levels {
+            self.word_levels.insert(seq_id, level);
+            self.word_level_map.entry(level).or_default().push(seq_id);
+        }
+
+        for (_, v) in self.word_level_map.iter_mut() {
+            v.sort_unstable();
+            v.dedup();
+        }
+    }
+
+    /// Sets the WaniKani levels of all kanji, replacing all existing levels
+    pub fn set_kanji_levels<I: IntoIterator<Item = (char, u8)>>(&mut self, levels: I) {
+        self.kanji_levels.clear();
+        self.kanji_level_map.clear();
+
+        for (literal, level) in levels {
+            self.kanji_levels.insert(literal, level);
+            self.kanji_level_map.entry(level).or_default().push(literal);
+        }
+
+        for (_, v) in self.kanji_level_map.iter_mut() {
+            v.sort_unstable();
+            v.dedup();
+        }
+    }
+
+    pub fn get_features(&self) -> Vec<Feature> {
+        let mut out = vec![];
+
+        if !self.word_levels.is_empty() {
+            out.push(Feature::WordWaniKani);
+        }
+
+        if !self.kanji_levels.is_empty() {
+            out.push(Feature::KanjiWaniKani);
+        }
+
+        out
+    }
+}
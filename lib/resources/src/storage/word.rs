@@ -1,7 +1,9 @@
+use super::backend::EntryBackend;
 use intmap::IntMap;
+use japanese::normalize::normalize_mut;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use types::jotoba::words::Word;
+use std::{collections::HashMap, num::NonZeroU8, sync::Arc};
+use types::jotoba::words::{pitch::raw_data::PitchValues, reference::Reference, Word};
 
 use super::feature::Feature;
 
@@ -16,12 +18,43 @@ pub struct WordStorage {
     pub irregular_ichidan: Vec<u32>,
     pub pos_map: HashMap<u8, Vec<u32>>,
     pub misc_map: HashMap<u8, Vec<u32>>,
+    pub dialect_map: HashMap<u8, Vec<u32>>,
     pub katakana_words: Vec<u32>,
 
+    /// Frequency of words by sequence id, either counted in a corpus or taken from an imported
+    /// frequency list
+    pub frequencies: IntMap<u32>,
+    /// Sequence ids of all words with a frequency, most frequent first
+    pub frequency_order: Vec<u32>,
+
+    /// Dates (yyyymmdd) of the JMdict releases in which words were added or last changed
+    pub revision_dates: IntMap<u32>,
+    /// Dates of all known JMdict releases, newest first
+    pub releases: Vec<u32>,
+    /// Sequence ids of all words with a revision date, ordered by their date (oldest first)
+    pub revision_order: Vec<u32>,
+    /// Fingerprints of the JMdict entries of the last imported release by sequence id, used to
+    /// detect changed entries in the next release
+    pub revision_hashes: IntMap<u64>,
+
+    /// Maps word sequence ids to names written and read the same way
+    pub name_links: HashMap<u32, Vec<u32>>,
+
+    /// Pitch accents of alternative kana readings by sequence id. Accents of the main kana
+    /// reading are stored in `Word::accents`
+    pub reading_accents: HashMap<u32, Vec<(String, PitchValues)>>,
+
     // Feature information
     has_accents: bool,
     has_sentence_mapping: bool,
     has_jlpt: bool,
+
+    /// Resolved cross references and antonyms of senses by sequence id
+    pub references: HashMap<u32, Vec<Reference>>,
+
+    /// Backend providing the words instead of `words` if set
+    #[serde(skip)]
+    backend: Option<Arc<dyn EntryBackend<Word>>>,
 }
 
 impl WordStorage {
@@ -29,17 +62,49 @@ impl WordStorage {
         Self::default()
     }
 
+    /// Returns the backend providing the words
+    #[inline(always)]
+    pub fn entries(&self) -> &dyn EntryBackend<Word> {
+        match self.backend {
+            Some(ref backend) => backend.as_ref(),
+            None => &self.words,
+        }
+    }
+
+    /// Replaces the in-memory words with `backend`
+    pub fn set_backend(&mut self, backend: Arc<dyn EntryBackend<Word>>) {
+        self.words = Default::default();
+        self.backend = Some(backend);
+    }
+
+    /// Returns `true` if the words are provided by a backend instead of `words`
+    #[inline]
+    pub fn has_backend(&self) -> bool {
+        self.backend.is_some()
+    }
+
+    /// Returns a mutable reference to the word with the given sequence id. Returns `None` if
+    /// there is no such word or if its backend is shared with another storage
+    pub fn get_mut(&mut self, seq: u32) -> Option<&mut Word> {
+        match self.backend {
+            Some(ref mut backend) => Arc::get_mut(backend)?.get_mut(seq),
+            None => self.words.get_mut(seq),
+        }
+    }
+
     /// Returns the amounot of words in the WordStorage
     #[inline]
     pub fn count(&self) -> usize {
-        self.words.len()
+        self.entries().len()
     }
 
     /// Inserts words into the WordStorage
     pub fn insert_words(&mut self, words: Vec<Word>) {
         self.clear_words();
 
-        for word in words {
+        for mut word in words {
+            normalize_reading(&mut word);
+
             if let Some(jlpt) = word.get_jlpt_lvl() {
                 self.jlpt_word_map
                     .entry(jlpt)
@@ -48,6 +113,13 @@ impl WordStorage {
                 self.has_jlpt = true;
             }
 
+            for dialect in word.senses.iter().filter_map(|i| i.dialect) {
+                self.dialect_map
+                    .entry(dialect as u8)
+                    .or_default()
+                    .push(word.sequence);
+            }
+
             if !self.has_accents && word.accents.count() > 0 {
                 self.has_accents = true;
             }
@@ -58,16 +130,151 @@ impl WordStorage {
         for (_, v) in self.jlpt_word_map.iter_mut() {
             v.sort();
         }
+
+        // Words can have multiple senses of the same dialect
+        for (_, v) in self.dialect_map.iter_mut() {
+            v.sort();
+            v.dedup();
+        }
+    }
+
+    /// Inserts frequencies of words, replacing all existing frequencies, and orders each words
+    /// collocations by them, placing the most frequent collocation first. Has to be called after
+    /// `insert_words`
+    pub fn insert_frequencies<I: IntoIterator<Item = (u32, u32)>>(&mut self, frequencies: I) {
+        self.frequencies.clear();
+        for (seq_id, freq) in frequencies {
+            self.frequencies.insert(seq_id, freq);
+        }
+
+        self.sort_collocations();
+    }
+
+    /// Sets the collocations of all words, replacing all existing collocations. Words without
+    /// collocations in `collocations` don't have any afterwards
+    pub fn set_collocations(&mut self, mut collocations: HashMap<u32, Vec<u32>>) {
+        for (seq_id, word) in self.words.iter_mut() {
+            word.collocations = collocations.remove(seq_id).filter(|i| !i.is_empty());
+        }
+        self.sort_collocations();
+    }
+
+    /// Orders each words collocations by their corpus frequency, placing the most frequent
+    /// collocation first
+    fn sort_collocations(&mut self) {
+        let freqs = &self.frequencies;
+        let mut order: Vec<_> = freqs.iter().map(|i| *i.0).collect();
+        order.sort_unstable_by_key(|i| (std::cmp::Reverse(freqs.get(*i).copied()), *i));
+        self.frequency_order = order;
+
+        for (_, word) in self.words.iter_mut() {
+            if let Some(ref mut collocations) = word.collocations {
+                collocations
+                    .sort_by_key(|i| std::cmp::Reverse(freqs.get(*i).copied().unwrap_or(0)));
+            }
+        }
+    }
+
+    /// Sets the JLPT levels of all words, replacing all existing levels. Words without a level in
+    /// `levels` don't have one afterwards
+    pub fn set_jlpt_levels<I: IntoIterator<Item = (u32, u8)>>(&mut self, levels: I) {
+        for (_, word) in self.words.iter_mut() {
+            word.jlpt_lvl = None;
+        }
+        self.jlpt_word_map.clear();
+
+        for (seq_id, level) in levels {
+            let word = match self.words.get_mut(seq_id) {
+                Some(word) => word,
+                None => continue,
+            };
+            word.jlpt_lvl = NonZeroU8::new(level);
+            self.jlpt_word_map.entry(level).or_default().push(seq_id);
+        }
+
+        for (_, v) in self.jlpt_word_map.iter_mut() {
+            v.sort_unstable();
+            v.dedup();
+        }
+        self.has_jlpt = !self.jlpt_word_map.is_empty();
+    }
+
+    /// Inserts the JMdict revision dates (yyyymmdd) of words, determined at import time by
+    /// comparing each entry with the previous release
+    pub fn insert_revisions<I: IntoIterator<Item = (u32, u32)>>(&mut self, revisions: I) {
+        self.revision_dates.clear();
+        for (seq_id, date) in revisions {
+            self.revision_dates.insert(seq_id, date);
+        }
+
+        let mut releases: Vec<_> = self.revision_dates.iter().map(|i| *i.1).collect();
+        releases.sort_unstable_by(|a, b| b.cmp(a));
+        releases.dedup();
+        self.releases = releases;
+
+        let dates = &self.revision_dates;
+        let mut order: Vec<_> = dates.iter().map(|i| *i.0).collect();
+        order.sort_unstable_by_key(|i| (dates.get(*i).copied(), *i));
+        self.revision_order = order;
+    }
+
+    /// Returns the pitch accents of the kana reading `kana` of the word `seq_id`
+    pub fn get_accents(&self, seq_id: u32, kana: &str) -> Option<PitchValues> {
+        let word = self.entries().get(seq_id)?;
+        if word.get_kana() == kana {
+            return (!word.accents.is_empty()).then_some(word.accents);
+        }
+
+        self.reading_accents
+            .get(&seq_id)?
+            .iter()
+            .find(|i| i.0 == kana)
+            .map(|i| i.1)
+    }
+
+    /// Sets the pitch accents of the kana reading `kana` of the word `seq_id`. Returns `false`
+    /// if the word doesn't have such a kana reading
+    pub fn set_accents(&mut self, seq_id: u32, kana: &str, accents: PitchValues) -> bool {
+        let word = match self.words.get_mut(seq_id) {
+            Some(word) => word,
+            None => return false,
+        };
+
+        if word.get_kana() == kana {
+            word.accents = accents;
+        } else if word
+            .reading
+            .alternative
+            .iter()
+            .any(|i| !i.kanji && i.reading == kana)
+        {
+            let readings = self.reading_accents.entry(seq_id).or_default();
+            match readings.iter_mut().find(|i| i.0 == kana) {
+                Some(reading) => reading.1 = accents,
+                None => readings.push((kana.to_string(), accents)),
+            }
+        } else {
+            return false;
+        }
+
+        self.has_accents |= !accents.is_empty();
+        true
+    }
+
+    /// Sets the resolved references of all words, replacing all existing references
+    pub fn set_references(&mut self, references: HashMap<u32, Vec<Reference>>) {
+        self.references = references;
+        self.references.retain(|_, v| !v.is_empty());
     }
 
     pub fn update_sentence_mapping(&mut self) {
-        self.has_sentence_mapping = self.words.iter().any(|i| i.1.sentences_available > 0);
+        self.has_sentence_mapping = self.entries().iter().any(|i| i.sentences_available > 0);
     }
 
     pub fn get_features(&self) -> Vec<Feature> {
         let mut out = vec![];
 
-        if !self.words.is_empty() {
+        if !self.entries().is_empty() {
             out.push(Feature::Words);
         }
 
@@ -79,6 +286,10 @@ impl WordStorage {
             out.push(Feature::WordKatakana);
         }
 
+        if !self.dialect_map.is_empty() {
+            out.push(Feature::WordDialects);
+        }
+
         if self.has_sentence_mapping {
             out.push(Feature::SentenceAvailable);
         }
@@ -91,13 +302,98 @@ impl WordStorage {
             out.push(Feature::WordJlpt);
         }
 
+        if !self.frequencies.is_empty() {
+            out.push(Feature::WordFrequency);
+        }
+
+        if !self.revision_dates.is_empty() {
+            out.push(Feature::WordRevisions);
+        }
+
+        if !self.references.is_empty() {
+            out.push(Feature::WordReferences);
+        }
+
         out
     }
 
     fn clear_words(&mut self) {
         self.words.clear();
         self.jlpt_word_map.clear();
+        self.dialect_map.clear();
+        self.reading_accents.clear();
         self.has_accents = false;
         self.has_sentence_mapping = false;
     }
 }
+
+/// Layout of [`WordStorage`] in storage format versions 1 and 2, which didn't have references
+#[derive(Deserialize)]
+pub(crate) struct WordStorageV2 {
+    words: IntMap<Word>,
+    jlpt_word_map: HashMap<u8, Vec<u32>>,
+    irregular_ichidan: Vec<u32>,
+    pos_map: HashMap<u8, Vec<u32>>,
+    misc_map: HashMap<u8, Vec<u32>>,
+    dialect_map: HashMap<u8, Vec<u32>>,
+    katakana_words: Vec<u32>,
+    frequencies: IntMap<u32>,
+    frequency_order: Vec<u32>,
+    revision_dates: IntMap<u32>,
+    releases: Vec<u32>,
+    revision_order: Vec<u32>,
+    revision_hashes: IntMap<u64>,
+    name_links: HashMap<u32, Vec<u32>>,
+    reading_accents: HashMap<u32, Vec<(String, PitchValues)>>,
+    has_accents: bool,
+    has_sentence_mapping: bool,
+    has_jlpt: bool,
+}
+
+impl From<WordStorageV2> for WordStorage {
+    fn from(old: WordStorageV2) -> Self {
+        Self {
+            words: old.words,
+            jlpt_word_map: old.jlpt_word_map,
+            irregular_ichidan: old.irregular_ichidan,
+            pos_map: old.pos_map,
+            misc_map: old.misc_map,
+            dialect_map: old.dialect_map,
+            katakana_words: old.katakana_words,
+            frequencies: old.frequencies,
+            frequency_order: old.frequency_order,
+            revision_dates: old.revision_dates,
+            releases: old.releases,
+            revision_order: old.revision_order,
+            revision_hashes: old.revision_hashes,
+            name_links: old.name_links,
+            reading_accents: old.reading_accents,
+            has_accents: old.has_accents,
+            has_sentence_mapping: old.has_sentence_mapping,
+            has_jlpt: old.has_jlpt,
+            references: HashMap::new(),
+            backend: None,
+        }
+    }
+}
+
+/// Frequency assumed for the most frequent word of a frequency list without frequencies
+const ZIPF_SCALE: u32 = 1_000_000;
+
+/// Estimates the frequency of the word at `rank` in a frequency list without frequencies, based
+/// on Zipf's law
+pub(crate) fn estimate_frequency(rank: u32) -> u32 {
+    (ZIPF_SCALE / rank.max(1)).max(1)
+}
+
+/// Normalizes all readings of a word
+fn normalize_reading(word: &mut Word) {
+    let reading = &mut word.reading;
+    let dicts = std::iter::once(&mut reading.kana)
+        .chain(reading.kanji.as_mut())
+        .chain(reading.alternative.iter_mut());
+
+    for dict in dicts {
+        normalize_mut(&mut dict.reading);
+    }
+}
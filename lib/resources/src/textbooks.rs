@@ -0,0 +1,66 @@
+//! Registry of textbooks which can be searched by lesson using tags like `#mnn12` or `#tobira5`.
+//! Each textbook is loaded from a json file within the textbook folder, the file name being the
+//! tag prefix of the textbook (eg. `mnn.json`).
+
+use once_cell::sync::OnceCell;
+use std::{error::Error, fs::File, io::BufReader, path::Path};
+use types::jotoba::textbook::Textbook;
+
+/// All loaded textbooks
+static TEXTBOOKS: OnceCell<Vec<Textbook>> = OnceCell::new();
+
+/// Loads all textbooks from the json files in `dir`
+pub fn load<P: AsRef<Path>>(dir: P) -> Result<(), Box<dyn Error>> {
+    let mut textbooks = vec![];
+
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().map_or(true, |i| i != "json") {
+            continue;
+        }
+
+        let tag = match path.file_stem().and_then(|i| i.to_str()) {
+            Some(tag) => tag.to_lowercase(),
+            None => continue,
+        };
+
+        let mut textbook: Textbook = serde_json::from_reader(BufReader::new(File::open(&path)?))?;
+        textbook.tag = tag;
+        for words in textbook.lessons.values_mut() {
+            words.sort_unstable();
+            words.dedup();
+        }
+        textbooks.push(textbook);
+    }
+
+    // Longer tags first so a tag which is a prefix of another one doesn't shadow it
+    textbooks.sort_by(|a, b| {
+        b.tag
+            .len()
+            .cmp(&a.tag.len())
+            .then_with(|| a.tag.cmp(&b.tag))
+    });
+
+    TEXTBOOKS.set(textbooks).ok();
+    Ok(())
+}
+
+/// Returns all loaded textbooks. Empty if none were loaded
+#[inline]
+pub fn get() -> &'static [Textbook] {
+    TEXTBOOKS.get().map(|i| i.as_slice()).unwrap_or_default()
+}
+
+/// Returns the textbook with the given index
+#[inline]
+pub fn by_index(index: u8) -> Option<&'static Textbook> {
+    get().get(index as usize)
+}
+
+/// Returns the sequence ids of all words taught in `lesson` of the textbook with the given index
+#[inline]
+pub fn lesson_words(textbook: u8, lesson: u8) -> &'static [u32] {
+    by_index(textbook)
+        .map(|i| i.lesson(lesson))
+        .unwrap_or_default()
+}
@@ -1,4 +1,5 @@
 pub mod names;
 pub mod radical;
 pub mod sentences;
+pub mod settings;
 pub mod words;
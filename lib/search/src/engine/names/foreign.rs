@@ -2,7 +2,10 @@ use index_framework::{
     retrieve::{retriever::default::DefaultRetrieve, Retrieve},
     traits::{backend::Backend, dictionary::IndexDictionary},
 };
-use indexes::{names::FOREIGN_NGRAM, words::native::N as NATIVE_NGRAM};
+use indexes::{
+    names::{format_foreign, FOREIGN_NGRAM},
+    words::native::N as NATIVE_NGRAM,
+};
 use ngindex::{item::IndexItem, termset::TermSet, utils::padded, NGIndex, Wordgrams};
 use types::jotoba::{language::Language, names::Name};
 
@@ -19,14 +22,13 @@ impl engine::Engine<'static> for Engine {
     type Query = TermSet;
 
     fn make_query<S: AsRef<str>>(inp: S, _: Option<Language>) -> Option<Self::Query> {
-        let fmt = format_word(inp.as_ref());
+        let fmt = format_foreign(inp.as_ref());
 
         let dict = Self::get_index(None).dict();
         let mut tids: Vec<_> = Wordgrams::new(&padded(&fmt, NATIVE_NGRAM - 1), NATIVE_NGRAM)
             .filter_map(|i| dict.get_id(i))
             .collect();
         tids.sort_unstable();
-        println!("{tids:#?}");
         if tids.is_empty() {
             return None;
         }
@@ -55,12 +57,3 @@ impl engine::Engine<'static> for Engine {
         Self::retrieve(lang).by_term_ids(query.iter().copied())
     }
 }
-
-#[inline]
-fn format_word(inp: &str) -> String {
-    let mut out = String::from(inp.to_lowercase());
-    for i in ".,[]() \t\"'\\/-;:".chars() {
-        out = out.replace(i, " ");
-    }
-    out.to_lowercase()
-}
@@ -26,13 +26,13 @@ impl engine::Engine<'static> for Engine {
     type Output = &'static Sentence;
     type Query = SpVec32;
 
-    fn make_query<S: AsRef<str>>(inp: S, _lang: Option<Language>) -> Option<Self::Query> {
+    fn make_query<S: AsRef<str>>(inp: S, lang: Option<Language>) -> Option<Self::Query> {
         let query = inp.as_ref();
 
         let mut terms = all_terms(&query.to_lowercase());
         terms.push(query.to_string().to_lowercase());
 
-        let index = Self::get_index(None);
+        let index = Self::get_index(lang);
 
         let term_ids = terms
             .into_iter()
@@ -51,8 +51,8 @@ impl engine::Engine<'static> for Engine {
     }
 
     #[inline]
-    fn get_index(_lang: Option<Language>) -> &'static Self::B {
-        indexes::get().sentence().foreign()
+    fn get_index(lang: Option<Language>) -> &'static Self::B {
+        indexes::get().sentence().foreign(lang.unwrap()).unwrap()
     }
 
     #[inline]
@@ -62,9 +62,7 @@ impl engine::Engine<'static> for Engine {
         lang: Option<Language>,
     ) -> index_framework::retrieve::Retrieve<'static, Self::B, Self::DictItem, Self::Document> {
         let term_iter = inp.dimensions().map(|i| i as u32);
-        Self::retrieve(lang)
-            .by_term_ids(term_iter)
-            .in_posting(lang.unwrap() as u32)
+        Self::retrieve(lang).by_term_ids(term_iter).in_posting(0)
     }
 }
 
@@ -0,0 +1,91 @@
+use crate::executor::out_builder::MergeStrategy;
+use engine::{task::SearchTask, Engine};
+use once_cell::sync::OnceCell;
+use std::collections::HashMap;
+use types::jotoba::search::SearchTarget;
+
+/// Configured settings of all engines
+static SETTINGS: OnceCell<HashMap<EngineKind, EngineSettings>> = OnceCell::new();
+
+/// Configured merge strategies of the search targets
+static MERGE: OnceCell<HashMap<SearchTarget, MergeStrategy>> = OnceCell::new();
+
+/// Engines with configurable settings
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EngineKind {
+    NativeWords,
+    ForeignWords,
+    Sentences,
+    Names,
+}
+
+/// Settings overwriting the defaults of an engine. Unset values keep the defaults of the
+/// producers using the engine
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EngineSettings {
+    /// Min relevance of results
+    pub threshold: Option<f32>,
+    /// Max amount of results
+    pub limit: Option<usize>,
+    /// Max amount of vectors retrieved from the index
+    pub vector_limit: Option<usize>,
+}
+
+impl EngineSettings {
+    /// Applies the settings to `task`
+    pub fn apply<'a, E: Engine<'a> + 'a>(&self, mut task: SearchTask<'a, E>) -> SearchTask<'a, E> {
+        if let Some(threshold) = self.threshold {
+            task = task.with_threshold(threshold);
+        }
+
+        if let Some(limit) = self.limit {
+            task = task.with_limit(limit);
+        }
+
+        if let Some(vector_limit) = self.vector_limit {
+            task = task.with_vector_limit(vector_limit);
+        }
+
+        task
+    }
+}
+
+/// Sets the settings of the engines. Has to be called before the first search to take effect
+pub fn init(settings: HashMap<EngineKind, EngineSettings>) {
+    SETTINGS.set(settings).ok();
+}
+
+/// Returns the settings of the engine `kind`
+#[inline]
+pub fn get(kind: EngineKind) -> EngineSettings {
+    SETTINGS
+        .get()
+        .and_then(|i| i.get(&kind))
+        .copied()
+        .unwrap_or_default()
+}
+
+/// Applies the configured settings of `kind` to `task`
+#[inline]
+pub fn apply<'a, E: Engine<'a> + 'a>(
+    kind: EngineKind,
+    task: SearchTask<'a, E>,
+) -> SearchTask<'a, E> {
+    get(kind).apply(task)
+}
+
+/// Sets the merge strategies of the search targets. Has to be called before the first search to
+/// take effect
+pub fn init_merge(merge: HashMap<SearchTarget, MergeStrategy>) {
+    MERGE.set(merge).ok();
+}
+
+/// Returns the merge strategy used for results of `target`
+#[inline]
+pub fn merge_strategy(target: SearchTarget) -> MergeStrategy {
+    MERGE
+        .get()
+        .and_then(|i| i.get(&target))
+        .copied()
+        .unwrap_or_default()
+}
@@ -4,18 +4,88 @@ use index_framework::{
         MemBackend,
     },
     retrieve::{retriever::default::DefaultRetrieve, Retrieve},
-    traits::{backend::Backend, dictionary::IndexDictionary},
+    traits::{
+        backend::Backend, dictionary::IndexDictionary, postings::IndexPostings,
+        storage::IndexStorage,
+    },
 };
 
-use once_cell::sync::Lazy;
-use regex::Regex;
+use engine::relevance::bm25::CollectionStats;
+use indexes::words::analyzer::Analyzer;
 use sparse_vec::{SpVec32, VecExt};
+use std::collections::HashMap;
 use types::jotoba::{language::Language, words::Word};
+use utils::epoch::Cached;
 use vsm::{dict_term::DictTerm, doc_vec::DocVector};
 
 pub struct Engine;
 
-const FORMAT_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new("^to ").unwrap());
+/// Collection statistics of all loaded foreign word indexes
+static STATS: Cached<HashMap<Language, Stats>> = Cached::new(|| {
+    indexes::get()
+        .word()
+        .languages()
+        .map(|lang| (lang, Stats::new(Engine::get_index(Some(lang)))))
+        .collect()
+});
+
+/// Computes the collection statistics of all foreign word indexes
+#[inline]
+pub(crate) fn warm_up() {
+    Cached::force(&STATS);
+}
+
+/// Collection statistics of a single foreign word index used for BM25 scoring
+pub struct Stats {
+    index: &'static <Engine as engine::Engine<'static>>::B,
+    doc_count: usize,
+    avg_doc_len: f32,
+}
+
+impl Stats {
+    fn new(index: &'static <Engine as engine::Engine<'static>>::B) -> Self {
+        let mut doc_count = 0;
+        let mut total_len = 0;
+        for doc in index.storage().iter() {
+            doc_count += 1;
+            total_len += doc.vec().as_vec().len();
+        }
+
+        let avg_doc_len = total_len as f32 / doc_count.max(1) as f32;
+
+        Self {
+            index,
+            doc_count,
+            avg_doc_len,
+        }
+    }
+}
+
+impl CollectionStats for Stats {
+    #[inline]
+    fn doc_count(&self) -> usize {
+        self.doc_count
+    }
+
+    #[inline]
+    fn avg_doc_len(&self) -> f32 {
+        self.avg_doc_len
+    }
+
+    #[inline]
+    fn doc_freq(&self, term: u32) -> usize {
+        self.index
+            .postings(0)
+            .map(|i| i.get_posting(term).len())
+            .unwrap_or_default()
+    }
+}
+
+/// Returns the collection statistics of the foreign word index of `lang`
+#[inline]
+pub fn stats(lang: Language) -> &'static Stats {
+    &STATS[&lang]
+}
 
 impl engine::Engine<'static> for Engine {
     type B = MemBackend<
@@ -32,15 +102,17 @@ impl engine::Engine<'static> for Engine {
     type Query = SpVec32;
 
     fn make_query<S: AsRef<str>>(inp: S, lang: Option<Language>) -> Option<Self::Query> {
-        let query_str = format_word(inp.as_ref().trim());
+        // Uses the same analyzer the index was built with, see `indexes::storage::build`
+        let analyzer = Analyzer::new(lang.unwrap_or_default());
         let dict = Self::get_index(lang).dict();
 
-        let inp = FORMAT_REGEX.replace_all(&query_str, "").to_string();
+        let inp = analyzer.normalize(inp.as_ref());
 
-        let add_term_iter = inp
-            .split(' ')
-            .map(|i| i.trim())
-            .filter_map(|term| dict.get_id(term))
+        let add_term_iter = analyzer
+            .terms(&inp)
+            .into_iter()
+            .flat_map(|term| analyzer.variants(term, |i| dict.get_id(i).is_some()))
+            .filter_map(|term| dict.get_id(&term))
             .map(|i| (i, 0.001));
 
         let sparse = dict
@@ -81,12 +153,3 @@ impl engine::Engine<'static> for Engine {
         Self::retrieve(lang).by_term_ids(term_iter)
     }
 }
-
-#[inline]
-fn format_word(inp: &str) -> String {
-    let mut out = String::from(inp);
-    for i in ".,[]() \t\"'\\/-;:・".chars() {
-        out = out.replace(i, " ");
-    }
-    out.to_lowercase()
-}
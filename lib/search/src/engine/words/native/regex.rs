@@ -1,8 +1,6 @@
 use crate::query::regex::RegexSQuery;
 use engine::utils::page_from_pqueue;
-use indexes::regex::RegexSearchIndex;
-use intmap::int_set::IntSet;
-use itertools::Itertools;
+use indexes::regex::Indexable;
 use order_struct::order_nh::OrderVal;
 use priority_container::StableUniquePrioContainerMax;
 use types::jotoba::words::Word;
@@ -24,10 +22,7 @@ where
     let queue_size = limit + offset;
     let mut out_queue = StableUniquePrioContainerMax::new_allocated(queue_size, queue_size);
 
-    let index = indexes::get().word().regex();
-    let possible_results = find_words(index, &query.get_chars());
-
-    for seq_id in possible_results.into_iter().sorted() {
+    for seq_id in find_words(query) {
         let word = word_resources.by_sequence(seq_id).unwrap();
 
         let item_iter = word
@@ -51,38 +46,11 @@ where
     RegexSearchResult { items, item_len }
 }
 
-/// Get all indexed words using characters in `chars`
-pub(crate) fn find_words(index: &RegexSearchIndex, chars: &[char]) -> IntSet {
-    if chars.is_empty() {
-        return IntSet::new();
-    }
-
-    let mut out = IntSet::new();
-
-    // Add words of first character to `out`
-    let mut chars_iter = chars.iter();
-
-    // We want to fill `out` with some values.
-    loop {
-        let first = match chars_iter.next() {
-            Some(f) => f,
-            None => break,
-        };
-
-        if let Some(v) = index.get_words_with(*first) {
-            out.reserve(v.len());
-            out.extend(v.iter().copied());
-            // exit first found character
-            break;
-        }
-    }
-
-    for v in chars_iter.filter_map(|c| index.get_words_with(*c)) {
-        out.retain(|i| v.contains(&i));
-        if out.is_empty() {
-            return IntSet::new();
-        }
-    }
-
-    out
+/// Returns the sequence ids of all words which might match `query`, sorted
+#[inline]
+pub(crate) fn find_words(query: &RegexSQuery) -> Vec<u32> {
+    indexes::get()
+        .word()
+        .substring()
+        .candidates(&query.fragments())
 }
@@ -0,0 +1,119 @@
+use super::producer::Cost;
+use std::time::{Duration, Instant};
+
+/// Weight of the share of the budget producers get allotted, by their cost
+#[inline]
+fn weight(cost: Cost) -> u32 {
+    match cost {
+        Cost::Cheap => 1,
+        Cost::Expensive => 3,
+    }
+}
+
+/// Latency budget of a request. Time is measured from the moment the request came in, so work
+/// done before and after the search (eg. parsing the query or loading kanji) is counted as well
+#[derive(Clone, Copy, Debug)]
+pub struct Deadline {
+    start: Instant,
+    total: Duration,
+}
+
+impl Deadline {
+    /// Creates a new deadline of `total` starting now
+    #[inline]
+    pub fn new(total: Duration) -> Self {
+        Self {
+            start: Instant::now(),
+            total,
+        }
+    }
+
+    /// Returns the time which passed since the request came in
+    #[inline]
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Returns the whole budget of the request
+    #[inline]
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+
+    /// Returns `true` if the budget is used up
+    #[inline]
+    pub fn is_over(&self) -> bool {
+        self.elapsed() >= self.total
+    }
+}
+
+/// Latency budget of a search, split across its producers by their cost. Each producer gets a
+/// slot of the budget in the order the producers run. Time left over by a producer is passed on
+/// to the following ones and time taken over the slot is taken from them
+#[derive(Clone, Debug)]
+pub struct Budget {
+    total: Duration,
+    /// Planned start of the slot of each producer, relative to the start of the search
+    starts: Vec<Duration>,
+}
+
+impl Budget {
+    /// Splits `total` across producers with the given `costs`
+    pub fn new(total: Duration, costs: &[Cost]) -> Self {
+        let total_weight: u32 = costs.iter().map(|i| weight(*i)).sum();
+
+        let mut starts = Vec::with_capacity(costs.len());
+        let mut weight_before = 0;
+        for cost in costs {
+            starts.push(total * weight_before / total_weight.max(1));
+            weight_before += weight(*cost);
+        }
+
+        Self { total, starts }
+    }
+
+    /// Returns `true` if the producer at `pos` with `cost` can run when the request has been
+    /// running for `elapsed`. No producer runs once the budget is used up and expensive producers
+    /// only run if the producers before them didn't take time of their slot
+    pub fn allows(&self, pos: usize, cost: Cost, elapsed: Duration) -> bool {
+        if elapsed >= self.total {
+            return false;
+        }
+
+        cost == Cost::Cheap || elapsed <= self.starts[pos]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const COSTS: &[Cost] = &[Cost::Cheap, Cost::Expensive, Cost::Cheap];
+
+    #[test]
+    fn test_starts() {
+        let budget = Budget::new(Duration::from_millis(50), COSTS);
+        let starts: Vec<_> = [0, 10, 40].into_iter().map(Duration::from_millis).collect();
+        assert_eq!(budget.starts, starts);
+    }
+
+    #[test]
+    fn test_allows() {
+        let budget = Budget::new(Duration::from_millis(50), COSTS);
+
+        // Expensive producers only run within their slot
+        assert!(budget.allows(1, Cost::Expensive, Duration::from_millis(5)));
+        assert!(budget.allows(1, Cost::Expensive, Duration::from_millis(10)));
+        assert!(!budget.allows(1, Cost::Expensive, Duration::from_millis(11)));
+
+        // Cheap producers run until the budget is used up
+        assert!(budget.allows(2, Cost::Cheap, Duration::from_millis(49)));
+        assert!(!budget.allows(2, Cost::Cheap, Duration::from_millis(50)));
+    }
+
+    #[test]
+    fn test_no_producers() {
+        let budget = Budget::new(Duration::from_millis(50), &[]);
+        assert!(budget.starts.is_empty());
+    }
+}
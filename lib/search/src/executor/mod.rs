@@ -1,15 +1,16 @@
+pub mod budget;
 pub mod out_builder;
 pub mod producer;
 pub mod search_result;
 pub mod searchable;
 
-use std::time::Instant;
-
+use budget::{Budget, Deadline};
 use engine::{pushable::FilteredMaxCounter, utils::page_from_pqueue_with_max_dist};
-use log::debug;
 use out_builder::OutputBuilder;
+use producer::Cost;
 use search_result::SearchResult;
 use searchable::Searchable;
+use tracing::{debug, debug_span, field::Empty};
 use types::jotoba::search::guess::{Guess, GuessType};
 
 use crate::executor::out_builder::OutputAddable;
@@ -20,13 +21,26 @@ pub const MAX_ESTIMATE: usize = 100;
 /// Executes a search
 pub struct SearchExecutor<S: Searchable> {
     search: S,
+    /// Time the request is allowed to take
+    budget: Option<Deadline>,
 }
 
 impl<S: Searchable> SearchExecutor<S> {
     /// Creates a new SearchExecutor
     #[inline]
     pub fn new(search: S) -> Self {
-        Self { search }
+        Self {
+            search,
+            budget: None,
+        }
+    }
+
+    /// Sets the latency budget of the request. It gets split across the producers by their cost
+    /// and producers exceeding it get skipped as long as there already are results to show
+    #[inline]
+    pub fn with_budget(mut self, budget: Option<Deadline>) -> Self {
+        self.budget = budget;
+        self
     }
 
     /// Executes the search
@@ -35,55 +49,113 @@ impl<S: Searchable> SearchExecutor<S> {
         let limit = query.settings.page_size as usize;
         let offset = query.page_offset;
 
-        let mut out = OutputBuilder::new(|i| self.search.filter(i), limit + offset);
+        let _span = debug_span!(
+            "search",
+            target = ?query.target,
+            query = %query.query_str,
+            page = query.page
+        )
+        .entered();
+
+        // Grouped results are paginated after grouping, so all items which can be part of a group
+        // have to be collected
+        let group_capacity = self.search.group_capacity();
+        let capacity = group_capacity.map_or(limit + offset, |i| i.max(limit + offset));
+
+        let mut out = OutputBuilder::new(|i| self.search.filter(i), capacity)
+            .with_max_dist(self.search.max_top_dist())
+            .with_merge(self.search.merge_strategy());
+
+        let producers = self.search.get_producer();
+        let budget = self.budget.map(|deadline| {
+            let costs: Vec<_> = producers.iter().map(|i| i.cost()).collect();
+            (deadline, Budget::new(deadline.total(), &costs))
+        });
+
+        let mut skipped = vec![];
+
+        for (pos, prod) in producers.iter().enumerate() {
+            // Only depends on the page size and not on the page, so the total amount of results
+            // is the same on every page
+            if prod.cost() == Cost::Expensive
+                && out.total_pushed() >= group_capacity.unwrap_or(limit)
+            {
+                debug!("{}: skipped (page covered by other producers)", prod.name());
+                continue;
+            }
 
-        for prod in self.search.get_producer() {
-            if !prod.should_run(out.p.total_pushed()) {
+            // Checked before `should_run` as checking whether a producer has to run can already
+            // be expensive, eg. parsing the query with the sentence reader
+            let in_budget = budget.as_ref().map_or(true, |(deadline, budget)| {
+                budget.allows(pos, prod.cost(), deadline.elapsed())
+            });
+            if !in_budget && out.total_pushed() > 0 {
+                debug!("{}: skipped (over budget)", prod.name());
+                skipped.push(prod.name());
+                continue;
+            }
+
+            if !prod.should_run(out.total_pushed()) {
                 continue;
             }
-            let before = out.p.total_pushed();
-            let start = Instant::now();
+
+            let span = debug_span!("producer", name = %prod.name(), pushed = Empty).entered();
+            let before = out.total_pushed();
             prod.produce(&mut out);
-            let dur = start.elapsed();
-            let after = out.p.total_pushed();
-            let name = prod.name();
-            debug!("{name}: {} Elements in {:?}", after - before, dur);
+            span.record("pushed", out.total_pushed() - before);
         }
 
+        let output_span = debug_span!("output", total = Empty).entered();
+        out.finish();
         self.search.mod_output(&mut out);
 
         if out.is_empty() && out.output_add.is_empty() {
             let mut res = SearchResult::default();
             res.other_data = out.output_add;
+            res.skipped = skipped;
             return res;
         }
 
-        // Get total len of results
-        let len;
-        if let Some(max_top_dist) = self.search.max_top_dist() {
-            len = out
-                .rel_list
-                .iter()
-                .filter(|i| **i + max_top_dist >= out.max)
-                .count();
-        } else {
-            len = out.p.total_pushed();
+        let max_top_dist = self.search.max_top_dist().unwrap_or(0.0);
+
+        if group_capacity.is_some() {
+            let max = out.max;
+            let mut items: Vec<_> = out
+                .p
+                .into_iter()
+                .filter(|i| i.relevance + max_top_dist >= max || max_top_dist == 0.0)
+                .map(|i| i.item)
+                .collect();
+            items.reverse();
+
+            let items = self.search.group_items(items);
+            let len = items.len();
+            output_span.record("total", len);
+
+            let items = items
+                .into_iter()
+                .skip(offset)
+                .take(limit)
+                .map(|i| self.search.to_output_item(i))
+                .collect();
+            return SearchResult::with_other_data(items, len, out.output_add).with_skipped(skipped);
         }
-        assert_eq!(out.p.total_pushed(), out.rel_list.len());
 
-        let max_top_dist = self.search.max_top_dist().unwrap_or(0.0);
+        // Get total len of results
+        let len = out.top_count();
+        output_span.record("total", len);
+
         let items: Vec<_> =
             page_from_pqueue_with_max_dist(limit, offset, max_top_dist, out.max, out.p)
                 .into_iter()
                 .map(|i| self.search.to_output_item(i.item))
                 .collect();
 
-        SearchResult::with_other_data(items, len, out.output_add)
+        SearchResult::with_other_data(items, len, out.output_add).with_skipped(skipped)
     }
 
+    #[tracing::instrument(level = "debug", name = "guess", skip_all)]
     pub fn guess(&self) -> Option<Guess> {
-        let start = Instant::now();
-
         let mut counter =
             FilteredMaxCounter::<S::Item>::new(MAX_ESTIMATE + 1, |i| self.search.filter(i));
 
@@ -114,7 +186,6 @@ impl<S: Searchable> SearchExecutor<S> {
             gt = GuessType::Accurate;
         }
 
-        debug!("Guessing took: {:?}", start.elapsed());
         Some(Guess::new(sum.min(MAX_ESTIMATE) as u32, gt))
     }
 }
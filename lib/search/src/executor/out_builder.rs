@@ -1,13 +1,55 @@
 use engine::{pushable::Pushable, relevance::item::RelItem};
 use priority_container::StableUniquePrioContainerMax;
-use std::hash::Hash;
+use std::{collections::HashMap, hash::Hash};
+
+/// Min amount of relevances or pending items to keep before pruning them
+const MIN_PRUNE_LEN: usize = 64;
+
+/// How the relevances of an item pushed by multiple producers get combined
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum MergeStrategy {
+    /// Keeps the relevance of the item pushed first
+    First,
+    /// Keeps the highest relevance
+    #[default]
+    Max,
+    /// Adds up all relevances
+    Sum,
+}
+
+impl MergeStrategy {
+    #[inline]
+    fn merge(&self, old: f32, new: f32) -> f32 {
+        match self {
+            MergeStrategy::First => old,
+            MergeStrategy::Max => old.max(new),
+            MergeStrategy::Sum => old + new,
+        }
+    }
+}
 
 pub struct OutputBuilder<'a, I, OA> {
     pub(crate) p: StableUniquePrioContainerMax<RelItem<I>>,
     pub(crate) filter: Box<dyn Fn(&I) -> bool + 'a>,
     pub(crate) output_add: OA,
-    pub(crate) rel_list: Vec<f32>,
+    /// Relevances of all pushed items which are within `max_dist` of the best item. Only kept
+    /// if `max_dist` is set
+    rel_list: Vec<f32>,
+    max_dist: Option<f32>,
+    /// Length of `rel_list` after it got pruned the last time
+    pruned_len: usize,
     pub(crate) max: f32,
+    merge: MergeStrategy,
+    /// Items which have been pushed but not yet moved into `p`, in order of their first push.
+    /// Only used if items get merged by something other than `MergeStrategy::First`
+    pending: Vec<RelItem<I>>,
+    pending_pos: HashMap<I, usize>,
+    /// Length of `pending` after it got pruned the last time
+    pending_pruned_len: usize,
+    /// Max amount of items in the output
+    capacity: usize,
+    /// Amount of pending items which have been pruned
+    pruned: usize,
 }
 
 impl<'a, I: Eq + Hash + Clone, OA: OutputAddable> OutputBuilder<'a, I, OA> {
@@ -18,37 +60,185 @@ impl<'a, I: Eq + Hash + Clone, OA: OutputAddable> OutputBuilder<'a, I, OA> {
             filter: Box::new(filter),
             output_add: OA::default(),
             rel_list: vec![],
+            max_dist: None,
+            pruned_len: MIN_PRUNE_LEN,
             max: 0.0,
+            merge: MergeStrategy::First,
+            pending: vec![],
+            pending_pos: HashMap::new(),
+            pending_pruned_len: MIN_PRUNE_LEN,
+            capacity: len,
+            pruned: 0,
         }
     }
 
+    /// Sets how the relevances of items pushed multiple times get merged
+    #[inline]
+    pub(crate) fn with_merge(mut self, merge: MergeStrategy) -> Self {
+        self.merge = merge;
+        self
+    }
+
+    /// Only counts items which are within `max_dist` of the best item. Relevances of all other
+    /// items get dropped right away so the amount of kept relevances doesn't grow with the
+    /// amount of matches
+    #[inline]
+    pub(crate) fn with_max_dist(mut self, max_dist: Option<f32>) -> Self {
+        self.max_dist = max_dist;
+        self
+    }
+
+    /// Returns the amount of pushed items which are within the max distance of the best item.
+    /// Returns the total amount of pushed items if no max distance is set
+    pub(crate) fn top_count(&self) -> usize {
+        match self.max_dist {
+            Some(max_dist) => self
+                .rel_list
+                .iter()
+                .filter(|i| **i + max_dist >= self.max)
+                .count(),
+            None => self.total_pushed(),
+        }
+    }
+
+    /// Returns the amount of unique items pushed so far. Items too far behind the best item
+    /// aren't counted and pruned items which get pushed again are counted twice
+    #[inline]
+    pub fn total_pushed(&self) -> usize {
+        self.p.total_pushed() + self.pending.len() + self.pruned
+    }
+
     #[inline]
     pub fn len(&self) -> usize {
-        self.p.len()
+        self.p.len() + self.pending.len()
     }
 
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.p.is_empty()
+        self.p.is_empty() && self.pending.is_empty()
     }
 
     /// Pushes an element into the output and  returns `true` if it was not filtered out
     #[inline]
     pub fn push(&mut self, item: RelItem<I>) -> bool {
-        if !(self.filter)(&item.item) {
-            if self.max < item.relevance {
-                self.max = item.relevance;
-            }
+        if (self.filter)(&item.item) {
+            return false;
+        }
+
+        if self.merge != MergeStrategy::First {
+            self.push_pending(item);
+            return true;
+        }
+
+        if self.max < item.relevance {
+            self.max = item.relevance;
+        }
+
+        // Items too far behind the best item are neither shown nor counted. Items which are kept
+        // keep the relevance they've been pushed with first
+        let rel = item.relevance;
+        if self
+            .max_dist
+            .map_or(false, |max_dist| rel + max_dist < self.max)
+        {
+            return true;
+        }
+
+        if self.p.insert(item) {
+            self.push_rel(rel);
+        }
 
+        true
+    }
+
+    /// Moves all pending items into the output. Has to be called after the last item got pushed
+    pub(crate) fn finish(&mut self) {
+        self.pending_pos.clear();
+        for item in std::mem::take(&mut self.pending) {
             let rel = item.relevance;
             if self.p.insert(item) {
-                self.rel_list.push(rel);
+                self.push_rel(rel);
             }
+        }
+    }
 
-            return true;
+    /// Merges `item` with the pending item it equals or adds it as new one
+    fn push_pending(&mut self, item: RelItem<I>) {
+        let rel = match self.pending_pos.get(&item.item) {
+            Some(pos) => {
+                let pending = &mut self.pending[*pos];
+                pending.relevance = self.merge.merge(pending.relevance, item.relevance);
+                pending.relevance
+            }
+            None => {
+                self.pending_pos
+                    .insert(item.item.clone(), self.pending.len());
+                let rel = item.relevance;
+                self.pending.push(item);
+                rel
+            }
+        };
+
+        if self.max < rel {
+            self.max = rel;
         }
 
-        false
+        if self.pending.len() >= self.pending_pruned_len * 2 {
+            self.prune_pending();
+        }
+    }
+
+    /// Drops pending items which can't make it into the output anymore. With `MergeStrategy::Max`
+    /// relevances only grow, so items behind the `capacity` best ones or too far behind the best
+    /// one never get shown. If such an item gets pushed again with a higher relevance it gets
+    /// added as new item with that relevance, which is the same as merging it. Summed up
+    /// relevances can overtake any item, so they never get pruned
+    fn prune_pending(&mut self) {
+        if self.merge != MergeStrategy::Max {
+            return;
+        }
+
+        let mut threshold = match self.max_dist {
+            Some(max_dist) => self.max - max_dist,
+            None => f32::MIN,
+        };
+        if self.capacity > 0 && self.pending.len() > self.capacity {
+            let mut rels: Vec<_> = self.pending.iter().map(|i| i.relevance).collect();
+            let (_, nth, _) = rels.select_nth_unstable_by(self.capacity - 1, |a, b| b.total_cmp(a));
+            threshold = threshold.max(*nth);
+        }
+
+        let len = self.pending.len();
+        self.pending.retain(|i| i.relevance >= threshold);
+        self.pruned += len - self.pending.len();
+        self.pending_pos = self
+            .pending
+            .iter()
+            .enumerate()
+            .map(|(pos, i)| (i.item.clone(), pos))
+            .collect();
+        self.pending_pruned_len = self.pending.len().max(MIN_PRUNE_LEN);
+    }
+
+    /// Keeps track of `rel` if it's within the max distance of the best item
+    fn push_rel(&mut self, rel: f32) {
+        let max_dist = match self.max_dist {
+            Some(max_dist) => max_dist,
+            None => return,
+        };
+
+        // The max relevance only grows, so items below the distance never get counted again
+        if rel + max_dist < self.max {
+            return;
+        }
+
+        self.rel_list.push(rel);
+
+        if self.rel_list.len() >= self.pruned_len * 2 {
+            let max = self.max;
+            self.rel_list.retain(|i| *i + max_dist >= max);
+            self.pruned_len = self.rel_list.len().max(MIN_PRUNE_LEN);
+        }
     }
 }
 
@@ -70,3 +260,115 @@ pub trait OutputAddable: Default {
 }
 
 impl OutputAddable for () {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_top_count() {
+        let mut out: OutputBuilder<u32, ()> =
+            OutputBuilder::new(|_| false, 1000).with_max_dist(Some(100.0));
+        for i in 0..1000 {
+            out.push(RelItem::new(i, i as f32));
+        }
+        // Items with a relevance of 899 up to 999
+        assert_eq!(out.top_count(), 101);
+        assert!(out.rel_list.len() < 1000);
+    }
+
+    fn merged(merge: MergeStrategy) -> Vec<(u32, f32)> {
+        let mut out: OutputBuilder<u32, ()> = OutputBuilder::new(|_| false, 10).with_merge(merge);
+        // Exact match producer
+        out.push(RelItem::new(1, 1.0));
+        out.push(RelItem::new(2, 0.5));
+        // Vector producer
+        out.push(RelItem::new(3, 0.8));
+        out.push(RelItem::new(2, 0.9));
+        out.finish();
+
+        let mut items: Vec<_> = out.p.into_iter().map(|i| (i.item, i.relevance)).collect();
+        items.reverse();
+        items
+    }
+
+    #[test]
+    fn test_total_pushed() {
+        let mut out: OutputBuilder<u32, ()> = OutputBuilder::new(|i| *i == 4, 2);
+        for i in [1, 2, 1, 3, 2, 4] {
+            out.push(RelItem::new(i, 1.0));
+        }
+        // Duplicates and filtered items aren't counted, items beyond the capacity are
+        assert_eq!(out.total_pushed(), 3);
+    }
+
+    #[test]
+    fn test_skip_far_items() {
+        let mut out: OutputBuilder<u32, ()> =
+            OutputBuilder::new(|_| false, 1000).with_max_dist(Some(10.0));
+        for i in (0..1000).rev() {
+            out.push(RelItem::new(i, i as f32));
+        }
+        // Items with a relevance of 989 up to 999
+        assert_eq!(out.top_count(), 11);
+        assert_eq!(out.len(), 11);
+    }
+
+    #[test]
+    fn test_prune_pending() {
+        let mut out: OutputBuilder<u32, ()> =
+            OutputBuilder::new(|_| false, 2).with_merge(MergeStrategy::Max);
+        for i in 0..1000 {
+            out.push(RelItem::new(i, i as f32));
+        }
+        assert!(out.pending.len() < 1000);
+        assert_eq!(out.total_pushed(), 1000);
+
+        // Pruned items pushed again with a higher relevance still get merged
+        out.push(RelItem::new(1, 2000.0));
+        out.push(RelItem::new(2, 1.0));
+        out.finish();
+
+        let items: Vec<_> = out.p.into_iter().map(|i| (i.item, i.relevance)).collect();
+        assert_eq!(items, vec![(999, 999.0), (1, 2000.0)]);
+    }
+
+    #[test]
+    fn test_prune_far_items() {
+        let mut out: OutputBuilder<u32, ()> = OutputBuilder::new(|_| false, 1000)
+            .with_max_dist(Some(10.0))
+            .with_merge(MergeStrategy::Max);
+        for i in 0..1000 {
+            out.push(RelItem::new(i, i as f32));
+        }
+        assert!(out.pending.len() < 1000);
+
+        out.finish();
+        assert_eq!(out.top_count(), 11);
+        assert_eq!(out.total_pushed(), 1000);
+    }
+
+    #[test]
+    fn test_merge_first() {
+        assert_eq!(
+            merged(MergeStrategy::First),
+            vec![(1, 1.0), (3, 0.8), (2, 0.5)]
+        );
+    }
+
+    #[test]
+    fn test_merge_max() {
+        assert_eq!(
+            merged(MergeStrategy::Max),
+            vec![(1, 1.0), (2, 0.9), (3, 0.8)]
+        );
+    }
+
+    #[test]
+    fn test_merge_sum() {
+        assert_eq!(
+            merged(MergeStrategy::Sum),
+            vec![(2, 1.4), (1, 1.0), (3, 0.8)]
+        );
+    }
+}
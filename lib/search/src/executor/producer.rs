@@ -2,6 +2,15 @@ use super::{out_builder::OutputBuilder, searchable::Searchable};
 use engine::pushable::FilteredMaxCounter;
 use std::any::type_name;
 
+/// How expensive it is to run a producer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cost {
+    Cheap,
+    /// Gets skipped if the producers before it already found enough items to fill a page.
+    /// Producers with other output than their items must not be expensive
+    Expensive,
+}
+
 pub trait Producer {
     type Target: Searchable;
 
@@ -19,6 +28,10 @@ pub trait Producer {
 
     fn estimate_to(&self, _out: &mut FilteredMaxCounter<<Self::Target as Searchable>::Item>) {}
 
+    fn cost(&self) -> Cost {
+        Cost::Cheap
+    }
+
     fn name(&self) -> String {
         format_debug_name::<Self>()
     }
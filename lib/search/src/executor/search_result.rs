@@ -6,6 +6,8 @@ pub struct SearchResult<T, O = ()> {
     pub items: Vec<T>,
     pub total: usize,
     pub other_data: O,
+    /// Names of producers which have been skipped due to the latency budget
+    pub skipped: Vec<String>,
 }
 
 impl<T> SearchResult<T, ()> {
@@ -17,6 +19,7 @@ impl<T> SearchResult<T, ()> {
             items,
             total,
             other_data: (),
+            skipped: vec![],
         }
     }
 
@@ -27,6 +30,7 @@ impl<T> SearchResult<T, ()> {
             items,
             total,
             other_data: (),
+            skipped: vec![],
         }
     }
 }
@@ -39,9 +43,23 @@ impl<T, O> SearchResult<T, O> {
             items,
             total,
             other_data,
+            skipped: vec![],
         }
     }
 
+    /// Sets the producers which have been skipped
+    #[inline]
+    pub fn with_skipped(mut self, skipped: Vec<String>) -> Self {
+        self.skipped = skipped;
+        self
+    }
+
+    /// Returns `true` if producers have been skipped so the result might be incomplete
+    #[inline]
+    pub fn is_partial(&self) -> bool {
+        !self.skipped.is_empty()
+    }
+
     #[inline]
     pub fn len(&self) -> usize {
         self.items.len()
@@ -65,6 +83,7 @@ impl<T, O: Default> SearchResult<T, O> {
             items,
             total,
             other_data: O::default(),
+            skipped: vec![],
         }
     }
 }
@@ -79,6 +98,7 @@ where
             items: vec![],
             total: 0,
             other_data: O::default(),
+            skipped: vec![],
         }
     }
 }
@@ -1,8 +1,8 @@
 use super::{
-    out_builder::{OutputAddable, OutputBuilder},
+    out_builder::{MergeStrategy, OutputAddable, OutputBuilder},
     producer::Producer,
 };
-use crate::query::Query;
+use crate::{engine::settings, query::Query};
 use std::{fmt::Debug, hash::Hash};
 
 pub trait Searchable {
@@ -20,6 +20,20 @@ pub trait Searchable {
     /// Allows modifying the collected producers output before converting it to a SearchResult
     fn mod_output(&self, _out: &mut OutputBuilder<Self::Item, Self::ResAdd>) {}
 
+    /// Max amount of items collected for [`Searchable::group_items`]. Searches which group their
+    /// results need more items than the requested page to build the groups before paginating
+    #[inline]
+    fn group_capacity(&self) -> Option<usize> {
+        None
+    }
+
+    /// Groups all collected items, which are ordered by relevance, before they get paginated.
+    /// Only called if [`Searchable::group_capacity`] is set
+    #[inline]
+    fn group_items(&self, items: Vec<Self::Item>) -> Vec<Self::Item> {
+        items
+    }
+
     /// Should return `true` if the passed item should be ignored / filtered
     fn filter(&self, _item: &Self::Item) -> bool {
         false
@@ -29,4 +43,10 @@ pub trait Searchable {
     fn max_top_dist(&self) -> Option<f32> {
         None
     }
+
+    /// How the relevances of an item found by multiple producers get merged
+    #[inline]
+    fn merge_strategy(&self) -> MergeStrategy {
+        settings::merge_strategy(self.get_query().target)
+    }
 }
@@ -122,12 +122,32 @@ pub fn guess_result(query: &Query) -> Option<Guess> {
 fn by_meaning(meaning: &str) -> Vec<Kanji> {
     // TODO: implement proper algo kek
     let meaning = meaning.to_lowercase();
-    resources::get()
+    let mut res = resources::get()
         .kanji()
         .iter()
         .filter(|i| i.meanings.contains(&meaning))
         .cloned()
-        .collect::<Vec<_>>()
+        .collect::<Vec<_>>();
+
+    // Append kanji whose mnemonic mentions the query
+    for kanji in by_mnemonic(&meaning) {
+        if !res.contains(&kanji) {
+            res.push(kanji);
+        }
+    }
+
+    res
+}
+
+/// Find kanji by terms used in their mnemonic
+fn by_mnemonic(query: &str) -> Vec<Kanji> {
+    resources::get()
+        .kanji()
+        .by_mnemonic(query)
+        .into_iter()
+        .take(100)
+        .cloned()
+        .collect()
 }
 
 #[inline]
@@ -5,6 +5,9 @@ use types::jotoba::{
     words::{filter_languages, Word},
 };
 
+/// Amount of reading compounds shown per page
+pub const COMPOUNDS_PER_PAGE: usize = 10;
+
 // The final result of a Kanji search
 #[derive(Default)]
 pub struct KanjiResult {
@@ -22,8 +25,8 @@ pub struct Item {
 
 impl Item {
     pub fn load_words(k: Kanji, lang: impl AsLangParam) -> Self {
-        let kun_dicts = load_dicts(&k.kun_dicts, lang);
-        let on_dicts = load_dicts(&k.on_dicts, lang);
+        let kun_dicts = load_dicts(compound_page(&k.kun_dicts, 1), lang);
+        let on_dicts = load_dicts(compound_page(&k.on_dicts, 1), lang);
 
         let has_compositions = resources::get().kanji().ids(k.literal).is_some();
 
@@ -36,7 +39,20 @@ impl Item {
     }
 }
 
-fn load_dicts(dicts: &Vec<u32>, lang: impl AsLangParam) -> Option<Vec<Word>> {
+/// Returns the sequence ids of the compounds on the given page. Pages start at 1
+pub fn compound_page(dicts: &[u32], page: usize) -> &[u32] {
+    let start = page.saturating_sub(1) * COMPOUNDS_PER_PAGE;
+    let end = (start + COMPOUNDS_PER_PAGE).min(dicts.len());
+    dicts.get(start..end).unwrap_or_default()
+}
+
+/// Returns `true` if there are more compounds after the given page
+#[inline]
+pub fn has_more_compounds(dicts: &[u32], page: usize) -> bool {
+    page * COMPOUNDS_PER_PAGE < dicts.len()
+}
+
+fn load_dicts(dicts: &[u32], lang: impl AsLangParam) -> Option<Vec<Word>> {
     let word_storage = resources::get().words();
     let mut words: Vec<_> = dicts
         .iter()
@@ -54,6 +70,18 @@ fn load_dicts(dicts: &Vec<u32>, lang: impl AsLangParam) -> Option<Vec<Word>> {
 }
 
 impl Item {
+    /// Returns `true` if the kanji has more on reading compounds than the ones loaded
+    #[inline]
+    pub fn has_more_on(&self) -> bool {
+        has_more_compounds(&self.kanji.on_dicts, 1)
+    }
+
+    /// Returns `true` if the kanji has more kun reading compounds than the ones loaded
+    #[inline]
+    pub fn has_more_kun(&self) -> bool {
+        has_more_compounds(&self.kanji.kun_dicts, 1)
+    }
+
     /// Returns the entries' frames (svg)
     pub fn get_frames(&self) -> Option<String> {
         read_to_string(self.kanji.get_stroke_frames_path()).ok()
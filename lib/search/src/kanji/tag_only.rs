@@ -12,6 +12,7 @@ pub fn search(query: &Query) -> Result<KanjiResult, Error> {
     match single_tag.unwrap() {
         Tag::Jlpt(jlpt) => jlpt_search(query, *jlpt),
         Tag::GenkiLesson(genki_lesson) => genki_search(query, *genki_lesson),
+        Tag::WaniKani(level) => wanikani_search(query, *level),
         _ => return Ok(KanjiResult::default()),
     }
 }
@@ -46,6 +47,33 @@ fn genki_search(query: &Query, genki_lesson: u8) -> Result<KanjiResult, Error> {
     Ok(KanjiResult { items, total_len })
 }
 
+fn wanikani_search(query: &Query, level: u8) -> Result<KanjiResult, Error> {
+    let kanji_retrieve = resources::get().kanji();
+
+    let level_kanji = match resources::get().wanikani().kanji_by_level(level) {
+        Some(kanji) => kanji,
+        None => return Ok(KanjiResult::default()),
+    };
+
+    let total_len = level_kanji.len();
+
+    let page_size = query.settings.page_size as usize;
+    let page_offset = query.page_offset(page_size);
+
+    let kanji = level_kanji
+        .iter()
+        .skip(page_offset)
+        .take(page_size)
+        .filter_map(|literal| kanji_retrieve.by_literal(*literal))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    Ok(KanjiResult {
+        items: super::to_item(kanji, query),
+        total_len,
+    })
+}
+
 fn jlpt_search(query: &Query, jlpt: u8) -> Result<KanjiResult, Error> {
     let kanji_retrieve = resources::get().kanji();
 
@@ -5,6 +5,7 @@ pub mod name;
 pub mod query;
 pub mod radical;
 pub mod sentence;
+pub mod warmup;
 pub mod word;
 
 pub use executor::SearchExecutor;
@@ -0,0 +1,34 @@
+use types::jotoba::names::{group::NameGroup, Name};
+
+use crate::query::Query;
+
+/// Orders names by their group into people, places, organizations and other names. The names
+/// within a group keep their order and each group is limited to the amount set in `query`
+pub fn order(names: Vec<&'static Name>, query: &Query) -> Vec<&'static Name> {
+    NameGroup::all()
+        .into_iter()
+        .flat_map(|group| {
+            let limit = query.name_group_limit(group);
+            names
+                .iter()
+                .copied()
+                .filter(move |i| i.group() == group)
+                .take(limit)
+        })
+        .collect()
+}
+
+/// Splits a page of names which have been ordered with [`order`] into their groups
+pub fn group(names: &[&'static Name]) -> Vec<(NameGroup, Vec<&'static Name>)> {
+    NameGroup::all()
+        .into_iter()
+        .filter_map(|group| {
+            let items: Vec<_> = names
+                .iter()
+                .copied()
+                .filter(|i| i.group() == group)
+                .collect();
+            (!items.is_empty()).then(|| (group, items))
+        })
+        .collect()
+}
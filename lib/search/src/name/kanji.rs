@@ -0,0 +1,18 @@
+use itertools::Itertools;
+use types::jotoba::{kanji::Kanji, names::Name};
+
+/// Returns the kanji `name` is composed of in the order they appear in. Characters without
+/// kanji information (eg. kana or 々) are skipped
+pub fn breakdown(name: &Name) -> Vec<&'static Kanji> {
+    let kanji = match name.kanji {
+        Some(ref kanji) => kanji,
+        None => return vec![],
+    };
+
+    let kanji_retr = resources::get().kanji();
+    kanji
+        .chars()
+        .unique()
+        .filter_map(|c| kanji_retr.by_literal(c))
+        .collect()
+}
@@ -1,5 +1,8 @@
+pub mod group;
+pub mod kanji;
 mod order;
 mod producer;
+pub mod result;
 
 use crate::{
     executor::{producer::Producer, searchable::Searchable},
@@ -7,12 +10,16 @@ use crate::{
 };
 use producer::{
     foreign::ForeignProducer, kanji_reading::KreadingProducer, native::NativeProducer,
-    sequence::SeqProducer,
+    romaji::RomajiProducer, sequence::SeqProducer, transliterate::TransliterationProducer,
 };
+use result::ResData;
 use types::jotoba::names::Name;
 
 use self::producer::native::split::SplitProducer;
 
+/// Max amount of names which get grouped. Names behind the best ones aren't shown
+const GROUP_CAPACITY: usize = 1000;
+
 pub struct Search<'a> {
     query: &'a Query,
     producer: Vec<Box<dyn Producer<Target = Self> + 'a>>,
@@ -24,6 +31,8 @@ impl<'a> Search<'a> {
         producer.push(Box::new(SeqProducer::new(query)));
         producer.push(Box::new(KreadingProducer::new(query)));
         producer.push(Box::new(ForeignProducer::new(query)));
+        producer.push(Box::new(RomajiProducer::new(query)));
+        producer.push(Box::new(TransliterationProducer::new(query)));
         producer.push(Box::new(NativeProducer::new(query)));
         producer.push(Box::new(SplitProducer::new(query)));
         Self { query, producer }
@@ -33,7 +42,7 @@ impl<'a> Search<'a> {
 impl<'a> Searchable for Search<'a> {
     type Item = &'static Name;
     type OutItem = &'static Name;
-    type ResAdd = ();
+    type ResAdd = ResData;
 
     #[inline]
     fn to_output_item(&self, item: Self::Item) -> Self::OutItem {
@@ -49,4 +58,14 @@ impl<'a> Searchable for Search<'a> {
     fn get_query(&self) -> &Query {
         self.query
     }
+
+    #[inline]
+    fn group_capacity(&self) -> Option<usize> {
+        Some(GROUP_CAPACITY)
+    }
+
+    #[inline]
+    fn group_items(&self, items: Vec<Self::Item>) -> Vec<Self::Item> {
+        group::order(items, self.query)
+    }
 }
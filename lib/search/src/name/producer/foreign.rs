@@ -1,7 +1,10 @@
 use engine::{pushable::FilteredMaxCounter, task::SearchTask};
 
 use crate::{
-    engine::names::foreign::Engine,
+    engine::{
+        names::foreign::Engine,
+        settings::{self, EngineKind},
+    },
     executor::{out_builder::OutputBuilder, producer::Producer, searchable::Searchable},
     name::{order::foreign::ForeignOrder, Search},
     query::{Query, QueryLang},
@@ -18,9 +21,10 @@ impl<'a> ForeignProducer<'a> {
 
     fn foreign_task(&self) -> SearchTask<'static, Engine> {
         let query = format_word(&self.query.query_str);
-        SearchTask::<Engine>::new(&query)
+        let task = SearchTask::<Engine>::new(&query)
             .with_custom_order(ForeignOrder)
-            .with_threshold(0.5)
+            .with_threshold(0.5);
+        settings::apply(EngineKind::Names, task)
     }
 }
 
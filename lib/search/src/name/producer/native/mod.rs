@@ -1,7 +1,10 @@
 pub mod split;
 
 use crate::{
-    engine::names::native::Engine,
+    engine::{
+        names::native::Engine,
+        settings::{self, EngineKind},
+    },
     executor::{out_builder::OutputBuilder, producer::Producer, searchable::Searchable},
     name::{order::japanese::NativeOrder, Search},
     query::{Query, QueryLang},
@@ -20,9 +23,10 @@ impl<'a> NativeProducer<'a> {
 
     #[inline]
     fn jp_task(&self) -> SearchTask<'static, Engine> {
-        SearchTask::<Engine>::new(&self.query.query_str)
+        let task = SearchTask::<Engine>::new(&self.query.query_str)
             .with_custom_order(NativeOrder)
-            .with_threshold(0.3)
+            .with_threshold(0.3);
+        settings::apply(EngineKind::Names, task)
     }
 }
 
@@ -9,7 +9,11 @@ use types::jotoba::names::Name;
 
 use crate::{
     engine::names::native::Engine,
-    executor::{out_builder::OutputBuilder, producer::Producer, searchable::Searchable},
+    executor::{
+        out_builder::OutputBuilder,
+        producer::{Cost, Producer},
+        searchable::Searchable,
+    },
     name::{order::japanese::NativeOrder, Search},
     query::Query,
 };
@@ -79,6 +83,11 @@ impl<'a> Producer for SplitProducer<'a> {
         //already_found == 0
     }
 
+    #[inline]
+    fn cost(&self) -> Cost {
+        Cost::Expensive
+    }
+
     fn estimate_to(&self, out: &mut FilteredMaxCounter<<Self::Target as Searchable>::Item>) {
         self.run(|engine, out| engine.estimate_to(out), out);
     }
@@ -0,0 +1,103 @@
+use engine::{
+    pushable::FilteredMaxCounter,
+    relevance::{data::SortData, RelevanceEngine},
+    task::SearchTask,
+};
+use japanese::guessing::could_be_romaji;
+use ngindex::{item::IndexItem, termset::TermSet};
+use types::jotoba::names::{name_type::NameType, Name};
+
+use crate::{
+    engine::names::native::Engine,
+    executor::{out_builder::OutputBuilder, producer::Producer, searchable::Searchable},
+    name::{order::japanese::NativeOrder, Search},
+    query::{Query, QueryLang},
+};
+
+/// Producer for romanized name queries like "Yuki" returning all kanji spellings of the name,
+/// clustered by their gender/type
+pub struct RomajiProducer<'a> {
+    query: &'a Query,
+}
+
+impl<'a> RomajiProducer<'a> {
+    pub fn new(query: &'a Query) -> Self {
+        Self { query }
+    }
+
+    fn hira_query(&self) -> String {
+        japanese::to_hira_fmt(&self.query.query_str)
+    }
+
+    fn task(&self) -> SearchTask<'static, Engine> {
+        let hira = self.hira_query();
+        SearchTask::<Engine>::new(&hira)
+            .with_custom_order(ClusterOrder)
+            .with_result_filter(move |name| name.has_kanji() && name.kana == hira)
+    }
+}
+
+impl<'a> Producer for RomajiProducer<'a> {
+    type Target = Search<'a>;
+
+    fn produce(
+        &self,
+        out: &mut OutputBuilder<
+            <Self::Target as Searchable>::Item,
+            <Self::Target as Searchable>::ResAdd,
+        >,
+    ) {
+        self.task().find_to(out);
+    }
+
+    fn should_run(&self, _already_found: usize) -> bool {
+        self.query.q_lang == QueryLang::Foreign && could_be_romaji(&self.query.query_str)
+    }
+
+    fn estimate_to(&self, out: &mut FilteredMaxCounter<<Self::Target as Searchable>::Item>) {
+        self.task().estimate_to(out);
+    }
+}
+
+/// Orders names by their cluster first and by their similarity to the query second, so that
+/// spellings of the same gender/type end up next to each other
+struct ClusterOrder;
+
+impl RelevanceEngine for ClusterOrder {
+    type OutItem = &'static Name;
+    type IndexItem = IndexItem<u32>;
+    type Query = TermSet;
+
+    #[inline]
+    fn score<'item, 'query>(
+        &self,
+        item: &SortData<'item, 'query, Self::OutItem, Self::IndexItem, Self::Query>,
+    ) -> f32 {
+        let cluster = (CLUSTERS.len() - cluster(item.item())) as f32;
+        cluster + NativeOrder.score(item) * 0.1
+    }
+}
+
+/// Name types to cluster names by, in the order they're displayed
+const CLUSTERS: &[NameType] = &[
+    NameType::Female,
+    NameType::Male,
+    NameType::Given,
+    NameType::Surname,
+    NameType::Person,
+    NameType::Place,
+];
+
+/// Returns the index of the cluster `name` belongs to. Names not matching any cluster are
+/// placed into the last one
+fn cluster(name: &Name) -> usize {
+    let types = match name.name_type {
+        Some(ref types) => types,
+        None => return CLUSTERS.len(),
+    };
+
+    CLUSTERS
+        .iter()
+        .position(|c| types.contains(c))
+        .unwrap_or(CLUSTERS.len())
+}
@@ -0,0 +1,60 @@
+use engine::{pushable::FilteredMaxCounter, task::SearchTask};
+
+use crate::{
+    engine::names::native::Engine,
+    executor::{out_builder::OutputBuilder, producer::Producer, searchable::Searchable},
+    name::{order::japanese::NativeOrder, Search},
+    query::{Query, QueryLang},
+};
+
+/// Producer for foreign names (eg. "Christopher") converting them into katakana and returning
+/// names matching one of those renderings. It's never skipped as expensive producer, since the
+/// renderings are shown even if they don't find any new names
+pub struct TransliterationProducer<'a> {
+    query: &'a Query,
+    candidates: Vec<String>,
+}
+
+impl<'a> TransliterationProducer<'a> {
+    pub fn new(query: &'a Query) -> Self {
+        let candidates = japanese::transliterate::to_katakana_candidates(&query.query_str);
+        Self { query, candidates }
+    }
+
+    fn tasks(&self) -> impl Iterator<Item = SearchTask<'static, Engine>> + '_ {
+        self.candidates.iter().map(String::as_str).map(Self::task)
+    }
+
+    fn task(candidate: &str) -> SearchTask<'static, Engine> {
+        let kana = candidate.to_string();
+        SearchTask::<Engine>::new(candidate)
+            .with_custom_order(NativeOrder)
+            .with_result_filter(move |name| name.kana == kana)
+    }
+}
+
+impl<'a> Producer for TransliterationProducer<'a> {
+    type Target = Search<'a>;
+
+    fn produce(
+        &self,
+        out: &mut OutputBuilder<
+            <Self::Target as Searchable>::Item,
+            <Self::Target as Searchable>::ResAdd,
+        >,
+    ) {
+        out.output_add.transliterations = self.candidates.clone();
+
+        SearchTask::find_parallel_to(&self.candidates, |i| Self::task(i), out);
+    }
+
+    fn should_run(&self, _already_found: usize) -> bool {
+        self.query.q_lang == QueryLang::Foreign && !self.candidates.is_empty()
+    }
+
+    fn estimate_to(&self, out: &mut FilteredMaxCounter<<Self::Target as Searchable>::Item>) {
+        for mut task in self.tasks() {
+            task.estimate_to(out);
+        }
+    }
+}
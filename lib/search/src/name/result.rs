@@ -0,0 +1,15 @@
+use crate::executor::out_builder::OutputAddable;
+
+/// Additional result data for a name search
+#[derive(Default, Clone, Debug)]
+pub struct ResData {
+    /// Katakana renderings of a foreign name query
+    pub transliterations: Vec<String>,
+}
+
+impl OutputAddable for ResData {
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.transliterations.is_empty()
+    }
+}
@@ -6,15 +6,17 @@ pub mod tags;
 pub mod user_settings;
 
 pub use form::Form;
-pub use tags::Tag;
+pub use tags::{Tag, TagExpr};
 pub use user_settings::UserSettings;
 
 use self::regex::RegexSQuery;
 use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use std::hash::Hash;
 use types::jotoba::{
+    kanji::reading::ReadingSearch,
     language::{LangParam, Language},
-    search::SearchTarget,
+    names::group::NameGroup,
+    search::{unknown_tag::UnknownTag, SearchTarget},
     words::{misc::Misc, part_of_speech::PosSimple},
 };
 
@@ -29,8 +31,11 @@ pub struct Query {
     pub query_str: String,
     /// Where to search {Words,Names,Kanji,Sentences}
     pub target: SearchTarget,
-    /// Additional tags eg. #kanji or #jlpt4
+    /// Additional tags eg. #kanji or #jlpt4. Only contains tags which have to match in any case.
+    /// OR-combined tags are only part of `tag_expr`
     pub tags: Vec<Tag>,
+    /// All tags as a boolean expression
+    pub tag_expr: TagExpr,
     /// The form of the Query. Eg. KanjiReadingSearch or TagOnly
     pub form: Form,
     /// The language of the passed query string
@@ -45,10 +50,17 @@ pub struct Query {
     pub word_index: usize,
     /// All terms the result has to contain to be shown
     pub must_contain: Vec<String>,
+    /// Readings of kanji within the query, given as eg. '生.なま'. Words have to use those
+    /// readings to be shown
+    pub kanji_readings: Vec<ReadingSearch>,
+    /// Tags within the query which couldn't be recognized and have been ignored
+    pub unknown_tags: Vec<UnknownTag>,
     /// Overwrite the users settings language temporarily
     pub cust_lang: Option<Language>,
     /// Regex query (for jp)
     pub regex: Option<RegexSQuery>,
+    /// Custom max amount of names shown per name group
+    pub name_group_limits: Vec<(NameGroup, usize)>,
 }
 
 /// The language of the query content itself
@@ -62,6 +74,16 @@ pub enum QueryLang {
 }
 
 impl Query {
+    /// Returns the max amount of names to show for the given name group across all pages.
+    /// Unlimited if no custom limit is set
+    pub fn name_group_limit(&self, group: NameGroup) -> usize {
+        self.name_group_limits
+            .iter()
+            .find(|i| i.0 == group)
+            .map(|i| i.1)
+            .unwrap_or(usize::MAX)
+    }
+
     /// Returns true if the query has at least one pos tag
     #[inline]
     pub fn has_part_of_speech_tags(&self) -> bool {
@@ -123,9 +145,11 @@ impl Query {
         utf8_percent_encode(&self.query_str, QUERY_ENCODE_SET).to_string()
     }
 
-    /// Returns the language with lang override applied
+    /// Returns the language with lang override applied. Falls back to English if the words of
+    /// the language aren't loaded
     pub fn get_search_lang(&self) -> Language {
-        self.cust_lang.unwrap_or(self.settings.user_lang)
+        let lang = self.cust_lang.unwrap_or(self.settings.user_lang);
+        indexes::get().word().loaded_language(lang)
     }
 
     /// Shortcut for query.settings.user_lang. This does not apply overwritten language. For that use `get_search_lang`
@@ -146,6 +170,13 @@ impl Query {
         self.settings.show_english
     }
 
+    /// Returns the encoded raw query with the unknown tag `tag` replaced by its suggestion
+    pub fn with_suggested_tag(&self, tag: &UnknownTag) -> Option<String> {
+        let suggestion = tag.suggestion.as_ref()?;
+        let query = parser::tags::replace_tag(&self.raw_query, &tag.tag, suggestion);
+        Some(utf8_percent_encode(&query, QUERY_ENCODE_SET).to_string())
+    }
+
     /// Returns `true` if the query is a regex query
     #[inline]
     pub fn is_regex(&self) -> bool {
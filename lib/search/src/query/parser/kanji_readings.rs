@@ -0,0 +1,50 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use types::jotoba::kanji::reading::ReadingSearch;
+
+/// Regex for kanji annotated with a reading, eg. `生.なま`. A trailing dot ends the reading so
+/// okurigana can follow, eg. `生.い.きる`
+static KANJI_READING: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(\p{Han})\.([\p{Hiragana}\p{Katakana}ー]+)\.?").unwrap());
+
+/// Extracts all readings annotated to kanji within `inp` and returns the query without the
+/// annotations along with the extracted readings
+pub fn parse(inp: &str) -> (String, Vec<ReadingSearch>) {
+    if !inp.contains('.') {
+        return (inp.to_string(), vec![]);
+    }
+
+    let mut readings = vec![];
+    let new_query = KANJI_READING.replace_all(inp, |caps: &regex::Captures| {
+        readings.push(ReadingSearch::new(&caps[1], &caps[2]));
+        caps[1].to_string()
+    });
+
+    (new_query.to_string(), readings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_kanji_readings() {
+        let (query, readings) = parse("生.なま");
+        assert_eq!(query, "生");
+        assert_eq!(readings, vec![ReadingSearch::new("生", "なま")]);
+
+        let (query, readings) = parse("生.い.きる");
+        assert_eq!(query, "生きる");
+        assert_eq!(readings, vec![ReadingSearch::new("生", "い")]);
+
+        let (query, readings) = parse("音.おん楽.がく");
+        assert_eq!(query, "音楽");
+        assert_eq!(readings.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_no_kanji_readings() {
+        assert_eq!(parse("音楽"), ("音楽".to_string(), vec![]));
+        assert_eq!(parse("e.g."), ("e.g.".to_string(), vec![]));
+    }
+}
@@ -1,11 +1,17 @@
+pub mod kanji_readings;
 pub mod lang;
 pub(crate) mod prefix;
 pub mod req_terms;
-pub(crate) mod tags;
+pub mod tags;
 
-use super::{prefix::SearchPrefix, regex::RegexSQuery, Form, Query, Tag, UserSettings};
+use super::{prefix::SearchPrefix, regex::RegexSQuery, Form, Query, Tag, TagExpr, UserSettings};
 use jp_utils::JapaneseExt;
-use types::jotoba::{kanji, language::Language as ContentLanguage, search::SearchTarget};
+use types::jotoba::{
+    kanji,
+    language::Language as ContentLanguage,
+    names::group::NameGroup,
+    search::{filter_preset::FilterPreset, SearchTarget},
+};
 
 /// Max amount of characters a query is allowed to have
 pub const MAX_QUERY_LEN: usize = 400;
@@ -30,6 +36,10 @@ pub struct QueryParser {
     word_index: usize,
     /// Overwrite the users settings language
     language_override: Option<ContentLanguage>,
+    /// Custom max amount of names shown per name group
+    name_group_limits: Vec<(NameGroup, usize)>,
+    /// Filter presets of the user which can be used as tags
+    filter_presets: Vec<FilterPreset>,
 }
 
 impl QueryParser {
@@ -47,15 +57,31 @@ impl QueryParser {
             page: 0,
             word_index: 0,
             language_override: None,
+            name_group_limits: vec![],
+            filter_presets: vec![],
         }
     }
 
+    #[inline]
+    pub fn with_filter_presets(mut self, presets: Vec<FilterPreset>) -> Self {
+        self.filter_presets = presets;
+        self
+    }
+
     #[inline]
     pub fn with_lang_overwrite(mut self, lang: ContentLanguage) -> Self {
         self.language_override = Some(lang);
         self
     }
 
+    /// Sets the max amount of names shown for `group`
+    #[inline]
+    pub fn with_name_group_limit(mut self, group: NameGroup, limit: usize) -> Self {
+        self.name_group_limits.retain(|i| i.0 != group);
+        self.name_group_limits.push((group, limit));
+        self
+    }
+
     #[inline]
     pub fn with_word_index(mut self, word_index: usize) -> Self {
         self.word_index = word_index;
@@ -70,14 +96,39 @@ impl QueryParser {
     }
 
     /// Parses a user query into Query
+    #[tracing::instrument(level = "debug", skip_all, fields(query = %self.raw_query))]
     pub fn parse(mut self) -> Option<Query> {
-        let (stripped, s_prefix) = prefix::parse_prefix(&self.raw_query);
+        let normalized = japanese::normalize::normalize(&self.raw_query);
+        let (stripped, s_prefix) = prefix::parse_prefix(&normalized);
         if let Some(SearchPrefix::LangOverwrite(r#lang_overwrite)) = s_prefix {
             self.language_override = Some(lang_overwrite);
         }
 
-        let (new_query, tags) = Self::extract_tags(&stripped);
+        let stripped = tags::expand_presets(stripped, &self.filter_presets);
+        let unknown_tags = tags::unknown_tags(&stripped);
+        let (new_query, mut tag_groups) = tags::extract_groups(&stripped);
+        let (new_query, mut tags) = Self::extract_tags(&new_query);
+
+        // Groups of a single tag are just regular tags
+        for group in tag_groups.iter().filter(|i| i.len() == 1) {
+            tags.push(group[0]);
+        }
+        tag_groups.retain(|i| i.len() > 1);
+
+        // Language tags force the gloss language for this query only
+        if let Some(lang) = tags.iter().find_map(|i| i.as_language()) {
+            self.user_settings.user_lang = lang;
+        }
+
+        // Limits set within the query take precedence over the ones passed by the client
+        for (group, limit) in tags.iter().filter_map(|i| i.as_name_group_limit()) {
+            self = self.with_name_group_limit(group, limit);
+        }
+
+        let tag_expr = TagExpr::new(&tags, tag_groups);
+
         let (new_query, must_contain) = req_terms::parse(&new_query);
+        let (new_query, kanji_readings) = kanji_readings::parse(&new_query);
         let query_str: String = new_query
             .trim()
             .chars()
@@ -86,13 +137,13 @@ impl QueryParser {
             .collect();
 
         // Don't allow empty queries
-        if query_str.is_empty() && !tags.iter().any(|i| i.is_producer()) {
+        if query_str.is_empty() && !tag_expr.tags().iter().any(|i| i.is_producer()) {
             return None;
         }
 
         let q_lang = lang::parse(&query_str);
         let target = self.get_search_target(&tags);
-        let form = self.parse_form(&query_str, &tags, s_prefix);
+        let form = self.parse_form(&query_str, &tag_expr, s_prefix);
 
         let regex = RegexSQuery::new(&query_str);
 
@@ -101,6 +152,7 @@ impl QueryParser {
             target,
             form,
             tags,
+            tag_expr,
             query_str,
             raw_query: self.raw_query,
             settings: self.user_settings,
@@ -109,7 +161,10 @@ impl QueryParser {
             word_index: self.word_index,
             cust_lang: self.language_override,
             must_contain,
+            kanji_readings,
+            unknown_tags,
             regex,
+            name_group_limits: self.name_group_limits,
         })
     }
 
@@ -132,14 +187,14 @@ impl QueryParser {
             .unwrap_or(self.q_type)
     }
 
-    fn parse_form(&self, query: &str, tags: &[Tag], s_prefix: Option<SearchPrefix>) -> Form {
+    fn parse_form(&self, query: &str, tags: &TagExpr, s_prefix: Option<SearchPrefix>) -> Form {
         // Sequence search
         if let Some(SearchPrefix::BySequence(r#seq)) = s_prefix {
             return Form::Sequence(seq);
         }
 
         // Tag only search
-        if query.is_empty() && tags.iter().any(|i| i.is_producer()) {
+        if query.is_empty() && tags.tags().iter().any(|i| i.is_producer()) {
             return Form::TagOnly;
         }
 
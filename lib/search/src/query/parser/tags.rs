@@ -1,17 +1,125 @@
 use crate::query::Tag;
+use itertools::Itertools;
 use once_cell::sync::Lazy;
 use regex::Regex;
-use std::str::FromStr;
+use std::{ops::RangeInclusive, str::FromStr};
 use types::jotoba::{
-    search::SearchTarget,
+    language::Language,
+    names::group::NameGroup,
+    search::{filter_preset::FilterPreset, unknown_tag::UnknownTag, SearchTarget},
     sentences,
-    words::{misc::Misc, part_of_speech::PosSimple},
+    words::{dialect::Dialect, misc::Misc, part_of_speech::PosSimple},
 };
 use utils::trim_string_end;
 
 /// Regex for finding tags within a query.
 static TAG_REGEX: Lazy<Regex> = Lazy::new(|| regex::Regex::new("#[a-zA-Z0-9\\-]+").unwrap());
 
+/// Regex for finding OR-combined groups of tags within a query, eg. `#n4|#n5` or `(#noun #verb)`
+static TAG_GROUP_REGEX: Lazy<Regex> = Lazy::new(|| {
+    regex::Regex::new(
+        "\\( *#[a-zA-Z0-9\\-]+( +#[a-zA-Z0-9\\-]+)* *\\)|#[a-zA-Z0-9\\-]+(\\|#[a-zA-Z0-9\\-]+)+",
+    )
+    .unwrap()
+});
+
+/// Extracts all OR-combined tag groups from the query and returns a new one without those groups
+/// along with the tags of each group
+pub fn extract_groups(inp: &str) -> (String, Vec<Vec<Tag>>) {
+    let mut groups = vec![];
+
+    let new_out = TAG_GROUP_REGEX.replace_all(inp, |caps: &regex::Captures| {
+        let group: Vec<Tag> = TAG_REGEX
+            .find_iter(&caps[0])
+            .flat_map(|m| parse(&m.as_str().to_lowercase()))
+            .collect();
+        if !group.is_empty() {
+            groups.push(group);
+        }
+        ""
+    });
+
+    if groups.is_empty() {
+        return (inp.to_string(), groups);
+    }
+
+    let new_out = new_out.split(' ').filter(|i| !i.is_empty()).join(" ");
+    (new_out, groups)
+}
+
+/// Replaces all tags within `inp` which match the name of a preset with the presets tags. Built
+/// in tags can't be overwritten by presets
+pub fn expand_presets(inp: &str, presets: &[FilterPreset]) -> String {
+    if presets.is_empty() {
+        return inp.to_string();
+    }
+
+    TAG_REGEX
+        .replace_all(inp, |caps: &regex::Captures| {
+            let tag = caps[0].to_lowercase();
+            if !parse(&tag).is_empty() {
+                return caps[0].to_string();
+            }
+
+            presets
+                .iter()
+                .find(|p| p.name == tag[1..])
+                .map(|p| p.tags.clone())
+                .unwrap_or_else(|| caps[0].to_string())
+        })
+        .to_string()
+}
+
+/// Replaces all occurrences of the tag `tag` within `inp` with `replacement`. Tags which only start
+/// with `tag` (eg. `#n5` for `#n`) are kept
+pub fn replace_tag(inp: &str, tag: &str, replacement: &str) -> String {
+    TAG_REGEX
+        .replace_all(inp, |caps: &regex::Captures| {
+            if &caps[0] == tag {
+                replacement.to_string()
+            } else {
+                caps[0].to_string()
+            }
+        })
+        .to_string()
+}
+
+/// Returns all tags within `inp` which can't be parsed, along with the most similar known tag
+pub fn unknown_tags(inp: &str) -> Vec<UnknownTag> {
+    TAG_REGEX
+        .find_iter(inp)
+        .map(|m| m.as_str())
+        .filter(|tag| parse(&tag.to_lowercase()).is_empty())
+        .unique()
+        .map(|tag| UnknownTag::new(tag.to_string(), suggest(tag)))
+        .collect()
+}
+
+/// Returns the known tag (including the leading '#') which is the most similar to `unknown`
+pub fn suggest(unknown: &str) -> Option<String> {
+    let unknown = unknown.to_lowercase();
+    let unknown = unknown.strip_prefix('#').unwrap_or(&unknown);
+    let max_dist = (unknown.chars().count() / 2).clamp(1, MAX_SUGGESTION_DIST);
+
+    all_tags()
+        .into_iter()
+        .map(|tag| (utils::levenshtein(unknown, &tag), tag))
+        .filter(|(dist, _)| *dist <= max_dist)
+        .min_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.len().cmp(&b.1.len())))
+        .map(|(_, tag)| format!("#{tag}"))
+}
+
+/// Name groups along with the strings their limit tags (eg. `#people5`) start with
+const NAME_GROUP_TAGS: &[(&str, NameGroup)] = &[
+    ("people", NameGroup::People),
+    ("places", NameGroup::Places),
+    ("organizations", NameGroup::Organizations),
+    ("other", NameGroup::Other),
+];
+
+/// Valid limits of name group tags
+const NAME_GROUP_LIMITS: RangeInclusive<u16> = 1..=100;
+
 /// Extracts all tags from the query and returns a new one without tags along with those tags which were extracted
 pub fn extract_parse<'a, F>(inp: &'a str, parse: F) -> (String, Vec<Tag>)
 where
@@ -54,25 +162,126 @@ where
     (trim_string_end(new_out), tags)
 }
 
+/// Tags without a value along with all strings they're parsed from. The first one is used for
+/// completions
+const FIXED_TAGS: &[(&[&str], Tag)] = &[
+    (&["hidden", "hide"], Tag::Hidden),
+    (
+        &["irrichidan", "irregularichidan", "irregular-ichidan"],
+        Tag::IrregularIruEru,
+    ),
+    (&["katakana"], Tag::Katakana),
+    (&["new", "recent"], Tag::New),
+];
+
+/// Search type tags along with all strings they're parsed from. The first one is used for
+/// completions
+const SEARCH_TYPE_TAGS: &[(&[&str], Tag)] = &[
+    (&["kanji"], Tag::SearchType(SearchTarget::Kanji)),
+    (
+        &["sentence", "sentences"],
+        Tag::SearchType(SearchTarget::Sentences),
+    ),
+    (&["name", "names"], Tag::SearchType(SearchTarget::Names)),
+    (&["word", "words"], Tag::SearchType(SearchTarget::Words)),
+    (&["abbreviation", "abbrev"], Tag::Misc(Misc::Abbreviation)),
+    (&["uwk"], Tag::Misc(Misc::UsuallyWrittenInKana)),
+];
+
+/// Dialect tags along with all strings they're parsed from. The first one is used for completions
+const DIALECT_TAGS: &[(&[&str], Tag)] = &[
+    (
+        &["kansai", "kansaiben", "ksb"],
+        Tag::Dialect(Dialect::Kansai),
+    ),
+    (&["osaka", "osakaben", "osb"], Tag::Dialect(Dialect::Osaka)),
+    (&["kyoto", "kyotoben", "kyb"], Tag::Dialect(Dialect::Kyoto)),
+    (
+        &["kyushu", "kyuushuu", "kyu"],
+        Tag::Dialect(Dialect::Kyuushuu),
+    ),
+    (&["kanto", "kantou", "ktb"], Tag::Dialect(Dialect::Kantou)),
+    (
+        &["tohoku", "touhoku", "thb"],
+        Tag::Dialect(Dialect::Touhoku),
+    ),
+    (&["hokkaido", "hob"], Tag::Dialect(Dialect::Hokkaido)),
+    (&["nagano", "nab"], Tag::Dialect(Dialect::Nagano)),
+    (
+        &["ryukyu", "ryuukyuu", "rkb"],
+        Tag::Dialect(Dialect::Ryuukyuu),
+    ),
+    (&["tosa", "tosaben", "tsb"], Tag::Dialect(Dialect::Tosa)),
+    (
+        &["tsugaru", "tsugaruben", "tsug"],
+        Tag::Dialect(Dialect::Tsugaru),
+    ),
+    (&["brazilian", "bra"], Tag::Dialect(Dialect::Brazilian)),
+];
+
+/// Language override tags along with all strings they're parsed from. The first one is used for
+/// completions
+const LANGUAGE_TAGS: &[(&[&str], Tag)] = &[
+    (&["en", "eng", "english"], Tag::Language(Language::English)),
+    (&["de", "ger", "german"], Tag::Language(Language::German)),
+    (&["fr", "fre", "french"], Tag::Language(Language::French)),
+    (&["ru", "rus", "russian"], Tag::Language(Language::Russian)),
+    (&["es", "spa", "spanish"], Tag::Language(Language::Spanish)),
+    (&["sv", "swe", "swedish"], Tag::Language(Language::Swedish)),
+    (&["nl", "dut", "dutch"], Tag::Language(Language::Dutch)),
+    (
+        &["hu", "hun", "hungarian"],
+        Tag::Language(Language::Hungarian),
+    ),
+    // "#sl" is already used for slang
+    (&["slv", "slovenian"], Tag::Language(Language::Slovenian)),
+];
+
+/// Max edit distance between an unknown tag and a suggested one
+const MAX_SUGGESTION_DIST: usize = 3;
+
+/// Valid JLPT levels
+const JLPT_LEVELS: RangeInclusive<u8> = 1..=5;
+
+/// Valid genki lessons
+const GENKI_LESSONS: RangeInclusive<u8> = 3..=23;
+
+/// Valid WaniKani levels
+const WANIKANI_LEVELS: RangeInclusive<u8> = 1..=60;
+
+/// Valid amounts of most frequent words for `#top<n>` tags
+const TOP_RANKED: RangeInclusive<u32> = 1..=100_000;
+
+/// Amounts of most frequent words suggested in completions
+const TOP_RANKED_SUGGESTIONS: &[u32] = &[1000, 5000, 10000];
+
 /// Parse a tag from a string
 pub fn parse(s: &str) -> Vec<Tag> {
     let mut tags: Vec<Tag> = vec![];
 
     if let Some(tag) = s.to_lowercase().strip_prefix("#") {
-        match tag {
-            "hidden" | "hide" => tags.push(Tag::Hidden),
-            "irrichidan" | "irregularichidan" | "irregular-ichidan" => {
-                tags.push(Tag::IrregularIruEru);
-            }
-            "katakana" => tags.push(Tag::Katakana),
-            _ => (),
+        if let Some(tag) = find_tag(FIXED_TAGS, tag) {
+            tags.push(tag);
+        }
+        if let Some(tag) = find_tag(DIALECT_TAGS, tag) {
+            tags.push(tag);
+        }
+        if let Some(tag) = find_tag(LANGUAGE_TAGS, tag) {
+            tags.push(tag);
         }
     }
 
-    if let Some(tag) = parse_genki_tag(s) {
+    tags.extend(parse_textbook_tags(s));
+    if let Some(tag) = parse_jlpt_tag(s) {
         tags.push(tag);
     }
-    if let Some(tag) = parse_jlpt_tag(s) {
+    if let Some(tag) = parse_top_tag(s) {
+        tags.push(tag);
+    }
+    if let Some(tag) = parse_wanikani_tag(s) {
+        tags.push(tag);
+    }
+    if let Some(tag) = parse_name_group_tag(s) {
         tags.push(tag);
     }
     if let Some(tag) = parse_search_type(s) {
@@ -91,6 +300,70 @@ pub fn parse(s: &str) -> Vec<Tag> {
     tags
 }
 
+/// Returns all tags (including the leading '#') starting with `partial`, eg. "#ad" completes to
+/// "#adjective" and "#adverb". Only tags which are accepted by [`parse`] are returned
+pub fn complete(partial: &str) -> Vec<String> {
+    let partial = partial.trim().to_lowercase();
+    let partial = partial.strip_prefix('#').unwrap_or(&partial);
+
+    let mut out: Vec<_> = all_tags()
+        .into_iter()
+        .filter(|i| i.starts_with(partial))
+        .map(|i| format!("#{i}"))
+        .filter(|i| !parse(i).is_empty())
+        .collect();
+
+    out.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+    out.dedup();
+    out
+}
+
+/// Returns the human readable description of the tag `tag` (including the leading '#')
+pub fn describe(tag: &str) -> Option<String> {
+    parse(tag).first().map(|i| i.description())
+}
+
+/// Returns the names of all tags known to the parser, without the leading '#'
+fn all_tags() -> Vec<String> {
+    let fixed = FIXED_TAGS
+        .iter()
+        .chain(SEARCH_TYPE_TAGS)
+        .chain(DIALECT_TAGS)
+        .chain(LANGUAGE_TAGS)
+        .map(|i| i.0[0].to_string());
+    let jlpt = JLPT_LEVELS.map(|i| format!("n{i}"));
+    let genki = GENKI_LESSONS.map(|i| format!("genki{i}"));
+    let top = TOP_RANKED_SUGGESTIONS.iter().map(|i| format!("top{i}"));
+    let wanikani = WANIKANI_LEVELS.map(|i| format!("wk{i}"));
+    let textbooks = resources::textbooks::get()
+        .iter()
+        .flat_map(|t| t.lesson_iter().map(move |l| format!("{}{l}", t.tag)));
+    let pos = PosSimple::iter().map(|i| i.as_ref().to_string());
+    let misc = Misc::iter().map(|i| i.as_ref().to_string());
+    let sentence = sentences::Tag::iter().map(|i| i.as_ref().to_string());
+
+    fixed
+        .chain(jlpt)
+        .chain(genki)
+        .chain(top)
+        .chain(wanikani)
+        .chain(textbooks)
+        .chain(pos)
+        .chain(misc)
+        .chain(sentence)
+        .map(|i| i.to_lowercase())
+        // Tags can't contain spaces
+        .filter(|i| !i.contains(' '))
+        .collect()
+}
+
+/// Returns the tag which has `name` as one of its names
+fn find_tag(tags: &[(&[&str], Tag)], name: &str) -> Option<Tag> {
+    tags.iter()
+        .find(|(names, _)| names.contains(&name))
+        .map(|i| i.1)
+}
+
 /// Returns `Some(u8)` if `s` is a valid N/jlpt-tag
 fn parse_jlpt_tag(s: &str) -> Option<Tag> {
     let jlpt = s
@@ -98,28 +371,82 @@ fn parse_jlpt_tag(s: &str) -> Option<Tag> {
         .or_else(|| s.strip_prefix("#jlpt"))?
         .parse::<u8>()
         .ok()?
-        .min(5)
-        .max(1);
+        .min(*JLPT_LEVELS.end())
+        .max(*JLPT_LEVELS.start());
     Some(Tag::Jlpt(jlpt))
 }
 
+/// Returns `Some(Tag)` if `s` is a valid `#top<n>` tag
+fn parse_top_tag(s: &str) -> Option<Tag> {
+    let n = s
+        .strip_prefix("#top")?
+        .parse::<u32>()
+        .ok()?
+        .clamp(*TOP_RANKED.start(), *TOP_RANKED.end());
+    Some(Tag::TopRanked(n))
+}
+
+/// Returns `Some(Tag)` if `s` is a valid `#wk<level>` tag
+fn parse_wanikani_tag(s: &str) -> Option<Tag> {
+    let level = s
+        .strip_prefix("#wk")?
+        .parse::<u8>()
+        .ok()?
+        .clamp(*WANIKANI_LEVELS.start(), *WANIKANI_LEVELS.end());
+    Some(Tag::WaniKani(level))
+}
+
+/// Parses textbook lesson tags like `#genki3` or `#mnn12`. Genki lessons are built in since
+/// they're also used for kanji, all other textbooks come from the textbook registry
+fn parse_textbook_tags(s: &str) -> Vec<Tag> {
+    parse_genki_tag(s)
+        .into_iter()
+        .chain(parse_registry_tag(s))
+        .collect()
+}
+
+/// Returns `Some(Tag)` if `s` is a lesson tag of a textbook within the textbook registry
+fn parse_registry_tag(s: &str) -> Option<Tag> {
+    let s = s.strip_prefix('#')?;
+
+    let textbooks = resources::textbooks::get().iter().enumerate();
+    textbooks.find_map(|(pos, textbook)| {
+        let lesson = s.strip_prefix(textbook.tag.as_str())?.parse::<u8>().ok()?;
+        let pos = u8::try_from(pos).ok()?;
+        textbook
+            .lessons
+            .contains_key(&lesson)
+            .then(|| Tag::TextbookLesson(pos, lesson))
+    })
+}
+
 /// Returns `Some(u8)` if `s` is a valid genki-tag
 fn parse_genki_tag(s: &str) -> Option<Tag> {
-    let genki = s.strip_prefix("#genki")?.parse::<u8>().ok()?.max(3).min(23);
+    let genki = s
+        .strip_prefix("#genki")?
+        .parse::<u8>()
+        .ok()?
+        .max(*GENKI_LESSONS.start())
+        .min(*GENKI_LESSONS.end());
     Some(Tag::GenkiLesson(genki))
 }
 
+/// Returns `Some(Tag)` if `s` is a valid name group limit tag like `#people5`
+fn parse_name_group_tag(s: &str) -> Option<Tag> {
+    let s = s.strip_prefix('#')?;
+    NAME_GROUP_TAGS.iter().find_map(|(name, group)| {
+        let limit = s
+            .strip_prefix(name)?
+            .parse::<u16>()
+            .ok()?
+            .clamp(*NAME_GROUP_LIMITS.start(), *NAME_GROUP_LIMITS.end());
+        Some(Tag::NameGroupLimit(*group, limit))
+    })
+}
+
 /// Parse only search type
 fn parse_search_type(s: &str) -> Option<Tag> {
-    Some(match s[1..].to_lowercase().as_str() {
-        "kanji" => Tag::SearchType(SearchTarget::Kanji),
-        "sentence" | "sentences" => Tag::SearchType(SearchTarget::Sentences),
-        "name" | "names" => Tag::SearchType(SearchTarget::Names),
-        "word" | "words" => Tag::SearchType(SearchTarget::Words),
-        "abbreviation" | "abbrev" => Tag::Misc(Misc::Abbreviation),
-        "uwk" => Tag::Misc(Misc::UsuallyWrittenInKana),
-        _ => return None,
-    })
+    find_tag(SEARCH_TYPE_TAGS, s[1..].to_lowercase().as_str())
 }
 
 #[cfg(test)]
@@ -131,9 +458,113 @@ mod test {
         assert_eq!(parse_jlpt_tag("#n4"), Some(Tag::Jlpt(4)));
     }
 
+    #[test]
+    fn test_parse_top_tag() {
+        assert_eq!(parse_top_tag("#top1000"), Some(Tag::TopRanked(1000)));
+        assert_eq!(parse_top_tag("#top0"), Some(Tag::TopRanked(1)));
+        assert_eq!(parse_top_tag("#top"), None);
+    }
+
+    #[test]
+    fn test_parse_wanikani_tag() {
+        assert_eq!(parse_wanikani_tag("#wk12"), Some(Tag::WaniKani(12)));
+        assert_eq!(parse_wanikani_tag("#wk99"), Some(Tag::WaniKani(60)));
+        assert_eq!(parse_wanikani_tag("#wk"), None);
+    }
+
+    #[test]
+    fn test_parse_name_group_tag() {
+        assert_eq!(
+            parse_name_group_tag("#people5"),
+            Some(Tag::NameGroupLimit(NameGroup::People, 5))
+        );
+        assert_eq!(
+            parse_name_group_tag("#places0"),
+            Some(Tag::NameGroupLimit(NameGroup::Places, 1))
+        );
+        assert_eq!(parse_name_group_tag("#people"), None);
+    }
+
     #[test]
     fn test_parse_genki_tag_parsing() {
         assert_eq!(parse_genki_tag("#genki3"), Some(Tag::GenkiLesson(3)));
         assert_eq!(parse_genki_tag("#genki23"), Some(Tag::GenkiLesson(23)));
     }
+
+    #[test]
+    fn test_parse_dialect_tag() {
+        assert!(parse("#kansai").contains(&Tag::Dialect(Dialect::Kansai)));
+        assert!(parse("#kyushu").contains(&Tag::Dialect(Dialect::Kyuushuu)));
+        assert!(parse("#ksb").contains(&Tag::Dialect(Dialect::Kansai)));
+    }
+
+    #[test]
+    fn test_parse_language_tag() {
+        assert_eq!(parse("#de"), vec![Tag::Language(Language::German)]);
+        assert_eq!(parse("#fr"), vec![Tag::Language(Language::French)]);
+        assert!(parse("#en").contains(&Tag::Language(Language::English)));
+    }
+
+    #[test]
+    fn test_extract_groups() {
+        let (query, groups) = extract_groups("#n4|#n5 dog");
+        assert_eq!(query, "dog");
+        assert_eq!(groups, vec![vec![Tag::Jlpt(4), Tag::Jlpt(5)]]);
+
+        let (query, groups) = extract_groups("dog (#noun #verb) #n5");
+        assert_eq!(query, "dog #n5");
+        assert_eq!(
+            groups,
+            vec![vec![
+                Tag::PartOfSpeech(PosSimple::Noun),
+                Tag::PartOfSpeech(PosSimple::Verb)
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_expand_presets() {
+        let presets = FilterPreset::parse_list("my-level:#n3 #common,hiragana:#katakana");
+        assert_eq!(expand_presets("dog #my-level", &presets), "dog #n3 #common");
+        // Built in tags can't be overwritten
+        assert_eq!(expand_presets("#n5 #other", &presets), "#n5 #other");
+    }
+
+    #[test]
+    fn test_replace_tag() {
+        assert_eq!(replace_tag("dog #n #n5", "#n", "#noun"), "dog #noun #n5");
+        assert_eq!(replace_tag("#n5-x #n", "#n5", "#n4"), "#n5-x #n");
+    }
+
+    #[test]
+    fn test_unknown_tags() {
+        let unknown = unknown_tags("dog #adjectve #n5 #xyzxyzxyz");
+        assert_eq!(unknown.len(), 2);
+        assert_eq!(unknown[0].tag, "#adjectve");
+        assert_eq!(unknown[0].suggestion.as_deref(), Some("#adjective"));
+        assert_eq!(unknown[1].suggestion, None);
+    }
+
+    #[test]
+    fn test_complete() {
+        let completions = complete("#ad");
+        assert!(completions.contains(&"#adjective".to_string()));
+        assert!(completions.contains(&"#adverb".to_string()));
+        assert!(complete("#n").contains(&"#n5".to_string()));
+        assert!(complete("#xyz").is_empty());
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(describe("#n5").as_deref(), Some("JLPT N5"));
+        assert_eq!(describe("#kansai").as_deref(), Some("Kansai dialect"));
+        assert_eq!(describe("#xyz"), None);
+    }
+
+    #[test]
+    fn test_completions_parse() {
+        for tag in complete("") {
+            assert!(!parse(&tag).is_empty(), "{tag} can't be parsed");
+        }
+    }
 }
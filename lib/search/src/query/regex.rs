@@ -57,6 +57,14 @@ impl RegexSQuery {
         out
     }
 
+    /// Returns the parts of the query between regex characters
+    pub fn fragments(&self) -> Vec<&str> {
+        self.query
+            .split(|c| REGEX_CHARS.contains(&c) || c == '+')
+            .filter(|i| !i.is_empty())
+            .collect()
+    }
+
     /// Returns a real regex expression which will be used to match words
     fn convert_regex(query: &str) -> String {
         let mut out = String::with_capacity(query.len() + 2);
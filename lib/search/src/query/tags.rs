@@ -1,31 +1,54 @@
+use localization::traits::Translatable;
 use types::jotoba::{
+    language::Language,
+    names::group::NameGroup,
     search::SearchTarget,
     sentences,
-    words::{misc::Misc, part_of_speech::PosSimple},
+    words::{dialect::Dialect, misc::Misc, part_of_speech::PosSimple},
 };
 
+/// Amount of newest JMdict releases in which words must have been added or changed to match
+/// [`Tag::New`]
+pub const NEW_TAG_RELEASES: usize = 3;
+
 /// Hashtag based search tags
 #[derive(Debug, Clone, Copy, PartialEq, Hash)]
 pub enum Tag {
     // Producer tags
     PartOfSpeech(PosSimple),
     Misc(Misc),
+    Dialect(Dialect),
     Jlpt(u8),
     GenkiLesson(u8),
+    /// Lesson of a textbook from the textbook registry: (textbook index, lesson)
+    TextbookLesson(u8, u8),
     Katakana,
     SentenceTag(sentences::Tag),
     IrregularIruEru,
+    /// Words added or changed within the last [`NEW_TAG_RELEASES`] JMdict releases
+    New,
+    /// Words within the given amount of most frequent words of the imported frequency list
+    TopRanked(u32),
+    /// Words and kanji taught in the given WaniKani level
+    WaniKani(u8),
 
     // Non producer
     SearchType(SearchTarget),
     Hidden,
+    /// Overrides the users language for a single query
+    Language(Language),
+    /// Max amount of names shown for a name group, eg. `#people5`
+    NameGroupLimit(NameGroup, u16),
 }
 
 impl Tag {
     /// Returns true if the tag can be used without a query
     #[inline]
     pub fn is_producer(&self) -> bool {
-        !self.is_search_type() && !self.is_hidden()
+        !self.is_search_type()
+            && !self.is_hidden()
+            && !self.is_language()
+            && self.as_name_group_limit().is_none()
     }
 
     /// Returns `true` if the tag is [`SearchType`].
@@ -75,6 +98,23 @@ impl Tag {
         }
     }
 
+    /// Returns `true` if the tag is [`Dialect`].
+    ///
+    /// [`Dialect`]: Tag::Dialect
+    #[inline]
+    pub fn is_dialect(&self) -> bool {
+        matches!(self, Self::Dialect(..))
+    }
+
+    #[inline]
+    pub fn as_dialect(&self) -> Option<Dialect> {
+        if let Self::Dialect(v) = self {
+            Some(*v)
+        } else {
+            None
+        }
+    }
+
     /// Returns `true` if the tag is [`Jlpt`].
     ///
     /// [`Jlpt`]: Tag::Jlpt
@@ -109,6 +149,23 @@ impl Tag {
         }
     }
 
+    /// Returns `true` if the tag is [`TextbookLesson`].
+    ///
+    /// [`TextbookLesson`]: Tag::TextbookLesson
+    #[inline]
+    pub fn is_textbook_lesson(&self) -> bool {
+        matches!(self, Self::TextbookLesson(..))
+    }
+
+    #[inline]
+    pub fn as_textbook_lesson(&self) -> Option<(u8, u8)> {
+        if let Self::TextbookLesson(t, l) = self {
+            Some((*t, *l))
+        } else {
+            None
+        }
+    }
+
     /// Returns `true` if the tag is [`IrregularIruEru`].
     ///
     /// [`IrregularIruEru`]: Tag::IrregularIruEru
@@ -142,6 +199,41 @@ impl Tag {
         }
     }
 
+    /// Returns `true` if the tag is [`Language`].
+    ///
+    /// [`Language`]: Tag::Language
+    #[inline]
+    pub fn is_language(&self) -> bool {
+        matches!(self, Self::Language(..))
+    }
+
+    #[inline]
+    pub fn as_language(&self) -> Option<Language> {
+        if let Self::Language(v) = self {
+            Some(*v)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn as_top_ranked(&self) -> Option<u32> {
+        if let Self::TopRanked(v) = self {
+            Some(*v)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn as_wanikani(&self) -> Option<u8> {
+        if let Self::WaniKani(v) = self {
+            Some(*v)
+        } else {
+            None
+        }
+    }
+
     /// Returns `true` if the tag is [`Katakana`].
     ///
     /// [`Katakana`]: Tag::Katakana
@@ -149,4 +241,100 @@ impl Tag {
     pub fn is_katakana(&self) -> bool {
         matches!(self, Self::Katakana)
     }
+
+    #[inline]
+    pub fn as_name_group_limit(&self) -> Option<(NameGroup, usize)> {
+        if let Self::NameGroupLimit(group, limit) = self {
+            Some((*group, *limit as usize))
+        } else {
+            None
+        }
+    }
+
+    /// Returns a human readable description of the tag, eg. "JLPT N5" for `#n5`
+    pub fn description(&self) -> String {
+        match self {
+            Tag::PartOfSpeech(pos) => format!("Part of speech: {}", pos.as_ref()),
+            Tag::Misc(misc) => misc.get_id().to_string(),
+            Tag::Dialect(dialect) => format!("{} dialect", dialect.get_id()),
+            Tag::Jlpt(level) => format!("JLPT N{level}"),
+            Tag::GenkiLesson(lesson) => format!("Genki lesson {lesson}"),
+            Tag::TextbookLesson(textbook, lesson) => {
+                match resources::textbooks::by_index(*textbook) {
+                    Some(textbook) => format!("{} lesson {lesson}", textbook.name),
+                    None => format!("Lesson {lesson}"),
+                }
+            }
+            Tag::Katakana => "Words written in katakana".to_string(),
+            Tag::SentenceTag(tag) => format!("Sentence tag: {}", tag.as_ref()),
+            Tag::IrregularIruEru => "Irregular ichidan verbs".to_string(),
+            Tag::New => format!("Added or changed in the last {NEW_TAG_RELEASES} JMdict releases"),
+            Tag::TopRanked(n) => format!("Within the {n} most frequent words"),
+            Tag::WaniKani(level) => format!("WaniKani level {level}"),
+            Tag::SearchType(target) => format!("Search for {}", target.get_id().to_lowercase()),
+            Tag::Hidden => "Hide translations of sentences".to_string(),
+            Tag::Language(language) => format!("Show results in {}", language.get_id()),
+            Tag::NameGroupLimit(group, limit) => {
+                format!("Show at most {limit} {}", group.get_id().to_lowercase())
+            }
+        }
+    }
+}
+
+/// Boolean expression of tags. Tags written next to each other are combined with AND, while tags
+/// separated by '|' (`#n4|#n5`) or grouped in parentheses (`(#noun #verb)`) are combined with OR
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum TagExpr {
+    Tag(Tag),
+    And(Vec<TagExpr>),
+    Or(Vec<TagExpr>),
+}
+
+impl Default for TagExpr {
+    #[inline]
+    fn default() -> Self {
+        Self::And(vec![])
+    }
+}
+
+impl TagExpr {
+    /// Creates a new expression requiring all `tags` and at least one tag of each group
+    pub fn new(tags: &[Tag], or_groups: Vec<Vec<Tag>>) -> Self {
+        let mut items: Vec<_> = tags.iter().copied().map(TagExpr::Tag).collect();
+        items.extend(
+            or_groups
+                .into_iter()
+                .map(|g| TagExpr::Or(g.into_iter().map(TagExpr::Tag).collect())),
+        );
+        Self::And(items)
+    }
+
+    /// Evaluates the expression using `matches` to check single tags
+    pub fn eval<F: Fn(&Tag) -> bool>(&self, matches: &F) -> bool {
+        match self {
+            TagExpr::Tag(tag) => matches(tag),
+            TagExpr::And(items) => items.iter().all(|i| i.eval(matches)),
+            TagExpr::Or(items) => items.iter().any(|i| i.eval(matches)),
+        }
+    }
+
+    /// Returns an iterator over all OR-groups on the top level of the expression
+    pub fn or_groups(&self) -> impl Iterator<Item = &TagExpr> {
+        let items = match self {
+            TagExpr::And(items) => items.as_slice(),
+            TagExpr::Or(_) => std::slice::from_ref(self),
+            TagExpr::Tag(_) => &[],
+        };
+        items.iter().filter(|i| matches!(i, TagExpr::Or(_)))
+    }
+
+    /// Returns all tags within the expression
+    pub fn tags(&self) -> Vec<&Tag> {
+        match self {
+            TagExpr::Tag(tag) => vec![tag],
+            TagExpr::And(items) | TagExpr::Or(items) => {
+                items.iter().flat_map(|i| i.tags()).collect()
+            }
+        }
+    }
 }
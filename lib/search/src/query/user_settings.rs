@@ -1,5 +1,71 @@
-use std::hash::{Hash, Hasher};
-use types::jotoba::language::{LangParam, Language};
+use itertools::Itertools;
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+use types::jotoba::{
+    language::{LangParam, Language},
+    search::SearchTarget,
+    sentences::{script::SentenceScript, stats::RARE_KANJI_GRADE},
+};
+
+/// Max amount of results per page
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Keys of all settings, which are the names of the cookies they're stored in
+pub const KEYS: &[&str] = &[
+    "default_lang",
+    "page_lang",
+    "show_english",
+    "show_english_on_top",
+    "items_per_page",
+    "show_sentences",
+    "sentence_furigana",
+    "sentence_script",
+    "sentence_max_katakana",
+    "sentence_max_kanji_grade",
+    "show_wk_level",
+    "section_order",
+    "show_kanji_panel",
+];
+
+/// Result sections in their default order
+pub const DEFAULT_SECTION_ORDER: [SearchTarget; 4] = [
+    SearchTarget::Words,
+    SearchTarget::Kanji,
+    SearchTarget::Sentences,
+    SearchTarget::Names,
+];
+
+/// A setting which can't be set to the given value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidSetting {
+    pub key: String,
+    pub value: String,
+}
+
+impl InvalidSetting {
+    fn new(key: &str, value: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+}
+
+impl Display for InvalidSetting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid value for setting {}: {:?}",
+            self.key, self.value
+        )
+    }
+}
+
+impl Error for InvalidSetting {}
 
 /// In-cookie saved personalized settings by an user
 #[derive(Debug, Clone, Copy)]
@@ -11,6 +77,18 @@ pub struct UserSettings {
     pub page_size: u32,
     pub show_example_sentences: bool,
     pub sentence_furigana: bool,
+    /// Script example sentences are displayed in
+    pub sentence_script: SentenceScript,
+    /// Hide sentences with more katakana (in percent) than this
+    pub sentence_max_katakana: Option<u8>,
+    /// Hide sentences containing kanji of a higher school grade than this
+    pub sentence_max_kanji_grade: Option<u8>,
+    /// Show the WaniKani levels of words and kanji
+    pub show_wanikani: bool,
+    /// Order of the result sections in the search bar. The first one is selected by default
+    pub section_order: [SearchTarget; 4],
+    /// Show the kanji of the found words next to word results
+    pub show_kanji_panel: bool,
 }
 
 impl UserSettings {
@@ -32,6 +110,161 @@ impl UserSettings {
     pub fn lang_param(&self) -> LangParam {
         LangParam::with_en_raw(self.user_lang, self.show_english())
     }
+
+    /// Returns the result section selected in the search bar by default
+    #[inline]
+    pub fn default_target(&self) -> SearchTarget {
+        self.section_order[0]
+    }
+
+    /// Builds settings from the values `get` returns for the [`KEYS`], eg. the users cookies.
+    /// Settings without or with an invalid value keep their default
+    pub fn from_values<F: Fn(&str) -> Option<String>>(get: F) -> Self {
+        let mut settings = Self::default();
+        for key in KEYS {
+            if let Some(value) = get(key) {
+                // Invalid values of cookies aren't the users fault, so they get ignored
+                settings.set(key, &value).ok();
+            }
+        }
+        settings.english_on_top &= settings.show_english;
+        settings
+    }
+
+    /// Sets the setting `key` to `value`. Fails if the key is unknown or the value isn't valid
+    /// for the setting, in which case the settings stay unchanged
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), InvalidSetting> {
+        let invalid = || InvalidSetting::new(key, value);
+
+        let mut new = *self;
+        match key {
+            "default_lang" => new.user_lang = Language::from_str(value).map_err(|_| invalid())?,
+            "page_lang" => {
+                new.page_lang =
+                    localization::language::Language::from_str(value).map_err(|_| invalid())?
+            }
+            "show_english" => new.show_english = value.parse().map_err(|_| invalid())?,
+            "show_english_on_top" => new.english_on_top = value.parse().map_err(|_| invalid())?,
+            "items_per_page" => new.page_size = value.parse().map_err(|_| invalid())?,
+            "show_sentences" => {
+                new.show_example_sentences = value.parse().map_err(|_| invalid())?
+            }
+            "sentence_furigana" => new.sentence_furigana = value.parse().map_err(|_| invalid())?,
+            "sentence_script" => {
+                new.sentence_script = SentenceScript::from_str(value).map_err(|_| invalid())?
+            }
+            // 100% allows every sentence
+            "sentence_max_katakana" => {
+                let max: u8 = value.parse().map_err(|_| invalid())?;
+                new.sentence_max_katakana = (max != 100).then(|| max);
+            }
+            // Grade 0 disables the filter
+            "sentence_max_kanji_grade" => {
+                let max: u8 = value.parse().map_err(|_| invalid())?;
+                new.sentence_max_kanji_grade = (max != 0).then(|| max);
+            }
+            "show_wk_level" => new.show_wanikani = value.parse().map_err(|_| invalid())?,
+            // Comma separated section names, eg. `words,kanji,sentences,names`
+            "section_order" => {
+                let sections: Vec<_> = value
+                    .split(',')
+                    .map(|i| section_from_name(i.trim()))
+                    .collect::<Option<_>>()
+                    .ok_or_else(invalid)?;
+                new.section_order = sections.try_into().map_err(|_| invalid())?;
+            }
+            "show_kanji_panel" => new.show_kanji_panel = value.parse().map_err(|_| invalid())?,
+            _ => return Err(invalid()),
+        }
+
+        new.validate().map_err(|_| invalid())?;
+        *self = new;
+        Ok(())
+    }
+
+    /// Returns the values of all settings by their [`KEYS`], in the format [`Self::set`] accepts
+    pub fn values(&self) -> Vec<(&'static str, String)> {
+        let script = match self.sentence_script {
+            SentenceScript::Native => "native",
+            SentenceScript::Kana => "kana",
+            SentenceScript::Romaji => "romaji",
+        };
+
+        vec![
+            ("default_lang", self.user_lang.as_ref().to_string()),
+            ("page_lang", self.page_lang.as_ref().to_string()),
+            ("show_english", self.show_english.to_string()),
+            ("show_english_on_top", self.english_on_top.to_string()),
+            ("items_per_page", self.page_size.to_string()),
+            ("show_sentences", self.show_example_sentences.to_string()),
+            ("sentence_furigana", self.sentence_furigana.to_string()),
+            ("sentence_script", script.to_string()),
+            (
+                "sentence_max_katakana",
+                self.sentence_max_katakana.unwrap_or(100).to_string(),
+            ),
+            (
+                "sentence_max_kanji_grade",
+                self.sentence_max_kanji_grade.unwrap_or(0).to_string(),
+            ),
+            ("show_wk_level", self.show_wanikani.to_string()),
+            ("section_order", format_sections(&self.section_order)),
+            ("show_kanji_panel", self.show_kanji_panel.to_string()),
+        ]
+    }
+
+    /// Returns the first setting having a value out of its allowed range
+    pub fn validate(&self) -> Result<(), InvalidSetting> {
+        if !(1..=MAX_PAGE_SIZE).contains(&self.page_size) {
+            return Err(InvalidSetting::new(
+                "items_per_page",
+                &self.page_size.to_string(),
+            ));
+        }
+
+        if let Some(max) = self.sentence_max_katakana.filter(|i| *i > 100) {
+            return Err(InvalidSetting::new(
+                "sentence_max_katakana",
+                &max.to_string(),
+            ));
+        }
+
+        if let Some(max) = self.sentence_max_kanji_grade {
+            if !(1..=RARE_KANJI_GRADE).contains(&max) {
+                return Err(InvalidSetting::new(
+                    "sentence_max_kanji_grade",
+                    &max.to_string(),
+                ));
+            }
+        }
+
+        if !self.section_order.iter().all_unique() {
+            let order = format_sections(&self.section_order);
+            return Err(InvalidSetting::new("section_order", &order));
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the name of a result section used in the `section_order` setting
+fn section_name(target: SearchTarget) -> &'static str {
+    match target {
+        SearchTarget::Words => "words",
+        SearchTarget::Kanji => "kanji",
+        SearchTarget::Sentences => "sentences",
+        SearchTarget::Names => "names",
+    }
+}
+
+/// Formats result sections in the format of the `section_order` setting
+fn format_sections(sections: &[SearchTarget]) -> String {
+    sections.iter().map(|i| section_name(*i)).join(",")
+}
+
+/// Returns the result section with the name `name`
+fn section_from_name(name: &str) -> Option<SearchTarget> {
+    SearchTarget::iterate().find(|i| section_name(*i).eq_ignore_ascii_case(name))
 }
 
 impl Default for UserSettings {
@@ -45,6 +278,12 @@ impl Default for UserSettings {
             page_size: 10,
             show_example_sentences: true,
             sentence_furigana: true,
+            sentence_script: SentenceScript::Native,
+            sentence_max_katakana: None,
+            sentence_max_kanji_grade: None,
+            show_wanikani: false,
+            section_order: DEFAULT_SECTION_ORDER,
+            show_kanji_panel: true,
         }
     }
 }
@@ -52,7 +291,10 @@ impl Default for UserSettings {
 impl PartialEq for UserSettings {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
-        self.user_lang == other.user_lang && self.show_english == other.show_english
+        self.user_lang == other.user_lang
+            && self.show_english == other.show_english
+            && self.sentence_max_katakana == other.sentence_max_katakana
+            && self.sentence_max_kanji_grade == other.sentence_max_kanji_grade
     }
 }
 
@@ -61,5 +303,50 @@ impl Hash for UserSettings {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.user_lang.hash(state);
         self.show_english.hash(state);
+        self.sentence_max_katakana.hash(state);
+        self.sentence_max_kanji_grade.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("items_per_page", "20", true; "Page size")]
+    #[test_case("items_per_page", "0", false; "Empty pages")]
+    #[test_case("items_per_page", "1000", false; "Too large pages")]
+    #[test_case("sentence_max_katakana", "100", true; "All katakana")]
+    #[test_case("sentence_max_katakana", "101", false; "Too much katakana")]
+    #[test_case("sentence_max_kanji_grade", "12", false; "Unknown grade")]
+    #[test_case("sentence_script", "romaji", true; "Romaji")]
+    #[test_case("show_english", "yes", false; "Invalid bool")]
+    #[test_case("unknown", "true", false; "Unknown key")]
+    #[test_case("section_order", "names,Words,kanji,sentences", true; "Section order")]
+    #[test_case("section_order", "words,kanji,sentences", false; "Missing section")]
+    #[test_case("section_order", "words,words,kanji,names", false; "Duplicate section")]
+    #[test_case("section_order", "words,kanji,radicals,names", false; "Unknown section")]
+    fn test_set(key: &str, value: &str, valid: bool) {
+        let mut settings = UserSettings::default();
+        assert_eq!(settings.set(key, value).is_ok(), valid);
+    }
+
+    #[test]
+    fn test_values_roundtrip() {
+        let mut settings = UserSettings::default();
+        settings.set("sentence_script", "kana").unwrap();
+        settings.set("sentence_max_kanji_grade", "6").unwrap();
+        settings.set("items_per_page", "25").unwrap();
+        settings
+            .set("section_order", "sentences,words,names,kanji")
+            .unwrap();
+        settings.set("show_kanji_panel", "false").unwrap();
+
+        let values = settings.values();
+        let parsed = UserSettings::from_values(|key| {
+            values.iter().find(|i| i.0 == key).map(|i| i.1.clone())
+        });
+
+        assert_eq!(parsed.values(), values);
     }
 }
@@ -17,6 +17,7 @@ pub struct Search<'a> {
 impl<'a> Search<'a> {
     #[inline]
     pub fn new(query: &'a str, lang: Language) -> Self {
+        let lang = indexes::get().word().loaded_language(lang);
         Self { query, lang }
     }
 
@@ -1,6 +1,8 @@
 pub mod order;
 mod producer;
 pub mod result;
+pub mod sync;
+pub mod terms;
 
 use super::query::Query;
 use crate::{
@@ -8,7 +10,8 @@ use crate::{
     query::Tag,
 };
 use producer::{
-    foreign::ForeignProducer, native::NativeProducer, sequence::SequenceProducer, tag::TagProducer,
+    foreign::ForeignProducer, native::NativeProducer, sequence::SequenceProducer,
+    sync::SyncProducer, tag::TagProducer,
 };
 use result::ResData;
 use types::jotoba::{language::Language, sentences::Sentence};
@@ -25,11 +28,13 @@ impl<'a> Search<'a> {
             Box::new(ForeignProducer::new(query, query.lang())),
             Box::new(TagProducer::new(query)),
             Box::new(NativeProducer::new(query, query.lang())),
+            Box::new(SyncProducer::new(query, query.lang())),
         ];
 
         if query.lang() != Language::English && query.show_english() {
             producer.push(Box::new(ForeignProducer::new(query, Language::English)));
             producer.push(Box::new(NativeProducer::new(query, Language::English)));
+            producer.push(Box::new(SyncProducer::new(query, Language::English)));
         }
 
         Self { query, producer }
@@ -3,7 +3,7 @@ use crate::{engine, query::Query};
 use index_framework::traits::{backend::Backend, dictionary::IndexDictionary};
 use jp_utils::JapaneseExt;
 use sparse_vec::VecExt;
-use types::jotoba::sentences::Sentence;
+use types::jotoba::{language::Language, sentences::Sentence};
 use vsm::doc_vec::DocVector;
 
 pub(crate) fn filter_sentence(query: &Query, sentence: &Sentence) -> bool {
@@ -35,9 +35,44 @@ pub(crate) fn filter_sentence(query: &Query, sentence: &Sentence) -> bool {
         return false;
     }
 
+    // At least one tag of each OR-combined group has to match. Non sentence tags are ignored
+    if !query
+        .tag_expr
+        .or_groups()
+        .all(|group| group.eval(&|tag| tag.as_sentence_tag().map_or(true, |t| sentence.has_tag(t))))
+    {
+        return false;
+    }
+
+    if !by_script(query, sentence) {
+        return false;
+    }
+
     true
 }
 
+/// Filters out sentences with too many loanwords or too difficult kanji for the user's settings
+fn by_script(query: &Query, sentence: &Sentence) -> bool {
+    let settings = &query.settings;
+    if settings.sentence_max_katakana.is_none() && settings.sentence_max_kanji_grade.is_none() {
+        return true;
+    }
+
+    let stats = match resources::get().sentences().script_stats(sentence.id) {
+        Some(stats) => stats,
+        None => return true,
+    };
+
+    let katakana_ok = settings
+        .sentence_max_katakana
+        .map_or(true, |max| stats.katakana_percent <= max);
+    let kanji_ok = settings
+        .sentence_max_kanji_grade
+        .map_or(true, |max| stats.max_kanji_grade <= max);
+
+    katakana_ok && kanji_ok
+}
+
 fn by_quot_marks(query: &Query, sentence: &Sentence) -> bool {
     if !by_quot_marks_jp(query, sentence) {
         return false;
@@ -98,14 +133,21 @@ pub struct FeQotTermsVecFilter {
 }
 
 impl FeQotTermsVecFilter {
-    pub fn new(query: &Query) -> Self {
+    pub fn new(query: &Query, language: Language) -> Self {
         // If there is a term that is not indexed and thus can't be found,
         // filter out all results
         let mut filter_all = false;
         let mut mc_terms = vec![];
 
-        let index = indexes::get().sentence().foreign();
-        let ix_dict = index.dict();
+        let ix_dict = match indexes::get().sentence().foreign(language) {
+            Some(index) => index.dict(),
+            None => {
+                return Self {
+                    mc_terms,
+                    filter_all: true,
+                }
+            }
+        };
 
         'o: for t in query.must_contain.iter().filter(|i| !i.is_japanese()) {
             for term in engine::sentences::foreign::all_terms(t).into_iter() {
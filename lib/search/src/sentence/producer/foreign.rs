@@ -1,6 +1,9 @@
 use super::filter::{self, FeQotTermsVecFilter};
 use crate::{
-    engine::sentences::foreign,
+    engine::{
+        sentences::foreign,
+        settings::{self, EngineKind},
+    },
     executor::{out_builder::OutputBuilder, producer::Producer, searchable::Searchable},
     query::{Query, QueryLang},
     sentence::{order::foreign::ForeignOrder, Search},
@@ -22,13 +25,14 @@ impl<'a> ForeignProducer<'a> {
     fn task(&self) -> SearchTask<'static, foreign::Engine> {
         let query_str = &self.query.query_str;
         let query_c = self.query.clone();
-        let vec_filter = FeQotTermsVecFilter::new(&self.query);
+        let vec_filter = FeQotTermsVecFilter::new(&self.query, self.language);
         let lang = self.query.lang();
 
-        SearchTask::with_language(query_str, self.language)
+        let task = SearchTask::with_language(query_str, self.language)
             .with_result_filter(move |i| filter::filter_sentence(&query_c, *i))
             .with_item_filter(move |i| vec_filter.filter(i))
-            .with_custom_order(ForeignOrder::new(lang))
+            .with_custom_order(ForeignOrder::new(lang));
+        settings::apply(EngineKind::Sentences, task)
     }
 }
 
@@ -46,7 +50,9 @@ impl<'a> Producer for ForeignProducer<'a> {
     }
 
     fn should_run(&self, _already_found: usize) -> bool {
-        self.query.form.is_normal() && self.query.q_lang == QueryLang::Foreign
+        self.query.form.is_normal()
+            && self.query.q_lang == QueryLang::Foreign
+            && indexes::get().sentence().foreign(self.language).is_some()
     }
 
     fn estimate_to(&self, out: &mut FilteredMaxCounter<<Self::Target as Searchable>::Item>) {
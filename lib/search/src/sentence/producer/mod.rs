@@ -3,4 +3,5 @@ pub mod foreign;
 mod kanji;
 pub mod native;
 pub mod sequence;
+pub mod sync;
 pub mod tag;
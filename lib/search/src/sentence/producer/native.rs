@@ -1,6 +1,9 @@
 use super::filter;
 use crate::{
-    engine::sentences::native,
+    engine::{
+        sentences::native,
+        settings::{self, EngineKind},
+    },
     executor::{out_builder::OutputBuilder, producer::Producer, searchable::Searchable},
     query::{Query, QueryLang},
     sentence::{order::native::NativeOrder, Search},
@@ -23,9 +26,10 @@ impl<'a> NativeProducer<'a> {
         let query = self.query.clone();
         let query_str = self.jp_reading();
 
-        SearchTask::with_language(&query_str, self.lang)
+        let task = SearchTask::with_language(&query_str, self.lang)
             .with_result_filter(move |sentence| filter::filter_sentence(&query, *sentence))
-            .with_custom_order(NativeOrder::new(self.query.lang()))
+            .with_custom_order(NativeOrder::new(self.query.lang()));
+        settings::apply(EngineKind::Sentences, task)
     }
 
     fn jp_reading(&self) -> String {
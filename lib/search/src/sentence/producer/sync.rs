@@ -0,0 +1,95 @@
+use super::filter;
+use crate::{
+    executor::{out_builder::OutputBuilder, producer::Producer, searchable::Searchable},
+    query::{Query, QueryLang},
+    sentence::{sync, Search},
+};
+use engine::{
+    pushable::FilteredMaxCounter,
+    pushable::{PushMod, Pushable},
+    relevance::item::RelItem,
+};
+use std::collections::HashMap;
+use types::jotoba::{language::Language, sentences::Sentence};
+
+/// Producer for sentences which were appended by a sentence sync and aren't in the vector
+/// indexes yet
+pub struct SyncProducer<'a> {
+    query: &'a Query,
+    language: Language,
+}
+
+impl<'a> SyncProducer<'a> {
+    pub fn new(query: &'a Query, language: Language) -> Self {
+        Self { query, language }
+    }
+
+    fn find_to<P>(&self, out: &mut P)
+    where
+        P: Pushable<Item = RelItem<&'static Sentence>>,
+    {
+        let index = indexes::get().sentence().sync();
+        let native = self.query.q_lang == QueryLang::Japanese;
+
+        let query_str = &self.query.query_str;
+        let terms = if native {
+            sync::native_terms(query_str)
+        } else {
+            sync::foreign_terms(query_str)
+        };
+
+        // Amount of query terms within each sentence
+        let mut matches: HashMap<u32, usize> = HashMap::new();
+        for term in terms.iter() {
+            let ids = if native {
+                index.native(term)
+            } else {
+                index.foreign(self.language, term)
+            };
+            for id in ids {
+                *matches.entry(*id).or_default() += 1;
+            }
+        }
+
+        let sentences = resources::get().sentences();
+        for (id, count) in matches {
+            let sentence = match sentences.by_id(id) {
+                Some(sentence) => sentence,
+                None => continue,
+            };
+            if !filter::filter_sentence(self.query, sentence) {
+                continue;
+            }
+
+            let rel = count as f32 / terms.len() as f32;
+            out.push(RelItem::new(sentence, rel));
+        }
+    }
+}
+
+impl<'a> Producer for SyncProducer<'a> {
+    type Target = Search<'a>;
+
+    fn produce(
+        &self,
+        out: &mut OutputBuilder<
+            <Self::Target as Searchable>::Item,
+            <Self::Target as Searchable>::ResAdd,
+        >,
+    ) {
+        self.find_to(out);
+    }
+
+    fn estimate_to(&self, out: &mut FilteredMaxCounter<<Self::Target as Searchable>::Item>) {
+        let mut m = PushMod::new(out, |i: RelItem<&Sentence>| i.item);
+        self.find_to(&mut m);
+    }
+
+    fn should_run(&self, _already_found: usize) -> bool {
+        // Japanese terms aren't indexed by language, so they only have to be looked up once
+        let native_once =
+            self.query.q_lang != QueryLang::Japanese || self.language == self.query.lang();
+
+        self.query.form.is_normal() && native_once && !indexes::get().sentence().sync().is_empty()
+    }
+}
@@ -1,5 +1,8 @@
 use jp_utils::furigana::{self, reading_part_ref::ReadingPartRef};
-use types::jotoba::language::{param::AsLangParam, Language};
+use types::jotoba::{
+    language::{param::AsLangParam, Language},
+    sentences::script::SentenceScript,
+};
 
 use crate::executor::out_builder::OutputAddable;
 
@@ -35,6 +38,15 @@ impl Sentence {
         furigana::parse::unchecked(&self.furigana)
     }
 
+    /// Returns the sentence converted into `script` or `None` if it's displayed as written
+    pub fn converted(&self, script: SentenceScript) -> Option<String> {
+        match script {
+            SentenceScript::Native => None,
+            SentenceScript::Kana => Some(japanese::furigana::to_kana(self.furigana)),
+            SentenceScript::Romaji => Some(japanese::furigana::to_romaji(self.furigana)),
+        }
+    }
+
     #[inline]
     pub fn get_english(&self) -> Option<&str> {
         self.eng.as_deref()
@@ -0,0 +1,19 @@
+//! Terms of sentences appended by a sentence sync. The same terms are used for adding sentences
+//! to the [`SyncIndex`] and for looking up queries in it
+
+use super::terms::{foreign_terms, native_terms};
+use indexes::sentences::SyncIndex;
+use types::jotoba::sentences::Sentence;
+
+/// Adds the japanese text and all translations of `sentence` to `index`
+pub fn index_sentence(index: &mut SyncIndex, sentence: &Sentence) {
+    for term in native_terms(&sentence.japanese) {
+        index.add_native(sentence.id, &term);
+    }
+
+    for translation in &sentence.translations {
+        for term in foreign_terms(&translation.text) {
+            index.add_foreign(sentence.id, translation.language, &term);
+        }
+    }
+}
@@ -0,0 +1,34 @@
+//! Terms the sentence indexes get built from. They match the terms the sentence engines split
+//! queries into
+
+use crate::engine::sentences::foreign::all_terms;
+use jp_utils::JapaneseExt;
+use sentence_reader::output::ParseResult;
+use std::collections::HashSet;
+
+/// Returns the inflected and normalized forms of all words of the japanese text `inp`, along
+/// with `inp` itself
+pub fn native_terms(inp: &str) -> HashSet<String> {
+    let mut terms = HashSet::new();
+    terms.insert(inp.to_string());
+
+    match sentence_reader::Parser::new(inp).parse() {
+        ParseResult::Sentence(s) => {
+            terms.extend(s.iter().map(|i| i.get_inflected()));
+            terms.extend(s.iter().map(|i| i.get_normalized()));
+        }
+        ParseResult::InflectedWord(w) => {
+            terms.insert(w.get_inflected());
+            terms.insert(w.get_normalized());
+        }
+        ParseResult::None => (),
+    }
+
+    terms.into_iter().map(|i| i.to_halfwidth()).collect()
+}
+
+/// Returns all lowercase words of the foreign text `inp`
+#[inline]
+pub fn foreign_terms(inp: &str) -> HashSet<String> {
+    all_terms(&inp.to_lowercase()).into_iter().collect()
+}
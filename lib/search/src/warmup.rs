@@ -0,0 +1,78 @@
+//! Warm-up of lazily built indexes and of the most common queries after startup, so the first
+//! requests don't have to pay for it.
+
+use crate::{
+    engine::words::foreign,
+    query::{parser::QueryParser, UserSettings},
+    word, SearchExecutor,
+};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use types::jotoba::search::SearchTarget;
+
+static TOTAL: AtomicUsize = AtomicUsize::new(0);
+static DONE: AtomicUsize = AtomicUsize::new(0);
+static FINISHED: AtomicBool = AtomicBool::new(false);
+
+/// Progress of the warm-up
+#[derive(Serialize, Clone, Copy, Debug)]
+pub struct Progress {
+    /// Amount of queries already run
+    pub done: usize,
+    /// Amount of queries to run
+    pub total: usize,
+    pub finished: bool,
+}
+
+/// Returns the current progress of the warm-up
+pub fn progress() -> Progress {
+    Progress {
+        done: DONE.load(Ordering::Relaxed),
+        total: TOTAL.load(Ordering::Relaxed),
+        finished: FINISHED.load(Ordering::Relaxed),
+    }
+}
+
+/// Builds all lazily built search indexes and runs a word search for each query in `queries`.
+/// `on_query` gets called for each query as well, eg. to fill caches of other crates. Requires
+/// resources and indexes to be loaded. Each step pins the reader on its own, so resources and
+/// indexes replaced in the meantime can be freed during the warm-up
+pub fn run<F>(queries: &[String], on_query: F)
+where
+    F: Fn(&str),
+{
+    TOTAL.store(queries.len(), Ordering::Relaxed);
+
+    {
+        let _pin = utils::epoch::pin();
+        indexes();
+    }
+
+    for query in queries {
+        let _pin = utils::epoch::pin();
+        search(query);
+        on_query(query);
+        DONE.fetch_add(1, Ordering::Relaxed);
+    }
+
+    FINISHED.store(true, Ordering::Relaxed);
+}
+
+/// Builds all lazily built search indexes, eg. after they got invalidated by replacing the
+/// resources and indexes
+pub fn indexes() {
+    foreign::warm_up();
+}
+
+fn search(query: &str) {
+    let parsed = QueryParser::new(
+        query.to_string(),
+        SearchTarget::Words,
+        UserSettings::default(),
+    )
+    .parse();
+
+    if let Some(query) = parsed {
+        SearchExecutor::new(word::Search::new(&query)).run();
+    }
+}
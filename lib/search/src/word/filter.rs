@@ -1,7 +1,8 @@
-use crate::query::Query;
+use crate::query::{tags::NEW_TAG_RELEASES, Query, Tag};
+use japanese::ToKanaExt;
 use jp_utils::JapaneseExt;
 use std::borrow::Borrow;
-use types::jotoba::words::Word;
+use types::jotoba::{kanji::reading::ReadingSearch, words::Word};
 
 pub struct WordFilter {
     query: Query,
@@ -24,8 +25,15 @@ impl WordFilter {
             wf.by_pos_tags(word)?;
             wf.by_jlpt(word)?;
             wf.by_katakana_tag(word)?;
+            wf.by_tags(word, |i| matches!(i, Tag::New))?;
+            wf.by_tags(word, |i| i.as_top_ranked().is_some())?;
+            wf.by_tags(word, |i| i.as_wanikani().is_some())?;
+            wf.by_tags(word, |i| i.is_textbook_lesson())?;
+            wf.by_tags(word, |i| i.as_dialect().is_some())?;
+            wf.by_tag_groups(word)?;
 
             wf.by_quot_marks(word)?;
+            wf.by_kanji_readings(word)?;
 
             Some(())
         }
@@ -44,6 +52,18 @@ impl WordFilter {
         (!has_tag || w.get_reading_str().is_katakana()).then(|| ())
     }
 
+    /// Requires the word to have all tags of the query selected by `select`, eg. to be within the
+    /// most frequent words of all `#top<n>` tags or taught in all textbook lessons of the query
+    #[inline]
+    fn by_tags<F: Fn(&Tag) -> bool>(&self, w: &Word, select: F) -> Option<()> {
+        self.query
+            .tags
+            .iter()
+            .filter(|i| select(i))
+            .all(|tag| word_has_tag(w, tag))
+            .then(|| ())
+    }
+
     #[inline]
     fn by_jlpt(&self, w: &Word) -> Option<()> {
         // Ignore if not set
@@ -68,6 +88,16 @@ impl WordFilter {
             .then(|| ())
     }
 
+    /// Requires at least one tag of each OR-combined tag group to match
+    #[inline]
+    fn by_tag_groups(&self, w: &Word) -> Option<()> {
+        self.query
+            .tag_expr
+            .or_groups()
+            .all(|group| group.eval(&|tag| word_has_tag(w, tag)))
+            .then(|| ())
+    }
+
     fn by_quot_marks(&self, w: &Word) -> Option<()> {
         if self.query.must_contain.is_empty() {
             return Some(());
@@ -99,6 +129,25 @@ impl WordFilter {
         fn_q_terms.is_empty().then(|| ())
     }
 
+    /// Requires the kanji of the word to be read as given within the query (eg. '生.なま')
+    fn by_kanji_readings(&self, w: &Word) -> Option<()> {
+        if self.query.kanji_readings.is_empty() {
+            return Some(());
+        }
+
+        let furigana = w.get_furigana()?;
+        self.query
+            .kanji_readings
+            .iter()
+            .all(|reading| {
+                furigana
+                    .iter()
+                    .flat_map(|part| part.reading_iter())
+                    .any(|(kanji, kana)| kanji_read_as(kanji, kana, reading))
+            })
+            .then(|| ())
+    }
+
     #[inline]
     fn by_quot_marks_jp(&self, w: &Word, q_term: &str) -> Option<()> {
         if q_term.is_kana() {
@@ -112,3 +161,46 @@ impl WordFilter {
         Some(())
     }
 }
+
+/// Returns `true` if `kanji` contains the literal of `reading` and is read with its reading.
+/// Readings spanning multiple kanji (eg. 今日) only have to contain the reading
+fn kanji_read_as(kanji: &str, kana: Option<&str>, reading: &ReadingSearch) -> bool {
+    let kana = match kana {
+        Some(kana) if kanji.contains(reading.literal) => kana.to_hiragana(),
+        _ => return false,
+    };
+    let expected = reading.reading.to_hiragana();
+
+    if kanji.chars().count() == 1 {
+        kana == expected
+    } else {
+        kana.contains(&expected)
+    }
+}
+
+/// Returns `true` if `word` matches `tag`. Tags which can't be applied to words always match
+pub(crate) fn word_has_tag(word: &Word, tag: &Tag) -> bool {
+    match tag {
+        Tag::PartOfSpeech(pos) => word.has_pos(&[*pos]),
+        Tag::Misc(misc) => word.has_misc(misc),
+        Tag::Dialect(dialect) => word.has_dialect(*dialect),
+        Tag::Jlpt(jlpt) => word.get_jlpt_lvl() == Some(*jlpt),
+        Tag::Katakana => word.get_reading_str().is_katakana(),
+        Tag::IrregularIruEru => resources::get().words().is_irregular_ichidan(word.sequence),
+        Tag::New => resources::get()
+            .words()
+            .is_recent(word.sequence, NEW_TAG_RELEASES),
+        Tag::TopRanked(n) => resources::get()
+            .words()
+            .is_top_ranked(word.sequence, *n as usize),
+        Tag::WaniKani(level) => {
+            resources::get().wanikani().word_level(word.sequence) == Some(*level)
+        }
+        Tag::TextbookLesson(textbook, lesson) => {
+            resources::textbooks::lesson_words(*textbook, *lesson)
+                .binary_search(&word.sequence)
+                .is_ok()
+        }
+        _ => true,
+    }
+}
@@ -1,5 +1,6 @@
 use super::REMOVE_PARENTHESES;
-use engine::relevance::{data::SortData, RelevanceEngine};
+use crate::engine::words::foreign;
+use engine::relevance::{bm25, data::SortData, RelevanceEngine};
 use indexes::ng_freq::{term_dist, NgFreqIndex};
 use sparse_vec::{SpVec32, VecExt};
 use types::jotoba::{
@@ -84,11 +85,11 @@ impl RelevanceEngine for ForeignOrder {
         let mut rel_add = 0.0;
         if text_sim >= 0.5 {
             let index_item = item.index_item().vec();
-            let gloss_sim = item.query().scalar(index_item);
+            let gloss_sim = gloss_sim(item.query(), index_item, lang);
             rel_add += gloss_sim * 100.0;
         }
 
-        (rel_add + text_sim) / 2.0
+        (rel_add + text_sim) / 2.0 * super::frequency_weight(word)
     }
 
     fn init(&mut self, init: engine::relevance::RelEngineInit) {
@@ -105,6 +106,16 @@ impl RelevanceEngine for ForeignOrder {
     }
 }
 
+/// Similarity between the query and the glosses of a word. Mixes the weights of the index vector
+/// with a BM25 score, so matches in short glosses and on rare terms rank higher than matches in
+/// long glosses or on very common terms
+#[inline]
+fn gloss_sim(query: &SpVec32, index_item: &SpVec32, lang: Language) -> f32 {
+    let tf_idf = query.scalar(index_item);
+    let bm25 = bm25::score(query, index_item, foreign::stats(lang));
+    (tf_idf + bm25) / 2.0
+}
+
 #[inline]
 fn get_ng_index(lang: Language) -> &'static NgFreqIndex {
     indexes::get().word().foreign(lang).unwrap().ng_index()
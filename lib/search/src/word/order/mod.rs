@@ -4,7 +4,19 @@ pub mod native;
 pub mod regex;
 
 use once_cell::sync::Lazy;
+use types::jotoba::words::Word;
 
 /// A Regex matching parentheses and its contents
 pub(crate) static REMOVE_PARENTHESES: Lazy<::regex::Regex> =
     Lazy::new(|| ::regex::Regex::new("\\(.*\\)").unwrap());
+
+/// Small factor preferring frequent words over less frequent words or words without a frequency.
+/// Ranges from 0.999 for words without a frequency to 1.0 for words with a frequency of a million
+/// or more, so it only decides between otherwise equally relevant words
+#[inline]
+pub(crate) fn frequency_weight(word: &Word) -> f32 {
+    match resources::get().words().frequency(word.sequence) {
+        Some(freq) => 0.999 + ((freq as f32).log10() / 6.0).clamp(0.0, 1.0) * 0.001,
+        None => 0.999,
+    }
+}
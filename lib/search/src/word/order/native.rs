@@ -125,6 +125,8 @@ impl RelevanceEngine for NativeOrder {
             score *= 0.999;
         }
 
+        score *= super::frequency_weight(word);
+
         //let reading_len = utils::real_string_len(&reading);
         /* if reading_len == 1 && reading.is_kanji() {
             let kanji = reading.chars().next().unwrap();
@@ -6,7 +6,7 @@ use crate::{
     query::{Query, QueryLang},
     word::Search,
 };
-use engine::pushable::FilteredMaxCounter;
+use engine::{pushable::FilteredMaxCounter, task::SearchTask};
 use task::ForeignSearch;
 use types::jotoba::language::Language;
 
@@ -37,16 +37,18 @@ impl<'a> Producer for ForeignProducer<'a> {
         let q_str = &self.query.query_str;
         let lang = self.query.get_search_lang();
 
-        ForeignSearch::new(self.query, q_str, lang)
-            .task()
-            .find_to(out);
+        let mut langs = vec![lang];
 
         // Add english results
         if lang != Language::English && self.query.show_english() {
-            ForeignSearch::new(self.query, q_str, Language::English)
-                .task()
-                .find_to(out);
+            langs.push(Language::English);
         }
+
+        SearchTask::find_parallel_to(
+            &langs,
+            |lang| ForeignSearch::new(self.query, q_str, *lang).task(),
+            out,
+        );
     }
 
     fn estimate_to(&self, out: &mut FilteredMaxCounter<<Self::Target as Searchable>::Item>) {
@@ -2,7 +2,11 @@ use japanese::guessing::could_be_romaji;
 
 use crate::{
     engine::words::native::Engine,
-    executor::{out_builder::OutputBuilder, producer::Producer, searchable::Searchable},
+    executor::{
+        out_builder::OutputBuilder,
+        producer::{Cost, Producer},
+        searchable::Searchable,
+    },
     query::{Query, QueryLang},
     word::{producer::japanese::task::NativeSearch, Search},
 };
@@ -48,8 +52,17 @@ impl<'a> Producer for RomajiProducer<'a> {
             <Self::Target as Searchable>::ResAdd,
         >,
     ) {
-        self.hira_task().find_to(out);
-        self.kk_task().find_to(out);
+        SearchTask::find_parallel_to(
+            &[true, false],
+            |hira| {
+                if *hira {
+                    self.hira_task()
+                } else {
+                    self.kk_task()
+                }
+            },
+            out,
+        );
     }
 
     fn estimate_to(&self, out: &mut FilteredMaxCounter<<Self::Target as Searchable>::Item>) {
@@ -57,6 +70,11 @@ impl<'a> Producer for RomajiProducer<'a> {
         self.kk_task().estimate_to(out);
     }
 
+    #[inline]
+    fn cost(&self) -> Cost {
+        Cost::Expensive
+    }
+
     fn should_run(&self, already_found: usize) -> bool {
         already_found < 100
             // Don't run on jp input
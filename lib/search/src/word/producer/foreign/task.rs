@@ -2,7 +2,10 @@ use engine::task::SearchTask;
 use types::jotoba::language::Language;
 
 use crate::{
-    engine::words::foreign::Engine,
+    engine::{
+        settings::{self, EngineKind},
+        words::foreign::Engine,
+    },
     query::Query,
     word::{filter::WordFilter, order::foreign::ForeignOrder},
 };
@@ -25,8 +28,9 @@ impl<'a> ForeignSearch<'a> {
 
     pub fn task(&self) -> SearchTask<'static, Engine> {
         let filter = WordFilter::new(self.query.clone());
-        SearchTask::with_language(self.query_str, self.language)
+        let task = SearchTask::with_language(self.query_str, self.language)
             .with_custom_order(ForeignOrder::new())
-            .with_result_filter(move |item| !filter.filter_word(*item))
+            .with_result_filter(move |item| !filter.filter_word(*item));
+        settings::apply(EngineKind::ForeignWords, task)
     }
 }
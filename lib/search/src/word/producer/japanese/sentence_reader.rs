@@ -8,6 +8,7 @@ use jp_utils::{
     JapaneseExt,
 };
 use ngindex::{item::IndexItem, termset::TermSet};
+use once_cell::unsync::OnceCell;
 use sentence_reader::{output::ParseResult, Parser, Part, Sentence};
 use types::jotoba::words::{part_of_speech::PosSimple, Word};
 
@@ -27,18 +28,29 @@ use super::task::NativeSearch;
 /// Producer for sentence reader and inflection information
 pub struct SReaderProducer<'a> {
     query: &'a Query,
-    parsed: ParseResult,
+    /// Parsed query. Parsing is expensive, so it's done on first use, which is within the
+    /// latency budget of the search
+    parsed: OnceCell<ParseResult>,
 }
 
 impl<'a> SReaderProducer<'a> {
     pub fn new(query: &'a Query) -> Self {
-        let parsed = Parser::new(&query.query_str).parse();
-        Self { query, parsed }
+        Self {
+            query,
+            parsed: OnceCell::new(),
+        }
+    }
+
+    /// Returns the query parsed by the sentence reader
+    #[inline]
+    fn parsed(&self) -> &ParseResult {
+        self.parsed
+            .get_or_init(|| Parser::new(&self.query.query_str).parse())
     }
 
     /// Search task for inflected word
     fn infl_task(&self) -> Option<SearchTask<'static, Engine>> {
-        let infl = self.parsed.as_inflected_word()?;
+        let infl = self.parsed().as_inflected_word()?;
 
         let normalized = infl.get_normalized();
 
@@ -53,7 +65,7 @@ impl<'a> SReaderProducer<'a> {
     /// Selected word index within the sentence
     #[inline]
     fn sentence_index(&self) -> usize {
-        self.parsed
+        self.parsed()
             .as_sentence()
             .map(|s| self.query.word_index.clamp(0, s.word_count() - 1))
             .unwrap_or(0)
@@ -62,7 +74,7 @@ impl<'a> SReaderProducer<'a> {
     /// Selected word in the sentence
     #[inline]
     fn sentence_word(&self) -> Option<&Part> {
-        let sentence = self.parsed.as_sentence()?;
+        let sentence = self.parsed().as_sentence()?;
         let index = self.sentence_index();
         sentence.get_at(index)
     }
@@ -102,13 +114,13 @@ impl<'a> Producer for SReaderProducer<'a> {
             <Self::Target as Searchable>::ResAdd,
         >,
     ) {
-        if let ParseResult::InflectedWord(infl) = &self.parsed {
+        if let ParseResult::InflectedWord(infl) = self.parsed() {
             self.infl_task().unwrap().find_to(out);
             out.output_add.inflection = InflectionInformation::from_part(infl);
             return;
         }
 
-        if let ParseResult::Sentence(mut sentence) = self.parsed.clone() {
+        if let ParseResult::Sentence(mut sentence) = self.parsed().clone() {
             set_furigana(&mut sentence);
 
             self.snt_task_normalized().unwrap().find_to(out);
@@ -129,16 +141,16 @@ impl<'a> Producer for SReaderProducer<'a> {
     }
 
     fn should_run(&self, already_found: usize) -> bool {
-        if self.parsed.is_none()
-            || self.query.q_lang != QueryLang::Japanese
+        if self.query.q_lang != QueryLang::Japanese
             || !self.query.form.is_normal()
             || self.query.query_str.is_empty()
+            || self.parsed().is_none()
         {
             return false;
         }
 
         // Always run inlfections
-        if self.parsed.is_inflected_word() {
+        if self.parsed().is_inflected_word() {
             return true;
         }
 
@@ -158,7 +170,7 @@ impl<'a> Producer for SReaderProducer<'a> {
             return;
         }
 
-        if self.parsed.is_sentence() {
+        if self.parsed().is_sentence() {
             self.snt_task_normalized().unwrap().estimate_to(out);
             let word = self.sentence_word().unwrap();
             if word.get_inflected() != word.get_normalized() {
@@ -2,7 +2,10 @@ use engine::task::SearchTask;
 use jp_utils::JapaneseExt;
 
 use crate::{
-    engine::words::native::Engine,
+    engine::{
+        settings::{self, EngineKind},
+        words::native::Engine,
+    },
     query::Query,
     word::{filter::WordFilter, order::native::NativeOrder},
 };
@@ -50,10 +53,11 @@ impl<'a> NativeSearch<'a> {
         let filter = WordFilter::new(self.query.clone());
         let original_query = self.original_query().to_string();
 
-        SearchTask::new(self.query_str)
+        let task = SearchTask::new(self.query_str)
             .with_custom_order(NativeOrder::new(original_query))
             .with_result_filter(move |item| !filter.filter_word(*item))
-            .with_threshold(self.threshold)
+            .with_threshold(self.threshold);
+        settings::apply(EngineKind::NativeWords, task)
     }
 
     #[inline]
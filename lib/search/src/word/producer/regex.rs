@@ -1,4 +1,3 @@
-use itertools::Itertools;
 use types::jotoba::words::Word;
 
 use crate::{
@@ -68,10 +67,7 @@ where
 {
     let word_resources = resources::get().words();
 
-    let index = indexes::get().word().regex();
-    let possible_results = regex::find_words(index, &query.get_chars());
-
-    for seq_id in possible_results.into_iter().sorted() {
+    for seq_id in regex::find_words(query) {
         let word = word_resources.by_sequence(seq_id).unwrap();
 
         let item_iter = word
@@ -2,7 +2,7 @@ use types::jotoba::words::Word;
 
 use crate::{
     executor::{out_builder::OutputBuilder, producer::Producer, searchable::Searchable},
-    query::{Query, Tag},
+    query::{tags::NEW_TAG_RELEASES, Query, Tag},
     word::Search,
 };
 use engine::{
@@ -20,24 +20,44 @@ impl<'a> TagProducer<'a> {
         Self { query }
     }
 
-    fn get_producer_tag(&self) -> Option<&Tag> {
-        self.query
+    /// Returns the tags to produce words for. If there is no tag which has to match in any case,
+    /// all tags of the first OR-combined tag group are used
+    fn get_producer_tags(&self) -> Vec<&Tag> {
+        let tag = self
+            .query
             .tags
             .iter()
-            .filter(|i| i.is_producer() && !i.is_sentence_tag())
+            .filter(|i| Self::is_producer_tag(i))
             // Use tag with fewest items that it'll produce to reduce the amount of items that have to be filtered
             .map(|i| (self.tag_len(i).unwrap_or(usize::MAX), i))
             .min_by_key(|i| i.0)
-            .map(|i| i.1)
+            .map(|i| i.1);
+
+        if let Some(tag) = tag {
+            return vec![tag];
+        }
+
+        self.query
+            .tag_expr
+            .or_groups()
+            .map(|group| group.tags())
+            .find(|tags| tags.iter().all(|i| Self::is_producer_tag(i)))
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    fn is_producer_tag(tag: &Tag) -> bool {
+        tag.is_producer() && !tag.is_sentence_tag()
     }
 
     fn find_to<P>(&self, out: &mut P)
     where
         P: Pushable<Item = RelItem<&'static Word>>,
     {
-        // Find first producer tag. All other tags are treated as filter
-        let producer_tag = self.get_producer_tag().unwrap();
-        self.find_words(out, producer_tag);
+        // All other tags are treated as filter
+        for producer_tag in self.get_producer_tags() {
+            self.find_words(out, producer_tag);
+        }
     }
 
     fn find_words<P>(&self, out: &mut P, tag: &Tag)
@@ -48,9 +68,22 @@ impl<'a> TagProducer<'a> {
         match tag {
             Tag::PartOfSpeech(pos) => self.push_iter(words.by_pos_simple(*pos), out),
             Tag::Misc(m) => self.push_iter(words.by_misc(*m), out),
+            Tag::Dialect(d) => self.push_iter(words.by_dialect(*d), out),
             Tag::Jlpt(jlpt) => self.push_iter(words.by_jlpt(*jlpt), out),
             Tag::Katakana => self.push_iter(words.katakana(), out),
             Tag::IrregularIruEru => self.push_iter(words.irregular_ichidan(), out),
+            Tag::New => self.push_iter(words.recent(NEW_TAG_RELEASES), out),
+            // Most frequent words have to be pushed last to get the highest relevance
+            Tag::TopRanked(n) => self.push_iter(words.top_ranked(*n as usize).rev(), out),
+            Tag::WaniKani(level) => {
+                let seqs = resources::get().wanikani().words_by_level(*level);
+                let iter = seqs.into_iter().flatten();
+                self.push_iter(iter.filter_map(|i| words.by_sequence(*i)), out)
+            }
+            Tag::TextbookLesson(textbook, lesson) => {
+                let seqs = resources::textbooks::lesson_words(*textbook, *lesson);
+                self.push_iter(seqs.iter().filter_map(|i| words.by_sequence(*i)), out)
+            }
             _ => (),
         }
     }
@@ -79,9 +112,21 @@ impl<'a> TagProducer<'a> {
         match tag {
             Tag::PartOfSpeech(p) => w_retr.pos_simple_len(p),
             Tag::Misc(m) => w_retr.misc_len(m),
+            Tag::Dialect(d) => w_retr.dialect_len(*d),
             Tag::Jlpt(j) => w_retr.jlpt_len(*j),
             Tag::IrregularIruEru => Some(w_retr.irregular_ichidan_len()),
             Tag::Katakana => Some(w_retr.katakana_len()),
+            Tag::New => Some(w_retr.recent_len(NEW_TAG_RELEASES)),
+            Tag::TopRanked(n) => Some(w_retr.top_ranked_len(*n as usize)),
+            Tag::WaniKani(level) => Some(
+                resources::get()
+                    .wanikani()
+                    .words_by_level(*level)
+                    .map_or(0, |i| i.len()),
+            ),
+            Tag::TextbookLesson(textbook, lesson) => {
+                Some(resources::textbooks::lesson_words(*textbook, *lesson).len())
+            }
             _ => None,
         }
     }
@@ -102,7 +147,7 @@ impl<'a> Producer for TagProducer<'a> {
 
     fn should_run(&self, _already_found: usize) -> bool {
         // Only run this producer if there is no query (except tags) and there are tags which can produce output
-        self.query.query_str.is_empty() && self.get_producer_tag().is_some()
+        self.query.query_str.is_empty() && !self.get_producer_tags().is_empty()
     }
 
     fn estimate_to(&self, out: &mut FilteredMaxCounter<<Self::Target as Searchable>::Item>) {
@@ -0,0 +1,48 @@
+use crate::{
+    api::search::word::Word,
+    jotoba::language::{LangParam, Language},
+};
+use serde::{Deserialize, Serialize};
+
+/// Request for a vocabulary list of a text
+#[derive(Deserialize)]
+pub struct Request {
+    pub text: String,
+
+    #[serde(default)]
+    pub language: Language,
+
+    #[serde(default)]
+    pub no_english: bool,
+}
+
+impl Request {
+    /// Returns language parameters for the request
+    #[inline]
+    pub fn lang_param(&self) -> LangParam {
+        LangParam::with_en_raw(self.language, !self.no_english)
+    }
+}
+
+/// All distinct words used in a text, ordered by their frequency
+#[derive(Serialize)]
+pub struct Response {
+    pub words: Vec<AnalyzedWord>,
+    /// Lemmas which couldn't be found in the dictionary
+    pub unknown: Vec<UnknownLemma>,
+}
+
+/// A dictionary entry along with the amount of times it occurs in the text
+#[derive(Serialize)]
+pub struct AnalyzedWord {
+    pub count: usize,
+    /// Lemmas within the text which were mapped to this word
+    pub lemmas: Vec<String>,
+    pub word: Word,
+}
+
+#[derive(Serialize)]
+pub struct UnknownLemma {
+    pub lemma: String,
+    pub count: usize,
+}
@@ -21,10 +21,36 @@ pub struct Request {
 
     #[serde(default)]
     pub hashtag: bool,
+
+    /// Mix word, kanji and name suggestions if the search type isn't specified within the
+    /// input. Each suggestion is labeled with the search type it belongs to
+    #[serde(default)]
+    pub smart: bool,
+
+    /// Opt-in token identifying the session of the user. Suggestions selected within the same
+    /// session (see [`SelectionRequest`]) get ranked higher
+    #[serde(default)]
+    pub session: Option<String>,
+}
+
+/// Request payload structure for reporting a selected suggestion
+#[derive(Deserialize, Debug)]
+pub struct SelectionRequest {
+    /// The session token also passed in [`Request`]
+    pub session: String,
+    /// The primary value of the selected suggestion
+    pub suggestion: String,
+}
+
+/// Request payload structure for tag completion endpoint
+#[derive(Deserialize, Debug)]
+pub struct TagRequest {
+    /// The (partial) tag to complete. The leading '#' is optional
+    pub input: String,
 }
 
 /// Response struct for suggestion endpoint
-#[derive(Serialize, Deserialize, Default)]
+#[derive(Serialize, Deserialize, Default, Clone)]
 pub struct Response {
     pub suggestions: Vec<WordPair>,
     pub suggestion_type: SuggestionType,
@@ -49,7 +75,7 @@ impl Response {
 }
 
 /// The type of suggestion. `Default` in most cases
-#[derive(Deserialize, Serialize, Default)]
+#[derive(Deserialize, Serialize, Default, Clone, Copy)]
 #[serde(rename_all = "snake_case")]
 pub enum SuggestionType {
     /// Default suggestion type
@@ -59,6 +85,8 @@ pub enum SuggestionType {
     KanjiReading,
     /// Hash tag suggestions
     Hashtag,
+    /// Suggestions of multiple search types, each labeled with [`WordPair::search_target`]
+    Mixed,
 }
 
 /// A word with kana and kanji reading used within [`SuggestionResponse`]
@@ -67,6 +95,10 @@ pub struct WordPair {
     pub primary: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub secondary: Option<String>,
+    /// The search type the suggestion belongs to. Only set for [`SuggestionType::Mixed`] and for
+    /// suggestions of a different search type than requested (eg. example sentences)
+    #[serde(rename = "search_type", skip_serializing_if = "Option::is_none")]
+    pub search_target: Option<SearchTarget>,
 }
 
 #[cfg(feature = "jotoba_intern")]
@@ -76,6 +108,7 @@ impl WordPair {
         Self {
             primary,
             secondary: None,
+            search_target: None,
         }
     }
 
@@ -84,6 +117,7 @@ impl WordPair {
         Self {
             primary,
             secondary: Some(secondary),
+            search_target: None,
         }
     }
 
@@ -113,11 +147,13 @@ impl From<&crate::jotoba::words::Word> for WordPair {
             WordPair {
                 secondary: Some(main_reading),
                 primary: word.reading.kana.reading.clone(),
+                search_target: None,
             }
         } else {
             WordPair {
                 primary: main_reading,
                 secondary: None,
+                search_target: None,
             }
         }
     }
@@ -1,6 +1,9 @@
 use crate::{
     api::app::deserialize_lang,
-    jotoba::language::{LangParam, Language},
+    jotoba::{
+        language::{LangParam, Language},
+        sentences::script::SentenceScript,
+    },
 };
 use serde::Deserialize;
 
@@ -10,6 +13,9 @@ pub struct DetailsPayload {
     #[serde(deserialize_with = "deserialize_lang")]
     pub language: Language,
     pub show_english: bool,
+    /// Script the sentence should additionally be returned in
+    #[serde(default)]
+    pub sentence_script: SentenceScript,
 }
 
 impl DetailsPayload {
@@ -13,12 +13,28 @@ pub struct Details {
     #[serde(skip_serializing_if = "Option::is_none")]
     conjugations: Option<Inflections>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
-    collocations: Vec<Word>,
+    collocations: Vec<Collocation>,
     has_sentence: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     transitivity_pair: Option<TransitivityPair>,
 }
 
+/// A collocation of a word along with its corpus frequency
+#[derive(Serialize)]
+pub struct Collocation {
+    #[serde(flatten)]
+    word: Word,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frequency: Option<u32>,
+}
+
+impl Collocation {
+    #[inline]
+    pub fn new(word: Word, frequency: Option<u32>) -> Self {
+        Self { word, frequency }
+    }
+}
+
 #[derive(Serialize)]
 #[serde(tag = "t", content = "w")]
 pub enum TransitivityPair {
@@ -32,7 +48,7 @@ impl Details {
         word: Word,
         kanji: Vec<Kanji>,
         conjugations: Option<Inflections>,
-        collocations: Vec<Word>,
+        collocations: Vec<Collocation>,
         has_sentence: bool,
         transitivity_pair: Option<TransitivityPair>,
     ) -> Self {
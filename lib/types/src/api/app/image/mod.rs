@@ -1,3 +1,8 @@
+use super::{
+    deserialize_lang_option,
+    search::responses::{words, Response as SearchResult},
+};
+use crate::jotoba::language::Language;
 use serde::{Deserialize, Serialize};
 
 /// Scan endpoint response
@@ -15,8 +20,57 @@ pub struct Request {
     pub threshold: i32,
 }
 
+/// Image search endpoint request
+#[derive(Deserialize)]
+pub struct SearchRequest {
+    /// Same as [`Request::threshold`]
+    #[serde(default = "default_conf_threshold")]
+    pub threshold: i32,
+
+    /// Language of the glosses in the search results. English if not set
+    #[serde(default, deserialize_with = "deserialize_lang_option")]
+    pub lang: Option<Language>,
+
+    /// Whether to show english glosses along with the ones in `lang`
+    #[serde(default = "default_show_english")]
+    pub show_english: bool,
+
+    /// Amount of words per page
+    #[serde(default = "default_page_size")]
+    pub page_size: u32,
+
+    /// Result page, starting at 1
+    pub page: Option<u32>,
+}
+
+/// Image search endpoint response
+#[derive(Serialize)]
+pub struct SearchResponse {
+    /// Recognized text
+    pub text: String,
+    /// Word search results for `text`
+    pub words: SearchResult<words::Response>,
+}
+
+impl SearchResponse {
+    #[inline]
+    pub fn new(text: String, words: SearchResult<words::Response>) -> Self {
+        Self { text, words }
+    }
+}
+
 /// Default mit threshold value for detection confidence
 #[inline]
 fn default_conf_threshold() -> i32 {
     55
 }
+
+#[inline]
+fn default_show_english() -> bool {
+    true
+}
+
+#[inline]
+fn default_page_size() -> u32 {
+    10
+}
@@ -5,6 +5,7 @@ pub mod kanji;
 pub mod news;
 pub mod radical;
 pub mod search;
+pub mod settings;
 
 use crate::jotoba::language::Language;
 use serde::{Deserialize, Deserializer};
@@ -1,8 +1,14 @@
 use crate::{
     api::app::{deserialize_lang, deserialize_lang_option},
-    jotoba::language::{LangParam, Language},
+    jotoba::{
+        language::{LangParam, Language},
+        names::group::NameGroup,
+        search::filter_preset::{FilterPreset, MAX_PRESETS},
+        sentences::script::SentenceScript,
+    },
 };
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct SearchPayload {
@@ -22,9 +28,46 @@ pub struct SearchPayload {
     /// Overwrite
     #[serde(default, deserialize_with = "deserialize_lang_option")]
     pub lang_overwrite: Option<Language>,
+
+    /// Max amount of names returned per name group across all pages. Limit tags within the query
+    /// (eg. `#people5`) take precedence
+    #[serde(default)]
+    pub name_group_limits: HashMap<NameGroup, usize>,
+
+    /// Filter presets of the user which can be used as tags
+    #[serde(default, deserialize_with = "deserialize_presets")]
+    pub filter_presets: Vec<FilterPreset>,
+}
+
+/// Deserializes filter presets the same way [`FilterPreset::parse_list`] parses them. Invalid
+/// presets are ignored and only the first [`MAX_PRESETS`] ones are kept
+fn deserialize_presets<'de, D>(d: D) -> Result<Vec<FilterPreset>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let presets = Vec::<FilterPreset>::deserialize(d)?
+        .into_iter()
+        .filter_map(|i| FilterPreset::new(&i.name, &i.tags))
+        .take(MAX_PRESETS)
+        .collect();
+    Ok(presets)
 }
 
 impl SearchPayload {
+    /// Creates a new payload searching for `query_str` on the first page
+    #[inline]
+    pub fn new(query_str: String, settings: UserSettings) -> Self {
+        Self {
+            settings,
+            query_str,
+            page: None,
+            word_index: None,
+            lang_overwrite: None,
+            name_group_limits: HashMap::new(),
+            filter_presets: vec![],
+        }
+    }
+
     /// Returns language parameters for the query
     #[inline]
     pub fn lang_param(&self) -> LangParam {
@@ -41,6 +84,12 @@ pub struct UserSettings {
     pub page_size: u32,
     pub show_example_sentences: bool,
     pub sentence_furigana: bool,
+    #[serde(default)]
+    pub sentence_script: SentenceScript,
+    #[serde(default)]
+    pub sentence_max_katakana: Option<u8>,
+    #[serde(default)]
+    pub sentence_max_kanji_grade: Option<u8>,
 }
 
 impl UserSettings {
@@ -20,12 +20,26 @@ pub struct CompoundSet {
     pub on: Vec<CompoundWord>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub kun: Vec<CompoundWord>,
+    /// Whether there are more on reading compounds on the next page
+    pub has_more_on: bool,
+    /// Whether there are more kun reading compounds on the next page
+    pub has_more_kun: bool,
 }
 
 impl CompoundSet {
     #[inline]
-    pub fn new(on: Vec<CompoundWord>, kun: Vec<CompoundWord>) -> Self {
-        Self { on, kun }
+    pub fn new(
+        on: Vec<CompoundWord>,
+        kun: Vec<CompoundWord>,
+        has_more_on: bool,
+        has_more_kun: bool,
+    ) -> Self {
+        Self {
+            on,
+            kun,
+            has_more_on,
+            has_more_kun,
+        }
     }
 }
 
@@ -6,7 +6,10 @@ pub mod words;
 
 use serde::Serialize;
 
-use crate::jotoba::{pagination::page::Page, search::help::SearchHelp};
+use crate::jotoba::{
+    pagination::page::Page,
+    search::{help::SearchHelp, unknown_tag::UnknownTag},
+};
 
 #[derive(Serialize)]
 pub struct Response<T: Serialize> {
@@ -14,6 +17,9 @@ pub struct Response<T: Serialize> {
     inner: Page<T>,
     #[serde(skip_serializing_if = "Option::is_none")]
     search_help: Option<SearchHelp>,
+    /// Tags of the query which have been ignored since they're unknown
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    unknown_tags: Vec<UnknownTag>,
 }
 
 impl<T: Serialize> Response<T> {
@@ -21,6 +27,7 @@ impl<T: Serialize> Response<T> {
         Self {
             inner,
             search_help: None,
+            unknown_tags: vec![],
         }
     }
 
@@ -28,6 +35,7 @@ impl<T: Serialize> Response<T> {
         Self {
             inner,
             search_help: Some(search_help),
+            unknown_tags: vec![],
         }
     }
 
@@ -38,9 +46,15 @@ impl<T: Serialize> Response<T> {
         Self {
             search_help: help_fn(&inner),
             inner,
+            unknown_tags: vec![],
         }
     }
 
+    pub fn with_unknown_tags(mut self, unknown_tags: Vec<UnknownTag>) -> Self {
+        self.unknown_tags = unknown_tags;
+        self
+    }
+
     pub fn set_search_help(&mut self, search_help: SearchHelp) -> &mut Self {
         self.search_help = Some(search_help);
         self
@@ -1,16 +1,82 @@
+use std::collections::HashMap;
+
 use serde::Serialize;
 
-use crate::jotoba::names::Name;
+use crate::jotoba::{
+    kanji::Kanji,
+    names::{alias::Alias, group::NameGroup, Name},
+};
 
 /// Names API response. Contains all Names
 #[derive(Clone, Debug, Serialize)]
 pub struct Response {
     names: Vec<Name>,
+    /// Katakana renderings of a foreign name query
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    transliterations: Vec<String>,
+    /// Maps name sequence ids to words written and read the same way
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    linked_words: HashMap<u32, Vec<u32>>,
+    /// Maps name sequence ids to historical and alternative names
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    aliases: HashMap<u32, Vec<Alias>>,
+    /// Maps name sequence ids to the kanji they're composed of
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    kanji: HashMap<u32, Vec<NameKanji>>,
+    /// Sequence ids of names grouped by their type
+    groups: Vec<Group>,
+}
+
+/// A group of names
+#[derive(Clone, Debug, Serialize)]
+pub struct Group {
+    group: NameGroup,
+    names: Vec<u32>,
+}
+
+/// A single kanji of a name along with its meanings and name readings
+#[derive(Clone, Debug, Serialize)]
+pub struct NameKanji {
+    literal: char,
+    meanings: Vec<String>,
+    nanori: Vec<String>,
+}
+
+impl From<&Kanji> for NameKanji {
+    #[inline]
+    fn from(kanji: &Kanji) -> Self {
+        Self {
+            literal: kanji.literal,
+            meanings: kanji.meanings.clone(),
+            nanori: kanji.nanori.clone(),
+        }
+    }
+}
+
+impl Group {
+    #[inline]
+    pub fn new(group: NameGroup, names: Vec<u32>) -> Self {
+        Self { group, names }
+    }
 }
 
 impl Response {
     #[inline]
-    pub fn new(names: Vec<Name>) -> Self {
-        Self { names }
+    pub fn new(
+        names: Vec<Name>,
+        transliterations: Vec<String>,
+        linked_words: HashMap<u32, Vec<u32>>,
+        aliases: HashMap<u32, Vec<Alias>>,
+        kanji: HashMap<u32, Vec<NameKanji>>,
+        groups: Vec<Group>,
+    ) -> Self {
+        Self {
+            names,
+            transliterations,
+            linked_words,
+            aliases,
+            kanji,
+            groups,
+        }
     }
 }
@@ -18,6 +18,9 @@ pub struct Sentence {
     sequence: u32,
     content: String,
     translation: String,
+    /// The sentence in the requested script (kana or romaji)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    converted: Option<String>,
 }
 
 impl Sentence {
@@ -28,6 +31,14 @@ impl Sentence {
             sequence,
             content,
             translation,
+            converted: None,
         }
     }
+
+    /// Sets the sentence converted into the requested script
+    #[inline]
+    pub fn with_converted(mut self, converted: Option<String>) -> Self {
+        self.converted = converted;
+        self
+    }
 }
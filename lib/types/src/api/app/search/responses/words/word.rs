@@ -30,6 +30,15 @@ pub struct Word {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub intransive_version: Option<u32>,
     pub sentences_available: u16,
+    /// Sequence ids of names written and read the same way
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub linked_names: Vec<u32>,
+    /// Sequence ids of words referenced by the senses
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub see_also: Vec<u32>,
+    /// Sequence ids of words with the opposite meaning
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub antonyms: Vec<u32>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Request changing the settings of a user. Values have the format of the settings cookies, eg.
+/// `{"items_per_page": "20", "sentence_script": "romaji"}`
+#[derive(Deserialize)]
+pub struct Request {
+    pub settings: BTreeMap<String, String>,
+}
+
+/// All settings of a user by their keys
+#[derive(Serialize, Deserialize)]
+pub struct Response {
+    pub settings: BTreeMap<String, String>,
+}
@@ -0,0 +1,36 @@
+use serde::Serialize;
+
+/// Data version endpoint response
+#[derive(Serialize)]
+pub struct Response {
+    /// Git hash of the build the resources were loaded with
+    pub git_hash: String,
+    /// Overlay files applied on top of the resources
+    pub overlays: Vec<Overlay>,
+}
+
+impl Response {
+    #[inline]
+    pub fn new(git_hash: String, overlays: Vec<Overlay>) -> Self {
+        Self { git_hash, overlays }
+    }
+}
+
+/// An applied overlay file
+#[derive(Serialize)]
+pub struct Overlay {
+    pub file: String,
+    pub applied: usize,
+    pub skipped: usize,
+}
+
+impl Overlay {
+    #[inline]
+    pub fn new(file: String, applied: usize, skipped: usize) -> Self {
+        Self {
+            file,
+            applied,
+            skipped,
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use crate::{api::app::deserialize_lang_option, jotoba::language::Language};
+use serde::Deserialize;
+use std::str::FromStr;
+
+/// Request for exporting the word results of a search
+#[derive(Deserialize)]
+pub struct Request {
+    pub query: String,
+
+    #[serde(default)]
+    pub format: ExportFormat,
+
+    /// Comma separated list of columns to export. Defaults to kanji, kana and glosses
+    #[serde(default)]
+    pub columns: Option<String>,
+
+    /// Language of the exported glosses. English if not set
+    #[serde(default, deserialize_with = "deserialize_lang_option")]
+    pub language: Option<Language>,
+
+    /// Max amount of words to export
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+}
+
+impl Request {
+    /// Returns the requested columns or `None` if an unknown column was requested
+    pub fn columns(&self) -> Option<Vec<Column>> {
+        let columns = match self.columns.as_deref() {
+            Some(columns) if !columns.trim().is_empty() => columns,
+            _ => return Some(Column::DEFAULT.to_vec()),
+        };
+
+        columns
+            .split(',')
+            .map(|i| Column::from_str(i.trim()).ok())
+            .collect()
+    }
+}
+
+#[inline]
+fn default_limit() -> u32 {
+    100
+}
+
+/// File formats results can be exported in
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Tsv,
+    Jsonl,
+}
+
+impl ExportFormat {
+    #[inline]
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Tsv => "text/tab-separated-values; charset=utf-8",
+            ExportFormat::Jsonl => "application/jsonl; charset=utf-8",
+        }
+    }
+
+    #[inline]
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Tsv => "tsv",
+            ExportFormat::Jsonl => "jsonl",
+        }
+    }
+}
+
+/// Exportable properties of a word
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Column {
+    Sequence,
+    Kanji,
+    Kana,
+    Glosses,
+    Jlpt,
+    Pitch,
+}
+
+impl Column {
+    /// Columns which are exported if no columns were requested
+    pub const DEFAULT: [Column; 3] = [Column::Kanji, Column::Kana, Column::Glosses];
+
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Column::Sequence => "sequence",
+            Column::Kanji => "kanji",
+            Column::Kana => "kana",
+            Column::Glosses => "glosses",
+            Column::Jlpt => "jlpt",
+            Column::Pitch => "pitch",
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().as_str() {
+            "sequence" | "seq" => Column::Sequence,
+            "kanji" => Column::Kanji,
+            "kana" => Column::Kana,
+            "glosses" | "gloss" => Column::Glosses,
+            "jlpt" => Column::Jlpt,
+            "pitch" => Column::Pitch,
+            _ => return Err(()),
+        })
+    }
+}
@@ -0,0 +1,22 @@
+use crate::jotoba::words::inflection::Inflections;
+use serde::Serialize;
+
+/// Conjugation table of a single word
+#[derive(Serialize)]
+pub struct Response {
+    pub sequence: u32,
+    pub reading: String,
+    /// `None` if the word can't be conjugated
+    pub inflections: Option<Inflections>,
+}
+
+impl Response {
+    #[inline]
+    pub fn new(sequence: u32, reading: String, inflections: Option<Inflections>) -> Self {
+        Self {
+            sequence,
+            reading,
+            inflections,
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::jotoba::words::Word;
+use serde::{Deserialize, Serialize};
+
+/// Request for a page of the word dump. Either `page` or `after` can be used to select which
+/// words to return. `after` takes precedence and is stable even if words get added or removed
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Request {
+    /// Page to return, starting at 1
+    pub page: Option<u32>,
+    /// Cursor: only return words with a sequence id greater than this one
+    pub after: Option<u32>,
+}
+
+/// A page of words, ordered by their sequence id
+#[derive(Serialize, Deserialize)]
+pub struct Response {
+    pub words: Vec<Word>,
+    /// Total amount of words
+    pub total: usize,
+    /// Amount of words per page
+    pub page_size: usize,
+    /// Cursor to pass as `after` to fetch the next page. `None` if this was the last page
+    pub next: Option<u32>,
+}
+
+impl Response {
+    #[inline]
+    pub fn new(words: Vec<Word>, total: usize, page_size: usize, next: Option<u32>) -> Self {
+        Self {
+            words,
+            total,
+            page_size,
+            next,
+        }
+    }
+}
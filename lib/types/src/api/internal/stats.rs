@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// Usage statistics of the suggestion response cache
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy)]
+pub struct CacheStats {
+    /// Amount of currently cached responses
+    pub entries: usize,
+    /// Max amount of cached responses
+    pub capacity: usize,
+    pub hits: u64,
+    pub misses: u64,
+    /// Share of requests answered from the cache, between 0 and 1
+    pub hit_rate: f64,
+}
+
+impl CacheStats {
+    #[inline]
+    pub fn new(entries: usize, capacity: usize, hits: u64, misses: u64) -> Self {
+        let total = hits + misses;
+        let hit_rate = if total > 0 {
+            hits as f64 / total as f64
+        } else {
+            0.0
+        };
+
+        Self {
+            entries,
+            capacity,
+            hits,
+            misses,
+            hit_rate,
+        }
+    }
+}
@@ -1,3 +1,10 @@
+pub mod analyze;
 pub mod app;
+pub mod data_version;
+pub mod export;
+pub mod inflections;
 pub mod internal;
+pub mod pitch;
+pub mod radicals;
+pub mod recent;
 pub mod search;
@@ -0,0 +1,64 @@
+use crate::jotoba::words::pitch::{mora_pattern, split_kana};
+use serde::{Deserialize, Serialize};
+
+/// Request for the pitch accent of a word or a raw kana string
+#[derive(Deserialize)]
+pub struct Request {
+    /// Word (kanji or kana) to look up
+    pub text: String,
+    /// Position of the pitch drop. If set, `text` is treated as raw kana and not looked up
+    pub drop: Option<u8>,
+}
+
+#[derive(Serialize)]
+pub struct Response {
+    pub words: Vec<PitchWord>,
+}
+
+/// Pitch accents of a single word
+#[derive(Serialize)]
+pub struct PitchWord {
+    /// Sequence of the word. `None` for raw kana strings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence: Option<u32>,
+    pub reading: String,
+    pub kana: String,
+    pub accents: Vec<Accent>,
+}
+
+/// A single pitch accent pattern
+#[derive(Serialize)]
+pub struct Accent {
+    /// Mora after which the pitch drops. 0 if it doesn't drop at all
+    pub drop: u8,
+    /// Name of the accent pattern (heiban, atamadaka, nakadaka or odaka)
+    pub kind: &'static str,
+    pub morae: Vec<String>,
+    /// Whether each mora in `morae` is pronounced high
+    pub high: Vec<bool>,
+    /// Whether a particle following the word is pronounced high
+    pub particle_high: bool,
+}
+
+impl Accent {
+    pub fn new(kana: &str, drop: u8) -> Self {
+        let morae: Vec<String> = split_kana(kana).map(|i| i.to_string()).collect();
+        let mut high = mora_pattern(kana, drop);
+        let particle_high = high.pop().unwrap_or_default();
+
+        let kind = match drop as usize {
+            0 => "heiban",
+            1 => "atamadaka",
+            d if d == morae.len() => "odaka",
+            _ => "nakadaka",
+        };
+
+        Self {
+            drop,
+            kind,
+            morae,
+            high,
+            particle_high,
+        }
+    }
+}
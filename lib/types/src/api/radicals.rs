@@ -0,0 +1,65 @@
+use crate::jotoba::kanji::radical::DetailedRadical;
+use serde::Serialize;
+
+/// A radical which can be used to search for kanji
+#[derive(Serialize)]
+pub struct Radical {
+    pub literal: char,
+    pub stroke_count: u32,
+    /// Alternative form of the radical, eg. 氵 for 水
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alternative: Option<char>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub readings: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub translations: Vec<String>,
+}
+
+impl Radical {
+    /// Creates a new radical with the details of `detailed`, if available
+    pub fn new(literal: char, stroke_count: u32, detailed: Option<&DetailedRadical>) -> Self {
+        let (alternative, readings, translations) = match detailed {
+            Some(d) => (
+                d.alternative,
+                d.readings.clone(),
+                d.translations.clone().unwrap_or_default(),
+            ),
+            None => (None, vec![], vec![]),
+        };
+
+        Self {
+            literal,
+            stroke_count,
+            alternative,
+            readings,
+            translations,
+        }
+    }
+}
+
+/// Response for the radical list
+#[derive(Serialize)]
+pub struct ListResponse {
+    pub radicals: Vec<Radical>,
+}
+
+/// Response for the lookup of a single radical
+#[derive(Serialize)]
+pub struct RadicalResponse {
+    pub radical: Radical,
+    /// Amount of kanji using the radical
+    pub kanji_count: usize,
+}
+
+/// Kanji using a radical, ordered by their stroke count
+#[derive(Serialize)]
+pub struct KanjiResponse {
+    pub radical: char,
+    pub kanji: Vec<RadicalKanji>,
+}
+
+#[derive(Serialize)]
+pub struct RadicalKanji {
+    pub literal: char,
+    pub stroke_count: u8,
+}
@@ -0,0 +1,71 @@
+use crate::{
+    api::app::deserialize_lang_option,
+    jotoba::{
+        language::{LangParam, Language},
+        words::Word,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+/// Request for words which were added or changed in the newest JMdict releases
+#[derive(Deserialize)]
+pub struct Request {
+    /// Amount of newest releases to list the changes of
+    #[serde(default = "default_releases")]
+    pub releases: usize,
+
+    /// Max amount of words to return
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+
+    /// Language of the returned glosses. English if not set
+    #[serde(default, deserialize_with = "deserialize_lang_option")]
+    pub lang: Option<Language>,
+}
+
+impl Request {
+    /// Returns language parameters for the request
+    #[inline]
+    pub fn lang_param(&self) -> LangParam {
+        LangParam::with_en_raw(self.lang.unwrap_or_default(), true)
+    }
+}
+
+/// Recently added or changed words, newest first
+#[derive(Serialize)]
+pub struct Response {
+    /// Dates (yyyymmdd) of the releases the words were taken from, newest first
+    pub releases: Vec<u32>,
+    pub words: Vec<RecentWord>,
+}
+
+impl Response {
+    #[inline]
+    pub fn new(releases: Vec<u32>, words: Vec<RecentWord>) -> Self {
+        Self { releases, words }
+    }
+}
+
+/// A word along with the date of the release it was added or last changed in
+#[derive(Serialize)]
+pub struct RecentWord {
+    pub revision: u32,
+    pub word: Word,
+}
+
+impl RecentWord {
+    #[inline]
+    pub fn new(revision: u32, word: Word) -> Self {
+        Self { revision, word }
+    }
+}
+
+#[inline]
+fn default_releases() -> usize {
+    1
+}
+
+#[inline]
+fn default_limit() -> usize {
+    100
+}
@@ -5,7 +5,7 @@ pub mod word;
 
 use serde::Deserialize;
 
-use crate::jotoba::language::Language;
+use crate::jotoba::{language::Language, sentences::script::SentenceScript};
 
 /// An Search API payload
 #[derive(Deserialize)]
@@ -18,4 +18,39 @@ pub struct SearchRequest {
 
     #[serde(default)]
     pub no_english: bool,
+
+    /// Script sentences should additionally be returned in
+    #[serde(default)]
+    pub sentence_script: SentenceScript,
+
+    /// Result page, starting at 1
+    #[serde(default)]
+    pub page: Option<usize>,
+
+    /// Amount of results per page
+    #[serde(default)]
+    pub page_size: Option<u32>,
+
+    /// Embed the radicals and components of all kanji used in a word into word results
+    #[serde(default)]
+    pub kanji_parts: bool,
+}
+
+/// Query parameter selecting the fields of a response which should be returned, eg.
+/// `?fields=words.reading,words.senses.glosses`
+#[derive(Deserialize)]
+pub struct FieldSelection {
+    pub fields: Option<String>,
+}
+
+impl FieldSelection {
+    /// Returns an iterator over all selected field paths
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.fields
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(|i| i.trim())
+            .filter(|i| !i.is_empty())
+    }
 }
@@ -5,6 +5,8 @@ use crate::jotoba::names::name_type::NameType;
 #[derive(Serialize, Deserialize)]
 pub struct Response {
     names: Vec<Name>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    transliterations: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -29,10 +31,20 @@ impl From<&crate::jotoba::names::Name> for Name {
     }
 }
 
+impl Response {
+    #[inline]
+    pub fn new(names: Vec<&crate::jotoba::names::Name>, transliterations: Vec<String>) -> Self {
+        let names: Vec<Name> = names.into_iter().map(Name::from).collect();
+        Self {
+            names,
+            transliterations,
+        }
+    }
+}
+
 impl From<Vec<&crate::jotoba::names::Name>> for Response {
     #[inline]
     fn from(name: Vec<&crate::jotoba::names::Name>) -> Self {
-        let names: Vec<Name> = name.into_iter().map(Name::from).collect();
-        Self { names }
+        Self::new(name, vec![])
     }
 }
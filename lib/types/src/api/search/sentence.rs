@@ -15,6 +15,9 @@ pub struct Sentence {
     pub language: Language,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub eng: Option<String>,
+    /// The sentence in the requested script (kana or romaji)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub converted: Option<String>,
 }
 
 impl From<Vec<Sentence>> for Response {
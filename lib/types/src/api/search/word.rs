@@ -9,6 +9,8 @@ use crate::{
     },
 };
 
+use itertools::Itertools;
+use jp_utils::JapaneseExt;
 use serde::{Deserialize, Serialize};
 
 /// The API response struct for a word search
@@ -22,13 +24,21 @@ impl Response {
     pub fn new(words: Vec<Word>, kanji: Vec<Kanji>) -> Self {
         Self { kanji, words }
     }
+
+    #[inline]
+    pub fn words_mut(&mut self) -> &mut Vec<Word> {
+        &mut self.words
+    }
 }
 
 /// Represents a single Word result with 1 (main) Japanese reading and n glosses
 #[derive(Serialize, Deserialize)]
 pub struct Word {
+    sequence: u32,
     reading: Reading,
     common: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    jlpt: Option<u8>,
     senses: Vec<Sense>,
     #[serde(skip_serializing_if = "Option::is_none")]
     alt_readings: Option<Vec<Reading>>,
@@ -36,6 +46,50 @@ pub struct Word {
     audio: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pitch: Option<Vec<PitchPart>>,
+    /// Radical and component breakdown of the kanji used in the word. Only set if requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kanji_parts: Option<Vec<KanjiParts>>,
+}
+
+impl Word {
+    /// Returns all distinct kanji literals used in the words main reading
+    pub fn kanji_literals(&self) -> Vec<char> {
+        self.reading
+            .kanji
+            .as_deref()
+            .unwrap_or_default()
+            .chars()
+            .filter(|i| i.is_kanji())
+            .unique()
+            .collect()
+    }
+
+    #[inline]
+    pub fn set_kanji_parts(&mut self, kanji_parts: Vec<KanjiParts>) {
+        self.kanji_parts = Some(kanji_parts);
+    }
+}
+
+/// Radical and components of a single kanji
+#[derive(Serialize, Deserialize)]
+pub struct KanjiParts {
+    literal: String,
+    radical: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    radical_meanings: Vec<String>,
+    parts: Vec<String>,
+}
+
+impl From<&crate::jotoba::kanji::Kanji> for KanjiParts {
+    #[inline]
+    fn from(kanji: &crate::jotoba::kanji::Kanji) -> Self {
+        Self {
+            literal: kanji.literal.to_string(),
+            radical: kanji.radical.literal.to_string(),
+            radical_meanings: kanji.radical.translations.clone().unwrap_or_default(),
+            parts: kanji.parts.iter().map(|i| i.to_string()).collect(),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -102,17 +156,35 @@ impl From<&crate::jotoba::words::Word> for Word {
 
         let pitch = word.get_first_pitch().map(|i| i.parts.clone());
 
+        let alt_readings: Vec<_> = word
+            .reading
+            .alternative
+            .iter()
+            .map(|alt| Reading {
+                kana: if alt.kanji {
+                    kana.clone()
+                } else {
+                    alt.reading.clone()
+                },
+                kanji: alt.kanji.then(|| alt.reading.clone()),
+                furigana: None,
+            })
+            .collect();
+
         Self {
+            sequence: word.sequence,
             common: word.is_common(),
+            jlpt: word.get_jlpt_lvl(),
             reading: Reading {
                 kanji,
                 kana,
                 furigana,
             },
             senses,
-            alt_readings: None,
+            alt_readings: (!alt_readings.is_empty()).then(|| alt_readings),
             audio: word.audio_file().as_ref().map(|i| format!("/audio/{}", i)),
             pitch,
+            kanji_parts: None,
         }
     }
 }
@@ -0,0 +1,632 @@
+//! Compact, versioned bundle of words, kanji and sentences for offline clients.
+//!
+//! # Layout
+//!
+//! All integers are little endian. A string is stored as its length in bytes (`u16`) followed by
+//! its UTF-8 bytes, a list as its item count (`u16`) followed by the items.
+//!
+//! The file starts with a header:
+//!
+//! | Field     | Type      | Description                                                   |
+//! |-----------|-----------|---------------------------------------------------------------|
+//! | magic     | `[u8; 4]` | [`MAGIC`]                                                     |
+//! | version   | `u32`     | [`VERSION`]                                                   |
+//! | languages | `u16`     | Languages of the glosses and translations. Bit `i` is set if  |
+//! |           |           | the language with the id `i` is included                      |
+//!
+//! The sections of words, kanji and sentences follow in this order. Each section is laid out as:
+//!
+//! | Field   | Type           | Description                                                |
+//! |---------|----------------|------------------------------------------------------------|
+//! | count   | `u32`          | Amount of entries                                          |
+//! | ids     | `[u32; count]` | Ascending IDs of the entries                               |
+//! | offsets | `[u32; count]` | Start of each entry relative to the start of `data`        |
+//! | length  | `u32`          | Length of `data` in bytes                                  |
+//! | data    | `[u8; length]` | The entries                                                |
+//!
+//! IDs are the sequence ids of words, the code points of kanji and the IDs of sentences.
+//! Entries are encoded as:
+//!
+//! - Word: flags (`u8`, bit 0: common), JLPT level (`u8`, 0 if none), kana (string), kanji
+//!   (string, empty if written in kana only), furigana (string, empty if none), senses (list).
+//!   A sense is: language id (`u8`), parts of speech (list of strings, JMdict entity names eg.
+//!   `v5k`), glosses (list of strings), example sentence id (`u32`, [`NO_SENTENCE`] if none)
+//! - Kanji: stroke count (`u8`), school grade (`u8`, 0 if none), JLPT level (`u8`, 0 if none),
+//!   frequency rank (`u16`, 0 if none), onyomi, kunyomi and english meanings (lists of strings)
+//! - Sentence: japanese text (string), furigana (string), translations (list). A translation is:
+//!   language id (`u8`), text (string)
+//!
+//! Language ids are the discriminants of [`Language`].
+
+use crate::jotoba::language::Language;
+use std::{
+    convert::TryFrom,
+    fmt::{self, Display},
+    io::{self, Write},
+};
+
+/// Marks the start of a bundle
+pub const MAGIC: &[u8; 4] = b"JTBB";
+
+/// Version of the layout
+pub const VERSION: u32 = 1;
+
+/// Example sentence id of senses without example sentence
+pub const NO_SENTENCE: u32 = u32::MAX;
+
+/// Error of reading or writing a bundle
+#[derive(Debug)]
+pub enum BundleError {
+    /// The data doesn't start with [`MAGIC`]
+    NoBundle,
+    /// The bundle has a different layout version
+    Version(u32),
+    /// The data ends in the middle of the bundle or isn't valid
+    Malformed,
+    /// A string or list is too long to be stored
+    TooLong,
+    Io(io::Error),
+}
+
+impl Display for BundleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BundleError::NoBundle => write!(f, "not a bundle"),
+            BundleError::Version(v) => write!(
+                f,
+                "bundle has version {v} but only version {VERSION} is supported"
+            ),
+            BundleError::Malformed => write!(f, "malformed bundle"),
+            BundleError::TooLong => write!(f, "string or list too long"),
+            BundleError::Io(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for BundleError {}
+
+impl From<io::Error> for BundleError {
+    #[inline]
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// A word of a bundle
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct BundleWord {
+    pub sequence: u32,
+    pub common: bool,
+    pub jlpt: Option<u8>,
+    pub kana: String,
+    pub kanji: Option<String>,
+    pub furigana: Option<String>,
+    pub senses: Vec<BundleSense>,
+}
+
+/// A sense of a bundled word
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BundleSense {
+    pub language: Language,
+    pub part_of_speech: Vec<String>,
+    pub glosses: Vec<String>,
+    pub example_sentence: Option<u32>,
+}
+
+/// A kanji of a bundle
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BundleKanji {
+    pub literal: char,
+    pub stroke_count: u8,
+    pub grade: Option<u8>,
+    pub jlpt: Option<u8>,
+    pub frequency: Option<u16>,
+    pub onyomi: Vec<String>,
+    pub kunyomi: Vec<String>,
+    pub meanings: Vec<String>,
+}
+
+/// A sentence of a bundle
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BundleSentence {
+    pub id: u32,
+    pub japanese: String,
+    pub furigana: String,
+    pub translations: Vec<(Language, String)>,
+}
+
+/// Reads entries of a bundle without decoding all of them
+pub struct BundleReader<'a> {
+    languages: u16,
+    words: Section<'a>,
+    kanji: Section<'a>,
+    sentences: Section<'a>,
+}
+
+impl<'a> BundleReader<'a> {
+    /// Reads the header and section tables of the bundle `data`
+    pub fn new(data: &'a [u8]) -> Result<Self, BundleError> {
+        let mut cursor = Cursor::new(data);
+        if cursor.bytes(MAGIC.len()).ok() != Some(MAGIC.as_slice()) {
+            return Err(BundleError::NoBundle);
+        }
+
+        let version = cursor.u32()?;
+        if version != VERSION {
+            return Err(BundleError::Version(version));
+        }
+
+        let languages = cursor.u16()?;
+        let words = Section::read(&mut cursor)?;
+        let kanji = Section::read(&mut cursor)?;
+        let sentences = Section::read(&mut cursor)?;
+
+        Ok(Self {
+            languages,
+            words,
+            kanji,
+            sentences,
+        })
+    }
+
+    /// Returns the languages of the glosses and translations in the bundle
+    pub fn languages(&self) -> Vec<Language> {
+        (0..16u16)
+            .filter(|i| self.languages & (1 << i) != 0)
+            .filter_map(|i| Language::try_from(i as i32).ok())
+            .collect()
+    }
+
+    /// Returns the amount of words, kanji and sentences in the bundle
+    #[inline]
+    pub fn counts(&self) -> (usize, usize, usize) {
+        (self.words.len(), self.kanji.len(), self.sentences.len())
+    }
+
+    /// Returns the word with the sequence id `seq`
+    pub fn word(&self, seq: u32) -> Result<Option<BundleWord>, BundleError> {
+        self.words.get(seq).map(|i| decode_word(seq, i)).transpose()
+    }
+
+    /// Returns an iterator over all words, ordered by their sequence id
+    pub fn words(&self) -> impl Iterator<Item = Result<BundleWord, BundleError>> + '_ {
+        self.words.iter().map(|(seq, data)| decode_word(seq, data))
+    }
+
+    /// Returns the kanji `literal`
+    pub fn kanji(&self, literal: char) -> Result<Option<BundleKanji>, BundleError> {
+        self.kanji
+            .get(literal as u32)
+            .map(|i| decode_kanji(literal as u32, i))
+            .transpose()
+    }
+
+    /// Returns an iterator over all kanji, ordered by their code point
+    pub fn kanji_iter(&self) -> impl Iterator<Item = Result<BundleKanji, BundleError>> + '_ {
+        self.kanji.iter().map(|(lit, data)| decode_kanji(lit, data))
+    }
+
+    /// Returns the sentence with the ID `id`
+    pub fn sentence(&self, id: u32) -> Result<Option<BundleSentence>, BundleError> {
+        self.sentences
+            .get(id)
+            .map(|i| decode_sentence(id, i))
+            .transpose()
+    }
+
+    /// Returns an iterator over all sentences, ordered by their ID
+    pub fn sentences(&self) -> impl Iterator<Item = Result<BundleSentence, BundleError>> + '_ {
+        self.sentences
+            .iter()
+            .map(|(id, data)| decode_sentence(id, data))
+    }
+}
+
+/// Collects entries and writes them as bundle
+#[derive(Default)]
+pub struct BundleWriter {
+    languages: u16,
+    words: Vec<(u32, Vec<u8>)>,
+    kanji: Vec<(u32, Vec<u8>)>,
+    sentences: Vec<(u32, Vec<u8>)>,
+}
+
+impl BundleWriter {
+    /// Creates a new writer for a bundle containing glosses and translations in `languages`
+    pub fn new(languages: &[Language]) -> Self {
+        let languages = languages
+            .iter()
+            .fold(0u16, |mask, lang| mask | 1 << language_id(*lang));
+        Self {
+            languages,
+            ..Default::default()
+        }
+    }
+
+    pub fn add_word(&mut self, word: &BundleWord) -> Result<(), BundleError> {
+        let mut out = vec![word.common as u8, word.jlpt.unwrap_or(0)];
+        put_str(&mut out, &word.kana)?;
+        put_str(&mut out, word.kanji.as_deref().unwrap_or_default())?;
+        put_str(&mut out, word.furigana.as_deref().unwrap_or_default())?;
+        put_len(&mut out, word.senses.len())?;
+        for sense in &word.senses {
+            out.push(language_id(sense.language));
+            put_list(&mut out, &sense.part_of_speech)?;
+            put_list(&mut out, &sense.glosses)?;
+            let example = sense.example_sentence.unwrap_or(NO_SENTENCE);
+            out.extend(example.to_le_bytes());
+        }
+        self.words.push((word.sequence, out));
+        Ok(())
+    }
+
+    pub fn add_kanji(&mut self, kanji: &BundleKanji) -> Result<(), BundleError> {
+        let mut out = vec![
+            kanji.stroke_count,
+            kanji.grade.unwrap_or(0),
+            kanji.jlpt.unwrap_or(0),
+        ];
+        out.extend(kanji.frequency.unwrap_or(0).to_le_bytes());
+        put_list(&mut out, &kanji.onyomi)?;
+        put_list(&mut out, &kanji.kunyomi)?;
+        put_list(&mut out, &kanji.meanings)?;
+        self.kanji.push((kanji.literal as u32, out));
+        Ok(())
+    }
+
+    pub fn add_sentence(&mut self, sentence: &BundleSentence) -> Result<(), BundleError> {
+        let mut out = vec![];
+        put_str(&mut out, &sentence.japanese)?;
+        put_str(&mut out, &sentence.furigana)?;
+        put_len(&mut out, sentence.translations.len())?;
+        for (language, text) in &sentence.translations {
+            out.push(language_id(*language));
+            put_str(&mut out, text)?;
+        }
+        self.sentences.push((sentence.id, out));
+        Ok(())
+    }
+
+    /// Writes the bundle into `out`
+    pub fn write<W: Write>(mut self, mut out: W) -> Result<(), BundleError> {
+        out.write_all(MAGIC)?;
+        out.write_all(&VERSION.to_le_bytes())?;
+        out.write_all(&self.languages.to_le_bytes())?;
+        for section in [&mut self.words, &mut self.kanji, &mut self.sentences] {
+            write_section(&mut out, section)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_section<W: Write>(out: &mut W, entries: &mut [(u32, Vec<u8>)]) -> Result<(), BundleError> {
+    entries.sort_by_key(|i| i.0);
+
+    let count = u32::try_from(entries.len()).map_err(|_| BundleError::TooLong)?;
+    out.write_all(&count.to_le_bytes())?;
+    for (id, _) in entries.iter() {
+        out.write_all(&id.to_le_bytes())?;
+    }
+
+    let mut offset: u32 = 0;
+    for (_, data) in entries.iter() {
+        out.write_all(&offset.to_le_bytes())?;
+        offset = u32::try_from(data.len())
+            .ok()
+            .and_then(|len| offset.checked_add(len))
+            .ok_or(BundleError::TooLong)?;
+    }
+
+    out.write_all(&offset.to_le_bytes())?;
+    for (_, data) in entries.iter() {
+        out.write_all(data)?;
+    }
+    Ok(())
+}
+
+#[inline]
+fn language_id(language: Language) -> u8 {
+    let id: i32 = language.into();
+    id as u8
+}
+
+fn put_len(out: &mut Vec<u8>, len: usize) -> Result<(), BundleError> {
+    let len = u16::try_from(len).map_err(|_| BundleError::TooLong)?;
+    out.extend(len.to_le_bytes());
+    Ok(())
+}
+
+fn put_str(out: &mut Vec<u8>, s: &str) -> Result<(), BundleError> {
+    put_len(out, s.len())?;
+    out.extend(s.as_bytes());
+    Ok(())
+}
+
+fn put_list(out: &mut Vec<u8>, list: &[String]) -> Result<(), BundleError> {
+    put_len(out, list.len())?;
+    for item in list {
+        put_str(out, item)?;
+    }
+    Ok(())
+}
+
+/// A section of a bundle
+struct Section<'a> {
+    ids: &'a [u8],
+    offsets: &'a [u8],
+    data: &'a [u8],
+}
+
+impl<'a> Section<'a> {
+    fn read(cursor: &mut Cursor<'a>) -> Result<Self, BundleError> {
+        let count = cursor.u32()? as usize;
+        let ids = cursor.bytes(count * 4)?;
+        let offsets = cursor.bytes(count * 4)?;
+        let len = cursor.u32()? as usize;
+        let data = cursor.bytes(len)?;
+        Ok(Self { ids, offsets, data })
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.ids.len() / 4
+    }
+
+    #[inline]
+    fn id(&self, pos: usize) -> u32 {
+        read_u32(&self.ids[pos * 4..])
+    }
+
+    /// Returns the data of the entry at `pos`
+    fn entry(&self, pos: usize) -> &'a [u8] {
+        let start = read_u32(&self.offsets[pos * 4..]) as usize;
+        let end = if pos + 1 < self.len() {
+            read_u32(&self.offsets[(pos + 1) * 4..]) as usize
+        } else {
+            self.data.len()
+        };
+        self.data.get(start..end).unwrap_or_default()
+    }
+
+    /// Returns the data of the entry with the ID `id`
+    fn get(&self, id: u32) -> Option<&'a [u8]> {
+        let (mut low, mut high) = (0, self.len());
+        while low < high {
+            let mid = (low + high) / 2;
+            match self.id(mid).cmp(&id) {
+                std::cmp::Ordering::Equal => return Some(self.entry(mid)),
+                std::cmp::Ordering::Less => low = mid + 1,
+                std::cmp::Ordering::Greater => high = mid,
+            }
+        }
+        None
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (u32, &'a [u8])> + '_ {
+        (0..self.len()).map(|pos| (self.id(pos), self.entry(pos)))
+    }
+}
+
+fn decode_word(sequence: u32, data: &[u8]) -> Result<BundleWord, BundleError> {
+    let mut cursor = Cursor::new(data);
+    let flags = cursor.u8()?;
+    let jlpt = Some(cursor.u8()?).filter(|i| *i > 0);
+    let kana = cursor.string()?;
+    let kanji = Some(cursor.string()?).filter(|i| !i.is_empty());
+    let furigana = Some(cursor.string()?).filter(|i| !i.is_empty());
+
+    let sense_count = cursor.u16()?;
+    let mut senses = Vec::with_capacity(sense_count as usize);
+    for _ in 0..sense_count {
+        let language = cursor.language()?;
+        let part_of_speech = cursor.list()?;
+        let glosses = cursor.list()?;
+        let example_sentence = Some(cursor.u32()?).filter(|i| *i != NO_SENTENCE);
+        senses.push(BundleSense {
+            language,
+            part_of_speech,
+            glosses,
+            example_sentence,
+        });
+    }
+
+    Ok(BundleWord {
+        sequence,
+        common: flags & 1 != 0,
+        jlpt,
+        kana,
+        kanji,
+        furigana,
+        senses,
+    })
+}
+
+fn decode_kanji(literal: u32, data: &[u8]) -> Result<BundleKanji, BundleError> {
+    let mut cursor = Cursor::new(data);
+    Ok(BundleKanji {
+        literal: char::from_u32(literal).ok_or(BundleError::Malformed)?,
+        stroke_count: cursor.u8()?,
+        grade: Some(cursor.u8()?).filter(|i| *i > 0),
+        jlpt: Some(cursor.u8()?).filter(|i| *i > 0),
+        frequency: Some(cursor.u16()?).filter(|i| *i > 0),
+        onyomi: cursor.list()?,
+        kunyomi: cursor.list()?,
+        meanings: cursor.list()?,
+    })
+}
+
+fn decode_sentence(id: u32, data: &[u8]) -> Result<BundleSentence, BundleError> {
+    let mut cursor = Cursor::new(data);
+    let japanese = cursor.string()?;
+    let furigana = cursor.string()?;
+
+    let count = cursor.u16()?;
+    let mut translations = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        translations.push((cursor.language()?, cursor.string()?));
+    }
+
+    Ok(BundleSentence {
+        id,
+        japanese,
+        furigana,
+        translations,
+    })
+}
+
+#[inline]
+fn read_u32(data: &[u8]) -> u32 {
+    u32::from_le_bytes(data[..4].try_into().unwrap())
+}
+
+/// Reads values from the start of a byte slice
+struct Cursor<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Cursor<'a> {
+    #[inline]
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], BundleError> {
+        if self.data.len() < len {
+            return Err(BundleError::Malformed);
+        }
+        let (out, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(out)
+    }
+
+    fn u8(&mut self) -> Result<u8, BundleError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, BundleError> {
+        Ok(u16::from_le_bytes(self.bytes(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32, BundleError> {
+        Ok(read_u32(self.bytes(4)?))
+    }
+
+    fn string(&mut self) -> Result<String, BundleError> {
+        let len = self.u16()? as usize;
+        let bytes = self.bytes(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| BundleError::Malformed)
+    }
+
+    fn list(&mut self) -> Result<Vec<String>, BundleError> {
+        let len = self.u16()?;
+        (0..len).map(|_| self.string()).collect()
+    }
+
+    fn language(&mut self) -> Result<Language, BundleError> {
+        Language::try_from(self.u8()? as i32).map_err(|_| BundleError::Malformed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn bundle() -> Vec<u8> {
+        let mut writer = BundleWriter::new(&[Language::English, Language::German]);
+        writer
+            .add_word(&BundleWord {
+                sequence: 1358280,
+                common: true,
+                jlpt: Some(5),
+                kana: "たべる".to_string(),
+                kanji: Some("食べる".to_string()),
+                furigana: Some("[食|た]べる".to_string()),
+                senses: vec![BundleSense {
+                    language: Language::German,
+                    part_of_speech: vec!["v1".to_string(), "vt".to_string()],
+                    glosses: vec!["essen".to_string()],
+                    example_sentence: Some(74093),
+                }],
+            })
+            .unwrap();
+        writer
+            .add_word(&BundleWord {
+                sequence: 1000000,
+                kana: "ヽ".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        writer
+            .add_kanji(&BundleKanji {
+                literal: '食',
+                stroke_count: 9,
+                grade: Some(2),
+                jlpt: None,
+                frequency: Some(328),
+                onyomi: vec!["ショク".to_string()],
+                kunyomi: vec!["た.べる".to_string()],
+                meanings: vec!["eat".to_string()],
+            })
+            .unwrap();
+        writer
+            .add_sentence(&BundleSentence {
+                id: 74093,
+                japanese: "食べる".to_string(),
+                furigana: "[食|た]べる".to_string(),
+                translations: vec![(Language::German, "essen".to_string())],
+            })
+            .unwrap();
+
+        let mut out = vec![];
+        writer.write(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let data = bundle();
+        let reader = BundleReader::new(&data).unwrap();
+
+        assert_eq!(
+            reader.languages(),
+            vec![Language::English, Language::German]
+        );
+        assert_eq!(reader.counts(), (2, 1, 1));
+
+        let word = reader.word(1358280).unwrap().unwrap();
+        assert_eq!(word.kanji.as_deref(), Some("食べる"));
+        assert_eq!(word.senses[0].example_sentence, Some(74093));
+        assert_eq!(reader.word(1000000).unwrap().unwrap().kanji, None);
+        assert!(reader.word(1).unwrap().is_none());
+
+        let seqs: Vec<_> = reader.words().map(|i| i.unwrap().sequence).collect();
+        assert_eq!(seqs, vec![1000000, 1358280]);
+
+        let kanji = reader.kanji('食').unwrap().unwrap();
+        assert_eq!(kanji.frequency, Some(328));
+        assert_eq!(kanji.jlpt, None);
+
+        let sentence = reader.sentence(74093).unwrap().unwrap();
+        assert_eq!(sentence.translations[0].1, "essen");
+    }
+
+    #[test]
+    fn test_invalid() {
+        let data = bundle();
+        assert!(matches!(
+            BundleReader::new(b"JTBV"),
+            Err(BundleError::NoBundle)
+        ));
+        assert!(matches!(
+            BundleReader::new(&data[..data.len() - 1]),
+            Err(BundleError::Malformed)
+        ));
+
+        let mut newer = data.clone();
+        newer[4] = 2;
+        assert!(matches!(
+            BundleReader::new(&newer),
+            Err(BundleError::Version(2))
+        ));
+    }
+}
@@ -8,6 +8,8 @@ pub mod pagination;
 /// Contains search related structures and enums
 pub mod search;
 pub mod sentences;
+/// Textbooks which can be searched by lesson
+pub mod textbook;
 /// Contains all structures and enums for Jotoba words
 pub mod words;
 
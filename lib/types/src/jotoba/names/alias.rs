@@ -0,0 +1,55 @@
+#[cfg(feature = "jotoba_intern")]
+use localization::traits::Translatable;
+
+use serde::{Deserialize, Serialize};
+
+/// Another name a place or era is (or was) known by. Eg. 東京 was formerly known as 江戸
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Alias {
+    pub kind: AliasKind,
+    pub name: String,
+}
+
+impl Alias {
+    #[inline]
+    pub fn new(kind: AliasKind, name: String) -> Self {
+        Self { kind, name }
+    }
+}
+
+/// How an alias relates to the name it belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AliasKind {
+    /// The alias is a former name
+    Formerly,
+    /// The alias is the name used later on
+    LaterKnownAs,
+    /// The alias is used at the same time
+    AlsoKnownAs,
+}
+
+impl AliasKind {
+    /// Returns the kind of the alias in the opposite direction. If `a` was formerly known as `b`,
+    /// `b` was later known as `a`
+    #[inline]
+    pub fn inverse(&self) -> AliasKind {
+        match self {
+            AliasKind::Formerly => AliasKind::LaterKnownAs,
+            AliasKind::LaterKnownAs => AliasKind::Formerly,
+            AliasKind::AlsoKnownAs => AliasKind::AlsoKnownAs,
+        }
+    }
+}
+
+#[cfg(feature = "jotoba_intern")]
+impl Translatable for AliasKind {
+    #[inline]
+    fn get_id(&self) -> &'static str {
+        match self {
+            AliasKind::Formerly => "Formerly",
+            AliasKind::LaterKnownAs => "Later known as",
+            AliasKind::AlsoKnownAs => "Also known as",
+        }
+    }
+}
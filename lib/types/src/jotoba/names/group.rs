@@ -0,0 +1,64 @@
+#[cfg(feature = "jotoba_intern")]
+use localization::traits::Translatable;
+
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumString;
+
+use super::name_type::NameType;
+
+/// Sections name results are grouped into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumString)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum NameGroup {
+    People,
+    Places,
+    Organizations,
+    Other,
+}
+
+impl NameGroup {
+    /// Returns all name groups in the order they're displayed
+    #[inline]
+    pub fn all() -> [NameGroup; 4] {
+        [
+            NameGroup::People,
+            NameGroup::Places,
+            NameGroup::Organizations,
+            NameGroup::Other,
+        ]
+    }
+}
+
+impl NameType {
+    /// Returns the group names of this type belong to
+    pub fn group(&self) -> NameGroup {
+        match self {
+            NameType::Female
+            | NameType::Male
+            | NameType::Given
+            | NameType::Person
+            | NameType::Surname
+            | NameType::Character
+            | NameType::Deity => NameGroup::People,
+            NameType::Place | NameType::RailwayStation => NameGroup::Places,
+            NameType::Company | NameType::Organization | NameType::Group | NameType::Service => {
+                NameGroup::Organizations
+            }
+            _ => NameGroup::Other,
+        }
+    }
+}
+
+#[cfg(feature = "jotoba_intern")]
+impl Translatable for NameGroup {
+    #[inline]
+    fn get_id(&self) -> &'static str {
+        match self {
+            NameGroup::People => "People",
+            NameGroup::Places => "Places",
+            NameGroup::Organizations => "Organizations",
+            NameGroup::Other => "Other",
+        }
+    }
+}
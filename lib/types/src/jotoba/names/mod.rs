@@ -1,5 +1,8 @@
+pub mod alias;
+pub mod group;
 pub mod name_type;
 
+use group::NameGroup;
 use name_type::NameType;
 use serde::{Deserialize, Serialize};
 use std::hash::{Hash, Hasher};
@@ -38,6 +41,22 @@ impl Name {
             .unwrap_or(false)
     }
 
+    /// Returns the group the name belongs to. Names with a non-gender type are grouped by the
+    /// first of them
+    pub fn group(&self) -> NameGroup {
+        let types = match self.name_type {
+            Some(ref types) => types,
+            None => return NameGroup::Other,
+        };
+
+        types
+            .iter()
+            .find(|i| !i.is_gender())
+            .or_else(|| types.first())
+            .map(|i| i.group())
+            .unwrap_or(NameGroup::Other)
+    }
+
     #[inline]
     pub fn get_reading(&self) -> &str {
         self.kanji.as_ref().unwrap_or(&self.kana)
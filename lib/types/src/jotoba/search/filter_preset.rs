@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+/// Max amount of presets a user can define
+pub const MAX_PRESETS: usize = 20;
+
+/// A named set of tags defined by the user, eg. `my-level` = `#n3 #common`. Using the name as
+/// tag within a query (`#my-level`) expands it to its tags
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FilterPreset {
+    pub name: String,
+    pub tags: String,
+}
+
+impl FilterPreset {
+    /// Creates a new preset. Returns `None` if the name can't be used as a tag
+    pub fn new(name: &str, tags: &str) -> Option<Self> {
+        let name = name.trim().trim_start_matches('#').to_lowercase();
+        if !is_valid_name(&name) {
+            return None;
+        }
+
+        let tags = tags.trim().to_string();
+        Some(Self { name, tags })
+    }
+
+    /// Parses a list of presets in the format `name:#tag1 #tag2,name2:#tag3` as stored in the
+    /// settings cookie. Invalid entries are ignored
+    pub fn parse_list(s: &str) -> Vec<FilterPreset> {
+        s.split(',')
+            .filter_map(|entry| {
+                let (name, tags) = entry.split_once(':')?;
+                FilterPreset::new(name, tags)
+            })
+            .take(MAX_PRESETS)
+            .collect()
+    }
+}
+
+/// Returns `true` if `name` can be used as tag
+#[inline]
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
@@ -59,6 +59,13 @@ impl SearchHelp {
             .into_iter()
     }
 
+    /// Returns all (QueryType, Guess) pairs that have a value, ordered like the targets in `order`
+    pub fn items_ordered(&self, order: &[SearchTarget]) -> Vec<(SearchTarget, Guess)> {
+        let mut items: Vec<_> = self.iter_items().collect();
+        items.sort_by_key(|i| order.iter().position(|target| *target == i.0));
+        items
+    }
+
     pub fn iter_langs(&self) -> impl Iterator<Item = (Language, &'static str)> + '_ {
         self.other_langs
             .iter()
@@ -1,5 +1,7 @@
+pub mod filter_preset;
 pub mod guess;
 pub mod help;
 pub mod query_type;
+pub mod unknown_tag;
 
 pub use query_type::SearchTarget;
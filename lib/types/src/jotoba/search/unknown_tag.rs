@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// A tag within a search query which couldn't be recognized and has been ignored
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct UnknownTag {
+    /// The tag as written in the query, including the leading '#'
+    pub tag: String,
+    /// The most similar known tag, if there is one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+impl UnknownTag {
+    #[inline]
+    pub fn new(tag: String, suggestion: Option<String>) -> Self {
+        Self { tag, suggestion }
+    }
+}
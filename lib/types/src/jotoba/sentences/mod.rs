@@ -1,3 +1,5 @@
+pub mod script;
+pub mod stats;
 pub mod tag;
 pub mod translation;
 
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumString;
+
+/// Script example sentences are displayed in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumString)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum SentenceScript {
+    /// Kanji and kana as written, with optional furigana
+    Native,
+    /// Kana only, using the furigana of kanji as their reading
+    Kana,
+    /// Romaji generated from the kana reading
+    Romaji,
+}
+
+impl SentenceScript {
+    /// Returns `true` if sentences are displayed as written
+    #[inline]
+    pub fn is_native(&self) -> bool {
+        *self == Self::Native
+    }
+}
+
+impl Default for SentenceScript {
+    #[inline]
+    fn default() -> Self {
+        Self::Native
+    }
+}
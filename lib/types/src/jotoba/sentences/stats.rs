@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// Grade assigned to kanji which aren't taught in school (no grade in KANJIDIC)
+pub const RARE_KANJI_GRADE: u8 = 11;
+
+/// Precomputed statistics about the scripts used in a sentence
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScriptStats {
+    /// Percentage (0-100) of japanese characters written in katakana
+    pub katakana_percent: u8,
+    /// Highest school grade of all kanji in the sentence or 0 if there are none. Kanji without
+    /// a grade count as [`RARE_KANJI_GRADE`]
+    pub max_kanji_grade: u8,
+}
+
+impl ScriptStats {
+    #[inline]
+    pub fn new(katakana_percent: u8, max_kanji_grade: u8) -> Self {
+        Self {
+            katakana_percent,
+            max_kanji_grade,
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A textbook whose lessons can be searched for using tags like `#mnn12`
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Textbook {
+    /// Prefix of the textbooks tags, eg. "mnn" for `#mnn12`
+    #[serde(default)]
+    pub tag: String,
+    /// Full name of the textbook
+    pub name: String,
+    /// Sequence ids of the words taught in each lesson
+    pub lessons: BTreeMap<u8, Vec<u32>>,
+}
+
+impl Textbook {
+    /// Returns the sequence ids of all words taught in `lesson`
+    #[inline]
+    pub fn lesson(&self, lesson: u8) -> &[u32] {
+        self.lessons
+            .get(&lesson)
+            .map(|i| i.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns an iterator over all lessons of the textbook
+    #[inline]
+    pub fn lesson_iter(&self) -> impl Iterator<Item = u8> + '_ {
+        self.lessons.keys().copied()
+    }
+}
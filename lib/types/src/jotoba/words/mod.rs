@@ -10,12 +10,14 @@ pub mod part_of_speech;
 pub mod pitch;
 pub mod priority;
 pub mod reading;
+pub mod reference;
 pub mod sense;
 
 pub use dict::Dict;
 
 use super::language::{param::AsLangParam, Language};
 use bitflags::BitFlag;
+use dialect::Dialect;
 use itertools::Itertools;
 use jp_utils::furigana::{self, reading_part_ref::ReadingPartRef};
 use misc::Misc;
@@ -169,6 +171,12 @@ impl Word {
             .any(|i| i == *misc)
     }
 
+    /// Returns `true` if at least one sense of the word is used in `dialect`
+    #[inline]
+    pub fn has_dialect(&self, dialect: Dialect) -> bool {
+        self.senses.iter().any(|i| i.dialect == Some(dialect))
+    }
+
     /// Returns `true` if word has at least one of the provided part of speech
     pub fn has_pos(&self, pos_filter: &[PosSimple]) -> bool {
         for sense in self.senses.iter().map(|i| i.get_pos_simple()) {
@@ -304,14 +312,26 @@ impl Word {
     /// Get the audio path of a word
     #[inline]
     pub fn audio_file(&self) -> Option<String> {
+        self.audio_file_in("html/audio")
+    }
+
+    /// Get the audio path of a word, relative to the audio folder `dir`. Returns `None` if there
+    /// is no audio file for the word within `dir`
+    pub fn audio_file_in(&self, dir: &str) -> Option<String> {
         self.reading.kanji.as_ref().and_then(|kanji| {
             let file = format!("mp3/{}【{}】.mp3", kanji.reading, self.reading.kana.reading);
-            std::path::Path::new(&format!("html/audio/{}", file))
-                .exists()
-                .then(|| file)
+            std::path::Path::new(dir).join(&file).exists().then(|| file)
         })
     }
 
+    /// Returns the url the audio of the word is served at. Words without a pre-recorded audio file
+    /// only have audio if speech synthesis is enabled
+    #[inline]
+    pub fn audio_url(&self, tts_enabled: bool) -> Option<String> {
+        (tts_enabled || self.audio_file().is_some())
+            .then(|| format!("/audio/word/{}", self.sequence))
+    }
+
     /// Get alternative readings in a beautified, print-ready format
     #[inline]
     pub fn alt_readings_beautified(&self) -> String {
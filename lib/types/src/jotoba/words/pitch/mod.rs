@@ -110,6 +110,21 @@ impl PitchPart {
     }
 }
 
+/// Returns whether each mora of `kana` is pronounced high for the accent `drop`. The returned vec
+/// contains one more item than `kana` has morae, representing a particle following the word
+pub fn mora_pattern(kana: &str, drop: u8) -> Vec<bool> {
+    let mora_count = split_kana(kana).count();
+    let drop = drop as usize;
+
+    (1..=mora_count + 1)
+        .map(|pos| match drop {
+            0 => pos > 1,
+            1 => pos == 1,
+            _ => pos > 1 && pos <= drop,
+        })
+        .collect()
+}
+
 /// Returns an iterator over all kana characters. The reason for Item to be &str is that 'きゅう'
 /// gets split up into ["きゅ", "う"] which can't be represented with only one char
 pub fn split_kana(inp: &str) -> impl Iterator<Item = &str> {
@@ -153,6 +168,14 @@ mod test {
         assert_eq!(out, vec!["こ", "れ", "が", "す", "き"]);
     }
 
+    #[test]
+    fn test_mora_pattern() {
+        assert_eq!(mora_pattern("さくら", 0), vec![false, true, true, true]);
+        assert_eq!(mora_pattern("いのち", 1), vec![true, false, false, false]);
+        assert_eq!(mora_pattern("こころ", 2), vec![false, true, false, false]);
+        assert_eq!(mora_pattern("はな", 2), vec![false, true, false]);
+    }
+
     #[test]
     fn test_split_kana2() {
         let inp = "";
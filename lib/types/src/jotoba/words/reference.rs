@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+/// Kind of a reference between two words
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ReferenceKind {
+    /// A related word (JMdict `xref`)
+    SeeAlso,
+    /// A word with the opposite meaning (JMdict `ant`)
+    Antonym,
+}
+
+/// A cross reference or antonym of a sense, resolved to the referenced word
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Reference {
+    pub kind: ReferenceKind,
+    /// ID of the referencing sense
+    pub sense: u8,
+    /// Sequence id of the referenced word
+    pub target: u32,
+    /// Number of the referenced sense within the JMdict entry, starting at 1
+    pub target_sense: Option<u8>,
+}
+
+impl Reference {
+    /// Splits a JMdict reference, eg. `開ける・あける・2`, into the written form, the reading and
+    /// the number of the referenced sense
+    pub fn parse_raw(raw: &str) -> Option<(&str, Option<&str>, Option<u8>)> {
+        let mut parts = raw.split('・').map(|i| i.trim());
+        let written = parts.next().filter(|i| !i.is_empty())?;
+
+        let mut reading = None;
+        let mut sense = None;
+        for part in parts {
+            match part.parse::<u8>() {
+                Ok(nr) => sense = Some(nr),
+                Err(_) if reading.is_none() => reading = Some(part),
+                Err(_) => (),
+            }
+        }
+
+        Some((written, reading, sense))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_raw() {
+        assert_eq!(Reference::parse_raw("開ける"), Some(("開ける", None, None)));
+        assert_eq!(
+            Reference::parse_raw("開ける・あける"),
+            Some(("開ける", Some("あける"), None))
+        );
+        assert_eq!(
+            Reference::parse_raw("開ける・あける・2"),
+            Some(("開ける", Some("あける"), Some(2)))
+        );
+        assert_eq!(
+            Reference::parse_raw("あける・1"),
+            Some(("あける", None, Some(1)))
+        );
+        assert_eq!(Reference::parse_raw(""), None);
+    }
+}
@@ -155,6 +155,9 @@ impl Sense {
             let lang = gairaigo
                 .language
                 .pgettext(dict, "foreign_lang", Some(language));
+            if gairaigo.original.is_empty() {
+                return dict.gettext_fmt("From {}", &[lang], Some(language));
+            }
             dict.gettext_fmt("From {}: {}", &[lang, &gairaigo.original], Some(language))
         })
     }
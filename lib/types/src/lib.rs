@@ -5,6 +5,9 @@ pub mod raw;
 /// Contains all information holding structures for jotoba resources
 pub mod jotoba;
 
+/// Compact bundle of words, kanji and sentences for offline clients
+pub mod bundle;
+
 /// Contains all structures and informations required for the API
 #[cfg(feature = "api")]
 pub mod api;
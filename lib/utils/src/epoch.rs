@@ -0,0 +1,209 @@
+//! Deferred freeing of data which has been handed out with a `'static` lifetime, like replaced
+//! resources or evicted resource segments. Readers pin the current epoch while they use such data
+//! (eg. for the duration of a request) and retired data only gets freed once all readers which
+//! were pinned at the time it got retired are unpinned.
+//!
+//! Retired data is only ever freed when a pin gets dropped. Tools which never pin anything
+//! therefore never free retired data, while everything reading shared data next to pinned readers
+//! (eg. in the webserver) has to be pinned itself.
+//!
+//! Values derived from shared data can be kept in a [`Cached`], which gets rebuilt on its next
+//! access once the data it was built from has been replaced and [`invalidate`] got called.
+
+use arc_swap::ArcSwapOption;
+use std::{
+    collections::BTreeMap,
+    mem,
+    ops::Deref,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+struct State {
+    /// Current epoch, increased by every retirement
+    epoch: u64,
+    /// Amount of active pins by the epoch they were created in
+    pins: BTreeMap<u64, usize>,
+    /// Retired data with the epoch it got retired in
+    retired: Vec<(u64, Box<dyn Send>)>,
+}
+
+impl State {
+    /// Removes all retired data which can't be used by any pinned reader anymore
+    fn collect(&mut self) -> Vec<(u64, Box<dyn Send>)> {
+        let oldest = self.pins.keys().next().copied().unwrap_or(self.epoch);
+        let (free, keep) = mem::take(&mut self.retired)
+            .into_iter()
+            .partition(|i| i.0 < oldest);
+        self.retired = keep;
+        free
+    }
+}
+
+static STATE: Mutex<State> = Mutex::new(State {
+    epoch: 0,
+    pins: BTreeMap::new(),
+    retired: Vec::new(),
+});
+
+/// Keeps all data retired while it is alive from being freed
+#[must_use = "data is only protected while the guard is alive"]
+pub struct Guard {
+    epoch: u64,
+}
+
+/// Pins the current epoch. Data retired while the returned guard is alive won't be freed until it
+/// gets dropped
+pub fn pin() -> Guard {
+    let mut state = STATE.lock().unwrap();
+    let epoch = state.epoch;
+    *state.pins.entry(epoch).or_default() += 1;
+    Guard { epoch }
+}
+
+/// Frees `data` once all currently pinned readers are unpinned
+pub fn retire<T: Send + 'static>(data: T) {
+    let mut state = STATE.lock().unwrap();
+    let epoch = state.epoch;
+    state.retired.push((epoch, Box::new(data)));
+    state.epoch += 1;
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        let mut state = STATE.lock().unwrap();
+        if let Some(count) = state.pins.get_mut(&self.epoch) {
+            *count -= 1;
+            if *count == 0 {
+                state.pins.remove(&self.epoch);
+            }
+        }
+        let free = state.collect();
+        drop(state);
+
+        // Free outside of the lock, dropping large data may take a while
+        drop(free);
+    }
+}
+
+/// Generation of the shared data. Gets increased whenever the shared data got replaced
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Marks all [`Cached`] values as outdated. Has to be called after replacing the data they are
+/// built from
+pub fn invalidate() {
+    GENERATION.fetch_add(1, Ordering::AcqRel);
+}
+
+/// Lazily built value which gets rebuilt on its first access after [`invalidate`] got called.
+/// Outdated values are retired, so references to them stay valid for pinned readers
+pub struct Cached<T> {
+    /// The value along with the generation it was built in
+    value: ArcSwapOption<(u64, T)>,
+    /// Held while building, so each value only gets built once per generation
+    build: Mutex<()>,
+    init: fn() -> T,
+}
+
+impl<T: Send + Sync + 'static> Cached<T> {
+    pub const fn new(init: fn() -> T) -> Self {
+        Self {
+            value: ArcSwapOption::const_empty(),
+            build: Mutex::new(()),
+            init,
+        }
+    }
+
+    /// Returns the value, building it if it hasn't been built yet or is outdated. This is an
+    /// associated function like `Lazy::force`, so it doesn't shadow methods of
+    /// the value
+    pub fn force(this: &Self) -> &T {
+        let generation = GENERATION.load(Ordering::Acquire);
+        if let Some(value) = Self::current(this, generation) {
+            return value;
+        }
+
+        let _lock = this.build.lock().unwrap();
+        if let Some(value) = Self::current(this, generation) {
+            return value;
+        }
+
+        let value = Arc::new((generation, (this.init)()));
+        let ptr = Arc::as_ptr(&value);
+        if let Some(old) = this.value.swap(Some(value)) {
+            retire(old);
+        }
+
+        // Safety: values only get freed after being retired and all readers pinned at that time
+        // being unpinned
+        unsafe { &(*ptr).1 }
+    }
+
+    /// Returns the value if it has been built in `generation`
+    fn current(this: &Self, generation: u64) -> Option<&T> {
+        let value = this.value.load();
+        let value = value.as_ref().filter(|i| i.0 == generation)?;
+        let ptr = Arc::as_ptr(value);
+
+        // Safety: see `force`
+        Some(unsafe { &(*ptr).1 })
+    }
+}
+
+impl<T: Send + Sync + 'static> Deref for Cached<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        Self::force(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    struct Flag(Arc<AtomicBool>);
+
+    impl Drop for Flag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_retire() {
+        let outer = pin();
+
+        let freed = Arc::new(AtomicBool::new(false));
+        retire(Flag(freed.clone()));
+
+        // Pinned after the retirement
+        drop(pin());
+        assert!(!freed.load(Ordering::SeqCst));
+
+        drop(outer);
+        assert!(freed.load(Ordering::SeqCst));
+    }
+
+    static BUILDS: AtomicU64 = AtomicU64::new(0);
+    static CACHED: Cached<u64> = Cached::new(|| BUILDS.fetch_add(1, Ordering::SeqCst));
+
+    #[test]
+    fn test_cached() {
+        let _pin = pin();
+
+        let first = *CACHED;
+        assert_eq!(*CACHED, first);
+
+        invalidate();
+        assert_eq!(*CACHED, first + 1);
+        assert_eq!(*CACHED, first + 1);
+    }
+}
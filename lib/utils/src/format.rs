@@ -0,0 +1,109 @@
+//! Header of binary data files, storing the version of the format the data was written in. This
+//! allows refusing (or migrating) files which were written by a different version instead of
+//! deserializing garbage
+
+use std::{
+    error::Error,
+    fmt::Display,
+    io::{self, BufRead, Write},
+};
+
+/// Marks the start of a header
+pub const MAGIC: &[u8; 4] = b"JTBV";
+
+/// Length of a header in bytes
+pub const HEADER_LEN: usize = MAGIC.len() + 4;
+
+/// Writes a header for the format `version` into `out`
+pub fn write_header<W: Write>(mut out: W, version: u32) -> io::Result<()> {
+    out.write_all(MAGIC)?;
+    out.write_all(&version.to_le_bytes())
+}
+
+/// Returns the format version of `data` and the data following the header. Returns `None` if
+/// `data` doesn't start with a header
+pub fn split_header(data: &[u8]) -> Option<(u32, &[u8])> {
+    let rest = data.strip_prefix(MAGIC.as_slice())?;
+    let version = u32::from_le_bytes(rest.get(..4)?.try_into().ok()?);
+    Some((version, &rest[4..]))
+}
+
+/// Reads the header at the start of `reader` and returns its format version. Returns `None`
+/// without consuming anything if `reader` doesn't start with a header
+pub fn read_header<R: BufRead>(reader: &mut R) -> io::Result<Option<u32>> {
+    let version = split_header(reader.fill_buf()?).map(|i| i.0);
+    if version.is_some() {
+        reader.consume(HEADER_LEN);
+    }
+    Ok(version)
+}
+
+/// Error for files written in a format version which can't be read
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionError {
+    /// Name of the file
+    pub name: String,
+    /// Version of the file. `None` if it doesn't have a header
+    pub found: Option<u32>,
+    /// Version the file is expected to have
+    pub supported: u32,
+}
+
+impl VersionError {
+    pub fn new<S: ToString>(name: S, found: Option<u32>, supported: u32) -> Self {
+        Self {
+            name: name.to_string(),
+            found,
+            supported,
+        }
+    }
+}
+
+impl Display for VersionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.found {
+            None => write!(
+                f,
+                "{} has no format version. It was written by an older version of Jotoba and has to be regenerated",
+                self.name
+            ),
+            Some(found) if found > self.supported => write!(
+                f,
+                "{} has format version {found} but only versions up to {} are supported. Update Jotoba to load it",
+                self.name, self.supported
+            ),
+            Some(found) => write!(
+                f,
+                "{} has format version {found} which can't be migrated to version {}. It has to be regenerated",
+                self.name, self.supported
+            ),
+        }
+    }
+}
+
+impl Error for VersionError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_header_roundtrip() {
+        let mut data = vec![];
+        write_header(&mut data, 3).unwrap();
+        data.extend_from_slice(b"payload");
+
+        assert_eq!(split_header(&data), Some((3, b"payload".as_slice())));
+
+        let mut reader = data.as_slice();
+        assert_eq!(read_header(&mut reader).unwrap(), Some(3));
+        assert_eq!(reader, b"payload");
+    }
+
+    #[test]
+    fn test_no_header() {
+        let mut reader = b"payload".as_slice();
+        assert_eq!(read_header(&mut reader).unwrap(), None);
+        assert_eq!(reader, b"payload");
+    }
+}
@@ -1,5 +1,8 @@
 pub mod binary_search;
+pub mod epoch;
+pub mod format;
 pub mod korean;
+pub mod process;
 
 use itertools::Itertools;
 use rand::{distributions::Alphanumeric, thread_rng, Rng};
@@ -269,3 +272,21 @@ pub fn format_romaji_nn(inp: &str) -> String {
         .replace("nnnn", "nnn")
         .replace("nnnnn", "nnnn")
 }
+
+/// Returns the levenshtein distance between `a` and `b`, counted in characters
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = (ca != *cb) as usize;
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
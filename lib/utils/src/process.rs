@@ -0,0 +1,78 @@
+//! Running external programs with a time limit.
+
+use std::{
+    io::{self, Read},
+    process::{Command, Output, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Interval in which a running process gets checked for having exited
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Runs `command` and returns its exit status and everything it wrote to stdout. The process gets
+/// killed if it doesn't exit within `timeout`, in which case `None` is returned. Stderr is
+/// inherited, so messages of the program end up in the log
+pub fn output_with_timeout(mut command: Command, timeout: Duration) -> io::Result<Option<Output>> {
+    let mut child = command.stdout(Stdio::piped()).spawn()?;
+
+    // Read stdout in the background so the process can't block on a full pipe
+    let mut stdout = child.stdout.take();
+    let reader = thread::spawn(move || {
+        let mut out = vec![];
+        if let Some(ref mut stdout) = stdout {
+            stdout.read_to_end(&mut out).ok();
+        }
+        out
+    });
+
+    let start = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+
+        if start.elapsed() >= timeout {
+            child.kill().ok();
+            child.wait().ok();
+            return Ok(None);
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    let stdout = reader.join().unwrap_or_default();
+    Ok(Some(Output {
+        status,
+        stdout,
+        stderr: vec![],
+    }))
+}
+
+#[cfg(all(test, unix))]
+mod test {
+    use super::*;
+
+    fn sh(script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command.args(["-c", script]);
+        command
+    }
+
+    #[test]
+    fn test_output() {
+        let output = output_with_timeout(sh("echo hi"), Duration::from_secs(10))
+            .unwrap()
+            .unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"hi\n");
+    }
+
+    #[test]
+    fn test_timeout() {
+        let start = Instant::now();
+        let output = output_with_timeout(sh("sleep 10"), Duration::from_millis(50)).unwrap();
+        assert!(output.is_none());
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,90 @@
+//! Ranking regression tests. Runs real queries against the resources in `STORAGE_DATA` and the
+//! indexes in `INDEX_DATA` and checks the expected result to be ranked first. Each query should
+//! have an unambiguous best result, so changes to the ranking functions which break them are
+//! most likely regressions
+
+use search::{
+    executor::SearchExecutor,
+    query::{parser::QueryParser, Query, UserSettings},
+};
+use std::sync::Once;
+use test_case::test_case;
+use types::jotoba::{language::Language, search::SearchTarget};
+
+static LOAD: Once = Once::new();
+
+fn load() {
+    LOAD.call_once(|| {
+        let storage = std::env::var("STORAGE_DATA").expect("missing STORAGE_DATA");
+        let indexes = std::env::var("INDEX_DATA").expect("missing INDEX_DATA");
+        resources::load(storage).expect("Failed to load test resources");
+        indexes::storage::load(indexes).expect("Failed to load test indexes");
+    });
+}
+
+fn query(inp: &str, target: SearchTarget, lang: Language) -> Query {
+    load();
+    let settings = UserSettings {
+        user_lang: lang,
+        ..UserSettings::default()
+    };
+    QueryParser::new(inp.to_string(), target, settings)
+        .parse()
+        .expect("Invalid query")
+}
+
+/// Returns the readings of the first `n` words found for `inp`
+fn top_words(inp: &str, lang: Language, n: usize) -> Vec<String> {
+    let query = query(inp, SearchTarget::Words, lang);
+    SearchExecutor::new(search::word::Search::new(&query))
+        .run()
+        .items
+        .iter()
+        .take(n)
+        .map(|i| i.get_reading_str().to_string())
+        .collect()
+}
+
+#[test_case("water", Language::English, "水"; "English noun")]
+#[test_case("dog", Language::English, "犬"; "English noun 2")]
+#[test_case("to eat", Language::English, "食べる"; "English verb")]
+#[test_case("eat", Language::English, "食べる"; "English verb without to")]
+#[test_case("Wasser", Language::German, "水"; "German noun")]
+#[test_case("essen", Language::German, "食べる"; "German verb")]
+#[test_case("manger", Language::French, "食べる"; "French verb")]
+#[test_case("agua", Language::Spanish, "水"; "Spanish noun")]
+fn test_foreign_top(inp: &str, lang: Language, expected: &str) {
+    let top = top_words(inp, lang, 1);
+    assert_eq!(top.first().map(|i| i.as_str()), Some(expected), "{inp}");
+}
+
+#[test_case("みず", "水"; "Kana")]
+#[test_case("たべる", "食べる"; "Kana verb")]
+#[test_case("taberu", "食べる"; "Romaji")]
+#[test_case("食べた", "食べる"; "Inflected")]
+#[test_case("日本", "日本"; "Kanji")]
+#[test_case("コーヒー", "コーヒー"; "Katakana")]
+fn test_native_top(inp: &str, expected: &str) {
+    let top = top_words(inp, Language::English, 1);
+    assert_eq!(top.first().map(|i| i.as_str()), Some(expected), "{inp}");
+}
+
+#[test_case("bank", Language::English, "銀行"; "Ambiguous word")]
+#[test_case("house", Language::English, "家"; "Many synonyms")]
+fn test_foreign_top_3(inp: &str, lang: Language, expected: &str) {
+    let top = top_words(inp, lang, 3);
+    assert!(top.iter().any(|i| i == expected), "{inp}: {top:?}");
+}
+
+#[test_case("water", '水'; "Meaning")]
+#[test_case("水", '水'; "Literal")]
+#[test_case("みず", '水'; "Reading")]
+fn test_kanji_top(inp: &str, expected: char) {
+    let query = query(inp, SearchTarget::Kanji, Language::English);
+    let res = search::kanji::search(&query).expect("Kanji search failed");
+    assert_eq!(
+        res.items.first().map(|i| i.kanji.literal),
+        Some(expected),
+        "{inp}"
+    );
+}